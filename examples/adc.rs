@@ -9,7 +9,7 @@ use core::fmt::Write;
 use nb::block;
 
 use lpc8xx_hal::{
-    cortex_m_rt::entry, delay::Delay, prelude::*,
+    adc, cortex_m_rt::entry, delay::Delay, prelude::*,
     syscon::clock_source::AdcClock, usart, CorePeripherals, Peripherals,
 };
 
@@ -32,8 +32,10 @@ fn main() -> ! {
     let tx_pin = p.pins.pio0_25.into_swm_pin();
     let rx_pin = p.pins.pio0_24.into_swm_pin();
 
-    let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(rx_pin, &mut handle);
-    let (u0_txd, _) = swm.movable_functions.u0_txd.assign(tx_pin, &mut handle);
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
 
     let mut serial = p.USART0.enable_async(
         &clock_config,
@@ -55,6 +57,13 @@ fn main() -> ! {
         let adc_value =
             block! {adc.read(&mut adc_pin)}.expect("Read should never fail");
         write!(serial, "{}\n", adc_value).expect("Write should never fail");
+
+        // Read the same pin again, this time converting the result into
+        // millivolts. This assumes that VREFP is connected to 3.3 V.
+        let millivolts =
+            adc::to_millivolts(adc.read_blocking(&mut adc_pin), 3300);
+        write!(serial, "{} mV\n", millivolts).expect("Write should never fail");
+
         delay.delay_ms(100u8);
     }
 }
@@ -0,0 +1,91 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, delay::NonBlocking, gpio::Level, prelude::*, usart,
+    Peripherals,
+};
+use nb::block;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+    let mut wkt = p.WKT.enable(&mut syscon.handle);
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Set baud rate to 115200 baud. Please refer to the USART example for a
+    // detailed explanation.
+    #[cfg(feature = "82x")]
+    let clock_config = {
+        syscon.uartfrg.set_clkdiv(6);
+        syscon.uartfrg.set_frgmult(22);
+        syscon.uartfrg.set_frgdiv(0xff);
+        usart::Clock::new(&syscon.uartfrg, 0, 16)
+    };
+    #[cfg(feature = "845")]
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    #[cfg(feature = "82x")]
+    let tx_pin = p.pins.pio0_7.into_swm_pin();
+    #[cfg(feature = "82x")]
+    let rx_pin = p.pins.pio0_18.into_swm_pin();
+    #[cfg(feature = "845")]
+    let tx_pin = p.pins.pio0_25.into_swm_pin();
+    #[cfg(feature = "845")]
+    let rx_pin = p.pins.pio0_24.into_swm_pin();
+
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
+
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    // Use the self-wake-up timer for a delay that we can poll, instead of
+    // waiting for it.
+    wkt.select_enabled_clock(&syscon.iosc_derived_clock);
+    let mut delay = NonBlocking::new(wkt, &syscon.iosc_derived_clock);
+    delay.start_ms(500);
+
+    // Blink the LED and echo everything that is received over the USART, all
+    // in the same loop, without any interrupts.
+    loop {
+        if delay.poll().is_ok() {
+            led.toggle();
+            delay.start_ms(500);
+        }
+
+        if let Ok(b) = serial.read() {
+            // We only send what we've received, so the transmitter can keep
+            // up, and this won't block for long.
+            block!(serial.write(b)).unwrap();
+        }
+    }
+}
@@ -19,13 +19,8 @@ fn main() -> ! {
     let p = Peripherals::take().unwrap();
 
     // Initialize the APIs of the peripherals we need.
-    #[cfg(feature = "82x")]
-    let gpio = p.GPIO; // GPIO is initialized by default on LPC82x.
-    #[cfg(feature = "845")]
-    let gpio = {
-        let mut syscon = p.SYSCON.split();
-        p.GPIO.enable(&mut syscon.handle)
-    };
+    let mut syscon = p.SYSCON.split();
+    let gpio = p.GPIO.enable(&mut syscon.handle);
 
     // Select pin for LED
     #[cfg(feature = "82x")]
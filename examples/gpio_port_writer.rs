@@ -0,0 +1,72 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m::peripheral::{syst::SystClkSource, SYST},
+    cortex_m_rt::entry,
+    gpio::{Level, PortWriter},
+    CorePeripherals, Peripherals,
+};
+use rtt_target::rprintln;
+
+/// The number of edges generated for each measurement
+const EDGES: u32 = 1000;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token, port, mask) =
+        (p.pins.pio0_12, gpio.tokens.pio0_12, 0, 0x1 << 12);
+    #[cfg(feature = "845")]
+    let (led, token, port, mask) =
+        (p.pins.pio1_1, gpio.tokens.pio1_1, 1, 0x1 << 1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    // SysTick runs off the system clock, so it counts CPU cycles. It counts
+    // down, which is why the start value is larger than the end value below.
+    let mut syst = cp.SYST;
+    syst.set_clock_source(SystClkSource::Core);
+    syst.set_reload(0x00ff_ffff);
+    syst.clear_current();
+    syst.enable_counter();
+
+    // Toggle the pin through the regular API.
+    let start = SYST::get_current();
+    for _ in 0..EDGES {
+        led.toggle();
+    }
+    let per_call = start - SYST::get_current();
+
+    // Sound, as we own the pin and don't use it for writing while the
+    // `PortWriter` exists.
+    let mut writer = unsafe { PortWriter::new(port, mask) };
+
+    // Toggle the pin through the `PortWriter`. This should result in the same
+    // waveform on the pin, only faster.
+    let start = SYST::get_current();
+    for _ in 0..EDGES {
+        writer.toggle(mask);
+    }
+    let cached = start - SYST::get_current();
+
+    rprintln!("{} edges using GpioPin::toggle: {} cycles", EDGES, per_call);
+    rprintln!(
+        "{} edges using PortWriter::toggle: {} cycles",
+        EDGES,
+        cached
+    );
+
+    loop {}
+}
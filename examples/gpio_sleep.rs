@@ -22,9 +22,6 @@ fn main() -> ! {
     // Initialize the APIs of the peripherals we need.
     let mut syscon = p.SYSCON.split();
     let mut wkt = p.WKT.enable(&mut syscon.handle);
-    #[cfg(feature = "82x")]
-    let gpio = p.GPIO; // GPIO is initialized by default on LPC82x.
-    #[cfg(feature = "845")]
     let gpio = p.GPIO.enable(&mut syscon.handle);
 
     // We're going to need a clock for sleeping. Let's use the internal oscillator/IRC/FRO-derived clock
@@ -0,0 +1,66 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    gpio::{Level, SoftPwm},
+    prelude::*,
+    Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let mut wkt = p.WKT.enable(&mut syscon.handle);
+    let mrt = p.MRT0.split(&mut syscon.handle).mrt0;
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let led = led.into_output_pin(token, Level::Low);
+
+    // The MRT runs off the 12 MHz system clock, so this is a PWM frequency of
+    // 1 kHz, with a duty cycle resolution of 12000 steps.
+    let mut pwm = SoftPwm::new(led, mrt, 12_000);
+    pwm.enable();
+
+    let max_duty = pwm.get_max_duty();
+    let step = max_duty / 100;
+    let mut duty = 0;
+    let mut rising = true;
+
+    // The WKT runs off the 750 kHz IRC/FRO-derived clock. Change the
+    // brightness every 10 ms.
+    wkt.start(7_500u32);
+
+    loop {
+        // Toggling the LED needs to happen in time, so it's done as often as
+        // possible. An application that has more to do would call this from
+        // the MRT interrupt handler instead.
+        let Ok(()) = pwm.update();
+
+        if wkt.wait().is_ok() {
+            if rising {
+                duty += step;
+                rising = duty < max_duty;
+            } else {
+                duty -= step;
+                rising = duty == 0;
+            }
+            pwm.set_duty(duty);
+
+            wkt.start(7_500u32);
+        }
+    }
+}
@@ -27,9 +27,6 @@ fn main() -> ! {
     let mrt_channels = p.MRT0.split(&mut syscon.handle);
     let mut timer = mrt_channels.mrt0;
 
-    #[cfg(feature = "82x")]
-    let gpio = p.GPIO; // GPIO is initialized by default on LPC82x.
-    #[cfg(feature = "845")]
     let gpio = p.GPIO.enable(&mut syscon.handle);
 
     // Select pin for LED
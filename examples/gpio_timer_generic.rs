@@ -0,0 +1,63 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    gpio::{direction, GpioPin, Level},
+    pac::CorePeripherals,
+    timer::Timer,
+    Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let mut wkt = p.WKT.enable(&mut syscon.handle);
+    let mut mrt = p.MRT0.split(&mut syscon.handle).mrt0;
+    let mut syst = cp.SYST;
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    loop {
+        // The same blink code runs on top of each of the timers. The WKT runs
+        // off the 750 kHz IRC/FRO-derived clock, while SysTick and the MRT run
+        // off the 12 MHz system clock. Each of these blinks take half a second.
+        blink(&mut led, &mut wkt, 375_000);
+        blink(&mut led, &mut syst, 6_000_000);
+        blink(&mut led, &mut mrt, 6_000_000);
+    }
+}
+
+fn blink<P, T>(
+    led: &mut GpioPin<P, direction::Output>,
+    timer: &mut T,
+    ticks: u32,
+) where
+    P: lpc8xx_hal::pins::Trait,
+    T: Timer,
+{
+    led.set_high();
+    sleep(timer, ticks / 2);
+    led.set_low();
+    sleep(timer, ticks / 2);
+}
+
+fn sleep<T: Timer>(timer: &mut T, ticks: u32) {
+    timer.start(ticks);
+    while let Err(nb::Error::WouldBlock) = timer.wait() {}
+}
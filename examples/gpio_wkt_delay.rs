@@ -0,0 +1,39 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, delay::WktDelay, gpio::Level, prelude::*, Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let wkt = p.WKT.enable(&mut syscon.handle);
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Use the self-wake-up timer for delays. This leaves the SysTick timer free
+    // for other purposes.
+    let mut delay = WktDelay::new(wkt, &syscon.iosc_derived_clock);
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    loop {
+        delay.delay_ms(1_000_u16);
+        led.set_high();
+        delay.delay_ms(1_000_u16);
+        led.set_low();
+    }
+}
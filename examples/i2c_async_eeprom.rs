@@ -0,0 +1,133 @@
+//! Async I2C example reading from a 256 byte eeprom
+//!
+//! This example expects the microcontroller to be connected to the eeprom in
+//! the following way:
+//! - PIO0_11/I2C0_SDA to SDA
+//! - PIO0_10/I2C0_SCL to SCL
+//! - VSS to GND
+//! - VDD to VDD
+//!
+//! To keep the example self-contained, it comes with a minimal executor that
+//! can run a single future. A real application would use an async executor
+//! from the ecosystem instead.
+
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use lpc8xx_hal::{
+    cortex_m::asm,
+    cortex_m_rt::entry,
+    i2c,
+    pac::{self, interrupt, Interrupt, NVIC},
+    Peripherals,
+};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    let (i2c0_sda, _) = swm
+        .fixed_functions
+        .i2c0_sda
+        .assign(p.pins.pio0_11.into_swm_pin(), &mut handle);
+    let (i2c0_scl, _) = swm
+        .fixed_functions
+        .i2c0_scl
+        .assign(p.pins.pio0_10.into_swm_pin(), &mut handle);
+
+    let mut i2c = p
+        .I2C0
+        .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
+        .enable_master_mode(&i2c::Clock::new_400khz());
+
+    // Safe, as the interrupt handler only calls into the HAL, which expects
+    // exactly that.
+    unsafe { NVIC::unmask(Interrupt::I2C0) };
+
+    // Address of the eeprom
+    // ADJUST THIS
+    let address = 0b101_0000;
+
+    let mut buffer = [0u8; 2];
+    block_on(async {
+        // Set the eeprom's address to 0, then read the two bytes at 0 & 1.
+        i2c.master
+            .write_read_async(address, &[0], &mut buffer)
+            .await
+            .expect("Failed to read data");
+    });
+
+    rprintln!("{:?}", buffer);
+
+    loop {
+        asm::nop();
+    }
+}
+
+#[interrupt]
+fn I2C0() {
+    i2c::on_master_interrupt::<pac::I2C0>();
+}
+
+/// Set by the waker, whenever the future should be polled again
+static WOKEN: AtomicBool = AtomicBool::new(false);
+
+/// Run a future to completion, sleeping while it waits for interrupts
+fn block_on<F: Future>(future: F) -> F::Output {
+    // Sound, as the vtable functions don't use the data pointer.
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        // Interrupts are disabled while checking the flag, so an interrupt
+        // can't sneak in between the check and `wfi`. `wfi` still wakes up
+        // from a pending interrupt, which is handled right after.
+        cortex_m::interrupt::disable();
+        if !WOKEN.load(Ordering::Relaxed) {
+            asm::wfi();
+        }
+        WOKEN.store(false, Ordering::Relaxed);
+        // Sound, as we're not in a critical section.
+        unsafe { cortex_m::interrupt::enable() };
+    }
+}
+
+fn raw_waker() -> RawWaker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw_waker()
+    }
+    fn wake(_: *const ()) {
+        WOKEN.store(true, Ordering::Relaxed);
+    }
+    fn drop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable =
+        RawWakerVTable::new(clone, wake, wake, drop);
+
+    RawWaker::new(core::ptr::null(), &VTABLE)
+}
@@ -58,8 +58,10 @@ fn main() -> ! {
     #[cfg(feature = "845")]
     let rx_pin = p.pins.pio0_24.into_swm_pin();
 
-    let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(rx_pin, &mut handle);
-    let (u0_txd, _) = swm.movable_functions.u0_txd.assign(tx_pin, &mut handle);
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
 
     let mut serial = p.USART0.enable_async(
         &clock_config,
@@ -82,13 +84,8 @@ fn main() -> ! {
         .i2c0_scl
         .assign(p.pins.pio0_10.into_swm_pin(), &mut handle);
 
-    #[cfg(feature = "82x")]
-    let i2c_clock = &();
-    #[cfg(feature = "845")]
-    let i2c_clock = &syscon.iosc;
-
     let mut i2c = i2c
-        .enable(i2c_clock, i2c0_scl, i2c0_sda, &mut syscon.handle)
+        .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
         .enable_master_mode(&i2c::Clock::new_400khz());
 
     // Address of the eeprom
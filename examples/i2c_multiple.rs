@@ -0,0 +1,80 @@
+//! Example using two I2C buses at the same time
+//!
+//! I2C0 uses the fixed-function pins, while I2C1 can be assigned to any pin,
+//! using the switch matrix. This example expects an eeprom at address 0x50 on
+//! each bus, connected in the following way:
+//! - PIO0_11/I2C0_SDA and PIO0_10/I2C0_SCL to the first eeprom
+//! - PIO0_16/I2C1_SDA and PIO0_17/I2C1_SCL to the second eeprom
+//!
+//! Unlike I2C0, I2C1 doesn't have dedicated I2C pins, so the SDA and SCL
+//! lines of the second bus need external pull-up resistors.
+
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{cortex_m_rt::entry, i2c, prelude::*, Peripherals};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    // I2C0 uses fixed functions, which can only be assigned to one pin each.
+    let (i2c0_sda, _) = swm
+        .fixed_functions
+        .i2c0_sda
+        .assign(p.pins.pio0_11.into_swm_pin(), &mut handle);
+    let (i2c0_scl, _) = swm
+        .fixed_functions
+        .i2c0_scl
+        .assign(p.pins.pio0_10.into_swm_pin(), &mut handle);
+
+    // I2C1 uses movable functions, which can be assigned to any pin.
+    let (i2c1_sda, _) = swm
+        .movable_functions
+        .i2c1_sda
+        .assign(p.pins.pio0_16.into_swm_pin(), &mut handle);
+    let (i2c1_scl, _) = swm
+        .movable_functions
+        .i2c1_scl
+        .assign(p.pins.pio0_17.into_swm_pin(), &mut handle);
+
+    // Each instance has its own clock control, so both can be enabled and
+    // used independently.
+    let mut bus0 = p
+        .I2C0
+        .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
+        .enable_master_mode(&i2c::Clock::new_400khz());
+    let mut bus1 = p
+        .I2C1
+        .enable(&syscon.iosc, i2c1_scl, i2c1_sda, &mut syscon.handle)
+        .enable_master_mode(&i2c::Clock::new_100khz());
+
+    let address = 0b101_0000;
+
+    // Read the first two bytes of each eeprom
+    let mut buffer0 = [0u8; 2];
+    let mut buffer1 = [0u8; 2];
+    bus0.master
+        .write_read(address, &[0], &mut buffer0)
+        .expect("Failed to read from bus 0");
+    bus1.master
+        .write_read(address, &[0], &mut buffer1)
+        .expect("Failed to read from bus 1");
+
+    rtt_target::rprintln!("Bus 0: {:?}", buffer0);
+    rtt_target::rprintln!("Bus 1: {:?}", buffer1);
+
+    loop {}
+}
@@ -0,0 +1,94 @@
+//! I2C example, sharing one bus between two drivers
+//!
+//! The two "drivers" in this example are very simple and just read a
+//! register from a device. Adjust the addresses and registers to match the
+//! devices connected to your bus.
+//!
+//! This example expects the microcontroller to be connected to the devices in
+//! the following way:
+//! - PIO0_11/I2C0_SDA to SDA
+//! - PIO0_10/I2C0_SCL to SCL
+//! - VSS to GND
+//! - VDD to VDD
+
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    embedded_hal::blocking::i2c::WriteRead,
+    i2c::{self, BusManager},
+    Peripherals,
+};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let i2c = p.I2C0;
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    let (i2c0_sda, _) = swm
+        .fixed_functions
+        .i2c0_sda
+        .assign(p.pins.pio0_11.into_swm_pin(), &mut handle);
+    let (i2c0_scl, _) = swm
+        .fixed_functions
+        .i2c0_scl
+        .assign(p.pins.pio0_10.into_swm_pin(), &mut handle);
+
+    let i2c = i2c
+        .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
+        .enable_master_mode(&i2c::Clock::new_400khz());
+
+    // The bus manager takes ownership of the master. Each driver gets its own
+    // proxy, which it can use as if it owned the bus.
+    let bus = BusManager::new(i2c.master);
+
+    // ADJUST THESE
+    let mut sensor = Device::new(bus.acquire(), 0x29, 0xc0);
+    let mut eeprom = Device::new(bus.acquire(), 0x50, 0x00);
+
+    loop {
+        rprintln!("sensor: {:?}", sensor.read());
+        rprintln!("eeprom: {:?}", eeprom.read());
+    }
+}
+
+/// A simple driver that reads a single register
+struct Device<I> {
+    i2c: I,
+    address: u8,
+    register: u8,
+}
+
+impl<I> Device<I>
+where
+    I: WriteRead,
+{
+    fn new(i2c: I, address: u8, register: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            register,
+        }
+    }
+
+    fn read(&mut self) -> Result<u8, I::Error> {
+        let mut value = [0];
+        self.i2c
+            .write_read(self.address, &[self.register], &mut value)?;
+        Ok(value[0])
+    }
+}
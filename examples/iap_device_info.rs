@@ -0,0 +1,33 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{cortex_m_rt::entry, iap};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let part_id = iap::part_id();
+    rprintln!("Part ID: {:#010x}", part_id);
+
+    // Reading the part ID through IAP should yield the same value as reading
+    // the register. If it does, calling into the boot ROM works.
+    match iap::part_id_from_iap() {
+        Ok(id) if id == part_id => rprintln!("Part ID (IAP) matches"),
+        Ok(id) => rprintln!("Part ID (IAP) doesn't match: {:#010x}", id),
+        Err(err) => rprintln!("Error reading part ID: {:?}", err),
+    }
+
+    // The unique ID must be the same every time it is read.
+    let first = iap::unique_id().unwrap();
+    let second = iap::unique_id().unwrap();
+    assert_eq!(first, second);
+    assert_ne!(first.as_u128(), 0);
+
+    rprintln!("Unique ID: {:#034x}", first.as_u128());
+
+    loop {}
+}
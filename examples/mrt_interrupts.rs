@@ -0,0 +1,57 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::convert::TryInto as _;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, embedded_time::duration::Extensions as _, gpio::Level,
+    mrt::Mode, Peripherals,
+};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+    let channels = p.MRT0.split(&mut syscon.handle);
+
+    let mut led = p
+        .pins
+        .pio1_1
+        .into_output_pin(gpio.tokens.pio1_1, Level::High);
+
+    let mut interrupts = channels.interrupts;
+
+    // The first channel toggles the LED every 500 ms. It runs in repeat mode,
+    // so we don't need to touch it again after starting it.
+    let mut blink = channels.mrt0;
+    blink.start(500.milliseconds().try_into().unwrap());
+
+    // The second channel fires once, after 3 seconds, and is restarted after
+    // that.
+    let mut one_shot = channels.mrt1;
+    one_shot.set_mode(Mode::OneShot);
+    one_shot.start(3.seconds().try_into().unwrap());
+
+    loop {
+        // All channels share the same interrupt. Whether we're polling, like
+        // here, or checking from the `MRT0` interrupt handler, we need to find
+        // out which channels have fired.
+        let pending = interrupts.pending();
+        interrupts.clear(pending);
+
+        if pending.mrt0 {
+            led.toggle();
+        }
+        if pending.mrt1 {
+            rprintln!("One-shot timer fired");
+            one_shot.start(3.seconds().try_into().unwrap());
+        }
+    }
+}
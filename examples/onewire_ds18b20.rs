@@ -0,0 +1,64 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, delay::Delay, gpio::Level, onewire::OneWire,
+    pac::CorePeripherals, pins::DynamicPinDirection, Peripherals,
+};
+
+const SKIP_ROM: u8 = 0xcc;
+const CONVERT_T: u8 = 0x44;
+const READ_SCRATCHPAD: u8 = 0xbe;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // The DS18B20's data line is connected to PIO0_1, with a 4.7 kOhm pull-up
+    // resistor to 3.3 V. Only a single device must be connected to the bus,
+    // as we don't address the device by its ROM code.
+    let pin = p.pins.pio0_1.into_dynamic_pin(
+        gpio.tokens.pio0_1,
+        Level::Low,
+        DynamicPinDirection::Input,
+    );
+
+    let mut bus = OneWire::new(pin, Delay::new(cp.SYST));
+
+    loop {
+        if !bus.reset() {
+            rtt_target::rprintln!("No device present");
+            continue;
+        }
+
+        bus.write_byte(SKIP_ROM);
+        bus.write_byte(CONVERT_T);
+
+        // The device answers read slots with 0, until the conversion is done.
+        while !bus.read_bit() {}
+
+        bus.reset();
+        bus.write_byte(SKIP_ROM);
+        bus.write_byte(READ_SCRATCHPAD);
+
+        // The temperature is stored in the first two bytes of the scratchpad,
+        // in units of 1/16 °C.
+        let low = bus.read_byte();
+        let high = bus.read_byte();
+        let raw = i16::from_le_bytes([low, high]);
+
+        rtt_target::rprintln!(
+            "Temperature: {}.{:04} °C",
+            raw / 16,
+            (raw % 16).abs() as u32 * 625,
+        );
+    }
+}
@@ -0,0 +1,66 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m::asm,
+    cortex_m_rt::entry,
+    gpio::Level,
+    pinint::{Condition, Slice, PININT0, PININT1},
+    pins::{PIO0_12, PIO0_4},
+    Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let (gpio, mut pinint) = syscon.handle.enable_all((p.GPIO, p.PININT));
+
+    // Both buttons pull their pin low when pressed.
+    let _button_a = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+    let _button_b = p.pins.pio0_12.into_input_pin(gpio.tokens.pio0_12);
+
+    let _ = pinint
+        .interrupts
+        .pinint0
+        .select::<PIO0_4>(&mut syscon.handle);
+    let _ = pinint
+        .interrupts
+        .pinint1
+        .select::<PIO0_12>(&mut syscon.handle);
+
+    let mut led = p
+        .pins
+        .pio1_1
+        .into_output_pin(gpio.tokens.pio1_1, Level::High);
+
+    // Match, if both buttons are pressed at the same time. The product term
+    // consists of slices 0 and 1, and ends at slice 1.
+    let pattern = &mut pinint.pattern_match;
+    pattern.configure_slice::<PININT0>(Slice::Slice0, Condition::Low, false);
+    pattern.configure_slice::<PININT1>(Slice::Slice1, Condition::Low, true);
+
+    // Slice 7 is always an endpoint. Make sure the term formed by the
+    // remaining slices never matches.
+    pattern.configure_slice::<PININT0>(Slice::Slice2, Condition::Never, false);
+
+    pattern.enable();
+    pattern.enable_rxev();
+
+    loop {
+        // Sleep until the pattern match engine wakes us up. Since `wfe` can
+        // also return for other reasons, check the match before acting on it.
+        asm::wfe();
+
+        if pattern.is_match(Slice::Slice1) {
+            led.set_low();
+        } else {
+            led.set_high();
+        }
+    }
+}
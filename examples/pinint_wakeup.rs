@@ -0,0 +1,95 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use cortex_m::interrupt::Mutex;
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    gpio::Level,
+    init_state::Enabled,
+    pac::{interrupt, Interrupt, NVIC},
+    pinint::{self, PININT0},
+    pins::PIO0_4,
+    sleep, CorePeripherals, Peripherals,
+};
+
+/// The pin interrupt, shared with the interrupt handler
+static INT: Mutex<
+    RefCell<Option<pinint::Interrupt<PININT0, PIO0_4, Enabled>>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Set by the interrupt handler, every time the button is pressed
+static PRESSED: AtomicBool = AtomicBool::new(false);
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let mut cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    let mut pmu = p.PMU.split();
+    let mut syscon = p.SYSCON.split();
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+    let pinint = p.PININT.enable(&mut syscon.handle);
+
+    let mut led = p
+        .pins
+        .pio1_1
+        .into_output_pin(gpio.tokens.pio1_1, Level::Low);
+
+    // The button pulls the pin low when pressed, so we wake up on the falling
+    // edge. The start logic wakes us up on whichever edges are enabled here.
+    let _button = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+    let mut int = pinint
+        .interrupts
+        .pinint0
+        .select::<PIO0_4>(&mut syscon.handle);
+    int.enable_falling_edge();
+    int.enable_wakeup(&mut syscon.handle);
+
+    cortex_m::interrupt::free(|cs| {
+        INT.borrow(cs).replace(Some(int));
+    });
+
+    // Safe, as the interrupt handler and its shared state have been set up.
+    unsafe { NVIC::unmask(Interrupt::PIN_INT0) };
+
+    let mut sleep = sleep::WithHandler::prepare(&mut pmu.handle, &mut cp.SCB);
+
+    loop {
+        rtt_target::rprintln!("Going to deep-sleep. Press the button!");
+
+        // Sound, as we haven't changed the power configuration of any analog
+        // block, so PDAWAKECFG, which is still at its reset value, matches
+        // the states tracked by the HAL. The main clock is still running from
+        // the FRO, as required for deep-sleep mode.
+        unsafe {
+            sleep.deep_sleep_until(|| {
+                let pressed = PRESSED.load(Ordering::Acquire);
+                PRESSED.store(false, Ordering::Release);
+                pressed
+            });
+        }
+
+        rtt_target::rprintln!("Woke up.");
+        led.toggle();
+    }
+}
+
+#[interrupt]
+fn PIN_INT0() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(int) = INT.borrow(cs).borrow_mut().as_mut() {
+            int.clear_falling_edge_flag();
+        }
+    });
+
+    PRESSED.store(true, Ordering::Release);
+}
@@ -0,0 +1,51 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    clock::Ticks, cortex_m_rt::entry, sleep, CorePeripherals, Peripherals,
+};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let mut cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    // Initialize the APIs of the peripherals we need.
+    let mut pmu = p.PMU.split();
+    let mut syscon = p.SYSCON.split();
+    let mut wkt = p.WKT.enable(&mut syscon.handle);
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // We're going to sample the level of this pin. On the LPC845-BRK, it is
+    // connected to a button.
+    let input = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+
+    // The internal oscillator/IRC/FRO-derived clock runs at 750 kHz.
+    let clock = syscon.iosc_derived_clock;
+    let interval = Ticks {
+        value: 75_000,
+        clock: &clock,
+    }; // 100 ms
+
+    let regular =
+        sleep::Regular::prepare(&mut pmu.handle, &mut cp.SCB, &mut wkt);
+    let mut periodic = sleep::Periodic::new(regular, interval);
+
+    let mut sample: u32 = 0;
+    loop {
+        // Sleep until the next period starts. The time it takes to print the
+        // sample below doesn't delay the following period, so the samples are
+        // taken at a fixed rate of 10 Hz.
+        if let Err(sleep::Overrun) = periodic.wait_next() {
+            rprintln!("Missed a period");
+        }
+
+        rprintln!("Sample {}: {}", sample, input.is_high());
+        sample = sample.wrapping_add(1);
+    }
+}
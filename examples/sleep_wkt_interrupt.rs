@@ -0,0 +1,93 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use cortex_m::interrupt::Mutex;
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    gpio::Level,
+    pac::{interrupt, Interrupt, NVIC},
+    prelude::*,
+    sleep,
+    syscon::IoscDerivedClock,
+    CorePeripherals, Peripherals, WKT,
+};
+
+/// The WKT, shared with the interrupt handler
+static TIMER: Mutex<RefCell<Option<WKT>>> = Mutex::new(RefCell::new(None));
+
+/// Set by the interrupt handler, every time the WKT fires
+static TICK: AtomicBool = AtomicBool::new(false);
+
+/// The period of the WKT interrupt (500 ms at 750 kHz)
+const PERIOD: u32 = 375_000;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let mut cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    let mut pmu = p.PMU.split();
+    let mut syscon = p.SYSCON.split();
+    let mut wkt = p.WKT.enable(&mut syscon.handle);
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    // Start the WKT and move it into the static, so the interrupt handler can
+    // restart it. The interrupt handler is the only code that touches it from
+    // now on.
+    wkt.select_clock::<IoscDerivedClock>();
+    wkt.start(PERIOD);
+    cortex_m::interrupt::free(|cs| {
+        TIMER.borrow(cs).replace(Some(wkt));
+    });
+
+    // Safe, as the interrupt handler and its shared state have been set up.
+    unsafe { NVIC::unmask(Interrupt::WKT) };
+
+    let mut sleep = sleep::WithHandler::prepare(&mut pmu.handle, &mut cp.SCB);
+
+    loop {
+        // Sleep until the interrupt handler has run. The interrupt handler
+        // clears the WKT's alarm flag, so we can't rely on it here.
+        //
+        // The condition is checked with interrupts disabled, so it's fine to
+        // load and reset the flag separately.
+        sleep.sleep_until(|| {
+            let tick = TICK.load(Ordering::Acquire);
+            TICK.store(false, Ordering::Release);
+            tick
+        });
+
+        led.toggle();
+    }
+}
+
+#[interrupt]
+fn WKT() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(wkt) = TIMER.borrow(cs).borrow_mut().as_mut() {
+            // Restarting the timer resets the alarm flag, which is required to
+            // exit the interrupt handler.
+            wkt.start(PERIOD);
+        }
+    });
+
+    TICK.store(true, Ordering::Release);
+}
@@ -0,0 +1,65 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, delay::Delay, gpio::Level, prelude::*,
+    syscon::sysosc::SysOscClock, CorePeripherals, Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut swm_handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    // Connect the crystal to the system oscillator.
+    let (xtalin, _) = swm
+        .fixed_functions
+        .xtalin
+        .assign(p.pins.pio0_8.into_swm_pin(), &mut swm_handle);
+    let (xtalout, _) = swm
+        .fixed_functions
+        .xtalout
+        .assign(p.pins.pio0_9.into_swm_pin(), &mut swm_handle);
+
+    // Start the system oscillator with a 12 MHz crystal and switch the main
+    // clock over to it. Since the crystal runs at the same frequency as the
+    // internal oscillator, the delay below keeps working as before, only more
+    // accurately.
+    let sysosc = SysOscClock::enable(
+        syscon.sysosc,
+        &mut syscon.handle,
+        12_000_000,
+        &xtalin,
+        &xtalout,
+    );
+    sysosc.select_as_main_clock(&mut syscon.handle);
+
+    let mut delay = Delay::new(cp.SYST);
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    loop {
+        delay.delay_ms(1_000_u16);
+        led.set_high();
+        delay.delay_ms(1_000_u16);
+        led.set_low();
+    }
+}
@@ -75,8 +75,10 @@ fn main() -> ! {
     // LPC845-BRK development boards, they're connected to the integrated USB to
     // Serial converter. So by using the pins, we can use them to communicate
     // with a host PC, without additional hardware.
-    let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(rx_pin, &mut handle);
-    let (u0_txd, _) = swm.movable_functions.u0_txd.assign(tx_pin, &mut handle);
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
 
     // Enable USART0
     let mut serial = p.USART0.enable_async(
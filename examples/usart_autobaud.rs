@@ -0,0 +1,73 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, prelude::*, usart, CorePeripherals, Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+    let cp = CorePeripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    // SWM isn't enabled by default on LPC845.
+    let mut handle = swm.handle.enable(&mut syscon.handle);
+
+    // The initial baud rate doesn't matter much, as it's going to be replaced
+    // by the detected one. The oversampling is kept though.
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    // WARNING: The pinout for the lpc845brk uses tx/rx as seen from the
+    // perspective from the serial adapter, so this is used the opposite way
+    let tx_pin = p.pins.pio0_25.into_swm_pin();
+    let rx_pin = p.pins.pio0_24.into_swm_pin();
+
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
+
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    // SysTick runs off the 12 MHz system clock, so this is a timeout of one
+    // second.
+    let mut syst = cp.SYST;
+
+    loop {
+        rtt_target::rprintln!("Waiting for 'U' from host...");
+
+        // `clock_config` uses the internal oscillator, which drives the USART.
+        match serial.auto_baud(&syscon.iosc, &mut syst, 12_000_000) {
+            Ok(baudrate) => {
+                rtt_target::rprintln!("Detected {} baud", baudrate);
+                break;
+            }
+            Err(err) => {
+                rtt_target::rprintln!("Detection failed: {:?}", err);
+            }
+        }
+    }
+
+    // The character used for the measurement might have been received, or
+    // not. Either way, we don't need it.
+    let _ = serial.read();
+
+    serial
+        .bwrite_all(b"Hello, world!\r\n")
+        .expect("UART write shouldn't fail");
+
+    loop {}
+}
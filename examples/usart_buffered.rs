@@ -0,0 +1,105 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::{cell::RefCell, ptr};
+
+use cortex_m::interrupt::Mutex;
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    pac::{interrupt, USART0},
+    prelude::*,
+    usart::{self, state::AsyncMode, BufferedRx},
+    Peripherals,
+};
+use rtt_target::rprintln;
+
+/// The buffered receiver, shared with the interrupt handler
+static RX: Mutex<RefCell<Option<BufferedRx<USART0, AsyncMode>>>> =
+    Mutex::new(RefCell::new(None));
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    let (u0_rxd, _) = swm
+        .movable_functions
+        .u0_rxd
+        .assign(p.pins.pio0_24.into_swm_pin(), &mut swm_handle);
+    let (u0_txd, _) = swm
+        .movable_functions
+        .u0_txd
+        .assign(p.pins.pio0_25.into_swm_pin(), &mut swm_handle);
+
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    // The size of this buffer is the only thing that determines how long a
+    // burst we can absorb, without having to read from it in between. Make it
+    // smaller to save RAM, or larger to handle longer bursts.
+    static mut BUF: [u8; 1024] = [0; 1024];
+
+    // Sound, as this is the only reference to `BUF` that is ever created.
+    let buffer = unsafe { &mut *ptr::addr_of_mut!(BUF) };
+
+    serial.enable_in_nvic();
+
+    let rx = serial.rx.into_buffered(buffer);
+    cortex_m::interrupt::free(|cs| {
+        RX.borrow(cs).replace(Some(rx));
+    });
+
+    loop {
+        // Pretend to be busy with something else. Anything that arrives in
+        // the meantime ends up in the buffer.
+        cortex_m::asm::delay(12_000_000);
+
+        let mut received = 0;
+        let mut error = None;
+        cortex_m::interrupt::free(|cs| {
+            if let Some(rx) = RX.borrow(cs).borrow_mut().as_mut() {
+                loop {
+                    match rx.read() {
+                        Ok(_) => received += 1,
+                        Err(nb::Error::WouldBlock) => break,
+                        Err(nb::Error::Other(err)) => {
+                            error = Some(err);
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        if received > 0 {
+            rprintln!("Received {} bytes", received);
+        }
+        if let Some(error) = error {
+            rprintln!("Error: {:?}", error);
+        }
+    }
+}
+
+#[interrupt]
+fn USART0() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(rx) = RX.borrow(cs).borrow_mut().as_mut() {
+            rx.on_interrupt();
+        }
+    });
+}
@@ -0,0 +1,140 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::cell::{Cell, RefCell};
+
+use cortex_m::interrupt::Mutex;
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    pac::{interrupt, USART0},
+    usart::{self, state::AsyncMode, CallbackRx, FrameStatus},
+    Peripherals,
+};
+use rtt_target::rprintln;
+
+/// The receiver, shared with the interrupt handler
+static RX: Mutex<RefCell<Option<CallbackRx<USART0, u8, AsyncMode, Callback>>>> =
+    Mutex::new(RefCell::new(None));
+
+/// The state of the parser, which is only accessed by the callback
+static PARSER: Mutex<RefCell<Parser>> = Mutex::new(RefCell::new(Parser {
+    value: 0,
+    valid: true,
+}));
+
+/// The last number that was received, read by the main loop
+static LAST: Mutex<Cell<Option<u32>>> = Mutex::new(Cell::new(None));
+
+/// The number of lines that were discarded, read by the main loop
+static DISCARDED: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
+
+type Callback = fn(u8, FrameStatus);
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    let (u0_rxd, _) = swm
+        .movable_functions
+        .u0_rxd
+        .assign(p.pins.pio0_24.into_swm_pin(), &mut swm_handle);
+    let (u0_txd, _) = swm
+        .movable_functions
+        .u0_txd
+        .assign(p.pins.pio0_25.into_swm_pin(), &mut swm_handle);
+
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    serial.enable_in_nvic();
+
+    let rx = serial.rx.into_callback(on_byte as Callback);
+    cortex_m::interrupt::free(|cs| {
+        RX.borrow(cs).replace(Some(rx));
+    });
+
+    let mut last = None;
+    loop {
+        let (value, discarded) = cortex_m::interrupt::free(|cs| {
+            (LAST.borrow(cs).get(), DISCARDED.borrow(cs).get())
+        });
+
+        if value != last {
+            if let Some(value) = value {
+                rprintln!("Received {} ({} lines discarded)", value, discarded);
+            }
+            last = value;
+        }
+    }
+}
+
+#[interrupt]
+fn USART0() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(rx) = RX.borrow(cs).borrow_mut().as_mut() {
+            rx.on_interrupt();
+        }
+    });
+}
+
+/// Parses lines that contain a decimal number each
+///
+/// Called from the interrupt handler with every received byte.
+fn on_byte(byte: u8, status: FrameStatus) {
+    cortex_m::interrupt::free(|cs| {
+        let mut parser = PARSER.borrow(cs).borrow_mut();
+
+        // Bytes were lost or corrupted, so the current line can't be trusted.
+        if !status.is_ok() {
+            parser.valid = false;
+        }
+
+        match byte {
+            b'\n' => {
+                if parser.valid {
+                    LAST.borrow(cs).set(Some(parser.value));
+                } else {
+                    let discarded = DISCARDED.borrow(cs);
+                    discarded.set(discarded.get() + 1);
+                }
+
+                parser.value = 0;
+                parser.valid = true;
+            }
+            b'0'..=b'9' => {
+                let digit = (byte - b'0') as u32;
+                match parser
+                    .value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(digit))
+                {
+                    Some(value) => parser.value = value,
+                    None => parser.valid = false,
+                }
+            }
+            b'\r' => {}
+            _ => parser.valid = false,
+        }
+    });
+}
+
+struct Parser {
+    value: u32,
+    valid: bool,
+}
@@ -0,0 +1,59 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::ptr;
+
+use lpc8xx_hal::{cortex_m_rt::entry, usart, Peripherals};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    let dma = p.DMA.enable(&mut syscon.handle);
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    let (u0_rxd, _) = swm
+        .movable_functions
+        .u0_rxd
+        .assign(p.pins.pio0_24.into_swm_pin(), &mut swm_handle);
+    let (u0_txd, _) = swm
+        .movable_functions
+        .u0_txd
+        .assign(p.pins.pio0_25.into_swm_pin(), &mut swm_handle);
+
+    let serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    static mut BUF: [u8; 64] = [0; 64];
+
+    // Sound, as this is the only reference to `BUF` that is ever created.
+    let buffer = unsafe { &mut *ptr::addr_of_mut!(BUF) };
+
+    // From here on, the DMA controller receives everything that arrives on
+    // the serial port, without requiring any attention from the CPU. We only
+    // need to drain the buffer often enough, so the DMA controller doesn't
+    // overwrite data we haven't read yet.
+    let mut transfer = serial.rx.read_circular(buffer, dma.channels.channel0);
+
+    let mut chunk = [0; 16];
+    loop {
+        let n = transfer.read(&mut chunk);
+        if n > 0 {
+            rtt_target::rprintln!("{:?}", &chunk[..n]);
+        }
+    }
+}
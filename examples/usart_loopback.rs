@@ -0,0 +1,77 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry, nb::block, prelude::*, usart, Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    // Set baud rate to 115200 baud. Please refer to the USART example for a
+    // detailed explanation.
+    #[cfg(feature = "82x")]
+    let clock_config = {
+        syscon.uartfrg.set_clkdiv(6);
+        syscon.uartfrg.set_frgmult(22);
+        syscon.uartfrg.set_frgdiv(0xff);
+        usart::Clock::new(&syscon.uartfrg, 0, 16)
+    };
+    #[cfg(feature = "845")]
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    #[cfg(feature = "82x")]
+    let tx_pin = p.pins.pio0_7.into_swm_pin();
+    #[cfg(feature = "82x")]
+    let rx_pin = p.pins.pio0_18.into_swm_pin();
+    #[cfg(feature = "845")]
+    let tx_pin = p.pins.pio0_25.into_swm_pin();
+    #[cfg(feature = "845")]
+    let rx_pin = p.pins.pio0_24.into_swm_pin();
+
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
+
+    // Enable USART0 in loopback mode. Everything we send is received again,
+    // without requiring any external wiring.
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default().loopback_enabled(),
+    );
+
+    for &sent in b"Loopback" {
+        block!(serial.write(sent)).expect("UART write shouldn't fail");
+        let received = block!(serial.read()).expect("UART read failed");
+
+        assert_eq!(sent, received);
+    }
+
+    // Make sure everything has been sent completely, including the last word
+    // in the shift register, before moving on.
+    serial.bwrite_all(b"!").expect("UART write shouldn't fail");
+    serial.wait_tx_complete();
+    let received = block!(serial.read()).expect("UART read failed");
+    assert_eq!(received, b'!');
+
+    rtt_target::rprintln!("Loopback test successful");
+
+    loop {}
+}
@@ -0,0 +1,106 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{cortex_m_rt::entry, nb, prelude::*, usart, Peripherals};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    // Set baud rate to 115200 baud. Please refer to the USART example for a
+    // detailed explanation.
+    //
+    // On LPC82x, all USART instances share the same fractional baud rate
+    // generator (UARTFRG), so they all need to use the same clock
+    // configuration (although they can still use different baud rates, by
+    // passing a different divider to `usart::Clock::new`).
+    // `usart::FrgConfig::for_baudrates` can find a configuration that works
+    // for multiple baud rates.
+    #[cfg(feature = "82x")]
+    let clock_config = {
+        syscon.uartfrg.set_clkdiv(6);
+        syscon.uartfrg.set_frgmult(22);
+        syscon.uartfrg.set_frgdiv(0xff);
+        usart::Clock::new(&syscon.uartfrg, 0, 16)
+    };
+    #[cfg(feature = "845")]
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    // USART0 is connected to the host, through the debugger's USB-serial
+    // bridge.
+    #[cfg(feature = "82x")]
+    let u0_tx_pin = p.pins.pio0_7.into_swm_pin();
+    #[cfg(feature = "82x")]
+    let u0_rx_pin = p.pins.pio0_18.into_swm_pin();
+    #[cfg(feature = "845")]
+    let u0_tx_pin = p.pins.pio0_25.into_swm_pin();
+    #[cfg(feature = "845")]
+    let u0_rx_pin = p.pins.pio0_24.into_swm_pin();
+
+    // USART1 is available on PIO0_16 (TX) and PIO0_17 (RX). Connect another
+    // serial device there, or connect both pins to each other, to receive
+    // everything that is sent back again.
+    let u1_tx_pin = p.pins.pio0_16.into_swm_pin();
+    let u1_rx_pin = p.pins.pio0_17.into_swm_pin();
+
+    let ((u0_rxd, _), (u0_txd, _), (u1_rxd, _), (u1_txd, _)) = handle
+        .assign_all((
+            (swm.movable_functions.u0_rxd, u0_rx_pin),
+            (swm.movable_functions.u0_txd, u0_tx_pin),
+            (swm.movable_functions.u1_rxd, u1_rx_pin),
+            (swm.movable_functions.u1_txd, u1_tx_pin),
+        ));
+
+    // Both instances are set up in exactly the same way.
+    let mut usart0 = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+    let mut usart1 = p.USART1.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u1_rxd,
+        u1_txd,
+        usart::Settings::default(),
+    );
+
+    // Forward everything received on one USART to the other one.
+    loop {
+        match usart0.read() {
+            Ok(word) => {
+                nb::block!(usart1.write(word))
+                    .expect("UART write shouldn't fail");
+            }
+            Err(nb::Error::WouldBlock) => {}
+            Err(nb::Error::Other(err)) => {
+                rtt_target::rprintln!("USART0 error: {:?}", err);
+            }
+        }
+
+        match usart1.read() {
+            Ok(word) => {
+                nb::block!(usart0.write(word))
+                    .expect("UART write shouldn't fail");
+            }
+            Err(nb::Error::WouldBlock) => {}
+            Err(nb::Error::Other(err)) => {
+                rtt_target::rprintln!("USART1 error: {:?}", err);
+            }
+        }
+    }
+}
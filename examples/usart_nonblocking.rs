@@ -0,0 +1,131 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::cell::RefCell;
+
+use cortex_m::interrupt::Mutex;
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    pac::{interrupt, USART0},
+    usart::{
+        self,
+        state::{AsyncMode, NoThrottle},
+        WriteHandle,
+    },
+    Peripherals,
+};
+
+/// The write in progress, shared with the interrupt handler
+static WRITE: Mutex<
+    RefCell<Option<WriteHandle<USART0, u8, AsyncMode, NoThrottle>>>,
+> = Mutex::new(RefCell::new(None));
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Set baud rate to 115200 baud. Please refer to the USART example for a
+    // detailed explanation.
+    #[cfg(feature = "82x")]
+    let clock_config = {
+        syscon.uartfrg.set_clkdiv(6);
+        syscon.uartfrg.set_frgmult(22);
+        syscon.uartfrg.set_frgdiv(0xff);
+        usart::Clock::new(&syscon.uartfrg, 0, 16)
+    };
+    #[cfg(feature = "845")]
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    #[cfg(feature = "82x")]
+    let tx_pin = p.pins.pio0_7.into_swm_pin();
+    #[cfg(feature = "82x")]
+    let rx_pin = p.pins.pio0_18.into_swm_pin();
+    #[cfg(feature = "845")]
+    let tx_pin = p.pins.pio0_25.into_swm_pin();
+    #[cfg(feature = "845")]
+    let rx_pin = p.pins.pio0_24.into_swm_pin();
+
+    let ((u0_rxd, _), (u0_txd, _)) = handle.assign_all((
+        (swm.movable_functions.u0_rxd, rx_pin),
+        (swm.movable_functions.u0_txd, tx_pin),
+    ));
+
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    serial.enable_in_nvic();
+    let mut tx = serial.tx;
+
+    // Our "sensor" is a button, connected to PIO0_4.
+    let button = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+
+    loop {
+        let mut samples: u32 = 0;
+        let mut pressed: u32 = 0;
+
+        // Start sending the log line. While the interrupt handler keeps the
+        // USART busy, we keep reading the sensor, instead of waiting for the
+        // transmission to finish.
+        let write = tx.start_write(b"Sampling button...\r\n");
+        cortex_m::interrupt::free(|cs| {
+            WRITE.borrow(cs).replace(Some(write));
+        });
+
+        while !is_done() {
+            samples += 1;
+            if button.is_low() {
+                pressed += 1;
+            }
+        }
+
+        let write = cortex_m::interrupt::free(|cs| WRITE.borrow(cs).take())
+            .expect("Write was put there above");
+
+        rtt_target::rprintln!(
+            "Sent {} bytes, took {} samples ({} pressed)",
+            write.words_written(),
+            samples,
+            pressed,
+        );
+
+        tx = write.free().0;
+    }
+}
+
+fn is_done() -> bool {
+    cortex_m::interrupt::free(|cs| {
+        WRITE
+            .borrow(cs)
+            .borrow()
+            .as_ref()
+            .map_or(true, |write| write.is_done())
+    })
+}
+
+#[interrupt]
+fn USART0() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(write) = WRITE.borrow(cs).borrow_mut().as_mut() {
+            write.on_interrupt();
+        }
+    });
+}
@@ -0,0 +1,88 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    cortex_m_rt::entry,
+    gpio::Level,
+    nb::block,
+    prelude::*,
+    usart::{self, Rs485},
+    Peripherals,
+};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let clock_config = usart::Clock::new_with_baudrate(115200);
+
+    let (u0_rxd, _) = swm
+        .movable_functions
+        .u0_rxd
+        .assign(p.pins.pio0_24.into_swm_pin(), &mut swm_handle);
+    let (u0_txd, _) = swm
+        .movable_functions
+        .u0_txd
+        .assign(p.pins.pio0_25.into_swm_pin(), &mut swm_handle);
+
+    let serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    // Connect this pin to both DE and /RE of the transceiver. High enables the
+    // driver and disables the receiver, low does the opposite.
+    let de = p
+        .pins
+        .pio0_26
+        .into_output_pin(gpio.tokens.pio0_26, Level::Low);
+
+    let mut rx = serial.rx;
+    let mut rs485 = Rs485::new(serial.tx, de);
+
+    loop {
+        // The driver is enabled while the request is sent, and disabled
+        // right after the last bit has left the shift register. This makes
+        // sure the request isn't cut off, and the bus is free for the reply.
+        rs485.write_all(b"ping\n");
+
+        // Receive the reply, which is terminated by a newline.
+        let mut reply = [0; 32];
+        let mut len = 0;
+        loop {
+            let b = match block!(rx.read()) {
+                Ok(b) => b,
+                Err(err) => {
+                    rprintln!("Error receiving reply: {:?}", err);
+                    break;
+                }
+            };
+
+            if b == b'\n' {
+                break;
+            }
+            if len < reply.len() {
+                reply[len] = b;
+                len += 1;
+            }
+        }
+
+        rprintln!("Reply: {:?}", &reply[..len]);
+
+        lpc8xx_hal::cortex_m::asm::delay(12_000_000);
+    }
+}
@@ -0,0 +1,66 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use lpc8xx_hal::{
+    clock::Ticks, cortex_m_rt::entry, gpio::Level, prelude::*, sleep,
+    syscon::wdtosc::WdtOscClock, CorePeripherals, Peripherals,
+};
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let mut cp = CorePeripherals::take().unwrap();
+    let p = Peripherals::take().unwrap();
+
+    // Initialize the APIs of the peripherals we need.
+    let mut pmu = p.PMU.split();
+    let mut syscon = p.SYSCON.split();
+    let mut wkt = p.WKT.enable(&mut syscon.handle);
+    let gpio = p.GPIO.enable(&mut syscon.handle);
+
+    // Select pin for LED
+    #[cfg(feature = "82x")]
+    let (led, token) = (p.pins.pio0_12, gpio.tokens.pio0_12);
+    #[cfg(feature = "845")]
+    let (led, token) = (p.pins.pio1_1, gpio.tokens.pio1_1);
+
+    let mut led = led.into_output_pin(token, Level::Low);
+
+    // Run the main clock off the watchdog oscillator, at its lowest frequency
+    // (0.6 MHz / 64 = 9.375 kHz, nominally). This frequency is very
+    // imprecise, but good enough for a blinking LED.
+    let wdtosc = WdtOscClock::enable(syscon.wdtosc, &mut syscon.handle, 1, 31);
+    wdtosc.select_as_main_clock(&mut syscon.handle);
+
+    // The WKT can't run off the watchdog oscillator, so we use the 10 kHz
+    // low-power clock instead.
+    let clock = pmu.low_power_clock.enable(&mut pmu.handle);
+
+    // Nothing uses the IRC/FRO anymore, so we can power it down to save
+    // energy. Please note that the IRC/FRO-derived clock still claims to be
+    // enabled after this. We have to make sure not to use it.
+    syscon.handle.power_down(&syscon.iosc);
+
+    let mut sleep =
+        sleep::Regular::prepare(&mut pmu.handle, &mut cp.SCB, &mut wkt);
+
+    let low_time = Ticks {
+        value: 500,
+        clock: &clock,
+    }; //  50 ms
+    let high_time = Ticks {
+        value: 9_500,
+        clock: &clock,
+    }; // 950 ms
+
+    // Blink the LED
+    loop {
+        led.set_high();
+        sleep.sleep(high_time);
+        led.set_low();
+        sleep.sleep(low_time);
+    }
+}
@@ -0,0 +1,155 @@
+//! Async delay using the self-wake-up timer (WKT)
+//!
+//! Awaits a 100 ms delay, while another task keeps running. The other task
+//! prints a message and yields to the executor, until the delay has finished.
+//!
+//! To keep the example self-contained, it comes with a minimal executor that
+//! can run a single future. A real application would use an async executor
+//! from the ecosystem instead.
+
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+use core::{
+    cell::Cell,
+    future::{poll_fn, Future},
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use lpc8xx_hal::{
+    cortex_m::asm,
+    cortex_m_rt::entry,
+    delay::WktDelay,
+    pac::{interrupt, Interrupt, NVIC},
+    wkt, Peripherals,
+};
+use rtt_target::rprintln;
+
+#[entry]
+fn main() -> ! {
+    rtt_target::rtt_init_print!();
+
+    let p = Peripherals::take().unwrap();
+
+    let mut syscon = p.SYSCON.split();
+    let wkt = p.WKT.enable(&mut syscon.handle);
+
+    let mut delay = WktDelay::new(wkt, &syscon.iosc_derived_clock);
+
+    // Safe, as the interrupt handler only calls into the HAL, which expects
+    // exactly that.
+    unsafe { NVIC::unmask(Interrupt::WKT) };
+
+    let done = Cell::new(false);
+
+    let delay_task = async {
+        delay.delay_ms_async(100).await;
+        rprintln!("Delay finished");
+        done.set(true);
+    };
+    let other_task = async {
+        let mut runs = 0u32;
+        while !done.get() {
+            runs += 1;
+            yield_now().await;
+        }
+        rprintln!("Other task ran {} times during the delay", runs);
+    };
+
+    block_on(join(delay_task, other_task));
+
+    loop {
+        asm::nop();
+    }
+}
+
+#[interrupt]
+fn WKT() {
+    wkt::on_interrupt();
+}
+
+/// Run two futures concurrently, until both have completed
+async fn join(a: impl Future<Output = ()>, b: impl Future<Output = ()>) {
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+    let mut a_done = false;
+    let mut b_done = false;
+
+    poll_fn(|cx| {
+        if !a_done {
+            a_done = a.as_mut().poll(cx).is_ready();
+        }
+        if !b_done {
+            b_done = b.as_mut().poll(cx).is_ready();
+        }
+
+        if a_done && b_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// Return control to the executor once, asking to be polled again right away
+async fn yield_now() {
+    let mut yielded = false;
+    poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+/// Set by the waker, whenever the future should be polled again
+static WOKEN: AtomicBool = AtomicBool::new(false);
+
+/// Run a future to completion, sleeping while it waits for interrupts
+fn block_on<F: Future>(future: F) -> F::Output {
+    // Sound, as the vtable functions don't use the data pointer.
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        // Interrupts are disabled while checking the flag, so an interrupt
+        // can't sneak in between the check and `wfi`. `wfi` still wakes up
+        // from a pending interrupt, which is handled right after.
+        cortex_m::interrupt::disable();
+        if !WOKEN.load(Ordering::Relaxed) {
+            asm::wfi();
+        }
+        WOKEN.store(false, Ordering::Relaxed);
+        // Sound, as we're not in a critical section.
+        unsafe { cortex_m::interrupt::enable() };
+    }
+}
+
+fn raw_waker() -> RawWaker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw_waker()
+    }
+    fn wake(_: *const ()) {
+        WOKEN.store(true, Ordering::Relaxed);
+    }
+    fn drop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable =
+        RawWakerVTable::new(clone, wake, wake, drop);
+
+    RawWaker::new(core::ptr::null(), &VTABLE)
+}
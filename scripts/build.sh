@@ -41,10 +41,21 @@ function build() {
 
     cargo test \
         --verbose \
-        --features=$1,no-target-warning$TRYBUILD \
+        --features=$1,no-target-warning,fugit,debug-pin-checks$TRYBUILD \
         --target=$HOST_TARGET
     cargo build --verbose --features=$1-rt,no-target-warning --examples
 }
 
 build 82x
 build 845
+
+# Pins that are not available on the selected package should be rejected. This
+# is only tested for one package, as the compile-fail tests are the only thing
+# that differs.
+if [ "$STABLE_CHECKS" = true ]; then
+    cargo test \
+        --verbose \
+        --features=845m301jhi33,no-target-warning,trybuild \
+        --target=$HOST_TARGET \
+        --test compiletest
+fi
@@ -31,8 +31,18 @@
 //!     .expect("Read should never fail");
 //! ```
 //!
+//! Besides single conversions, the ADC can convert a set of channels in one
+//! sequence ([`ADC::read_sequence`]), or continuously ([`ADC::start_burst`]).
+//!
+//! The ADC is calibrated when it is enabled. Results are 12-bit values,
+//! relative to the reference voltage on VREFP. [`to_millivolts`] can be used to
+//! convert them into millivolts.
+//!
 //! Please refer to the [examples in the repository] for more example code.
 //!
+//! [`ADC::read_sequence`]: struct.ADC.html#method.read_sequence
+//! [`ADC::start_burst`]: struct.ADC.html#method.start_burst
+//! [`to_millivolts`]: fn.to_millivolts.html
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 use embedded_hal::adc::{Channel, OneShot};
@@ -109,6 +119,137 @@ impl ADC<init_state::Disabled> {
 }
 
 impl ADC<init_state::Enabled> {
+    /// Measure the voltage on a pin, blocking until the conversion is done
+    ///
+    /// Returns the 12-bit result of the conversion. Please refer to
+    /// [`to_millivolts`], if you need the result as a voltage.
+    ///
+    /// [`to_millivolts`]: fn.to_millivolts.html
+    pub fn read_blocking<PIN>(&mut self, _: &mut PIN) -> u16
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        // Start the measurement of the given channel
+        // Follows the description in the um
+        self.adc.seq_ctrla.write(|w| {
+            unsafe { w.channels().bits(1 << PIN::channel()) };
+            w.start().set_bit();
+            w.trigpol().set_bit();
+            w.seq_ena().enabled();
+            w.mode().end_of_conversion()
+        });
+
+        let mut read = self.adc.seq_gdata.read();
+
+        // Wait until the conversion is done
+        while read.datavalid().bit_is_clear() {
+            read = self.adc.seq_gdata.read();
+        }
+
+        read.result().bits()
+    }
+
+    /// Convert a set of channels, one after the other
+    ///
+    /// Blocks until all channels in the set have been converted, then returns
+    /// the 12-bit results.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     Peripherals,
+    ///     syscon::clock_source::AdcClock,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm    = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let mut adc = p.ADC.enable(&AdcClock::new_default(), &mut syscon.handle);
+    ///
+    /// let (mut adc_0, _) = swm
+    ///     .fixed_functions
+    ///     .adc_0
+    ///     .assign(p.pins.pio0_7.into_swm_pin(), &mut swm_handle);
+    /// let (mut adc_1, _) = swm
+    ///     .fixed_functions
+    ///     .adc_1
+    ///     .assign(p.pins.pio0_6.into_swm_pin(), &mut swm_handle);
+    ///
+    /// let readings = adc.read_sequence((&mut adc_0, &mut adc_1));
+    ///
+    /// let value_0 = readings.get(&adc_0);
+    /// let value_1 = readings.get(&adc_1);
+    /// ```
+    pub fn read_sequence<C>(&mut self, channels: C) -> Readings
+    where
+        C: ChannelSet,
+    {
+        let mask = channels.mask();
+
+        // Reading the data registers clears any stale DATAVALID flags.
+        for dat in self.adc.dat.iter() {
+            dat.read();
+        }
+
+        self.adc.seq_ctrla.write(|w| {
+            // Sound, as `ChannelSet` only sets bits of valid channels.
+            unsafe { w.channels().bits(mask) };
+            w.start().set_bit();
+            w.trigpol().set_bit();
+            w.seq_ena().enabled();
+            w.mode().end_of_sequence()
+        });
+
+        let mut values = [0; NUM_CHANNELS];
+        for (i, value) in values.iter_mut().enumerate() {
+            if mask & (0x1 << i) == 0 {
+                continue;
+            }
+
+            let mut read = self.adc.dat[i].read();
+            while read.datavalid().bit_is_clear() {
+                read = self.adc.dat[i].read();
+            }
+
+            *value = read.result().bits();
+        }
+
+        Readings { values, mask }
+    }
+
+    /// Start converting a set of channels continuously
+    ///
+    /// Consumes the ADC and the channels, until burst mode is stopped again
+    /// using [`Burst::stop`].
+    ///
+    /// [`Burst::stop`]: struct.Burst.html#method.stop
+    pub fn start_burst<C>(self, channels: C) -> Burst<C>
+    where
+        C: ChannelSet,
+    {
+        self.adc.seq_ctrla.write(|w| {
+            // Sound, as `ChannelSet` only sets bits of valid channels.
+            unsafe { w.channels().bits(channels.mask()) };
+            w.burst().set_bit();
+            w.trigpol().set_bit();
+            w.seq_ena().enabled();
+            w.mode().end_of_conversion()
+        });
+
+        Burst {
+            adc: self,
+            channels,
+        }
+    }
+
     /// Disable the ADC
     ///
     /// This method is only available, if `ADC` is in the [`Enabled`] state.
@@ -158,29 +299,165 @@ where
     type Error = ();
 
     /// Request that the ADC begin a conversion on the specified pin
-    fn read(&mut self, _: &mut PIN) -> nb::Result<u16, Self::Error> {
-        // Start the measurement of the given channel
-        // Follows the description in the um
-        self.adc.seq_ctrla.write(|w| {
-            unsafe { w.channels().bits(1 << PIN::channel()) };
-            w.start().set_bit();
-            w.trigpol().set_bit();
-            w.seq_ena().enabled();
-            w.mode().end_of_conversion()
-        });
+    fn read(&mut self, pin: &mut PIN) -> nb::Result<u16, Self::Error> {
+        // Returns the result as a 16 bit value
+        Ok(self.read_blocking(pin) << 4)
+    }
+}
 
-        let mut read = self.adc.seq_gdata.read();
+/// A set of ADC channels
+///
+/// Used by [`ADC::read_sequence`] and [`ADC::start_burst`]. This trait is
+/// implemented for tuples of up to 12 mutable references to channels (fixed
+/// functions like `ADC_0`, that have been assigned to a pin).
+///
+/// [`ADC::read_sequence`]: struct.ADC.html#method.read_sequence
+/// [`ADC::start_burst`]: struct.ADC.html#method.start_burst
+pub trait ChannelSet {
+    /// Returns a mask with the bits of all channels in the set set
+    fn mask(&self) -> u16;
+}
 
-        // Wait until the conversion is done
-        while read.datavalid().bit_is_clear() {
-            read = self.adc.seq_gdata.read();
+macro_rules! channel_set {
+    ($($ty:ident),*) => {
+        impl<'a, $($ty,)*> ChannelSet for ($(&'a mut $ty,)*)
+        where
+            $($ty: Channel<ADC, ID = u8>,)*
+        {
+            fn mask(&self) -> u16 {
+                0 $(| 0x1 << $ty::channel())*
+            }
         }
+    };
+}
 
-        // Returns the result as a 16 bit value
-        Ok(read.result().bits() << 4)
+channel_set!(C0);
+channel_set!(C0, C1);
+channel_set!(C0, C1, C2);
+channel_set!(C0, C1, C2, C3);
+channel_set!(C0, C1, C2, C3, C4);
+channel_set!(C0, C1, C2, C3, C4, C5);
+channel_set!(C0, C1, C2, C3, C4, C5, C6);
+channel_set!(C0, C1, C2, C3, C4, C5, C6, C7);
+channel_set!(C0, C1, C2, C3, C4, C5, C6, C7, C8);
+channel_set!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9);
+channel_set!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10);
+channel_set!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11);
+
+/// The results of a conversion sequence
+///
+/// Returned by [`ADC::read_sequence`].
+///
+/// [`ADC::read_sequence`]: struct.ADC.html#method.read_sequence
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Readings {
+    values: [u16; NUM_CHANNELS],
+    mask: u16,
+}
+
+impl Readings {
+    /// Returns the 12-bit value measured on the given channel
+    ///
+    /// Returns `None`, if the channel was not part of the sequence.
+    pub fn get<PIN>(&self, _: &PIN) -> Option<u16>
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        let channel = PIN::channel();
+
+        if self.mask & (0x1 << channel) != 0 {
+            Some(self.values[channel as usize])
+        } else {
+            None
+        }
+    }
+}
+
+/// The ADC in burst mode
+///
+/// In burst mode, the ADC converts a set of channels continuously, without
+/// any intervention from software. Use [`Burst::latest`] to get the most
+/// recent values.
+///
+/// Can be created using [`ADC::start_burst`].
+///
+/// [`Burst::latest`]: #method.latest
+/// [`ADC::start_burst`]: struct.ADC.html#method.start_burst
+pub struct Burst<C> {
+    adc: ADC,
+    channels: C,
+}
+
+impl<C> Burst<C>
+where
+    C: ChannelSet,
+{
+    /// Returns the most recent 12-bit value of the given channel
+    ///
+    /// `channel` is the number of the ADC channel (for example 0 for
+    /// `ADC_0`).
+    ///
+    /// Returns `None`, if there hasn't been a new conversion on the channel
+    /// since the last call, or if the channel is not part of the burst. This
+    /// includes channel numbers of [`NUM_CHANNELS`] and above.
+    ///
+    /// [`NUM_CHANNELS`]: constant.NUM_CHANNELS.html
+    pub fn latest(&self, channel: u8) -> Option<u16> {
+        if !is_in_set(self.channels.mask(), channel) {
+            return None;
+        }
+
+        let data = self.adc.adc.dat[channel as usize].read();
+        if data.datavalid().bit_is_set() {
+            Some(data.result().bits())
+        } else {
+            None
+        }
+    }
+
+    /// Stop burst mode
+    ///
+    /// Returns the ADC and the channels that were used.
+    pub fn stop(self) -> (ADC, C) {
+        self.adc.adc.seq_ctrla.write(|w| w.seq_ena().disabled());
+
+        (self.adc, self.channels)
     }
 }
 
+/// The number of ADC channels
+pub const NUM_CHANNELS: usize = 12;
+
+/// Returns whether a channel number is part of a channel mask
+fn is_in_set(mask: u16, channel: u8) -> bool {
+    (channel as usize) < NUM_CHANNELS && mask & (0x1 << channel) != 0
+}
+
+/// The maximum value returned by a 12-bit conversion
+pub const MAX_VALUE: u16 = 0xfff;
+
+/// Convert a 12-bit ADC value into millivolts
+///
+/// The result of a conversion is relative to the reference voltage that is
+/// applied to the VREFP pin (VREFN is assumed to be connected to ground).
+/// Since the reference voltage depends on how the chip is connected on the
+/// board, it needs to be passed in as `vref_mv`. On many boards, VREFP is
+/// connected to VDD, which is typically 3.3 V.
+///
+/// # Example
+///
+/// ```
+/// use lpc8xx_hal::adc::{to_millivolts, MAX_VALUE};
+///
+/// assert_eq!(to_millivolts(0, 3300), 0);
+/// assert_eq!(to_millivolts(MAX_VALUE, 3300), 3300);
+/// assert_eq!(to_millivolts(2048, 3300), 1650);
+/// ```
+pub fn to_millivolts(value: u16, vref_mv: u16) -> u16 {
+    (value as u32 * vref_mv as u32 / MAX_VALUE as u32) as u16
+}
+
 macro_rules! adc_channel {
     ($pin:ident, $num:expr) => {
         impl<PIN> Channel<ADC>
@@ -207,3 +484,24 @@ adc_channel!(ADC_8, 8);
 adc_channel!(ADC_9, 9);
 adc_channel!(ADC_10, 10);
 adc_channel!(ADC_11, 11);
+
+#[cfg(test)]
+mod tests {
+    use super::is_in_set;
+
+    #[test]
+    fn is_in_set_checks_mask() {
+        assert!(is_in_set(0b1010, 1));
+        assert!(is_in_set(0b1010, 3));
+        assert!(!is_in_set(0b1010, 0));
+        assert!(is_in_set(0xfff, 11));
+    }
+
+    #[test]
+    fn is_in_set_rejects_out_of_range_channels() {
+        assert!(!is_in_set(0xffff, 12));
+        assert!(!is_in_set(0xffff, 15));
+        assert!(!is_in_set(0xffff, 16));
+        assert!(!is_in_set(0xffff, 255));
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! This module defines types that are helpful for working with system clocks.
 
+use core::fmt;
+
+use embedded_time::duration::Nanoseconds;
+
 /// Represents a number of ticks of a given clock
 ///
 /// This struct is used to represent an amount of time, a duration, but in a
@@ -26,6 +30,436 @@ pub struct Ticks<'clock, C: 'clock> {
     pub clock: &'clock C,
 }
 
+impl<'clock, C> Ticks<'clock, C> {
+    /// Create a `Ticks` instance with a non-zero number of ticks
+    ///
+    /// Waiting for zero ticks is not possible with most timers. Sleeping for
+    /// zero ticks, for example, would mean never waking up again. This
+    /// constructor rejects zero, so the problem can be handled where the
+    /// value is created. Use [`Ticks::at_least_one`] to round zero up to one
+    /// tick instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZeroTicks`], if `value` is zero.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::{Ticks, ZeroTicks};
+    ///
+    /// struct Clock;
+    ///
+    /// assert_eq!(Ticks::new(10, &Clock).unwrap().value, 10);
+    /// assert_eq!(Ticks::new(0, &Clock).err(), Some(ZeroTicks));
+    /// ```
+    ///
+    /// [`Ticks::at_least_one`]: #method.at_least_one
+    /// [`ZeroTicks`]: struct.ZeroTicks.html
+    pub fn new(value: u32, clock: &'clock C) -> Result<Self, ZeroTicks> {
+        if value == 0 {
+            return Err(ZeroTicks);
+        }
+
+        Ok(Ticks { value, clock })
+    }
+
+    /// Create a `Ticks` instance, rounding zero up to one tick
+    ///
+    /// Works like [`Ticks::new`], except that a value of zero results in a
+    /// single tick, instead of an error.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::Ticks;
+    ///
+    /// struct Clock;
+    ///
+    /// assert_eq!(Ticks::at_least_one(10, &Clock).value, 10);
+    /// assert_eq!(Ticks::at_least_one(0, &Clock).value, 1);
+    /// ```
+    ///
+    /// [`Ticks::new`]: #method.new
+    pub fn at_least_one(value: u32, clock: &'clock C) -> Self {
+        Ticks {
+            value: value.max(1),
+            clock,
+        }
+    }
+
+    /// Add two numbers of ticks, checking for overflow
+    ///
+    /// Returns `None`, if the sum doesn't fit into a `u32`.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::Ticks;
+    ///
+    /// struct Clock;
+    ///
+    /// let a = Ticks { value: 3, clock: &Clock };
+    /// let b = Ticks { value: u32::MAX - 3, clock: &Clock };
+    ///
+    /// assert_eq!(a.checked_add(b).map(|t| t.value), Some(u32::MAX));
+    /// assert!(a.checked_add(a).unwrap().checked_add(b).is_none());
+    /// ```
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Ticks {
+            value: self.value.checked_add(other.value)?,
+            clock: self.clock,
+        })
+    }
+
+    /// Add two numbers of ticks, saturating at `u32::MAX`
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::Ticks;
+    ///
+    /// struct Clock;
+    ///
+    /// let a = Ticks { value: 3, clock: &Clock };
+    /// let b = Ticks { value: u32::MAX - 1, clock: &Clock };
+    ///
+    /// assert_eq!(a.saturating_add(a).value, 6);
+    /// assert_eq!(a.saturating_add(b).value, u32::MAX);
+    /// ```
+    pub fn saturating_add(self, other: Self) -> Self {
+        Ticks {
+            value: self.value.saturating_add(other.value),
+            clock: self.clock,
+        }
+    }
+
+    /// Multiply the number of ticks by a factor, checking for overflow
+    ///
+    /// Returns `None`, if the product doesn't fit into a `u32`.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::Ticks;
+    ///
+    /// struct Clock;
+    ///
+    /// let ticks = Ticks { value: 1_000_000, clock: &Clock };
+    ///
+    /// assert_eq!(ticks.checked_mul(4_000).map(|t| t.value), Some(4_000_000_000));
+    /// assert!(ticks.checked_mul(5_000).is_none());
+    /// ```
+    pub fn checked_mul(self, factor: u32) -> Option<Self> {
+        Some(Ticks {
+            value: self.value.checked_mul(factor)?,
+            clock: self.clock,
+        })
+    }
+}
+
+impl<'clock, C> Ticks<'clock, C>
+where
+    C: Frequency,
+{
+    /// Create a `Ticks` instance from a number of nanoseconds
+    ///
+    /// The number of ticks is rounded up, so the resulting duration is never
+    /// shorter than the requested one.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::{Frequency, Ticks};
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 750_000 }
+    /// }
+    ///
+    /// assert_eq!(Ticks::from_ns(1_000_000, &Clock).value, 750);
+    ///
+    /// // A single nanosecond is less than a tick, but is rounded up.
+    /// assert_eq!(Ticks::from_ns(1, &Clock).value, 1);
+    /// assert_eq!(Ticks::from_ns(0, &Clock).value, 0);
+    /// ```
+    pub fn from_ns(ns: u32, clock: &'clock C) -> Self {
+        Self::from_duration(ns, 1_000_000_000, clock)
+    }
+
+    /// Create a `Ticks` instance from a number of microseconds
+    ///
+    /// The number of ticks is rounded up, so the resulting duration is never
+    /// shorter than the requested one. If the number of ticks doesn't fit into
+    /// a `u32`, the value saturates at `u32::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::{Frequency, Ticks};
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 750_000 }
+    /// }
+    ///
+    /// assert_eq!(Ticks::from_us(1_000_000, &Clock).value, 750_000);
+    ///
+    /// // A single microsecond is less than a tick, but is rounded up.
+    /// assert_eq!(Ticks::from_us(1, &Clock).value, 1);
+    /// assert_eq!(Ticks::from_us(0, &Clock).value, 0);
+    /// ```
+    pub fn from_us(us: u32, clock: &'clock C) -> Self {
+        Self::from_duration(us, 1_000_000, clock)
+    }
+
+    /// Create a `Ticks` instance from a number of milliseconds
+    ///
+    /// The number of ticks is rounded up, so the resulting duration is never
+    /// shorter than the requested one. If the number of ticks doesn't fit into
+    /// a `u32`, the value saturates at `u32::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::clock::{Frequency, Ticks};
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 10_000 }
+    /// }
+    ///
+    /// assert_eq!(Ticks::from_ms(1_000, &Clock).value, 10_000);
+    /// assert_eq!(Ticks::from_ms(u32::MAX, &Clock).value, u32::MAX);
+    /// ```
+    pub fn from_ms(ms: u32, clock: &'clock C) -> Self {
+        Self::from_duration(ms, 1_000, clock)
+    }
+
+    /// Returns the duration that this number of ticks actually represents
+    ///
+    /// When a `Ticks` instance is created from a duration, the number of ticks
+    /// is rounded, so the actual duration can differ from the requested one.
+    /// This method computes the actual duration from the number of ticks and
+    /// the clock frequency, so the difference can be accounted for.
+    ///
+    /// The result is rounded down to whole nanoseconds.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{
+    ///     clock::{Frequency, Ticks},
+    ///     embedded_time::duration::Nanoseconds,
+    /// };
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 750_000 }
+    /// }
+    ///
+    /// // 10 us are 7.5 ticks, which are rounded up to 8 ticks.
+    /// let ticks = Ticks::from_us(10, &Clock);
+    /// assert_eq!(ticks.value, 8);
+    /// assert_eq!(ticks.actual_duration(), Nanoseconds(10_666_u64));
+    ///
+    /// // The maximum number of ticks doesn't overflow.
+    /// let ticks = Ticks { value: u32::MAX, clock: &Clock };
+    /// assert_eq!(ticks.actual_duration(), Nanoseconds(5_726_623_060_000_u64));
+    /// ```
+    pub fn actual_duration(&self) -> Nanoseconds<u64> {
+        // Can't overflow, as `u32::MAX * 1_000_000_000` fits into a `u64`.
+        Nanoseconds(self.value as u64 * 1_000_000_000 / self.clock.hz() as u64)
+    }
+
+    /// Create a `Ticks` instance from a `fugit` duration
+    ///
+    /// The number of ticks is rounded up, so the resulting duration is never
+    /// shorter than the requested one.
+    ///
+    /// Only available, if the `fugit` feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TicksOverflow`], if the number of ticks doesn't fit into a
+    /// `u32`.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{
+    ///     clock::{Frequency, Ticks, TicksOverflow},
+    ///     fugit::{MicrosDurationU32, MillisDurationU32, SecsDurationU32},
+    /// };
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 750_000 }
+    /// }
+    ///
+    /// let duration = MillisDurationU32::millis(20);
+    /// let ticks = Ticks::try_from_fugit(duration, &Clock).unwrap();
+    /// assert_eq!(ticks.value, 15_000);
+    ///
+    /// // 7.5 ticks are rounded up.
+    /// let duration = MicrosDurationU32::micros(10);
+    /// let ticks = Ticks::try_from_fugit(duration, &Clock).unwrap();
+    /// assert_eq!(ticks.value, 8);
+    ///
+    /// assert_eq!(
+    ///     Ticks::try_from_fugit(SecsDurationU32::secs(6_000), &Clock).err(),
+    ///     Some(TicksOverflow),
+    /// );
+    /// ```
+    ///
+    /// [`TicksOverflow`]: struct.TicksOverflow.html
+    #[cfg(feature = "fugit")]
+    pub fn try_from_fugit<const NOM: u32, const DENOM: u32>(
+        duration: fugit::Duration<u32, NOM, DENOM>,
+        clock: &'clock C,
+    ) -> Result<Self, TicksOverflow> {
+        // A duration is `ticks * NOM / DENOM` seconds.
+        let product = (duration.ticks() as u64)
+            .checked_mul(NOM as u64)
+            .and_then(|product| product.checked_mul(clock.hz() as u64))
+            .ok_or(TicksOverflow)?;
+
+        Self::checked_from_product(product, DENOM as u64, clock)
+    }
+
+    /// Create a `Ticks` instance from the period of a `fugit` rate
+    ///
+    /// The resulting number of ticks represents one period of `rate`. It is
+    /// rounded up, so the resulting period is never shorter than the requested
+    /// one, and the resulting rate is never higher.
+    ///
+    /// Only available, if the `fugit` feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TicksOverflow`], if the number of ticks doesn't fit into a
+    /// `u32`, which includes the case of `rate` being zero.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{
+    ///     clock::{Frequency, Ticks, TicksOverflow},
+    ///     fugit::HertzU32,
+    /// };
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 12_000_000 }
+    /// }
+    ///
+    /// let rate = HertzU32::kHz(1);
+    /// let ticks = Ticks::try_from_fugit_rate(rate, &Clock).unwrap();
+    /// assert_eq!(ticks.value, 12_000);
+    ///
+    /// assert_eq!(
+    ///     Ticks::try_from_fugit_rate(HertzU32::Hz(0), &Clock).err(),
+    ///     Some(TicksOverflow),
+    /// );
+    /// ```
+    ///
+    /// [`TicksOverflow`]: struct.TicksOverflow.html
+    #[cfg(feature = "fugit")]
+    pub fn try_from_fugit_rate<const NOM: u32, const DENOM: u32>(
+        rate: fugit::Rate<u32, NOM, DENOM>,
+        clock: &'clock C,
+    ) -> Result<Self, TicksOverflow> {
+        // A rate is `raw * NOM / DENOM` Hz, so its period is
+        // `DENOM / (raw * NOM)` seconds. Neither product can overflow a `u64`.
+        let product = clock.hz() as u64 * DENOM as u64;
+        let hz = rate.raw() as u64 * NOM as u64;
+
+        if hz == 0 {
+            return Err(TicksOverflow);
+        }
+
+        Self::checked_from_product(product, hz, clock)
+    }
+
+    /// Returns the duration that this number of ticks actually represents
+    ///
+    /// Works like [`actual_duration`], but returns a `fugit` duration.
+    ///
+    /// Only available, if the `fugit` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{
+    ///     clock::{Frequency, Ticks},
+    ///     fugit::NanosDurationU64,
+    /// };
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 750_000 }
+    /// }
+    ///
+    /// let ticks = Ticks { value: 8, clock: &Clock };
+    /// assert_eq!(ticks.to_fugit(), NanosDurationU64::nanos(10_666));
+    /// ```
+    ///
+    /// [`actual_duration`]: #method.actual_duration
+    #[cfg(feature = "fugit")]
+    pub fn to_fugit(&self) -> fugit::NanosDurationU64 {
+        fugit::NanosDurationU64::from_ticks(self.actual_duration().0)
+    }
+
+    #[cfg(feature = "fugit")]
+    fn checked_from_product(
+        product: u64,
+        divisor: u64,
+        clock: &'clock C,
+    ) -> Result<Self, TicksOverflow> {
+        // Round up, so we never end up with a shorter duration.
+        let ticks = product.div_ceil(divisor);
+
+        if ticks > u32::MAX as u64 {
+            return Err(TicksOverflow);
+        }
+
+        Ok(Ticks {
+            value: ticks as u32,
+            clock,
+        })
+    }
+
+    fn from_duration(
+        value: u32,
+        units_per_second: u64,
+        clock: &'clock C,
+    ) -> Self {
+        let product = value as u64 * clock.hz() as u64;
+
+        // Round up, so we never end up with a shorter duration.
+        let ticks = if product == 0 {
+            0
+        } else {
+            (product - 1) / units_per_second + 1
+        };
+
+        let value = if ticks > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            ticks as u32
+        };
+
+        Ticks { value, clock }
+    }
+}
+
 impl<'clock, Clock> Clone for Ticks<'clock, Clock> {
     fn clone(&self) -> Self {
         Ticks {
@@ -37,6 +471,101 @@ impl<'clock, Clock> Clone for Ticks<'clock, Clock> {
 
 impl<'clock, Clock> Copy for Ticks<'clock, Clock> {}
 
+/// Formats the number of ticks together with the duration they represent
+///
+/// The duration is printed with three decimal places, in whichever unit fits
+/// best. No floating-point math is used.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::clock::{Frequency, Ticks};
+///
+/// struct Clock;
+///
+/// impl Frequency for Clock {
+///     fn hz(&self) -> u32 { 750_000 }
+/// }
+///
+/// let ticks = Ticks { value: 750_000, clock: &Clock };
+/// assert_eq!(
+///     format!("{:?}", ticks),
+///     "Ticks { 750000 (1.000 s @ 750kHz) }",
+/// );
+///
+/// let ticks = Ticks { value: 8, clock: &Clock };
+/// assert_eq!(
+///     format!("{:?}", ticks),
+///     "Ticks { 8 (10.666 us @ 750kHz) }",
+/// );
+/// ```
+impl<'clock, C> fmt::Debug for Ticks<'clock, C>
+where
+    C: Frequency,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ticks {{ {} (", self.value)?;
+
+        let ns = self.actual_duration().0;
+        let units = [(1_000_000_000, "s"), (1_000_000, "ms"), (1_000, "us")];
+        match units.iter().find(|&&(unit, _)| ns >= unit) {
+            Some(&(unit, name)) => write!(
+                f,
+                "{}.{:03} {}",
+                ns / unit,
+                ns % unit / (unit / 1_000),
+                name
+            )?,
+            None => write!(f, "{} ns", ns)?,
+        }
+
+        let hz = self.clock.hz();
+        if hz.is_multiple_of(1_000_000) {
+            write!(f, " @ {}MHz) }}", hz / 1_000_000)
+        } else if hz.is_multiple_of(1_000) {
+            write!(f, " @ {}kHz) }}", hz / 1_000)
+        } else {
+            write!(f, " @ {}Hz) }}", hz)
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<'clock, C> defmt::Format for Ticks<'clock, C>
+where
+    C: Frequency,
+{
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "Ticks {{ {=u32} ({=u64} ns @ {=u32} Hz) }}",
+            self.value,
+            self.actual_duration().0,
+            self.clock.hz(),
+        )
+    }
+}
+
+/// Indicates that a number of ticks was zero
+///
+/// Returned by [`Ticks::new`].
+///
+/// [`Ticks::new`]: struct.Ticks.html#method.new
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ZeroTicks;
+
+/// Indicates that a number of ticks doesn't fit into a `u32`
+///
+/// Returned by [`Ticks::try_from_fugit`] and [`Ticks::try_from_fugit_rate`].
+///
+/// [`Ticks::try_from_fugit`]: struct.Ticks.html#method.try_from_fugit
+/// [`Ticks::try_from_fugit_rate`]: struct.Ticks.html#method.try_from_fugit_rate
+#[cfg(feature = "fugit")]
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TicksOverflow;
+
 /// Implemented by clocks that can return a frequency
 ///
 /// Implementations of this trait might be very simple, for clocks that run at
@@ -1,7 +1,18 @@
-//! API for delays with the systick timer
+//! API for delays with the systick timer or the self-wake-up timer (WKT)
 //!
-//! Please be aware of potential overflows when using `delay_us`.
-//! E.g. at 30MHz the maximum delay is 146 seconds.
+//! Please be aware of potential overflows when using `delay_us` with
+//! [`Delay`]. E.g. at 30MHz the maximum delay is 146 seconds.
+//!
+//! If the SysTick timer is needed for something else, [`WktDelay`] can be used
+//! instead. See its documentation for details.
+//!
+//! For very short delays, where both timers are too coarse, [`DelayCycles`] or
+//! [`cycles`] can be used. They busy-loop for a number of CPU cycles, without
+//! requiring a timer.
+//!
+//! All of these block until the delay has elapsed. If other work needs to be
+//! done in the meantime, [`NonBlocking`] can be used instead. If the `async`
+//! feature is enabled, [`WktDelay`] also provides async methods.
 //!
 //! # Example
 //!
@@ -19,19 +30,52 @@
 //!     delay.delay_ms(1_000_u16);
 //! }
 //! ```
+//!
+//! [`Delay`]: struct.Delay.html
+//! [`WktDelay`]: struct.WktDelay.html
+//! [`DelayCycles`]: struct.DelayCycles.html
+//! [`cycles`]: fn.cycles.html
+//! [`NonBlocking`]: struct.NonBlocking.html
 
 use cortex_m::peripheral::syst::SystClkSource;
 
-use crate::pac::SYST;
-use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+use crate::{
+    clock::{self, Ticks},
+    pac::SYST,
+    timer,
+    wkt::{self, WKT},
+};
+use embedded_hal::{
+    blocking::delay::{DelayMs, DelayUs},
+    timer::CountDown,
+};
 use embedded_hal_alpha::blocking::delay::{
     DelayMs as DelayMsAlpha, DelayUs as DelayUsAlpha,
 };
+use embedded_time::duration::Nanoseconds;
 use void::Void;
 
 const SYSTICK_RANGE: u32 = 0x0100_0000;
 const SYSTEM_CLOCK: u32 = 12_000_000;
 
+/// Implemented by delay providers that know their resolution
+///
+/// A delay provider rounds every requested delay up to a multiple of its
+/// resolution, so a delay can take up to one resolution step longer than
+/// requested. Code with tight timing requirements, like [`OneWire`], uses this
+/// to adjust its timing, or to reject delay providers that are too coarse.
+///
+/// [`OneWire`]: ../onewire/struct.OneWire.html
+pub trait Resolution {
+    /// The resolution of the delay provider
+    fn resolution(&self) -> Nanoseconds<u32>;
+}
+
+/// Returns the duration of one cycle of a clock running at `hz`, rounded up
+fn period(hz: u32) -> Nanoseconds<u32> {
+    Nanoseconds(1_000_000_000_u32.div_ceil(hz))
+}
+
 /// System timer (SysTick) as a delay provider
 ///
 /// # `embedded-hal` traits
@@ -60,6 +104,21 @@ impl Delay {
         // As access to the count register is possible without a reference to the systick, we can
         // safely clone the enabled instance.
     }
+
+    /// Pauses execution for `us` microseconds, returning the actual delay
+    ///
+    /// Works like [`delay_us`], but returns the duration the delay actually
+    /// lasted, according to the number of SysTick ticks it was converted to.
+    ///
+    /// [`delay_us`]: #impl-DelayUs%3Cu32%3E
+    pub fn delay_us_actual(&mut self, us: u32) -> Nanoseconds<u64> {
+        self.delay_us(us);
+
+        // Can't overflow, as `delay_us` has already made sure that the number
+        // of ticks fits into a `u32`.
+        let ticks = us as u64 * self.scale as u64;
+        Nanoseconds(ticks * 1_000_000_000 / SYSTEM_CLOCK as u64)
+    }
 }
 
 impl DelayMs<u32> for Delay {
@@ -119,6 +178,13 @@ impl DelayMsAlpha<u8> for Delay {
     }
 }
 
+impl Resolution for Delay {
+    /// One tick of the system clock
+    fn resolution(&self) -> Nanoseconds<u32> {
+        period(SYSTEM_CLOCK)
+    }
+}
+
 // At 30MHz (the maximum frequency), this overflows at approx. 2^32 / 30 = 146 seconds
 impl DelayUs<u32> for Delay {
     /// Pauses execution for `us` microseconds
@@ -189,3 +255,638 @@ impl DelayUsAlpha<u8> for Delay {
         Ok(self.delay_us(us))
     }
 }
+
+/// Self-wake-up timer (WKT) as a delay provider
+///
+/// Leaves the SysTick timer free for other purposes, like serving as the tick
+/// of an operating system. Any clock supported by the WKT can be used. See
+/// [`wkt::Clock`] for details.
+///
+/// The resolution of the delay is one tick of the selected clock. Requested
+/// delays are rounded up to the next tick, so a delay is never shorter than
+/// requested. With the IRC/FRO-derived clock (750 kHz), one tick is about
+/// 1.33 µs. With the low-power clock (10 kHz), it is 100 µs.
+///
+/// The WKT counts down while waiting. It must not be used for anything else,
+/// while it is owned by `WktDelay`.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     delay::WktDelay,
+///     Peripherals,
+/// };
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let wkt = p.WKT.enable(&mut syscon.handle);
+///
+/// let mut delay = WktDelay::new(wkt, &syscon.iosc_derived_clock);
+/// delay.delay_ms(1_000_u16);
+/// ```
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::blocking::delay::DelayUs`]
+/// - [`embedded_hal::blocking::delay::DelayMs`]
+///
+/// [`wkt::Clock`]: ../wkt/trait.Clock.html
+/// [`embedded_hal::blocking::delay::DelayUs`]: #impl-DelayUs%3Cu32%3E
+/// [`embedded_hal::blocking::delay::DelayMs`]: #impl-DelayMs%3Cu32%3E
+pub struct WktDelay<'clock, C> {
+    wkt: WKT,
+    clock: &'clock C,
+}
+
+impl<'clock, C> WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Configures the self-wake-up timer (WKT) as a delay provider
+    ///
+    /// Selects `clock` as the WKT's clock source.
+    pub fn new(mut wkt: WKT, clock: &'clock C) -> Self {
+        wkt.select_clock::<C>();
+        WktDelay { wkt, clock }
+    }
+
+    /// Return the WKT
+    ///
+    /// Consumes this instance of `WktDelay`, releasing the WKT for other uses.
+    pub fn free(self) -> WKT {
+        self.wkt
+    }
+
+    /// Pauses execution for `us` microseconds, returning the actual delay
+    ///
+    /// Works like [`delay_us`], but returns the duration the delay actually
+    /// lasted, according to the number of ticks of the WKT's clock it was
+    /// rounded to. See [`Ticks::actual_duration`].
+    ///
+    /// [`delay_us`]: #impl-DelayUs%3Cu32%3E
+    /// [`Ticks::actual_duration`]: ../clock/struct.Ticks.html#method.actual_duration
+    pub fn delay_us_actual(&mut self, us: u32) -> Nanoseconds<u64> {
+        let ticks = Ticks::from_us(us, self.clock);
+        self.wait(ticks);
+        ticks.actual_duration()
+    }
+
+    fn wait(&mut self, ticks: Ticks<C>) {
+        // The alarm will never fire, if we start the timer with zero ticks.
+        if ticks.value == 0 {
+            return;
+        }
+
+        self.wkt.start(ticks.value);
+        while let Err(nb::Error::WouldBlock) = self.wkt.wait() {}
+    }
+}
+
+impl<'clock, C> Resolution for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// One tick of the WKT's clock
+    fn resolution(&self) -> Nanoseconds<u32> {
+        period(self.clock.hz())
+    }
+}
+
+/// Async API
+///
+/// These methods are only available, if the `async` feature is enabled. They
+/// work like their blocking counterparts, but return control to the executor
+/// while the WKT counts down. Please refer to the [WKT's async API] for the
+/// requirements regarding its interrupt.
+///
+/// If a returned future is dropped before it completes, the WKT is stopped.
+///
+/// These methods can't be provided as an implementation of the
+/// `embedded_hal_async::delay::DelayNs` trait yet, as `embedded-hal-async`
+/// depends on a version of `embedded-hal` that conflicts with the pre-release
+/// version this crate depends on.
+///
+/// [WKT's async API]: ../wkt/struct.WKT.html#method.count_down_async
+#[cfg(feature = "async")]
+impl<'clock, C> WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses the task for `ns` nanoseconds
+    pub async fn delay_ns_async(&mut self, ns: u32) {
+        let ticks = Ticks::from_ns(ns, self.clock);
+        self.wkt.count_down_async(ticks.value).await
+    }
+
+    /// Pauses the task for `us` microseconds
+    pub async fn delay_us_async(&mut self, us: u32) {
+        let ticks = Ticks::from_us(us, self.clock);
+        self.wkt.count_down_async(ticks.value).await
+    }
+
+    /// Pauses the task for `ms` milliseconds
+    pub async fn delay_ms_async(&mut self, ms: u32) {
+        let ticks = Ticks::from_ms(ms, self.clock);
+        self.wkt.count_down_async(ticks.value).await
+    }
+}
+
+impl<'clock, C> DelayMs<u32> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, mut ms: u32) {
+        // The number of ticks might not fit into the WKT's counter, so we wait
+        // in chunks that are small enough for any supported clock.
+        const MAX_MS: u32 = 0x0000_FFFF;
+        while ms != 0 {
+            let current_ms = if ms <= MAX_MS { ms } else { MAX_MS };
+            self.wait(Ticks::from_ms(current_ms, self.clock));
+            ms -= current_ms;
+        }
+    }
+}
+
+impl<'clock, C> DelayMsAlpha<u32> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    type Error = Void;
+
+    /// Pauses execution for `ms` milliseconds
+    fn try_delay_ms(&mut self, ms: u32) -> Result<(), Self::Error> {
+        self.delay_ms(ms);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayMs<u16> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u16) {
+        self.wait(Ticks::from_ms(ms as u32, self.clock));
+    }
+}
+
+impl<'clock, C> DelayMsAlpha<u16> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    type Error = Void;
+
+    /// Pauses execution for `ms` milliseconds
+    fn try_delay_ms(&mut self, ms: u16) -> Result<(), Self::Error> {
+        self.delay_ms(ms);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayMs<u8> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u8) {
+        self.delay_ms(ms as u16);
+    }
+}
+
+impl<'clock, C> DelayMsAlpha<u8> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    type Error = Void;
+
+    /// Pauses execution for `ms` milliseconds
+    fn try_delay_ms(&mut self, ms: u8) -> Result<(), Self::Error> {
+        self.delay_ms(ms);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayUs<u32> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses execution for `us` microseconds
+    ///
+    /// The delay is rounded up to the next tick of the WKT's clock.
+    fn delay_us(&mut self, us: u32) {
+        self.wait(Ticks::from_us(us, self.clock));
+    }
+}
+
+impl<'clock, C> DelayUsAlpha<u32> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u32) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayUs<u16> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses execution for `us` microseconds
+    fn delay_us(&mut self, us: u16) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl<'clock, C> DelayUsAlpha<u16> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u16) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayUs<u8> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    /// Pauses execution for `us` microseconds
+    fn delay_us(&mut self, us: u8) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl<'clock, C> DelayUsAlpha<u8> for WktDelay<'clock, C>
+where
+    C: wkt::Clock + clock::Frequency + clock::Enabled,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u8) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+/// Busy-loop for at least `n` CPU cycles
+///
+/// Uses [`cortex_m::asm::delay`], which never delays for less than the
+/// requested number of cycles. The actual delay can be longer, due to flash
+/// wait states, the overhead of the call, or interrupts being serviced in the
+/// meantime.
+///
+/// [`cortex_m::asm::delay`]: https://docs.rs/cortex-m/0.6.7/cortex_m/asm/fn.delay.html
+pub fn cycles(n: u32) {
+    cortex_m::asm::delay(n);
+}
+
+/// Convert a duration in microseconds into CPU cycles
+///
+/// `frequency` is the frequency of the CPU clock, in Hz. The result is rounded
+/// up, so a delay of the returned number of cycles is never shorter than
+/// requested. If the result doesn't fit into a `u32`, it saturates at
+/// `u32::MAX`.
+///
+/// # Example
+///
+/// ```
+/// use lpc8xx_hal::delay::us_to_cycles;
+///
+/// assert_eq!(us_to_cycles(10, 12_000_000), 120);
+/// assert_eq!(us_to_cycles(1, 30_000_000), 30);
+///
+/// // 1 µs at 750 kHz is 0.75 cycles, which is rounded up.
+/// assert_eq!(us_to_cycles(1, 750_000), 1);
+/// assert_eq!(us_to_cycles(u32::MAX, 30_000_000), u32::MAX);
+/// ```
+pub fn us_to_cycles(us: u32, frequency: u32) -> u32 {
+    let cycles = (us as u64 * frequency as u64).div_ceil(1_000_000);
+
+    if cycles > u32::MAX as u64 {
+        u32::MAX
+    } else {
+        cycles as u32
+    }
+}
+
+/// CPU cycle counting as a delay provider
+///
+/// Busy-loops for the number of CPU cycles that corresponds to the requested
+/// delay, based on the frequency of the clock that is passed to [`new`]. That
+/// clock must be the one the CPU runs at, which is the main clock by default.
+///
+/// No timer is required, and the resolution is much better than that of
+/// [`Delay`] or [`WktDelay`], which makes this useful for short delays, like
+/// the setup times of bit-banged protocols. The delay is never shorter than
+/// requested, but it can be longer, due to flash wait states, the overhead of
+/// the call, or interrupts being serviced in the meantime. Requests that are
+/// shorter than that overhead (a few cycles) can't be honored precisely.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     delay::DelayCycles,
+///     Peripherals,
+/// };
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let main_clock = syscon.handle.set_main_clock(&syscon.iosc);
+///
+/// let mut delay = DelayCycles::new(&main_clock);
+/// delay.delay_us(5_u8);
+/// ```
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::blocking::delay::DelayUs`]
+///
+/// [`new`]: #method.new
+/// [`Delay`]: struct.Delay.html
+/// [`WktDelay`]: struct.WktDelay.html
+/// [`embedded_hal::blocking::delay::DelayUs`]: #impl-DelayUs%3Cu32%3E
+pub struct DelayCycles<'clock, C> {
+    clock: &'clock C,
+}
+
+impl<'clock, C> DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    /// Create a delay provider for a CPU running at `clock`
+    pub fn new(clock: &'clock C) -> Self {
+        DelayCycles { clock }
+    }
+
+    /// Pauses execution for `us` microseconds, returning the actual delay
+    ///
+    /// Works like [`delay_us`], but returns the duration the delay actually
+    /// lasted, according to the number of CPU cycles it was rounded to. See
+    /// [`Ticks::actual_duration`]. Any overhead on top of that, as described
+    /// in the documentation of [`DelayCycles`], is not included.
+    ///
+    /// [`delay_us`]: #impl-DelayUs%3Cu32%3E
+    /// [`Ticks::actual_duration`]: ../clock/struct.Ticks.html#method.actual_duration
+    /// [`DelayCycles`]: struct.DelayCycles.html
+    pub fn delay_us_actual(&mut self, us: u32) -> Nanoseconds<u64> {
+        let ticks = Ticks::from_us(us, self.clock);
+        cycles(ticks.value);
+        ticks.actual_duration()
+    }
+}
+
+impl<'clock, C> Resolution for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    /// One CPU cycle
+    fn resolution(&self) -> Nanoseconds<u32> {
+        period(self.clock.hz())
+    }
+}
+
+impl<'clock, C> DelayUs<u32> for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    /// Pauses execution for `us` microseconds
+    ///
+    /// The delay is rounded up to the next CPU cycle.
+    fn delay_us(&mut self, us: u32) {
+        cycles(us_to_cycles(us, self.clock.hz()));
+    }
+}
+
+impl<'clock, C> DelayUsAlpha<u32> for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u32) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayUs<u16> for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    /// Pauses execution for `us` microseconds
+    fn delay_us(&mut self, us: u16) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl<'clock, C> DelayUsAlpha<u16> for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u16) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+impl<'clock, C> DelayUs<u8> for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    /// Pauses execution for `us` microseconds
+    fn delay_us(&mut self, us: u8) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl<'clock, C> DelayUsAlpha<u8> for DelayCycles<'clock, C>
+where
+    C: clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u8) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+/// A delay that can be polled, instead of blocking until it has elapsed
+///
+/// Can be built on any [`Timer`], like the self-wake-up timer (WKT), an MRT
+/// channel, or the SysTick timer. Use [`start`] (or [`start_us`]/[`start_ms`])
+/// to start a delay, then [`poll`] it, until it returns `Ok`. Unlike with the
+/// blocking delays, or with the [`sleep`] API, the core is free to do other
+/// work in the meantime, which makes this suitable for cooperative loops.
+///
+/// Starting a new delay while another one is still running cancels the
+/// running one and starts over.
+///
+/// `clock` must be the clock that drives the timer. It is used to convert
+/// durations into ticks. Please note that this API doesn't configure the
+/// timer's clock source. For the WKT, use [`WKT::select_enabled_clock`] to do
+/// that.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{delay::NonBlocking, Peripherals};
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let mut wkt = p.WKT.enable(&mut syscon.handle);
+/// wkt.select_enabled_clock(&syscon.iosc_derived_clock);
+///
+/// let mut delay = NonBlocking::new(wkt, &syscon.iosc_derived_clock);
+///
+/// delay.start_ms(500);
+/// while delay.poll().is_err() {
+///     // do something else
+/// }
+/// ```
+///
+/// [`Timer`]: ../timer/trait.Timer.html
+/// [`start`]: #method.start
+/// [`start_us`]: #method.start_us
+/// [`start_ms`]: #method.start_ms
+/// [`poll`]: #method.poll
+/// [`sleep`]: ../sleep/index.html
+/// [`WKT::select_enabled_clock`]: ../wkt/struct.WKT.html#method.select_enabled_clock
+pub struct NonBlocking<'clock, T, C> {
+    timer: T,
+    clock: &'clock C,
+    running: bool,
+}
+
+impl<'clock, T, C> NonBlocking<'clock, T, C>
+where
+    T: timer::Timer,
+    C: clock::Frequency,
+{
+    /// Create a non-blocking delay from a timer and the clock that drives it
+    ///
+    /// No delay is running initially, so [`poll`] returns `Ok` right away.
+    ///
+    /// [`poll`]: #method.poll
+    pub fn new(timer: T, clock: &'clock C) -> Self {
+        NonBlocking {
+            timer,
+            clock,
+            running: false,
+        }
+    }
+
+    /// Start a delay that lasts `ticks`
+    ///
+    /// Cancels a delay that is still running. A delay of zero ticks has
+    /// elapsed immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is larger than `T::MAX_TICKS`.
+    pub fn start(&mut self, ticks: Ticks<'clock, C>) {
+        assert!(ticks.value <= T::MAX_TICKS);
+
+        if self.running {
+            self.timer.cancel();
+        }
+
+        self.running = ticks.value > 0;
+        if self.running {
+            self.timer.start(ticks.value);
+        }
+    }
+
+    /// Start a delay that lasts `us` microseconds
+    ///
+    /// The delay is rounded up to the next tick. See [`start`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the delay is longer than the timer can count, meaning the
+    /// number of ticks at the frequency of the clock passed to [`new`] is
+    /// larger than `T::MAX_TICKS`.
+    ///
+    /// [`start`]: #method.start
+    /// [`new`]: #method.new
+    pub fn start_us(&mut self, us: u32) {
+        self.start(Ticks::from_us(us, self.clock));
+    }
+
+    /// Start a delay that lasts `ms` milliseconds
+    ///
+    /// The delay is rounded up to the next tick. See [`start`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the delay is longer than the timer can count, meaning the
+    /// number of ticks at the frequency of the clock passed to [`new`] is
+    /// larger than `T::MAX_TICKS`.
+    ///
+    /// [`start`]: #method.start
+    /// [`new`]: #method.new
+    pub fn start_ms(&mut self, ms: u32) {
+        self.start(Ticks::from_ms(ms, self.clock));
+    }
+
+    /// Check whether the delay has elapsed
+    ///
+    /// Returns `nb::Error::WouldBlock`, while the delay is running. Once it
+    /// has elapsed, returns `Ok`, and keeps doing so until a new delay is
+    /// started.
+    pub fn poll(&mut self) -> nb::Result<(), Void> {
+        if !self.running {
+            return Ok(());
+        }
+
+        self.timer.wait()?;
+
+        // Some timers restart automatically. Make sure this one doesn't keep
+        // running in the background.
+        self.timer.cancel();
+        self.running = false;
+
+        Ok(())
+    }
+
+    /// Cancel a running delay
+    ///
+    /// Afterwards, [`poll`] returns `Ok`.
+    ///
+    /// [`poll`]: #method.poll
+    pub fn cancel(&mut self) {
+        if self.running {
+            self.timer.cancel();
+            self.running = false;
+        }
+    }
+
+    /// Return the timer
+    ///
+    /// Cancels a running delay, then releases the timer for other uses.
+    pub fn free(mut self) -> T {
+        self.cancel();
+        self.timer
+    }
+}
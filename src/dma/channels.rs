@@ -8,14 +8,17 @@ use crate::{
         self,
         dma0::{
             channel::{CFG, XFERCFG},
-            ACTIVE0, BUSY0, ENABLESET0, ERRINT0, INTA0, INTB0, INTENCLR0,
-            INTENSET0, SETTRIG0,
+            ABORT0, ACTIVE0, BUSY0, ENABLECLR0, ENABLESET0, ERRINT0, INTA0,
+            INTB0, INTENCLR0, INTENSET0, SETTRIG0,
         },
     },
     reg_proxy::{Reg, RegProxy},
 };
 
-use super::descriptors::ChannelDescriptor;
+use super::{
+    descriptors::ChannelDescriptor,
+    transfer::{state::Ready, Transfer},
+};
 
 /// A DMA channel
 ///
@@ -90,6 +93,61 @@ where
         let registers = SharedRegisters::<C>::new();
         registers.disable_interrupts();
     }
+
+    /// Copy from one memory buffer to another
+    ///
+    /// Returns a [`Transfer`] that copies `source` to `dest`, once it has been
+    /// started. The transfer doesn't involve a peripheral, so it runs as fast
+    /// as the DMA controller can move the data.
+    ///
+    /// Any channel can be used for this, but the peripheral that the channel
+    /// is associated with must not request DMA transfers while the copy is in
+    /// progress. Since the channel is moved into the [`Transfer`], the HAL
+    /// can't use it for anything else in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `source` or `dest` is 0 or larger than 1024,
+    /// or if their lengths differ.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use core::ptr;
+    ///
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// static SOURCE: [u8; 4] = [1, 2, 3, 4];
+    /// static mut DEST: [u8; 4] = [0; 4];
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let dma = p.DMA.enable(&mut syscon.handle);
+    ///
+    /// // Sound, as this is the only reference to `DEST` that is ever created.
+    /// let dest = unsafe { &mut *ptr::addr_of_mut!(DEST) };
+    ///
+    /// let payload = dma
+    ///     .channels
+    ///     .channel0
+    ///     .copy(&SOURCE, dest)
+    ///     .start()
+    ///     .wait()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(payload.dest, &[1, 2, 3, 4]);
+    /// ```
+    ///
+    /// [`Transfer`]: ../transfer/struct.Transfer.html
+    pub fn copy(
+        self,
+        source: &'static [u8],
+        dest: &'static mut [u8],
+    ) -> Transfer<Ready, C, &'static [u8], &'static mut [u8]> {
+        assert_eq!(source.len(), dest.len());
+        Transfer::new(self, source, dest)
+    }
 }
 
 /// Implemented for each DMA channel
@@ -113,8 +171,10 @@ pub trait Instance {
 }
 
 pub(super) struct SharedRegisters<C> {
+    abort0: &'static ABORT0,
     active0: &'static ACTIVE0,
     busy0: &'static BUSY0,
+    enableclr0: &'static ENABLECLR0,
     enableset0: &'static ENABLESET0,
     errint0: &'static ERRINT0,
     inta0: &'static INTA0,
@@ -139,8 +199,10 @@ where
             let registers = pac::DMA0::ptr();
 
             Self {
+                abort0: &(*registers).abort0,
                 active0: &(*registers).active0,
                 busy0: &(*registers).busy0,
+                enableclr0: &(*registers).enableclr0,
                 enableset0: &(*registers).enableset0,
                 errint0: &(*registers).errint0,
                 inta0: &(*registers).inta0,
@@ -175,6 +237,20 @@ where
         });
     }
 
+    pub(super) fn disable(&self) {
+        self.enableclr0.write(|w| {
+            // Sound, as all values assigned to `C::FLAG` are valid here.
+            unsafe { w.clr().bits(C::FLAG) }
+        });
+    }
+
+    pub(super) fn abort(&self) {
+        self.abort0.write(|w| {
+            // Sound, as all values assigned to `C::FLAG` are valid here.
+            unsafe { w.abortctrl().bits(C::FLAG) }
+        });
+    }
+
     pub(super) fn trigger(&self) {
         self.settrig0.write(|w| {
             // Sound, as all values assigned to `C::FLAG` are valid here.
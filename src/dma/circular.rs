@@ -0,0 +1,221 @@
+//! APIs related to circular DMA transfers
+
+use core::{
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use crate::{init_state::Enabled, pac::dma0::channel::xfercfg::DSTINC_A};
+
+use super::{
+    channels::{Instance, SharedRegisters},
+    transfer::Payload,
+    Channel, Source,
+};
+
+/// A circular DMA transfer from a peripheral into a buffer
+///
+/// The DMA controller continuously fills the buffer with data from the
+/// peripheral. Once the end of the buffer has been reached, it starts over at
+/// the beginning. This makes it possible to receive a continuous stream of
+/// data, without having to restart the transfer.
+///
+/// Instances of this struct can be acquired by calling a `read_circular`
+/// method of the peripheral that is the source of the transfer. The transfer
+/// is running from the moment it is returned by that method.
+///
+/// The application is responsible for draining the buffer often enough, using
+/// [`read`]. If the DMA controller overtakes the application, older data that
+/// wasn't read yet is overwritten without notice. The larger the buffer, the
+/// more time the application has.
+///
+/// [`read`]: #method.read
+pub struct CircularTransfer<C, S>
+where
+    C: Instance,
+{
+    payload: Payload<C, S, &'static mut [u8]>,
+    read_pos: usize,
+}
+
+impl<C, S> CircularTransfer<C, S>
+where
+    C: Instance,
+    S: Source,
+{
+    /// Create and start a new circular DMA transfer
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 1024, or if
+    /// `source` is not a peripheral.
+    ///
+    /// # Limitations
+    ///
+    /// The caller must make sure to call this method only for the correct
+    /// combination of channel and source.
+    pub(crate) fn new(
+        channel: Channel<C, Enabled>,
+        source: S,
+        buffer: &'static mut [u8],
+    ) -> Self {
+        assert!(!buffer.is_empty());
+        assert!(buffer.len() <= 1024);
+        assert!(source.is_valid());
+        assert!(source.transfer_count().is_none());
+
+        compiler_fence(Ordering::SeqCst);
+
+        // Configure channel
+        // See user manual, section 12.6.16.
+        channel.cfg.write(|w| {
+            w.periphreqen().enabled();
+            w.hwtrigen().disabled();
+            unsafe { w.chpriority().bits(0) }
+        });
+
+        // Set channel transfer configuration. Unlike a regular transfer, this
+        // one reloads the channel descriptor once it is complete.
+        // See user manual, section 12.6.18.
+        channel.xfercfg.write(|w| {
+            w.cfgvalid().valid();
+            w.reload().enabled();
+            w.swtrig().not_set();
+            // Keep the trigger set after the descriptor is exhausted, so the
+            // transfer continues after the reload.
+            w.clrtrig().not_cleared();
+            w.setinta().no_effect();
+            w.setintb().no_effect();
+            w.width().bit_8();
+            w.srcinc().variant(source.increment());
+            w.dstinc().variant(DSTINC_A::WIDTH_X_1);
+            // Sound, as the buffer length is limited to 1024 above.
+            unsafe { w.xfercount().bits(buffer.len() as u16 - 1) }
+        });
+
+        // Configure channel descriptor. It links back to itself, so the
+        // transfer starts over, whenever the buffer is full. On reload, the
+        // `config` field is copied into XFERCFG, so it needs to hold the same
+        // configuration we just wrote.
+        // See user manual, sections 12.5.2 and 12.5.3.
+        channel.descriptor.config = channel.xfercfg.read().bits();
+        channel.descriptor.source_end = source.end_addr();
+        // Sound, as we stay within the bounds of the slice.
+        channel.descriptor.dest_end =
+            unsafe { buffer.as_mut_ptr().add(buffer.len() - 1) };
+        channel.descriptor.next_desc = &*channel.descriptor;
+
+        let registers = SharedRegisters::<C>::new();
+        registers.reset_flags();
+        registers.enable();
+        registers.trigger();
+
+        Self {
+            payload: Payload {
+                channel,
+                source,
+                dest: buffer,
+            },
+            read_pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes that are available for reading
+    ///
+    /// This is the number of bytes the DMA controller has written to the
+    /// buffer, since the last call to [`read`]. If the DMA controller has
+    /// wrapped around and overtaken the application, the bytes that were
+    /// overwritten are not accounted for.
+    ///
+    /// [`read`]: #method.read
+    pub fn available(&self) -> usize {
+        let len = self.payload.dest.len();
+        (self.write_pos() + len - self.read_pos) % len
+    }
+
+    /// Read the available bytes into the provided buffer
+    ///
+    /// Reads as many bytes as are available (see [`available`]), but no more
+    /// than fit into `out`. Returns the number of bytes read. Never blocks.
+    ///
+    /// [`available`]: #method.available
+    pub fn read(&mut self, out: &mut [u8]) -> usize {
+        let len = self.payload.dest.len();
+        let n = self.available().min(out.len());
+
+        // Make sure we don't read from the buffer before we've seen the DMA
+        // controller's progress.
+        compiler_fence(Ordering::SeqCst);
+
+        let buffer = self.payload.dest.as_ptr();
+        for (i, byte) in out[..n].iter_mut().enumerate() {
+            let pos = (self.read_pos + i) % len;
+
+            // Sound, as `pos` is always within the bounds of the buffer. The
+            // read is volatile, as the DMA controller writes to the buffer
+            // behind the compiler's back.
+            *byte = unsafe { ptr::read_volatile(buffer.add(pos)) };
+        }
+
+        self.read_pos = (self.read_pos + n) % len;
+
+        n
+    }
+
+    /// Stop the transfer
+    ///
+    /// Disables the channel, waits until it is no longer busy, then aborts the
+    /// transfer. Returns the transfer payload, which contains all resources
+    /// that were held by this transfer. Any data that was not read at this
+    /// point remains in the buffer.
+    pub fn stop(self) -> Payload<C, S, &'static mut [u8]> {
+        let registers = SharedRegisters::<C>::new();
+
+        // See user manual, section 12.6.6.
+        registers.disable();
+        while registers.is_busy() {}
+        registers.abort();
+
+        compiler_fence(Ordering::SeqCst);
+
+        self.payload
+    }
+
+    /// The position in the buffer the DMA controller is going to write to next
+    fn write_pos(&self) -> usize {
+        write_pos(
+            self.payload.dest.len(),
+            self.payload.channel.xfercfg.read().xfercount().bits(),
+        )
+    }
+}
+
+/// Compute the write position from the buffer length and XFERCOUNT
+///
+/// XFERCOUNT counts down the remaining transfers, minus one. It is reset from
+/// the descriptor, once the buffer is full. While no transfer is active, it
+/// reads 0x3ff, which would be more than the buffer length, for buffers
+/// shorter than 1024 bytes. In that case, the buffer has just been filled, so
+/// the next write goes to the start of it.
+fn write_pos(len: usize, xfercount: u16) -> usize {
+    let remaining = (xfercount as usize + 1).min(len);
+    (len - remaining) % len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_pos;
+
+    #[test]
+    fn write_pos_follows_xfercount() {
+        assert_eq!(write_pos(16, 15), 0);
+        assert_eq!(write_pos(16, 14), 1);
+        assert_eq!(write_pos(16, 0), 15);
+    }
+
+    #[test]
+    fn write_pos_handles_idle_channel() {
+        assert_eq!(write_pos(16, 0x3ff), 0);
+        assert_eq!(write_pos(1024, 0x3ff), 0);
+    }
+}
@@ -20,10 +20,10 @@ impl DescriptorTable {
 #[derive(Clone, Copy)]
 #[repr(C, align(16))]
 pub(super) struct ChannelDescriptor {
-    config: u32,
+    pub(super) config: u32,
     pub(super) source_end: *const u8,
     pub(super) dest_end: *mut u8,
-    next_desc: *const ChannelDescriptor,
+    pub(super) next_desc: *const ChannelDescriptor,
 }
 
 impl ChannelDescriptor {
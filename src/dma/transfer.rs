@@ -20,15 +20,11 @@ use super::{
 /// A `Transfer` instance is used to represent a DMA transfer that uses a
 /// specific [`Channel`]. Instances of this can be acquired by calling a
 /// `write_all` or `read_all` method of the peripheral that should be involved
-/// in the transfer.
-///
-/// # Limitations
-///
-/// Currently, memory-to-memory transfers are not supported. If you need this
-/// features, feel free to [comment on the respective GitHub issue].
+/// in the transfer, or by calling [`Channel::copy`] for a memory-to-memory
+/// transfer.
 ///
 /// [`Channel`]: ../struct.Channel.html
-/// [comment on the respective GitHub issue]: https://github.com/lpc-rs/lpc8xx-hal/issues/125
+/// [`Channel::copy`]: ../struct.Channel.html#method.copy
 pub struct Transfer<State, C, S, D>
 where
     C: Instance,
@@ -48,7 +44,8 @@ where
     /// # Panics
     ///
     /// Panics, if the length of any buffer passed to this function is 0 or
-    /// larger than 1024.
+    /// larger than 1024. Panics, if both source and destination are memory,
+    /// but their lengths differ.
     ///
     /// # Limitations
     ///
@@ -66,22 +63,29 @@ where
 
         compiler_fence(Ordering::SeqCst);
 
-        // Currently we don't support memory-to-memory transfers, which means
-        // exactly one participant is providing the transfer count.
+        // If one participant is a peripheral, the other one provides the
+        // transfer count, and the peripheral paces the transfer using its DMA
+        // request. If both are memory, the transfer runs as fast as possible,
+        // once triggered by software.
         let source_count = source.transfer_count();
         let dest_count = dest.transfer_count();
-        let transfer_count = match (source_count, dest_count) {
-            (Some(transfer_count), None) => transfer_count,
-            (None, Some(transfer_count)) => transfer_count,
-            _ => {
-                panic!("Unsupported transfer type");
-            }
-        };
+        let (transfer_count, peripheral_request) =
+            match (source_count, dest_count) {
+                (Some(transfer_count), None) => (transfer_count, true),
+                (None, Some(transfer_count)) => (transfer_count, true),
+                (Some(source_count), Some(dest_count)) => {
+                    assert_eq!(source_count, dest_count);
+                    (source_count, false)
+                }
+                (None, None) => {
+                    panic!("Unsupported transfer type");
+                }
+            };
 
         // Configure channel
         // See user manual, section 12.6.16.
         channel.cfg.write(|w| {
-            w.periphreqen().enabled();
+            w.periphreqen().bit(peripheral_request);
             w.hwtrigen().disabled();
             unsafe { w.chpriority().bits(0) }
         });
@@ -23,9 +23,6 @@
 //!
 //! let mut syscon = p.SYSCON.split();
 //!
-//! #[cfg(feature = "82x")]
-//! let gpio = p.GPIO;
-//! #[cfg(feature = "845")]
 //! let gpio = p.GPIO.enable(&mut syscon.handle);
 //!
 //! let pio0_12 = p.pins.pio0_12.into_output_pin(
@@ -41,30 +38,46 @@
 //! [`GpioPin`]: struct.GpioPin.html
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
-use core::marker::PhantomData;
+use core::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+};
 
-use embedded_hal::digital::v2::{
-    InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin,
+use cortex_m::interrupt;
+use embedded_hal::{
+    blocking::delay::DelayUs,
+    digital::v2::{
+        InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin,
+    },
+    PwmPin,
 };
-use embedded_hal_alpha::digital::{
-    InputPin as InputPinAlpha, OutputPin as OutputPinAlpha,
-    StatefulOutputPin as StatefulOutputPinAlpha,
-    ToggleableOutputPin as ToggleableOutputPinAlpha,
+use embedded_hal_alpha::{
+    digital::{
+        InputPin as InputPinAlpha, OutputPin as OutputPinAlpha,
+        StatefulOutputPin as StatefulOutputPinAlpha,
+        ToggleableOutputPin as ToggleableOutputPinAlpha,
+    },
+    pwm::PwmPin as PwmPinAlpha,
 };
 use void::Void;
 
 use crate::{
     init_state, pac,
-    pins::{self, Token},
+    pins::{
+        self,
+        iocon::{IoconPin, Pull},
+        Pin, Token,
+    },
     syscon,
+    timer::Timer,
 };
 
 #[cfg(feature = "845")]
-use crate::pac::gpio::{CLR, DIRCLR, DIRSET, NOT, PIN, SET};
+use crate::pac::gpio::{CLR, DIR, DIRCLR, DIRSET, NOT, PIN, SET};
 #[cfg(feature = "82x")]
 use crate::pac::gpio::{
-    CLR0 as CLR, DIRCLR0 as DIRCLR, DIRSET0 as DIRSET, NOT0 as NOT,
-    PIN0 as PIN, SET0 as SET,
+    CLR0 as CLR, DIR0 as DIR, DIRCLR0 as DIRCLR, DIRSET0 as DIRSET,
+    NOT0 as NOT, PIN0 as PIN, SET0 as SET,
 };
 
 use self::direction::{Direction, DynamicPinErr};
@@ -100,6 +113,13 @@ pub struct GPIO<State = init_state::Enabled> {
     /// [`enable`]: #method.enable
     /// [`disable`]: #method.disable
     pub tokens: pins::Tokens<State>,
+
+    /// The MASK registers of all ports
+    ///
+    /// Required to create a [`MaskedPort`].
+    ///
+    /// [`MaskedPort`]: struct.MaskedPort.html
+    pub masks: Masks,
 }
 
 impl<State> GPIO<State> {
@@ -109,6 +129,7 @@ impl<State> GPIO<State> {
             _state: PhantomData,
 
             tokens: pins::Tokens::new(),
+            masks: Masks::new(),
         }
     }
 
@@ -154,10 +175,35 @@ impl GPIO<init_state::Disabled> {
             gpio: self.gpio,
             _state: PhantomData,
             tokens,
+            masks: self.masks,
         }
     }
 }
 
+#[cfg(feature = "82x")]
+impl GPIO<init_state::Enabled> {
+    /// Enable the GPIO peripheral
+    ///
+    /// This method is only available on LPC82x, where the GPIO peripheral is
+    /// already enabled by default. It doesn't do anything, but it allows the
+    /// same code to be used on both LPC82x and LPC845:
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    /// ```
+    ///
+    /// If the GPIO peripheral has been disabled, it can be enabled again, using
+    /// the `enable` method of `GPIO<Disabled>`.
+    pub fn enable(self, _syscon: &mut syscon::Handle) -> Self {
+        self
+    }
+}
+
 impl GPIO<init_state::Enabled> {
     /// Disable the GPIO peripheral
     ///
@@ -183,10 +229,207 @@ impl GPIO<init_state::Enabled> {
             gpio: self.gpio,
             _state: PhantomData,
             tokens,
+            masks: self.masks,
         }
     }
 }
 
+/// Returns the output levels that are currently set for a port
+///
+/// Bit `n` of the returned value corresponds to pin `n` of the port. A set
+/// bit means that the pin is commanded to output HIGH, regardless of the
+/// level that is actually present on the pin. The latter can differ, for
+/// example, if the pin is configured as open-drain, or if the line is
+/// shorted. Bits of pins that are configured as inputs reflect the level
+/// they would output, if they were switched to output.
+///
+/// The value is read back from the hardware's output register (by reading the
+/// SET register), so it includes changes made by other means than this API.
+///
+/// # Panics
+///
+/// Panics, if `port` doesn't exist on the target. The LPC82x only has port
+/// 0, the LPC845 has ports 0 and 1.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{self, Level},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let mut pin = p.pins.pio0_12.into_output_pin(gpio.tokens.pio0_12, Level::Low);
+/// assert_eq!(gpio::output_state(0) & (0x1 << 12), 0);
+///
+/// pin.toggle();
+/// assert_eq!(gpio::output_state(0) & (0x1 << 12), 0x1 << 12);
+/// ```
+pub fn output_state(port: usize) -> u32 {
+    // This is sound, as we only read from a register.
+    let gpio = unsafe { &*pac::GPIO::ptr() };
+    let registers = Registers::new(gpio);
+    assert!(port < registers.set.len());
+
+    registers.set[port].read().setp().bits()
+}
+
+/// Returns the directions that are currently set for a port
+///
+/// Bit `n` of the returned value corresponds to pin `n` of the port. A set
+/// bit means that the pin is configured as an output, a cleared bit means it
+/// is configured as an input. Pins that are not used for GPIO are reported as
+/// inputs, unless their direction has been set to output before.
+///
+/// The value is read from the hardware's DIR register in a single access, so
+/// it includes changes made by other means than this API. This makes it
+/// possible to take a snapshot of the directions of many pins at once, for
+/// example of multiple dynamic pins.
+///
+/// # Panics
+///
+/// Panics, if `port` doesn't exist on the target. The LPC82x only has port
+/// 0, the LPC845 has ports 0 and 1.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{self, Level},
+///     pins::DynamicPinDirection,
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let mut a = p.pins.pio0_12.into_dynamic_pin(
+///     gpio.tokens.pio0_12,
+///     Level::Low,
+///     DynamicPinDirection::Output,
+/// );
+/// let b = p.pins.pio0_13.into_dynamic_pin(
+///     gpio.tokens.pio0_13,
+///     Level::Low,
+///     DynamicPinDirection::Input,
+/// );
+///
+/// let mask = (0x1 << 12) | (0x1 << 13);
+/// assert_eq!(gpio::direction_state(0) & mask, 0x1 << 12);
+///
+/// a.switch_to_input();
+/// assert_eq!(gpio::direction_state(0) & mask, 0);
+/// ```
+pub fn direction_state(port: usize) -> u32 {
+    // This is sound, as we only read from a register.
+    let gpio = unsafe { &*pac::GPIO::ptr() };
+    direction_state_in(&Registers::new(gpio), port)
+}
+
+fn direction_state_in(registers: &Registers, port: usize) -> u32 {
+    assert!(port < registers.dir.len());
+    registers.dir[port].read().dirp().bits()
+}
+
+/// Set the output level of pins, bypassing the GPIO API
+///
+/// Writes `mask` to the SET or CLR register of `port`, depending on `level`,
+/// which sets the output level of the pins whose bits are set in `mask`. This
+/// works without access to the respective [`GpioPin`]s.
+///
+/// This is intended for diagnostics in panic and fault handlers, like
+/// blinking an error LED, where the ownership of the pin is no longer
+/// available. Don't use it for anything else.
+///
+/// The pins need to have been configured as GPIO outputs before, for the
+/// write to have a visible effect. Other pins are not affected by the write.
+///
+/// # Safety
+///
+/// This function bypasses the ownership model of the GPIO API, which relies
+/// on each pin's bits in the GPIO registers only being written by the
+/// `GpioPin` that owns it. The pins in `mask` might be owned by a `GpioPin`
+/// that still believes to have exclusive control over them, or they might be
+/// used by another peripheral.
+///
+/// The caller must make sure that this can't cause any harm, for example
+/// because the regular program will never run again, as is the case in a
+/// panic handler. The GPIO peripheral must have been enabled.
+///
+/// # Invalid ports
+///
+/// Does nothing, if `port` doesn't exist on the target. The LPC82x only has
+/// port 0, the LPC845 has ports 0 and 1. This function never panics, as a
+/// panic within a panic handler would hide the original problem.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::gpio::{self, Level};
+///
+/// // Call this from your panic handler (the function annotated with
+/// // `#[panic_handler]`). The LED on PIO0_12 must have been configured as an
+/// // output during initialization.
+/// fn on_panic() -> ! {
+///     unsafe { gpio::force_set(0, 0x1 << 12, Level::High) };
+///
+///     loop {
+///         unsafe { gpio::force_toggle(0, 0x1 << 12) };
+///         lpc8xx_hal::cortex_m::asm::delay(6_000_000);
+///     }
+/// }
+/// ```
+///
+/// [`GpioPin`]: struct.GpioPin.html
+pub unsafe fn force_set(port: usize, mask: u32, level: Level) {
+    let gpio = &*pac::GPIO::ptr();
+    force_set_in(&Registers::new(gpio), port, mask, level);
+}
+
+fn force_set_in(registers: &Registers, port: usize, mask: u32, level: Level) {
+    if port >= registers.set.len() {
+        return;
+    }
+
+    set_level(registers, port, mask, level);
+}
+
+/// Toggle the output level of pins, bypassing the GPIO API
+///
+/// Writes `mask` to the NOT register of `port`, which toggles the output level
+/// of the pins whose bits are set in `mask`. See [`force_set`] for the
+/// intended use and an example.
+///
+/// Like [`force_set`], this does nothing, if `port` doesn't exist on the
+/// target.
+///
+/// # Safety
+///
+/// See [`force_set`].
+///
+/// [`force_set`]: fn.force_set.html
+pub unsafe fn force_toggle(port: usize, mask: u32) {
+    let gpio = &*pac::GPIO::ptr();
+    force_toggle_in(&Registers::new(gpio), port, mask);
+}
+
+fn force_toggle_in(registers: &Registers, port: usize, mask: u32) {
+    if port >= registers.not.len() {
+        return;
+    }
+
+    toggle(registers, port, mask);
+}
+
 /// A pin used for general purpose I/O (GPIO)
 ///
 /// You can get access to an instance of this struct by switching a pin to the
@@ -207,6 +450,7 @@ impl GPIO<init_state::Enabled> {
 /// [`embedded_hal::digital::v2::StatefulOutputPin`]: #impl-StatefulOutputPin
 /// [`embedded_hal::digital::v2::ToggleableOutputPin`]: #impl-ToggleableOutputPin
 pub struct GpioPin<T, D> {
+    ty: T,
     token: pins::Token<T, init_state::Enabled>,
     _direction: D,
 }
@@ -217,6 +461,7 @@ where
     D: Direction,
 {
     pub(crate) fn new(
+        ty: T,
         token: Token<T, init_state::Enabled>,
         arg: D::SwitchArg,
     ) -> Self {
@@ -226,13 +471,172 @@ where
         // no race conditions.
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
-        let direction = D::switch::<T>(&registers, arg);
+
+        // Sound, as `init` only touches the IOCON clock bit, restoring it to
+        // its previous state without interruption, and the IOCON register of
+        // the pin represented by `T`, which we own.
+        let syscon = unsafe { &*pac::SYSCON::ptr() };
+
+        let direction =
+            init::<T, D>(&registers, syscon, pac::IOCON::ptr() as *mut _, arg);
 
         Self {
+            ty,
             token,
             _direction: direction,
         }
     }
+
+    /// Transition pin back to the unused state
+    ///
+    /// Consumes this `GpioPin` instance and returns the pin in the unused
+    /// state, together with the GPIO token that was used to create this
+    /// instance. From there, the pin can be used for something else, like
+    /// function assignment via the switch matrix (see
+    /// [`Pin::into_swm_pin`]), or transitioned back into GPIO mode using the
+    /// returned token.
+    ///
+    /// Before the pin is returned, its direction is set to input, to make sure
+    /// it's not driving its output anymore.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     prelude::*,
+    ///     Peripherals,
+    ///     gpio,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm = p.SWM.split();
+    ///
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// // Use the pin for GPIO for a while
+    /// let mut pin = p.pins.pio0_12.into_output_pin(
+    ///     gpio.tokens.pio0_12,
+    ///     gpio::Level::Low,
+    /// );
+    /// pin.set_high();
+    ///
+    /// // Transition the pin back into the unused state, then assign a movable
+    /// // function to it.
+    /// let (pin, token) = pin.into_unused_pin();
+    /// let (clkout, pin) = swm.movable_functions.clkout.assign(
+    ///     pin.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// // Unassign the function again, and go back to GPIO.
+    /// let (_, pin) = clkout.unassign(pin, &mut swm_handle);
+    /// let pin = pin.into_unused_pin().into_input_pin(token);
+    /// ```
+    ///
+    /// [`Pin::into_swm_pin`]: ../pins/struct.Pin.html#method.into_swm_pin
+    pub fn into_unused_pin(
+        self,
+    ) -> (Pin<T, pins::state::Unused>, Token<T, init_state::Enabled>) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_direction_input(&registers, T::PORT, T::MASK);
+        claims::release(T::PORT, T::MASK);
+
+        let pin = Pin {
+            ty: self.ty,
+            _state: pins::state::Unused,
+        };
+
+        (pin, self.token)
+    }
+
+    /// Invert the logical sense of this pin
+    ///
+    /// Consumes this `GpioPin` and wraps it in an [`Inverted`], which flips
+    /// the meaning of HIGH and LOW in its `embedded-hal` trait
+    /// implementations. This is useful for active-low signals.
+    ///
+    /// [`Inverted`]: struct.Inverted.html
+    pub fn with_inverted_polarity(self) -> Inverted<Self> {
+        Inverted::new(self)
+    }
+
+    /// Select the pull resistor configuration of the pin
+    ///
+    /// This is available regardless of the pin's direction. The pull
+    /// resistors are mostly relevant for inputs, but can also be used with
+    /// outputs in open-drain mode (see [`IoconPin::set_open_drain`]). See
+    /// [`Pull`] for the available modes, including repeater mode.
+    ///
+    /// The IOCON clock must be enabled, before this method is called. This
+    /// can be done using `syscon.handle.enable_clock(&p.IOCON)`.
+    ///
+    /// The true open-drain pins (PIO0_10 and PIO0_11) don't have pull
+    /// resistors. For them, this method does nothing, and [`pull`] always
+    /// returns [`Pull::None`]. An external pull-up resistor is required.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     gpio::Level,
+    ///     pins::{iocon::Pull, DynamicPinDirection},
+    ///     Peripherals,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// syscon.handle.enable_clock(&p.IOCON);
+    ///
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// let mut button = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+    /// button.set_pull(Pull::Up);
+    /// assert_eq!(button.pull(), Pull::Up);
+    ///
+    /// // Hold the last level of the line, while nobody drives it.
+    /// let mut data = p.pins.pio0_12.into_dynamic_pin(
+    ///     gpio.tokens.pio0_12,
+    ///     Level::Low,
+    ///     DynamicPinDirection::Input,
+    /// );
+    /// data.set_pull(Pull::Repeater);
+    /// ```
+    ///
+    /// [`IoconPin::set_open_drain`]: ../pins/iocon/struct.IoconPin.html#method.set_open_drain
+    /// [`Pull`]: ../pins/iocon/enum.Pull.html
+    /// [`pull`]: #method.pull
+    /// [`Pull::None`]: ../pins/iocon/enum.Pull.html#variant.None
+    pub fn set_pull(&mut self, pull: Pull) {
+        // The MODE field is reserved on the true open-drain pins.
+        if !T::TRUE_OPEN_DRAIN {
+            IoconPin::<T>::new().set_pull(pull);
+        }
+    }
+
+    /// Returns the pull resistor configuration of the pin
+    ///
+    /// See [`set_pull`].
+    ///
+    /// [`set_pull`]: #method.set_pull
+    pub fn pull(&self) -> Pull {
+        if T::TRUE_OPEN_DRAIN {
+            return Pull::None;
+        }
+
+        IoconPin::<T>::new().pull()
+    }
 }
 
 impl<T> GpioPin<T, direction::Input>
@@ -245,15 +649,42 @@ where
     ///
     /// Consumes the pin instance and returns a new instance that is in output
     /// mode, making the methods to set the output level available.
+    ///
+    /// The output level is written before the direction, so the pin never
+    /// drives any other level. Use [`into_output_with_policy`], if you need
+    /// the opposite order.
+    ///
+    /// [`into_output_with_policy`]: #method.into_output_with_policy
     pub fn into_output(self, initial: Level) -> GpioPin<T, direction::Output> {
+        self.into_output_with_policy(initial, OutputInitPolicy::LevelThenDir)
+    }
+
+    /// Set pin direction to output, writing level and direction in the given
+    /// order
+    ///
+    /// This method is only available while the pin is in input mode.
+    ///
+    /// Works like [`into_output`], except that `policy` determines whether the
+    /// output level or the direction is written first. Please refer to
+    /// [`OutputInitPolicy`] for details.
+    ///
+    /// [`into_output`]: #method.into_output
+    /// [`OutputInitPolicy`]: enum.OutputInitPolicy.html
+    pub fn into_output_with_policy(
+        self,
+        initial: Level,
+        policy: OutputInitPolicy,
+    ) -> GpioPin<T, direction::Output> {
         // This is sound, as we only do a stateless write to a bit that no other
         // `GpioPin` instance writes to.
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        let direction = direction::Output::switch::<T>(&registers, initial);
+        let direction =
+            direction::Output::switch::<T>(&registers, (initial, policy));
 
         GpioPin {
+            ty: self.ty,
             token: self.token,
             _direction: direction,
         }
@@ -277,6 +708,7 @@ where
         let registers = Registers::new(gpio);
 
         GpioPin {
+            ty: self.ty,
             token: self.token,
             // always switch to ensure initial level and direction are set correctly
             _direction: direction::Dynamic::switch::<T>(
@@ -298,12 +730,11 @@ where
     /// [`Pin::into_input_pin`]: ../pins/struct.Pin.html#method.into_input_pin
     /// [`into_input`]: #method.into_input
     pub fn is_high(&self) -> bool {
-        // This is sound, as we only do a stateless write to a bit that no other
-        // `GpioPin` instance writes to.
+        // This is sound, as we only read a bit from a register.
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        is_high::<T>(&registers)
+        is_high(&registers, T::PORT, T::MASK)
     }
 
     /// Indicates wether the pin input is LOW
@@ -320,6 +751,54 @@ where
     pub fn is_low(&self) -> bool {
         !self.is_high()
     }
+
+    /// Enable or disable hardware input inversion
+    ///
+    /// Sets the INV bit in the pin's IOCON register. Unlike [`Inverted`],
+    /// which only flips the meaning of HIGH and LOW in software, this inverts
+    /// the level that is sampled by the hardware. Every consumer of the input
+    /// sees the inverted level, including the pin interrupts, which means an
+    /// edge interrupt triggers on the physically opposite edge.
+    ///
+    /// Combining this with [`Inverted`] cancels out the inversion, as far as
+    /// the `embedded-hal` traits are concerned.
+    ///
+    /// The IOCON clock must be enabled, before this method is called. This
+    /// can be done using `syscon.handle.enable_clock(&p.IOCON)`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// syscon.handle.enable_clock(&p.IOCON);
+    ///
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// let mut button = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+    ///
+    /// let level = button.is_high();
+    /// button.set_input_inverted(true);
+    /// assert!(button.is_input_inverted());
+    /// assert_eq!(button.is_high(), !level);
+    /// ```
+    ///
+    /// [`Inverted`]: struct.Inverted.html
+    pub fn set_input_inverted(&mut self, inverted: bool) {
+        IoconPin::<T>::new().set_input_inverted(inverted);
+    }
+
+    /// Indicates whether hardware input inversion is enabled
+    ///
+    /// See [`set_input_inverted`].
+    ///
+    /// [`set_input_inverted`]: #method.set_input_inverted
+    pub fn is_input_inverted(&self) -> bool {
+        IoconPin::<T>::new().is_input_inverted()
+    }
 }
 
 impl<T> GpioPin<T, direction::Output>
@@ -341,6 +820,7 @@ where
         let direction = direction::Input::switch::<T>(&registers, ());
 
         GpioPin {
+            ty: self.ty,
             token: self.token,
             _direction: direction,
         }
@@ -364,6 +844,7 @@ where
         let registers = Registers::new(gpio);
 
         GpioPin {
+            ty: self.ty,
             token: self.token,
             // always switch to ensure initial level and direction are set correctly
             _direction: direction::Dynamic::switch::<T>(
@@ -390,7 +871,7 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        set_high::<T>(&registers);
+        set_high(&registers, T::PORT, T::MASK);
     }
 
     /// Set the pin output to LOW
@@ -410,7 +891,43 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        set_low::<T>(&registers);
+        set_low(&registers, T::PORT, T::MASK);
+    }
+
+    /// Set the pin output to `level`
+    ///
+    /// Equivalent to calling [`set_high`] or [`set_low`], depending on
+    /// `level`. This is useful for code that already has a [`Level`] value.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{gpio::Level, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// let mut pin = p
+    ///     .pins
+    ///     .pio0_12
+    ///     .into_output_pin(gpio.tokens.pio0_12, Level::Low);
+    ///
+    /// pin.set_level(Level::High);
+    /// assert!(pin.is_set_high());
+    /// ```
+    ///
+    /// [`set_high`]: #method.set_high
+    /// [`set_low`]: #method.set_low
+    /// [`Level`]: enum.Level.html
+    pub fn set_level(&mut self, level: Level) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_level(&registers, T::PORT, T::MASK, level);
     }
 
     /// Indicates whether the pin output is currently set to HIGH
@@ -422,14 +939,19 @@ where
     /// See [`Pin::into_output_pin`] and [`into_output`]. Unless both of these
     /// conditions are met, code trying to call this method will not compile.
     ///
+    /// This reflects the level the pin is commanded to output, which is not
+    /// necessarily the level that is actually present on the pin. Use
+    /// [`is_high`] to read the actual level.
+    ///
     /// [`Pin::into_output_pin`]: ../pins/struct.Pin.html#method.into_output_pin
     /// [`into_output`]: #method.into_output
+    /// [`is_high`]: #method.is_high
     pub fn is_set_high(&self) -> bool {
         // This is sound, as we only read a bit from a register.
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        is_high::<T>(&registers)
+        is_set_high(&registers, T::PORT, T::MASK)
     }
 
     /// Indicates whether the pin output is currently set to LOW
@@ -464,7 +986,96 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        registers.not[T::PORT].write(|w| unsafe { w.notp().bits(T::MASK) });
+        toggle(&registers, T::PORT, T::MASK);
+    }
+
+    /// Set the pin output, then read back the actual level of the pin
+    ///
+    /// This is useful for arbitration on open-drain buses that are shared by
+    /// multiple devices (like an SMBus alert line). Those buses form a
+    /// wired-AND: The line is only high, if no device is pulling it low. If
+    /// `level` is [`Level::High`], but this method returns [`Level::Low`],
+    /// another device is driving the line low, and this device has lost the
+    /// arbitration.
+    ///
+    /// The pin needs to be in open-drain mode for this to work. Otherwise, it
+    /// would drive the line high, fighting any device that pulls it low. See
+    /// [`IoconPin::set_open_drain`].
+    ///
+    /// The level is read back immediately. If the line takes a while to
+    /// settle, for example due to a weak pull-up and high bus capacitance, use
+    /// [`set_and_sense_with_delay`] instead.
+    ///
+    /// # Example
+    ///
+    /// Two pins connected to the same line, which is pulled up externally:
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{gpio::Level, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// syscon.handle.enable_clock(&p.IOCON);
+    ///
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// p.pins.pio0_12.iocon().set_open_drain(true);
+    /// p.pins.pio0_13.iocon().set_open_drain(true);
+    ///
+    /// let mut a = p.pins.pio0_12.into_output_pin(gpio.tokens.pio0_12, Level::High);
+    /// let mut b = p.pins.pio0_13.into_output_pin(gpio.tokens.pio0_13, Level::High);
+    ///
+    /// // Nobody is pulling the line low.
+    /// assert_eq!(a.set_and_sense(Level::High), Level::High);
+    ///
+    /// // `b` pulls the line low, so `a` can't release it.
+    /// assert_eq!(b.set_and_sense(Level::Low), Level::Low);
+    /// assert_eq!(a.set_and_sense(Level::High), Level::Low);
+    ///
+    /// // Once `b` releases the line, it goes high again.
+    /// assert_eq!(b.set_and_sense(Level::High), Level::High);
+    /// ```
+    ///
+    /// [`Level::High`]: enum.Level.html#variant.High
+    /// [`Level::Low`]: enum.Level.html#variant.Low
+    /// [`IoconPin::set_open_drain`]: ../pins/iocon/struct.IoconPin.html#method.set_open_drain
+    /// [`set_and_sense_with_delay`]: #method.set_and_sense_with_delay
+    pub fn set_and_sense(&mut self, level: Level) -> Level {
+        self.set_and_sense_inner(level, || {})
+    }
+
+    /// Set the pin output, wait, then read back the actual level of the pin
+    ///
+    /// Works like [`set_and_sense`], except that it waits for `settle_us`
+    /// microseconds, using `delay`, before reading back the level. This gives
+    /// the line time to settle after it has been released.
+    ///
+    /// [`set_and_sense`]: #method.set_and_sense
+    pub fn set_and_sense_with_delay<D>(
+        &mut self,
+        level: Level,
+        delay: &mut D,
+        settle_us: u32,
+    ) -> Level
+    where
+        D: DelayUs<u32>,
+    {
+        self.set_and_sense_inner(level, || delay.delay_us(settle_us))
+    }
+
+    fn set_and_sense_inner(
+        &mut self,
+        level: Level,
+        settle: impl FnOnce(),
+    ) -> Level {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to, and otherwise only read from a
+        // register.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_and_sense(&registers, T::PORT, T::MASK, level, settle)
     }
 }
 
@@ -474,8 +1085,7 @@ where
 {
     /// Tell us whether this pin's direction is currently set to Output.
     pub fn direction_is_output(&self) -> bool {
-        return self._direction.current_direction
-            == pins::DynamicPinDirection::Output;
+        return self._direction.current_direction.is_output();
     }
 
     /// Tell us whether this pin's direction is currently set to Input.
@@ -483,10 +1093,35 @@ where
         return !self.direction_is_output();
     }
 
+    /// Update the cached direction of this pin from the hardware
+    ///
+    /// The direction that is returned by [`direction_is_output`] and
+    /// [`direction_is_input`] is cached, and only updated when the direction is
+    /// changed through this API. If the direction has been changed by other
+    /// means (for example, via the raw registers), this method reads the
+    /// actual direction from the DIR register, updates the cache, and returns
+    /// the direction.
+    ///
+    /// [`direction_is_output`]: #method.direction_is_output
+    /// [`direction_is_input`]: #method.direction_is_input
+    pub fn sync_direction(&mut self) -> pins::DynamicPinDirection {
+        // This is sound, as we only read from a register.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        let direction = if is_output(&registers, T::PORT, T::MASK) {
+            pins::DynamicPinDirection::Output
+        } else {
+            pins::DynamicPinDirection::Input
+        };
+
+        self._direction.current_direction = direction;
+        direction
+    }
+
     /// Switch pin direction to input. If the pin is already an input pin, this does nothing.
     pub fn switch_to_input(&mut self) {
-        if self._direction.current_direction == pins::DynamicPinDirection::Input
-        {
+        if self._direction.current_direction.is_input() {
             return;
         }
 
@@ -496,23 +1131,44 @@ where
         let registers = Registers::new(gpio);
 
         // switch direction
-        set_direction_input::<T>(&registers);
+        set_direction_input(&registers, T::PORT, T::MASK);
         self._direction.current_direction = pins::DynamicPinDirection::Input;
     }
 
     /// Switch pin direction to output with output level set to `level`.
     /// If the pin is already an output pin, this function only switches its level to `level`.
+    ///
+    /// The output level is always written first, even if the pin is already an
+    /// output pin. An external observer will never see the pin drive its
+    /// previous level after it has been switched to output. Please refer to
+    /// [`set_output_with`] for details.
+    ///
+    /// [`set_output_with`]: #method.set_output_with
     pub fn switch_to_output(&mut self, level: Level) {
-        // First set the output level, before we switch the mode.
-        match level {
-            Level::High => self.set_high(),
-            Level::Low => self.set_low(),
-        }
+        self.switch_to_output_with_policy(level, OutputInitPolicy::LevelThenDir)
+    }
 
-        // we are already in output, nothing else to do
-        if self._direction.current_direction
-            == pins::DynamicPinDirection::Output
-        {
+    /// Switch pin direction to output, writing level and direction in the
+    /// given order
+    ///
+    /// Works like [`switch_to_output`], except that `policy` determines
+    /// whether the output level or the direction is written first, if the pin
+    /// is currently an input pin. Please refer to [`OutputInitPolicy`] for
+    /// details.
+    ///
+    /// If the pin is already an output pin, this method only switches its
+    /// level to `level`, and `policy` has no effect.
+    ///
+    /// [`switch_to_output`]: #method.switch_to_output
+    /// [`OutputInitPolicy`]: enum.OutputInitPolicy.html
+    pub fn switch_to_output_with_policy(
+        &mut self,
+        level: Level,
+        policy: OutputInitPolicy,
+    ) {
+        // we are already in output, only the level needs to change
+        if self._direction.current_direction.is_output() {
+            self.set_level(level);
             return;
         }
 
@@ -521,10 +1177,31 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        // Now that the output level is configured, we can safely switch to
-        // output mode, without risking an undesired signal between now and
-        // the first call to `set_high`/`set_low`.
-        set_direction_output::<T>(&registers);
+        set_output_with_policy(&registers, T::PORT, T::MASK, level, policy);
+        self._direction.current_direction = pins::DynamicPinDirection::Output;
+    }
+
+    /// Set the output level to `level`, then switch pin direction to output
+    ///
+    /// This method guarantees that the output level is written (using the SET
+    /// or CLR register) before the direction is switched to output (using the
+    /// DIRSET register). This makes sure the pin never drives a level other
+    /// than `level`, not even for a short time, which could happen otherwise,
+    /// if the pin had a different output level configured while it was an
+    /// input.
+    ///
+    /// Unlike [`switch_to_output`], this method writes the direction, even if
+    /// the pin is already an output pin. This has no effect, other than making
+    /// the behavior independent of the current direction.
+    ///
+    /// [`switch_to_output`]: #method.switch_to_output
+    pub fn set_output_with(&mut self, level: Level) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_output_with_level(&registers, T::PORT, T::MASK, level);
         self._direction.current_direction = pins::DynamicPinDirection::Output;
     }
 
@@ -537,7 +1214,7 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        set_high::<T>(&registers);
+        set_high(&registers, T::PORT, T::MASK);
     }
 
     /// Set the pin level to Low.
@@ -549,7 +1226,24 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        set_low::<T>(&registers);
+        set_low(&registers, T::PORT, T::MASK);
+    }
+
+    /// Set the pin level to `level`
+    ///
+    /// Equivalent to calling [`set_high`] or [`set_low`], depending on
+    /// `level`. Like those, this will be executed regardless of the current
+    /// pin direction.
+    ///
+    /// [`set_high`]: #method.set_high
+    /// [`set_low`]: #method.set_low
+    pub fn set_level(&mut self, level: Level) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_level(&registers, T::PORT, T::MASK, level);
     }
 
     /// Indicates whether the voltage at this pin is currently set to HIGH
@@ -566,7 +1260,7 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        is_high::<T>(&registers)
+        is_high(&registers, T::PORT, T::MASK)
     }
 
     /// Indicates whether the voltage at this pin is currently set to LOW
@@ -581,6 +1275,26 @@ where
     pub fn is_low(&self) -> bool {
         !self.is_high()
     }
+
+    /// Enable or disable hardware input inversion
+    ///
+    /// Works like the [method of the same name] for input pins. The setting
+    /// only affects the input. It is kept while the pin is an output, and
+    /// takes effect again once it is switched back to input.
+    ///
+    /// [method of the same name]: #method.set_input_inverted
+    pub fn set_input_inverted(&mut self, inverted: bool) {
+        IoconPin::<T>::new().set_input_inverted(inverted);
+    }
+
+    /// Indicates whether hardware input inversion is enabled
+    ///
+    /// See [`set_input_inverted`].
+    ///
+    /// [`set_input_inverted`]: #method.set_input_inverted
+    pub fn is_input_inverted(&self) -> bool {
+        IoconPin::<T>::new().is_input_inverted()
+    }
 }
 
 impl<T> OutputPin for GpioPin<T, direction::Dynamic>
@@ -625,8 +1339,11 @@ where
     fn is_set_high(&self) -> Result<bool, Self::Error> {
         match self._direction.current_direction {
             pins::DynamicPinDirection::Output => {
-                // Re-use level reading function
-                Ok(self.is_high())
+                // This is sound, as we only read a bit from a register.
+                let gpio = unsafe { &*pac::GPIO::ptr() };
+                let registers = Registers::new(gpio);
+
+                Ok(is_set_high(&registers, T::PORT, T::MASK))
             }
             pins::DynamicPinDirection::Input => {
                 Err(Self::Error::WrongDirection)
@@ -636,10 +1353,7 @@ where
 
     fn is_set_low(&self) -> Result<bool, Self::Error> {
         match self._direction.current_direction {
-            pins::DynamicPinDirection::Output => {
-                // Re-use level reading function
-                Ok(self.is_low())
-            }
+            pins::DynamicPinDirection::Output => Ok(!self.is_set_high()?),
             pins::DynamicPinDirection::Input => {
                 Err(Self::Error::WrongDirection)
             }
@@ -800,44 +1514,1529 @@ where
     }
 }
 
-/// The voltage level of a pin
-#[derive(Debug)]
-pub enum Level {
-    /// High voltage
-    High,
-
-    /// Low voltage
-    Low,
-}
+/// Inverts the logical sense of a pin
+///
+/// Wraps a pin that implements the `embedded-hal` digital traits and
+/// implements those same traits, with HIGH and LOW swapped: Setting the
+/// wrapper high sets the wrapped pin low, and reading the wrapper returns the
+/// opposite of the wrapped pin's level. This is useful for active-low signals,
+/// like an LED that is connected to VCC, or a button that pulls its pin low
+/// when pressed.
+///
+/// This is a pure software layer. It works with any pin that implements the
+/// traits, including [`GpioPin`] in any of its directions, and doesn't change
+/// the hardware configuration of the pin in any way. Please note that the
+/// inherent methods of [`GpioPin`] are not affected, and that the initial
+/// level passed when creating an output pin still refers to the physical
+/// level.
+///
+/// Use [`GpioPin::with_inverted_polarity`] or [`Inverted::new`] to create an
+/// instance of this struct.
+///
+/// # Example
+///
+/// ```
+/// use embedded_hal::digital::v2::{InputPin, OutputPin};
+/// use lpc8xx_hal::gpio::Inverted;
+///
+/// // Stand-in for a real pin that stores the physical level.
+/// struct Pin(bool);
+///
+/// impl OutputPin for Pin {
+///     type Error = ();
+///
+///     fn set_high(&mut self) -> Result<(), ()> {
+///         self.0 = true;
+///         Ok(())
+///     }
+///
+///     fn set_low(&mut self) -> Result<(), ()> {
+///         self.0 = false;
+///         Ok(())
+///     }
+/// }
+///
+/// impl InputPin for Pin {
+///     type Error = ();
+///
+///     fn is_high(&self) -> Result<bool, ()> {
+///         Ok(self.0)
+///     }
+///
+///     fn is_low(&self) -> Result<bool, ()> {
+///         Ok(!self.0)
+///     }
+/// }
+///
+/// let mut pin = Inverted::new(Pin(false));
+///
+/// pin.set_high().unwrap();
+/// assert_eq!(pin.is_high(), Ok(true));
+/// assert_eq!(pin.free().0, false);
+///
+/// let mut pin = Inverted::new(Pin(true));
+///
+/// pin.set_low().unwrap();
+/// assert_eq!(pin.is_low(), Ok(true));
+/// assert_eq!(pin.free().0, true);
+/// ```
+///
+/// [`GpioPin`]: struct.GpioPin.html
+/// [`GpioPin::with_inverted_polarity`]: struct.GpioPin.html#method.with_inverted_polarity
+/// [`Inverted::new`]: #method.new
+pub struct Inverted<P>(P);
+
+impl<P> Inverted<P> {
+    /// Wrap a pin, inverting its logical sense
+    pub fn new(pin: P) -> Self {
+        Inverted(pin)
+    }
+
+    /// Return the wrapped pin
+    pub fn free(self) -> P {
+        self.0
+    }
+}
+
+impl<P> InputPin for Inverted<P>
+where
+    P: InputPin,
+{
+    type Error = P::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.0.is_low()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.0.is_high()
+    }
+}
+
+impl<P> InputPinAlpha for Inverted<P>
+where
+    P: InputPinAlpha,
+{
+    type Error = P::Error;
+
+    fn try_is_high(&self) -> Result<bool, Self::Error> {
+        self.0.try_is_low()
+    }
+
+    fn try_is_low(&self) -> Result<bool, Self::Error> {
+        self.0.try_is_high()
+    }
+}
+
+impl<P> OutputPin for Inverted<P>
+where
+    P: OutputPin,
+{
+    type Error = P::Error;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low()
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high()
+    }
+}
+
+impl<P> OutputPinAlpha for Inverted<P>
+where
+    P: OutputPinAlpha,
+{
+    type Error = P::Error;
+
+    fn try_set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.try_set_low()
+    }
+
+    fn try_set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.try_set_high()
+    }
+}
+
+impl<P> StatefulOutputPin for Inverted<P>
+where
+    P: StatefulOutputPin,
+{
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.0.is_set_low()
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        self.0.is_set_high()
+    }
+}
+
+impl<P> StatefulOutputPinAlpha for Inverted<P>
+where
+    P: StatefulOutputPinAlpha,
+{
+    fn try_is_set_high(&self) -> Result<bool, Self::Error> {
+        self.0.try_is_set_low()
+    }
+
+    fn try_is_set_low(&self) -> Result<bool, Self::Error> {
+        self.0.try_is_set_high()
+    }
+}
+
+// Toggling looks the same, regardless of the logical sense of the pin.
+impl<P> ToggleableOutputPin for Inverted<P>
+where
+    P: ToggleableOutputPin,
+{
+    type Error = P::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.0.toggle()
+    }
+}
+
+impl<P> ToggleableOutputPinAlpha for Inverted<P>
+where
+    P: ToggleableOutputPinAlpha,
+{
+    type Error = P::Error;
+
+    fn try_toggle(&mut self) -> Result<(), Self::Error> {
+        self.0.try_toggle()
+    }
+}
+
+/// A pin that can be shared between tasks and interrupt handlers
+///
+/// Wraps a pin (usually a [`GpioPin`]) and only provides access to it through
+/// [`SharedPin::lock`], which runs a closure within a critical section. Since
+/// `SharedPin` is `Sync` (as long as the wrapped pin is `Send`, which
+/// [`GpioPin`] is), it can be put into a `static`, or into a resource that is
+/// shared between tasks in RTIC or a similar framework.
+///
+/// Interrupts are disabled while the closure runs. Please keep it short, to
+/// avoid delaying interrupt handlers.
+///
+/// # Nested locks
+///
+/// Locks of different `SharedPin`s can be nested, as nested critical sections
+/// are fine. Locking the same `SharedPin` again from within the closure is a
+/// bug, however, and will panic.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{gpio::{self, SharedPin}, Peripherals};
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let pin = p
+///     .pins
+///     .pio0_12
+///     .into_output_pin(gpio.tokens.pio0_12, gpio::Level::Low);
+///
+/// let led = SharedPin::new(pin);
+///
+/// // This could happen in different tasks or interrupt handlers.
+/// led.lock(|pin| pin.set_high());
+/// led.lock(|pin| pin.toggle());
+///
+/// let is_high = led.lock(|pin| pin.is_set_high());
+/// ```
+///
+/// [`GpioPin`]: struct.GpioPin.html
+/// [`SharedPin::lock`]: #method.lock
+pub struct SharedPin<P> {
+    pin: RefCell<P>,
+}
+
+// Sound, as the pin is only ever accessed from within a critical section, so
+// no two contexts can access it at the same time. The `Send` bound makes sure
+// that the pin may be accessed from other contexts in the first place.
+unsafe impl<P> Sync for SharedPin<P> where P: Send {}
+
+impl<P> SharedPin<P> {
+    /// Wrap a pin, to share it
+    pub const fn new(pin: P) -> Self {
+        Self {
+            pin: RefCell::new(pin),
+        }
+    }
+
+    /// Access the pin within a critical section
+    ///
+    /// Disables interrupts, then calls `f` with a mutable reference to the
+    /// pin, and returns the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if called from within a closure that was passed to `lock` on
+    /// the same `SharedPin`.
+    pub fn lock<R>(&self, f: impl FnOnce(&mut P) -> R) -> R {
+        interrupt::free(|_| {
+            let mut pin = self.pin.borrow_mut();
+            f(&mut pin)
+        })
+    }
+
+    /// Return the wrapped pin
+    pub fn free(self) -> P {
+        self.pin.into_inner()
+    }
+}
+
+/// Debounces an input pin
+///
+/// Wraps an input pin and a [`Timer`], and only reports a change of the
+/// pin's level, once the new level has been stable for a given number of
+/// timer ticks. This is useful for buttons and other mechanical switches,
+/// which bounce between levels for a while, when they are pressed or
+/// released.
+///
+/// The pin is only sampled when its level is queried, through the
+/// `embedded-hal` [`InputPin`] implementation. To detect a stable level in
+/// time, the level needs to be queried regularly, at least once per debounce
+/// period. A level that is stable between two queries, but not at the time
+/// of either of them, is not detected.
+///
+/// As there's no stable level to report before the first query, the first
+/// query reports the level of the pin as-is, and considers it stable. If the
+/// pin is bouncing at that point, the reported level is corrected once it
+/// has settled.
+///
+/// Works with any pin that implements `InputPin`. For [`GpioPin`]s, the
+/// glitch filter of the pin (see [`IoconPin::set_filter`]) can be used in
+/// addition, to reject short spikes in hardware.
+///
+/// # Example
+///
+/// ``` no_run
+/// use embedded_hal::digital::v2::InputPin;
+/// use lpc8xx_hal::{gpio::Debounced, Peripherals};
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// #[cfg(feature = "82x")]
+/// let gpio = p.GPIO;
+/// #[cfg(feature = "845")]
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let wkt = p.WKT.enable(&mut syscon.handle);
+///
+/// let button = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+///
+/// // The WKT runs at 750 kHz by default, so the level needs to be stable for
+/// // 5 ms, before it is reported.
+/// let button = Debounced::new(button, wkt, 3_750);
+///
+/// loop {
+///     if button.is_low().unwrap() {
+///         // button is pressed
+///     }
+/// }
+/// ```
+///
+/// [`Timer`]: ../timer/trait.Timer.html
+/// [`InputPin`]: #impl-InputPin
+/// [`GpioPin`]: struct.GpioPin.html
+/// [`IoconPin::set_filter`]: ../pins/iocon/struct.IoconPin.html#method.set_filter
+pub struct Debounced<P, T> {
+    pin: P,
+    timer: RefCell<T>,
+    ticks: u32,
+    stable: Cell<Option<bool>>,
+    candidate: Cell<bool>,
+}
+
+impl<P, T> Debounced<P, T>
+where
+    T: Timer,
+{
+    /// Wrap a pin, debouncing it using `timer`
+    ///
+    /// A new level is only reported, once it has been stable for `ticks`
+    /// ticks of `timer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero or larger than `T::MAX_TICKS`.
+    pub fn new(pin: P, timer: T, ticks: u32) -> Self {
+        assert!(ticks > 0 && ticks <= T::MAX_TICKS);
+
+        Self {
+            pin,
+            timer: RefCell::new(timer),
+            ticks,
+            stable: Cell::new(None),
+            candidate: Cell::new(false),
+        }
+    }
+
+    /// Return the wrapped pin and the timer
+    pub fn free(self) -> (P, T) {
+        (self.pin, self.timer.into_inner())
+    }
+}
+
+impl<P, T> Debounced<P, T>
+where
+    P: InputPin,
+    T: Timer,
+{
+    /// Sample the pin and return the debounced level
+    fn sample(&self) -> Result<bool, P::Error> {
+        let level = self.pin.is_high()?;
+
+        let stable = match self.stable.get() {
+            Some(stable) => stable,
+            None => {
+                self.stable.set(Some(level));
+                self.candidate.set(level);
+                return Ok(level);
+            }
+        };
+
+        let mut timer = self.timer.borrow_mut();
+
+        if level != self.candidate.get() {
+            // The level changed, so it needs to be stable for the whole
+            // period, starting now.
+            self.candidate.set(level);
+            timer.start(self.ticks);
+        } else if level != stable && timer.wait().is_ok() {
+            self.stable.set(Some(level));
+            return Ok(level);
+        }
+
+        Ok(stable)
+    }
+}
+
+impl<P, T> InputPin for Debounced<P, T>
+where
+    P: InputPin,
+    T: Timer,
+{
+    type Error = P::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.sample()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.sample().map(|level| !level)
+    }
+}
+
+impl<P, T> InputPinAlpha for Debounced<P, T>
+where
+    P: InputPin,
+    T: Timer,
+{
+    type Error = P::Error;
+
+    fn try_is_high(&self) -> Result<bool, Self::Error> {
+        self.sample()
+    }
+
+    fn try_is_low(&self) -> Result<bool, Self::Error> {
+        self.sample().map(|level| !level)
+    }
+}
+
+/// Software PWM on any output pin
+///
+/// Approximates a PWM signal by toggling an output pin, timed by a [`Timer`].
+/// This is useful for pins that aren't connected to a hardware PWM output,
+/// like those of the CTIMER. Implements the `embedded-hal` [`PwmPin`] trait,
+/// with the period and duty cycle measured in timer ticks.
+///
+/// The pin is only toggled from [`SoftPwm::update`], which needs to be called
+/// whenever the timer has finished its count down. Typically, that happens
+/// from the timer's interrupt handler, but calling it in a loop works too.
+///
+/// A duty cycle of zero holds the pin low, a duty cycle of
+/// [`get_max_duty`] holds it high. The timer isn't used in either case.
+///
+/// # Jitter
+///
+/// As the pin is toggled in software, any delay between the timer finishing
+/// and the call to [`SoftPwm::update`] (for example due to higher-priority
+/// interrupts, or a critical section) delays the edge. Since the timer is
+/// restarted from [`SoftPwm::update`] too, the delay also lengthens the
+/// current period. The average duty cycle stays close to the configured one,
+/// as long as the delays are short compared to the durations of the high and
+/// low phases, but for short periods or under heavy interrupt load, expect a
+/// noticeable amount of jitter. Use the CTIMER, if that's not acceptable.
+///
+/// # Example
+///
+/// ``` no_run
+/// use embedded_hal::PwmPin;
+/// use lpc8xx_hal::{
+///     gpio::{Level, SoftPwm},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// #[cfg(feature = "82x")]
+/// let gpio = p.GPIO;
+/// #[cfg(feature = "845")]
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let mrt = p.MRT0.split(&mut syscon.handle).mrt0;
+///
+/// let led = p
+///     .pins
+///     .pio0_12
+///     .into_output_pin(gpio.tokens.pio0_12, Level::Low);
+///
+/// // The MRT runs at 12 MHz, so this results in a period of 1 ms.
+/// let mut pwm = SoftPwm::new(led, mrt, 12_000);
+/// pwm.set_duty(pwm.get_max_duty() / 4);
+/// pwm.enable();
+///
+/// loop {
+///     pwm.update().unwrap();
+/// }
+/// ```
+///
+/// [`Timer`]: ../timer/trait.Timer.html
+/// [`PwmPin`]: #impl-PwmPin
+/// [`SoftPwm::update`]: #method.update
+/// [`get_max_duty`]: #method.get_max_duty
+pub struct SoftPwm<P, T> {
+    pin: P,
+    timer: T,
+    period: u32,
+    duty: u32,
+    enabled: bool,
+    high: bool,
+}
+
+impl<P, T> SoftPwm<P, T>
+where
+    P: OutputPin,
+    T: Timer,
+{
+    /// Wrap an output pin, generating a PWM signal on it using `timer`
+    ///
+    /// `period` is the length of one PWM period in ticks of `timer`, which is
+    /// also the maximum duty cycle. The PWM starts out disabled, with a duty
+    /// cycle of zero. The pin is not touched until it is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `period` is smaller than two, or larger than
+    /// `T::MAX_TICKS`.
+    pub fn new(pin: P, timer: T, period: u32) -> Self {
+        assert!(period >= 2 && period <= T::MAX_TICKS);
+
+        Self {
+            pin,
+            timer,
+            period,
+            duty: 0,
+            enabled: false,
+            high: false,
+        }
+    }
+
+    /// Toggle the pin, if the timer has finished
+    ///
+    /// Needs to be called whenever the timer has finished its count down,
+    /// usually from the timer's interrupt handler. Does nothing, if the timer
+    /// hasn't finished yet, the PWM is disabled, or the duty cycle is zero or
+    /// at its maximum.
+    pub fn update(&mut self) -> Result<(), P::Error> {
+        if !self.is_toggling() || self.timer.wait().is_err() {
+            return Ok(());
+        }
+
+        if self.high {
+            self.pin.set_low()?;
+            self.timer.start(self.period - self.duty);
+        } else {
+            self.pin.set_high()?;
+            self.timer.start(self.duty);
+        }
+        self.high = !self.high;
+
+        Ok(())
+    }
+
+    /// Provide access to the wrapped pin
+    pub fn pin(&self) -> &P {
+        &self.pin
+    }
+
+    /// Return the wrapped pin and the timer
+    ///
+    /// The timer is stopped. The pin is left at its current level.
+    pub fn free(mut self) -> (P, T) {
+        self.timer.cancel();
+        (self.pin, self.timer)
+    }
+
+    /// Whether the pin needs to be toggled for the current configuration
+    fn is_toggling(&self) -> bool {
+        self.enabled && self.duty > 0 && self.duty < self.period
+    }
+
+    /// Start a new period with the current configuration
+    fn restart(&mut self) -> Result<(), P::Error> {
+        self.timer.cancel();
+
+        if !self.enabled || self.duty == 0 {
+            self.high = false;
+            return self.pin.set_low();
+        }
+
+        self.pin.set_high()?;
+        self.high = true;
+        if self.duty < self.period {
+            self.timer.start(self.duty);
+        }
+
+        Ok(())
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> Result<(), P::Error> {
+        self.enabled = enabled;
+        self.restart()
+    }
+
+    fn set_duty_inner(&mut self, duty: u32) -> Result<(), P::Error> {
+        let was_toggling = self.is_toggling();
+        self.duty = duty.min(self.period);
+
+        // If the pin is already being toggled, the new duty cycle takes
+        // effect with the next edge, which avoids glitches.
+        if was_toggling && self.is_toggling() {
+            return Ok(());
+        }
+
+        self.restart()
+    }
+}
+
+impl<P, T> PwmPin for SoftPwm<P, T>
+where
+    P: OutputPin<Error = Void>,
+    T: Timer,
+{
+    type Duty = u32;
+
+    /// Enables the PWM, starting a new period
+    fn enable(&mut self) {
+        let _ = self.set_enabled(true);
+    }
+
+    /// Disables the PWM, stopping the timer and setting the pin low
+    fn disable(&mut self) {
+        let _ = self.set_enabled(false);
+    }
+
+    /// Returns the current duty cycle
+    fn get_duty(&self) -> Self::Duty {
+        self.duty
+    }
+
+    /// Returns the maximum duty cycle value, which is the period in ticks
+    fn get_max_duty(&self) -> Self::Duty {
+        self.period
+    }
+
+    /// Sets a new duty cycle
+    ///
+    /// Values larger than the maximum duty cycle are treated as the maximum.
+    fn set_duty(&mut self, duty: Self::Duty) {
+        let _ = self.set_duty_inner(duty);
+    }
+}
+
+impl<P, T> PwmPinAlpha for SoftPwm<P, T>
+where
+    P: OutputPin,
+    T: Timer,
+{
+    type Error = P::Error;
+    type Duty = u32;
+
+    /// Enables the PWM, starting a new period
+    fn try_enable(&mut self) -> Result<(), Self::Error> {
+        self.set_enabled(true)
+    }
+
+    /// Disables the PWM, stopping the timer and setting the pin low
+    fn try_disable(&mut self) -> Result<(), Self::Error> {
+        self.set_enabled(false)
+    }
+
+    /// Returns the current duty cycle
+    fn try_get_duty(&self) -> Result<Self::Duty, Self::Error> {
+        Ok(self.duty)
+    }
+
+    /// Returns the maximum duty cycle value, which is the period in ticks
+    fn try_get_max_duty(&self) -> Result<Self::Duty, Self::Error> {
+        Ok(self.period)
+    }
+
+    /// Sets a new duty cycle
+    ///
+    /// Values larger than the maximum duty cycle are treated as the maximum.
+    fn try_set_duty(&mut self, duty: Self::Duty) -> Result<(), Self::Error> {
+        self.set_duty_inner(duty)
+    }
+}
+
+/// Reads the levels of multiple pins efficiently
+///
+/// Reading a pin using [`GpioPin::is_high`] sets up access to the GPIO
+/// registers on every call. `GpioSampler` does this once, when it is created,
+/// which saves a few instructions per read. This can make a difference when
+/// sampling many pins in a tight loop.
+///
+/// `GpioSampler` can be used alongside the regular API. It only ever reads
+/// from the PIN registers, which doesn't affect anything else.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{GpioSampler, Level},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let pio0_12 = p.pins.pio0_12.into_input_pin(gpio.tokens.pio0_12);
+/// let pio0_13 = p.pins.pio0_13.into_input_pin(gpio.tokens.pio0_13);
+/// let pio0_14 = p.pins.pio0_14.into_input_pin(gpio.tokens.pio0_14);
+///
+/// let sampler = GpioSampler::new();
+///
+/// loop {
+///     let levels = [
+///         sampler.read(&pio0_12),
+///         sampler.read(&pio0_13),
+///         sampler.read(&pio0_14),
+///     ];
+///
+///     // The sampler reads the same levels as the pins themselves.
+///     assert_eq!(matches!(levels[0], Level::High), pio0_12.is_high());
+/// }
+/// ```
+///
+/// [`GpioPin::is_high`]: struct.GpioPin.html#method.is_high
+pub struct GpioSampler {
+    registers: Registers<'static>,
+}
+
+impl GpioSampler {
+    /// Create a new instance of `GpioSampler`
+    ///
+    /// This doesn't require access to the [`GPIO`] peripheral, as pins can only
+    /// be read through [`read`], if they are in the GPIO state, which in turn
+    /// requires the peripheral to be enabled.
+    ///
+    /// [`GPIO`]: struct.GPIO.html
+    /// [`read`]: #method.read
+    pub fn new() -> Self {
+        // This is sound, as we only ever read from the PIN registers.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+
+        Self {
+            registers: Registers::new(gpio),
+        }
+    }
+
+    /// Read the level of a pin
+    ///
+    /// Returns the level that is present on the pin. This works for pins in
+    /// any direction. For output pins, this is the level that the pin is
+    /// actually at, which can differ from the commanded one, for example if
+    /// the pin is configured as open-drain.
+    pub fn read<T, D>(&self, _: &GpioPin<T, D>) -> Level
+    where
+        T: pins::Trait,
+    {
+        if is_high(&self.registers, T::PORT, T::MASK) {
+            Level::High
+        } else {
+            Level::Low
+        }
+    }
+}
+
+impl Default for GpioSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes the output levels of pins on a single port efficiently
+///
+/// Writing to a pin using [`GpioPin::set_high`], [`GpioPin::set_low`], or
+/// [`GpioPin::toggle`] sets up access to the GPIO registers on every call.
+/// `PortWriter` does this once, when it is created, and keeps references to
+/// the SET, CLR, and NOT registers of its port. This saves a few instructions
+/// per write, which can make a difference for precisely timed bit-banging,
+/// for example from a timer interrupt handler.
+///
+/// Since `PortWriter` doesn't borrow anything, it can be stored in a static
+/// and moved into an interrupt handler. It only ever writes to the pins whose
+/// bits are set in the mask it was created with.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{Level, PortWriter},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let clock = p
+///     .pins
+///     .pio0_12
+///     .into_output_pin(gpio.tokens.pio0_12, Level::Low);
+/// let data = p
+///     .pins
+///     .pio0_13
+///     .into_output_pin(gpio.tokens.pio0_13, Level::Low);
+///
+/// const CLOCK: u32 = 0x1 << 12;
+/// const DATA: u32 = 0x1 << 13;
+///
+/// // Sound, as we own both pins and keep them around, so nothing else can
+/// // write to them.
+/// let mut port = unsafe { PortWriter::new(0, CLOCK | DATA) };
+///
+/// // Shift out a byte, MSB first.
+/// for i in (0..8).rev() {
+///     if 0xa5_u8 & (0x1 << i) != 0 {
+///         port.set_high(DATA);
+///     } else {
+///         port.set_low(DATA);
+///     }
+///     port.toggle(CLOCK);
+///     port.toggle(CLOCK);
+/// }
+/// # let _ = (clock, data);
+/// ```
+///
+/// [`GpioPin::set_high`]: struct.GpioPin.html#method.set_high
+/// [`GpioPin::set_low`]: struct.GpioPin.html#method.set_low
+/// [`GpioPin::toggle`]: struct.GpioPin.html#method.toggle
+pub struct PortWriter {
+    set: &'static SET,
+    clr: &'static CLR,
+    not: &'static NOT,
+    mask: u32,
+}
+
+// Sound, as the registers are only ever written to, and all of them are
+// stateless. Writes only affect the bits in `mask`, which the creator of the
+// `PortWriter` guaranteed nothing else would write to.
+unsafe impl Send for PortWriter {}
+
+impl PortWriter {
+    /// Create a new instance of `PortWriter`
+    ///
+    /// The new instance can write to the pins of `port` whose bits are set in
+    /// `mask`. All writes to other pins are ignored.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that nothing else writes to the pins in
+    /// `mask` for as long as the `PortWriter` exists. This is typically done
+    /// by owning the respective [`GpioPin`]s, and not using them for writing,
+    /// while the `PortWriter` exists. The bits of multiple `PortWriter`s for
+    /// the same port must be disjoint.
+    ///
+    /// The pins need to have been configured as GPIO outputs, for the writes
+    /// to have a visible effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `port` doesn't exist on the target. The LPC82x only has port
+    /// 0, the LPC845 has ports 0 and 1.
+    ///
+    /// [`GpioPin`]: struct.GpioPin.html
+    pub unsafe fn new(port: usize, mask: u32) -> Self {
+        let gpio = &*pac::GPIO::ptr();
+        Self::from_registers(&Registers::new(gpio), port, mask)
+    }
+
+    fn from_registers(
+        registers: &Registers<'static>,
+        port: usize,
+        mask: u32,
+    ) -> Self {
+        assert!(port < registers.set.len());
+
+        Self {
+            set: &registers.set[port],
+            clr: &registers.clr[port],
+            not: &registers.not[port],
+            mask,
+        }
+    }
+
+    /// Returns the mask of pins this instance can write to
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Set the output level of the pins in `bits` to HIGH
+    pub fn set_high(&mut self, bits: u32) {
+        let bits = bits & self.mask;
+
+        // Sound, as we only write bits we own to a stateless register.
+        self.set.write(|w| unsafe { w.setp().bits(bits) });
+    }
+
+    /// Set the output level of the pins in `bits` to LOW
+    pub fn set_low(&mut self, bits: u32) {
+        let bits = bits & self.mask;
+
+        // Sound, as we only write bits we own to a stateless register.
+        self.clr.write(|w| unsafe { w.clrp().bits(bits) });
+    }
+
+    /// Set the output level of the pins in `bits` to `level`
+    pub fn set_level(&mut self, bits: u32, level: Level) {
+        match level {
+            Level::High => self.set_high(bits),
+            Level::Low => self.set_low(bits),
+        }
+    }
+
+    /// Toggle the output level of the pins in `bits`
+    pub fn toggle(&mut self, bits: u32) {
+        let bits = bits & self.mask;
+
+        // Sound, as we only write bits we own to a stateless register.
+        self.not.write(|w| unsafe { w.notp().bits(bits) });
+    }
+}
+
+/// The MASK registers of all ports
+///
+/// Can be accessed via the `masks` field of [`GPIO`].
+///
+/// [`GPIO`]: struct.GPIO.html
+pub struct Masks {
+    /// The MASK register of port 0
+    pub port0: Mask,
+
+    /// The MASK register of port 1
+    #[cfg(feature = "845")]
+    pub port1: Mask,
+}
+
+impl Masks {
+    fn new() -> Self {
+        Self {
+            port0: Mask { port: 0 },
+            #[cfg(feature = "845")]
+            port1: Mask { port: 1 },
+        }
+    }
+}
+
+/// The MASK register of a port
+///
+/// Only one instance per port exists, which makes sure that only one
+/// [`MaskedPort`] per port can exist at a time.
+///
+/// [`MaskedPort`]: struct.MaskedPort.html
+pub struct Mask {
+    port: usize,
+}
+
+/// Provides access to a subset of a port's pins in a single operation
+///
+/// Uses the MASK and MPIN registers of the GPIO peripheral. The MASK register
+/// is set once, when the `MaskedPort` is created. After that, [`read`] and
+/// [`write`] only affect the pins that are part of the `MaskedPort`, leaving
+/// all other pins on the port alone. This is useful for parallel buses that
+/// share a port with unrelated pins.
+///
+/// Unlike the approach taken by [`GpioPin`], which uses the SET and CLR
+/// registers and requires two writes to set some pins and clear others, a
+/// write to the MPIN register changes all pins at once. Reads are also done in
+/// a single access.
+///
+/// Values passed to [`write`] and returned from [`read`] use the bit layout of
+/// the port, meaning bit `n` corresponds to pin `n` of the port. Bits that
+/// belong to other pins are ignored when writing, and are always 0 when
+/// reading.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{Level, MaskedPort},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let pio0_12 = p.pins.pio0_12.into_output_pin(gpio.tokens.pio0_12, Level::Low);
+/// let pio0_13 = p.pins.pio0_13.into_output_pin(gpio.tokens.pio0_13, Level::Low);
+/// let pio0_14 = p.pins.pio0_14.into_output_pin(gpio.tokens.pio0_14, Level::Low);
+///
+/// let mut port = MaskedPort::new((pio0_12, pio0_13, pio0_14), gpio.masks.port0);
+///
+/// // Set PIO0_12 and PIO0_14, clear PIO0_13. All other pins on port 0 are
+/// // not affected.
+/// port.write(0b101 << 12);
+/// assert_eq!(port.read(), 0b101 << 12);
+/// ```
+///
+/// [`read`]: #method.read
+/// [`write`]: #method.write
+/// [`GpioPin`]: struct.GpioPin.html
+pub struct MaskedPort<P> {
+    pins: P,
+    mask: Mask,
+}
+
+impl<P> MaskedPort<P>
+where
+    P: MaskedPins,
+{
+    /// Create a new `MaskedPort` from a set of pins
+    ///
+    /// `pins` is a tuple of [`GpioPin`]s. Writes the MASK register, so that
+    /// only those pins are accessible through the MPIN register.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the pins are not all on the port that `mask` belongs to. Use
+    /// [`try_new`], if you need to handle this case.
+    ///
+    /// [`GpioPin`]: struct.GpioPin.html
+    /// [`try_new`]: #method.try_new
+    pub fn new(pins: P, mask: Mask) -> Self {
+        match Self::try_new(pins, mask) {
+            Ok(port) => port,
+            Err(_) => panic!("Pins are not all on the port of the MASK"),
+        }
+    }
+
+    /// Create a new `MaskedPort`, if all pins are on the right port
+    ///
+    /// Works like [`new`], but returns the pins and the MASK register, instead
+    /// of panicking, if the pins are not all on the port that `mask` belongs
+    /// to.
+    ///
+    /// Which ports are available depends on the target. The LPC82x only has
+    /// port 0. The LPC845 also has port 1, which contains the `PIO1_x` pins.
+    /// Since only the MASK registers of existing ports are available from
+    /// [`Masks`], using a port that doesn't exist won't compile.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// # #[cfg(feature = "845")]
+    /// # {
+    /// use lpc8xx_hal::{
+    ///     gpio::{Level, MaskedPort},
+    ///     Peripherals,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// let pio1_0 = p.pins.pio1_0.into_output_pin(gpio.tokens.pio1_0, Level::Low);
+    /// let pio1_1 = p.pins.pio1_1.into_output_pin(gpio.tokens.pio1_1, Level::Low);
+    ///
+    /// // The pins are on port 1, so passing `gpio.masks.port0` would return
+    /// // an error here.
+    /// let mut port = MaskedPort::try_new((pio1_0, pio1_1), gpio.masks.port1)
+    ///     .ok()
+    ///     .unwrap();
+    ///
+    /// port.write(0b01);
+    /// assert_eq!(port.read(), 0b01);
+    /// # }
+    /// ```
+    ///
+    /// [`new`]: #method.new
+    /// [`Masks`]: struct.Masks.html
+    pub fn try_new(pins: P, mask: Mask) -> Result<Self, (P, Mask)> {
+        if !pins.is_on_port(mask.port) {
+            return Err((pins, mask));
+        }
+
+        // Sound, as we own the MASK register of this port.
+        set_mask(unsafe { &*pac::GPIO::ptr() }, mask.port, P::MASK);
+
+        Ok(Self { pins, mask })
+    }
+
+    /// Read the levels of all pins
+    ///
+    /// Bits that don't belong to any of the pins are always 0.
+    pub fn read(&self) -> u32 {
+        // Sound, as we own the MASK register, which controls which bits of
+        // MPIN are affected.
+        read_masked(unsafe { &*pac::GPIO::ptr() }, self.mask.port)
+    }
+
+    /// Set the output levels of all pins at once
+    ///
+    /// Bits that don't belong to any of the pins are ignored. Writing to pins
+    /// that are configured as inputs has no effect on them.
+    pub fn write(&mut self, value: u32) {
+        // Sound, as MASK has been set up so that only our pins are affected.
+        write_masked(unsafe { &*pac::GPIO::ptr() }, self.mask.port, value);
+    }
+
+    /// Release the pins and the MASK register
+    ///
+    /// Resets the MASK register, so all pins are accessible again.
+    pub fn free(self) -> (P, Mask) {
+        // Sound, as we own the MASK register of this port.
+        set_mask(unsafe { &*pac::GPIO::ptr() }, self.mask.port, !0);
+
+        (self.pins, self.mask)
+    }
+}
+
+fn set_and_sense(
+    registers: &Registers,
+    port: usize,
+    mask: u32,
+    level: Level,
+    settle: impl FnOnce(),
+) -> Level {
+    set_level(registers, port, mask, level);
+    settle();
+
+    if is_high(registers, port, mask) {
+        Level::High
+    } else {
+        Level::Low
+    }
+}
+
+/// Make the given pins accessible through MPIN, and mask all others
+fn set_mask(gpio: &pac::gpio::RegisterBlock, port: usize, pins: u32) {
+    // Sound, as all bit patterns are valid. A 0 bit enables access to a pin, a
+    // 1 bit masks it.
+    target::mask(gpio, port).write(|w| unsafe { w.maskp().bits(!pins) });
+}
+
+fn read_masked(gpio: &pac::gpio::RegisterBlock, port: usize) -> u32 {
+    target::mpin(gpio, port).read().mportp().bits()
+}
+
+fn write_masked(gpio: &pac::gpio::RegisterBlock, port: usize, value: u32) {
+    // Sound, as all bit patterns are valid.
+    target::mpin(gpio, port).write(|w| unsafe { w.mportp().bits(value) });
+}
+
+/// Implemented for sets of pins that can be used with [`MaskedPort`]
+///
+/// This trait is implemented for tuples of up to 8 [`GpioPin`]s.
+///
+/// [`MaskedPort`]: struct.MaskedPort.html
+/// [`GpioPin`]: struct.GpioPin.html
+pub trait MaskedPins {
+    /// The combined mask of all pins
+    const MASK: u32;
+
+    /// Indicates whether all pins are on the given port
+    fn is_on_port(&self, port: usize) -> bool;
+}
+
+macro_rules! masked_pins {
+    ($($t:ident, $d:ident;)*) => {
+        impl<$($t, $d,)*> MaskedPins for ($(GpioPin<$t, $d>,)*)
+        where
+            $($t: pins::Trait,)*
+        {
+            const MASK: u32 = 0 $(| $t::MASK)*;
+
+            fn is_on_port(&self, port: usize) -> bool {
+                true $(&& $t::PORT == port)*
+            }
+        }
+    };
+}
+
+masked_pins!(T1, D1;);
+masked_pins!(T1, D1; T2, D2;);
+masked_pins!(T1, D1; T2, D2; T3, D3;);
+masked_pins!(T1, D1; T2, D2; T3, D3; T4, D4;);
+masked_pins!(T1, D1; T2, D2; T3, D3; T4, D4; T5, D5;);
+masked_pins!(T1, D1; T2, D2; T3, D3; T4, D4; T5, D5; T6, D6;);
+masked_pins!(T1, D1; T2, D2; T3, D3; T4, D4; T5, D5; T6, D6; T7, D7;);
+masked_pins!(T1, D1; T2, D2; T3, D3; T4, D4; T5, D5; T6, D6; T7, D7; T8, D8;);
+
+/// Transition multiple pins into GPIO outputs at once
+///
+/// Takes a tuple of up to 8 `(pin, token, initial level)` triples and returns
+/// a tuple of [`GpioPin`]s in output mode, in the same order. Each pin is
+/// converted like with [`Pin::into_output_pin`], and keeps its distinct type.
+///
+/// The resulting tuple can be passed to [`MaskedPort::new`] directly, to
+/// access all of the pins in a single operation.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{self, Level, MaskedPort},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let (mut led0, mut led1, mut led2, mut led3) = gpio::into_output_pins((
+///     (p.pins.pio0_12, gpio.tokens.pio0_12, Level::Low),
+///     (p.pins.pio0_13, gpio.tokens.pio0_13, Level::Low),
+///     (p.pins.pio0_14, gpio.tokens.pio0_14, Level::High),
+///     (p.pins.pio0_15, gpio.tokens.pio0_15, Level::High),
+/// ));
+///
+/// led0.set_high();
+/// led1.toggle();
+/// led2.set_low();
+/// led3.toggle();
+///
+/// // The pins can also be combined into a `MaskedPort`.
+/// let mut port =
+///     MaskedPort::new((led0, led1, led2, led3), gpio.masks.port0);
+/// port.write(0);
+/// ```
+///
+/// [`GpioPin`]: struct.GpioPin.html
+/// [`Pin::into_output_pin`]: ../pins/struct.Pin.html#method.into_output_pin
+/// [`MaskedPort::new`]: struct.MaskedPort.html#method.new
+pub fn into_output_pins<P>(pins: P) -> P::Output
+where
+    P: IntoOutputPins,
+{
+    pins.into_output_pins()
+}
+
+/// Implemented for sets of pins that can be transitioned into outputs at once
+///
+/// This trait is implemented for tuples of up to 8 `(pin, token, level)`
+/// triples. Please refer to [`into_output_pins`] for the public API that uses
+/// this trait.
+///
+/// [`into_output_pins`]: fn.into_output_pins.html
+pub trait IntoOutputPins {
+    /// The result of the transition
+    ///
+    /// A tuple with one [`GpioPin`] in output mode for each triple that was
+    /// passed in.
+    ///
+    /// [`GpioPin`]: struct.GpioPin.html
+    type Output;
+
+    /// Transition all pins into GPIO outputs
+    fn into_output_pins(self) -> Self::Output;
+}
+
+macro_rules! into_output_pins {
+    ($($t:ident, $pin:ident, $token:ident, $level:ident;)*) => {
+        impl<$($t,)*> IntoOutputPins
+            for ($(
+                (
+                    Pin<$t, pins::state::Unused>,
+                    Token<$t, init_state::Enabled>,
+                    Level,
+                ),
+            )*)
+        where
+            $($t: pins::Trait,)*
+        {
+            type Output = ($(GpioPin<$t, direction::Output>,)*);
+
+            fn into_output_pins(self) -> Self::Output {
+                let ($(($pin, $token, $level),)*) = self;
+                ($($pin.into_output_pin($token, $level),)*)
+            }
+        }
+    };
+}
+
+into_output_pins!(T1, p1, t1, l1;);
+into_output_pins!(T1, p1, t1, l1; T2, p2, t2, l2;);
+into_output_pins!(T1, p1, t1, l1; T2, p2, t2, l2; T3, p3, t3, l3;);
+into_output_pins!(
+    T1, p1, t1, l1;
+    T2, p2, t2, l2;
+    T3, p3, t3, l3;
+    T4, p4, t4, l4;
+);
+into_output_pins!(
+    T1, p1, t1, l1;
+    T2, p2, t2, l2;
+    T3, p3, t3, l3;
+    T4, p4, t4, l4;
+    T5, p5, t5, l5;
+);
+into_output_pins!(
+    T1, p1, t1, l1;
+    T2, p2, t2, l2;
+    T3, p3, t3, l3;
+    T4, p4, t4, l4;
+    T5, p5, t5, l5;
+    T6, p6, t6, l6;
+);
+into_output_pins!(
+    T1, p1, t1, l1;
+    T2, p2, t2, l2;
+    T3, p3, t3, l3;
+    T4, p4, t4, l4;
+    T5, p5, t5, l5;
+    T6, p6, t6, l6;
+    T7, p7, t7, l7;
+);
+into_output_pins!(
+    T1, p1, t1, l1;
+    T2, p2, t2, l2;
+    T3, p3, t3, l3;
+    T4, p4, t4, l4;
+    T5, p5, t5, l5;
+    T6, p6, t6, l6;
+    T7, p7, t7, l7;
+    T8, p8, t8, l8;
+);
+
+/// The voltage level of a pin
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Level {
+    /// High voltage
+    High,
+
+    /// Low voltage
+    Low,
+}
+
+/// The order in which a pin's output level and direction are written
+///
+/// When a pin is switched to output, two registers need to be written: One to
+/// set the output level, one to set the direction. This determines the order
+/// in which that happens.
+///
+/// Methods that don't take an `OutputInitPolicy` always use
+/// [`OutputInitPolicy::LevelThenDir`], which is also the [`Default`].
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{Level, OutputInitPolicy},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let pin = p.pins.pio0_12.into_input_pin(gpio.tokens.pio0_12);
+/// let pin = pin.into_output_with_policy(
+///     Level::High,
+///     OutputInitPolicy::DirThenLevel,
+/// );
+/// ```
+///
+/// [`OutputInitPolicy::LevelThenDir`]: #variant.LevelThenDir
+/// [`Default`]: #impl-Default
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OutputInitPolicy {
+    /// Write the output level, then switch the direction to output
+    ///
+    /// The pin never drives any level other than the requested one, not even
+    /// for a short time.
+    #[default]
+    LevelThenDir,
+
+    /// Switch the direction to output, then write the output level
+    ///
+    /// The pin drives whatever output level was configured before, until the
+    /// requested level is written a few clock cycles later. Only use this, if
+    /// your hardware requires this exact sequence.
+    DirThenLevel,
+}
+
+// The following helpers are parameterized by port and mask, instead of the pin
+// type. This keeps all register accesses in one place, regardless of whether
+// the pin is known at compile time.
+
+fn set_high(registers: &Registers, port: usize, mask: u32) {
+    registers.set[port].write(|w| unsafe { w.setp().bits(mask) });
+}
+
+fn set_low(registers: &Registers, port: usize, mask: u32) {
+    registers.clr[port].write(|w| unsafe { w.clrp().bits(mask) });
+}
 
-fn set_high<T: pins::Trait>(registers: &Registers) {
-    registers.set[T::PORT].write(|w| unsafe { w.setp().bits(T::MASK) });
+fn set_level(registers: &Registers, port: usize, mask: u32, level: Level) {
+    match level {
+        Level::High => set_high(registers, port, mask),
+        Level::Low => set_low(registers, port, mask),
+    }
+}
+
+fn toggle(registers: &Registers, port: usize, mask: u32) {
+    registers.not[port].write(|w| unsafe { w.notp().bits(mask) });
+}
+
+fn is_high(registers: &Registers, port: usize, mask: u32) -> bool {
+    registers.pin[port].read().port().bits() & mask == mask
+}
+
+fn is_output(registers: &Registers, port: usize, mask: u32) -> bool {
+    registers.dir[port].read().dirp().bits() & mask == mask
+}
+
+// Reading SET returns the output register, not the pin levels.
+fn is_set_high(registers: &Registers, port: usize, mask: u32) -> bool {
+    registers.set[port].read().setp().bits() & mask == mask
+}
+
+// Sets the output level, then switches the pin to output. This order is
+// important. The other way around, the pin could briefly drive the level that
+// was configured before.
+fn set_output_with_level(
+    registers: &impl OutputWrites,
+    port: usize,
+    mask: u32,
+    level: Level,
+) {
+    set_output_with_policy(
+        registers,
+        port,
+        mask,
+        level,
+        OutputInitPolicy::LevelThenDir,
+    );
+}
+
+fn set_output_with_policy(
+    registers: &impl OutputWrites,
+    port: usize,
+    mask: u32,
+    level: Level,
+    policy: OutputInitPolicy,
+) {
+    for step in policy.steps().iter() {
+        match step {
+            OutputInitStep::Level => registers.write_level(port, mask, level),
+            OutputInitStep::Direction => {
+                registers.write_direction_output(port, mask)
+            }
+        }
+    }
 }
 
-fn set_low<T: pins::Trait>(registers: &Registers) {
-    registers.clr[T::PORT].write(|w| unsafe { w.clrp().bits(T::MASK) });
+/// The register writes that switch a pin to output
+///
+/// Only implemented by `Registers`. Exists, so the tests can record the
+/// order of the writes.
+trait OutputWrites {
+    /// Write the output level, using SET or CLR
+    fn write_level(&self, port: usize, mask: u32, level: Level);
+
+    /// Switch the direction to output, using DIRSET
+    fn write_direction_output(&self, port: usize, mask: u32);
+}
+
+impl OutputWrites for Registers<'_> {
+    fn write_level(&self, port: usize, mask: u32, level: Level) {
+        set_level(self, port, mask, level)
+    }
+
+    fn write_direction_output(&self, port: usize, mask: u32) {
+        set_direction_output(self, port, mask)
+    }
+}
+
+/// A register write that is part of switching a pin to output
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum OutputInitStep {
+    /// Write the output level, using SET or CLR
+    Level,
+
+    /// Switch the direction to output, using DIRSET
+    Direction,
 }
 
-fn is_high<T: pins::Trait>(registers: &Registers) -> bool {
-    registers.pin[T::PORT].read().port().bits() & T::MASK == T::MASK
+impl OutputInitPolicy {
+    /// The register writes of this policy, in the order they are done in
+    fn steps(self) -> [OutputInitStep; 2] {
+        match self {
+            OutputInitPolicy::LevelThenDir => {
+                [OutputInitStep::Level, OutputInitStep::Direction]
+            }
+            OutputInitPolicy::DirThenLevel => {
+                [OutputInitStep::Direction, OutputInitStep::Level]
+            }
+        }
+    }
 }
 
 // For internal use only.
 // Use the direction helpers of GpioPin<T, direction::Output> and GpioPin<T, direction::Dynamic>
 // instead.
-fn set_direction_output<T: pins::Trait>(registers: &Registers) {
-    registers.dirset[T::PORT].write(|w| unsafe { w.dirsetp().bits(T::MASK) });
+fn set_direction_output(registers: &Registers, port: usize, mask: u32) {
+    registers.dirset[port].write(|w| unsafe { w.dirsetp().bits(mask) });
 }
 
 // For internal use only.
 // Use the direction helpers of GpioPin<T, direction::Input> and GpioPin<T, direction::Dynamic>
 // instead.
-fn set_direction_input<T: pins::Trait>(registers: &Registers) {
-    registers.dirclr[T::PORT].write(|w| unsafe { w.dirclrp().bits(T::MASK) });
+fn set_direction_input(registers: &Registers, port: usize, mask: u32) {
+    registers.dirclr[port].write(|w| unsafe { w.dirclrp().bits(mask) });
 }
 
 /// This is an internal type that should be of no concern to users of this crate
 pub struct Registers<'gpio> {
+    dir: &'gpio [DIR],
     dirset: &'gpio [DIRSET],
     dirclr: &'gpio [DIRCLR],
     pin: &'gpio [PIN],
@@ -852,7 +3051,7 @@ impl<'gpio> Registers<'gpio> {
     /// If the reference to `RegisterBlock` is not exclusively owned by the
     /// caller, accessing all registers is still completely race-free, as long
     /// as the following rules are upheld:
-    /// - Never write to `pin`, only use it for reading.
+    /// - Never write to `dir` or `pin`, only use them for reading.
     /// - For all other registers, only set bits that no other callers are
     ///   setting.
     fn new(gpio: &'gpio pac::gpio::RegisterBlock) -> Self {
@@ -861,6 +3060,7 @@ impl<'gpio> Registers<'gpio> {
             use core::slice;
 
             Self {
+                dir: slice::from_ref(&gpio.dir0),
                 dirset: slice::from_ref(&gpio.dirset0),
                 dirclr: slice::from_ref(&gpio.dirclr0),
                 pin: slice::from_ref(&gpio.pin0),
@@ -872,6 +3072,7 @@ impl<'gpio> Registers<'gpio> {
 
         #[cfg(feature = "845")]
         Self {
+            dir: &gpio.dir,
             dirset: &gpio.dirset,
             dirclr: &gpio.dirclr,
             pin: &gpio.pin,
@@ -882,6 +3083,136 @@ impl<'gpio> Registers<'gpio> {
     }
 }
 
+/// Transition pin `T` into GPIO mode, with direction `D`
+///
+/// This is the common path of every transition into GPIO mode, no matter the
+/// direction, so true open-drain pins are always in GPIO mode in IOCON, even
+/// if they start out as inputs and are switched to output later.
+fn init<T, D>(
+    registers: &Registers,
+    syscon: &pac::syscon::RegisterBlock,
+    iocon: *mut pac::iocon::RegisterBlock,
+    arg: D::SwitchArg,
+) -> D
+where
+    T: pins::Trait,
+    D: Direction,
+{
+    claims::claim(T::PORT, T::MASK);
+    pins::iocon::select_gpio_mode::<T>(syscon, iocon);
+    D::switch::<T>(registers, arg)
+}
+
+/// Debug-build registry of the pins currently used for GPIO
+///
+/// The type system makes sure that every pin is used by at most one
+/// `GpioPin`. This registry double-checks that at runtime, to catch any bugs
+/// that sneak past it (for example via code that creates tokens unsafely).
+///
+/// Only enabled with the `debug-pin-checks` feature, and only in builds with
+/// debug assertions. Otherwise, it compiles to nothing.
+#[cfg(all(feature = "debug-pin-checks", debug_assertions))]
+mod claims {
+    use core::cell::Cell;
+
+    use cortex_m::interrupt::{self, Mutex};
+
+    /// One bit per pin, set while the pin is claimed
+    static CLAIMED: Mutex<Cell<[u32; 2]>> = Mutex::new(Cell::new([0; 2]));
+
+    pub fn claim(port: usize, mask: u32) {
+        interrupt::free(|cs| {
+            let claimed = CLAIMED.borrow(cs);
+            let mut bits = claimed.get();
+
+            assert!(
+                bits[port] & mask == 0,
+                "PIO{}_{} is already used as a GPIO pin",
+                port,
+                mask.trailing_zeros(),
+            );
+
+            bits[port] |= mask;
+            claimed.set(bits);
+        })
+    }
+
+    pub fn release(port: usize, mask: u32) {
+        interrupt::free(|cs| {
+            let claimed = CLAIMED.borrow(cs);
+            let mut bits = claimed.get();
+            bits[port] &= !mask;
+            claimed.set(bits);
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        // The registry is global, so every test uses its own pins.
+
+        #[test]
+        fn claim_after_release() {
+            super::claim(0, 0x1 << 0);
+            super::release(0, 0x1 << 0);
+            super::claim(0, 0x1 << 0);
+        }
+
+        #[test]
+        #[should_panic(expected = "PIO0_1 is already used as a GPIO pin")]
+        fn double_claim_panics() {
+            super::claim(0, 0x1 << 1);
+            super::claim(0, 0x1 << 1);
+        }
+    }
+}
+
+#[cfg(not(all(feature = "debug-pin-checks", debug_assertions)))]
+mod claims {
+    pub fn claim(_port: usize, _mask: u32) {}
+    pub fn release(_port: usize, _mask: u32) {}
+}
+
+#[cfg(feature = "82x")]
+mod target {
+    use crate::pac::{
+        self,
+        gpio::{MASK0 as MASK, MPIN0 as MPIN},
+    };
+
+    pub fn mask(gpio: &pac::gpio::RegisterBlock, port: usize) -> &MASK {
+        check_port(port);
+        &gpio.mask0
+    }
+
+    pub fn mpin(gpio: &pac::gpio::RegisterBlock, port: usize) -> &MPIN {
+        check_port(port);
+        &gpio.mpin0
+    }
+
+    fn check_port(port: usize) {
+        // The LPC82x only has port 0. Checked in release builds too, as
+        // accessing the registers of port 0 instead would silently affect the
+        // wrong pins.
+        assert_eq!(port, 0, "GPIO port {} doesn't exist on LPC82x", port);
+    }
+}
+
+#[cfg(feature = "845")]
+mod target {
+    use crate::pac::{
+        self,
+        gpio::{MASK, MPIN},
+    };
+
+    pub fn mask(gpio: &pac::gpio::RegisterBlock, port: usize) -> &MASK {
+        &gpio.mask[port]
+    }
+
+    pub fn mpin(gpio: &pac::gpio::RegisterBlock, port: usize) -> &MPIN {
+        &gpio.mpin[port]
+    }
+}
+
 /// Contains types to indicate the direction of GPIO pins
 ///
 /// Please refer to [`GpioPin`] for documentation on how these types are used.
@@ -890,7 +3221,7 @@ impl<'gpio> Registers<'gpio> {
 pub mod direction {
     use crate::pins;
 
-    use super::{Level, Registers};
+    use super::{Level, OutputInitPolicy, Registers};
 
     /// Implemented by types that indicate GPIO pin direction
     ///
@@ -926,7 +3257,7 @@ pub mod direction {
             registers: &Registers,
             _: Self::SwitchArg,
         ) -> Self {
-            super::set_direction_input::<T>(registers);
+            super::set_direction_input(registers, T::PORT, T::MASK);
             Self(())
         }
     }
@@ -940,22 +3271,20 @@ pub mod direction {
     pub struct Output(());
 
     impl Direction for Output {
-        type SwitchArg = Level;
+        type SwitchArg = (Level, OutputInitPolicy);
 
         fn switch<T: pins::Trait>(
             registers: &Registers,
-            initial: Level,
+            initial: Self::SwitchArg,
         ) -> Self {
-            // First set the output level, before we switch the mode.
-            match initial {
-                Level::High => super::set_high::<T>(registers),
-                Level::Low => super::set_low::<T>(registers),
-            }
-
-            // Now that the output level is configured, we can safely switch to
-            // output mode, without risking an undesired signal between now and
-            // the first call to `set_high`/`set_low`.
-            super::set_direction_output::<T>(&registers);
+            let (level, policy) = initial;
+            super::set_output_with_policy(
+                registers,
+                T::PORT,
+                T::MASK,
+                level,
+                policy,
+            );
 
             Self(())
         }
@@ -974,6 +3303,7 @@ pub mod direction {
 
     /// Error that can be thrown by operations on a Dynamic pin
     #[derive(Copy, Clone)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     pub enum DynamicPinErr {
         /// you called a function that is not applicable to the pin's current direction
         WrongDirection,
@@ -988,24 +3318,20 @@ pub mod direction {
         ) -> Self {
             let (level, current_direction) = initial;
 
-            // First set the output level, before we switch the mode.
-            match level {
-                Level::High => super::set_high::<T>(registers),
-                Level::Low => super::set_low::<T>(registers),
-            }
-
             match current_direction {
                 pins::DynamicPinDirection::Input => {
-                    // Now that the output level is configured, we can safely switch to
-                    // output mode, without risking an undesired signal between now and
-                    // the first call to `set_high`/`set_low`.
-                    super::set_direction_input::<T>(registers);
+                    // Set the output level anyway, so it's already configured
+                    // when the pin is switched to output later.
+                    super::set_level(registers, T::PORT, T::MASK, level);
+                    super::set_direction_input(registers, T::PORT, T::MASK);
                 }
                 pins::DynamicPinDirection::Output => {
-                    // Now that the output level is configured, we can safely switch to
-                    // output mode, without risking an undesired signal between now and
-                    // the first call to `set_high`/`set_low`.
-                    super::set_direction_output::<T>(registers);
+                    super::set_output_with_level(
+                        registers,
+                        T::PORT,
+                        T::MASK,
+                        level,
+                    );
                 }
             }
 
@@ -1013,3 +3339,563 @@ pub mod direction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::RefCell, mem, ptr};
+
+    use crate::{
+        pac,
+        pins::{PIO0_10, PIO0_12, PIO0_14},
+        timer::mock::MockTimer,
+    };
+
+    use super::{
+        direction::{self, Direction as _},
+        Level, MaskedPins, OutputInitPolicy, OutputWrites, PortWriter,
+        Registers,
+    };
+
+    // Use the highest port, to make sure the port index is respected.
+    #[cfg(feature = "82x")]
+    const PORT: usize = 0;
+    #[cfg(feature = "845")]
+    const PORT: usize = 1;
+
+    const MASK: u32 = 0x1 << 3;
+
+    fn register_block() -> &'static pac::gpio::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        Box::leak(Box::new(unsafe { mem::zeroed() }))
+    }
+
+    /// Read a register, even if it is write-only
+    fn bits<R>(register: &R) -> u32 {
+        // Sound, as a register only consists of a `u32` value.
+        unsafe { ptr::read_volatile(register as *const R as *const u32) }
+    }
+
+    /// A register write done while switching a pin to output
+    #[derive(Debug, Eq, PartialEq)]
+    enum Write {
+        Set(usize, u32),
+        Clr(usize, u32),
+        DirSet(usize, u32),
+    }
+
+    /// Records the register writes, in the order they are done in
+    #[derive(Default)]
+    struct WriteLog(RefCell<Vec<Write>>);
+
+    impl WriteLog {
+        fn writes(self) -> Vec<Write> {
+            self.0.into_inner()
+        }
+    }
+
+    impl OutputWrites for WriteLog {
+        fn write_level(&self, port: usize, mask: u32, level: Level) {
+            let write = match level {
+                Level::High => Write::Set(port, mask),
+                Level::Low => Write::Clr(port, mask),
+            };
+            self.0.borrow_mut().push(write);
+        }
+
+        fn write_direction_output(&self, port: usize, mask: u32) {
+            self.0.borrow_mut().push(Write::DirSet(port, mask));
+        }
+    }
+
+    #[test]
+    fn set_output_with_level_writes_level_before_direction() {
+        let log = WriteLog::default();
+        super::set_output_with_level(&log, PORT, MASK, Level::High);
+        assert_eq!(
+            log.writes(),
+            [Write::Set(PORT, MASK), Write::DirSet(PORT, MASK)],
+        );
+
+        let log = WriteLog::default();
+        super::set_output_with_level(&log, PORT, MASK, Level::Low);
+        assert_eq!(
+            log.writes(),
+            [Write::Clr(PORT, MASK), Write::DirSet(PORT, MASK)],
+        );
+    }
+
+    #[test]
+    fn set_output_with_level_high_writes_set_and_dirset() {
+        let registers = Registers::new(register_block());
+
+        super::set_output_with_level(&registers, PORT, MASK, Level::High);
+
+        assert_eq!(bits(&registers.set[PORT]), MASK);
+        assert_eq!(bits(&registers.clr[PORT]), 0);
+        assert_eq!(bits(&registers.dirset[PORT]), MASK);
+    }
+
+    #[test]
+    fn set_output_with_level_low_writes_clr_and_dirset() {
+        let registers = Registers::new(register_block());
+
+        super::set_output_with_level(&registers, PORT, MASK, Level::Low);
+
+        assert_eq!(bits(&registers.set[PORT]), 0);
+        assert_eq!(bits(&registers.clr[PORT]), MASK);
+        assert_eq!(bits(&registers.dirset[PORT]), MASK);
+    }
+
+    #[test]
+    fn level_helpers_write_mask_to_port() {
+        let registers = Registers::new(register_block());
+
+        super::set_high(&registers, PORT, MASK);
+        assert_eq!(bits(&registers.set[PORT]), MASK);
+
+        super::set_low(&registers, PORT, MASK);
+        assert_eq!(bits(&registers.clr[PORT]), MASK);
+
+        super::toggle(&registers, PORT, MASK);
+        assert_eq!(bits(&registers.not[PORT]), MASK);
+
+        #[cfg(feature = "845")]
+        {
+            assert_eq!(bits(&registers.set[0]), 0);
+            assert_eq!(bits(&registers.clr[0]), 0);
+            assert_eq!(bits(&registers.not[0]), 0);
+        }
+    }
+
+    #[test]
+    fn direction_helpers_write_mask_to_port() {
+        let registers = Registers::new(register_block());
+
+        super::set_direction_output(&registers, PORT, MASK);
+        assert_eq!(bits(&registers.dirset[PORT]), MASK);
+
+        super::set_direction_input(&registers, PORT, MASK);
+        assert_eq!(bits(&registers.dirclr[PORT]), MASK);
+    }
+
+    #[test]
+    fn read_helpers_check_mask_on_port() {
+        let gpio = register_block();
+        let registers = Registers::new(gpio);
+
+        assert!(!super::is_high(&registers, PORT, MASK));
+        assert!(!super::is_output(&registers, PORT, MASK));
+        assert!(!super::is_set_high(&registers, PORT, MASK));
+
+        // Sound, as nothing else accesses this register block.
+        unsafe {
+            registers.pin[PORT].write(|w| w.bits(MASK));
+            registers.dir[PORT].write(|w| w.bits(MASK));
+            registers.set[PORT].write(|w| w.bits(MASK));
+        }
+
+        assert!(super::is_high(&registers, PORT, MASK));
+        assert!(super::is_output(&registers, PORT, MASK));
+        assert!(super::is_set_high(&registers, PORT, MASK));
+
+        // Other pins of the port are not affected.
+        assert!(!super::is_high(&registers, PORT, MASK << 1));
+        assert!(!super::is_output(&registers, PORT, MASK << 1));
+        assert!(!super::is_set_high(&registers, PORT, MASK << 1));
+    }
+
+    #[test]
+    fn default_policy_writes_level_before_direction() {
+        let log = WriteLog::default();
+        super::set_output_with_policy(
+            &log,
+            PORT,
+            MASK,
+            Level::High,
+            OutputInitPolicy::default(),
+        );
+        assert_eq!(
+            log.writes(),
+            [Write::Set(PORT, MASK), Write::DirSet(PORT, MASK)],
+        );
+    }
+
+    #[test]
+    fn dir_then_level_policy_writes_direction_before_level() {
+        let log = WriteLog::default();
+        super::set_output_with_policy(
+            &log,
+            PORT,
+            MASK,
+            Level::High,
+            OutputInitPolicy::DirThenLevel,
+        );
+        assert_eq!(
+            log.writes(),
+            [Write::DirSet(PORT, MASK), Write::Set(PORT, MASK)],
+        );
+
+        let log = WriteLog::default();
+        super::set_output_with_policy(
+            &log,
+            PORT,
+            MASK,
+            Level::Low,
+            OutputInitPolicy::DirThenLevel,
+        );
+        assert_eq!(
+            log.writes(),
+            [Write::DirSet(PORT, MASK), Write::Clr(PORT, MASK)],
+        );
+    }
+
+    #[test]
+    fn set_output_with_policy_writes_level_and_direction() {
+        for &policy in &[
+            OutputInitPolicy::LevelThenDir,
+            OutputInitPolicy::DirThenLevel,
+        ] {
+            let registers = Registers::new(register_block());
+
+            super::set_output_with_policy(
+                &registers,
+                PORT,
+                MASK,
+                Level::Low,
+                policy,
+            );
+
+            assert_eq!(bits(&registers.clr[PORT]), MASK);
+            assert_eq!(bits(&registers.dirset[PORT]), MASK);
+        }
+    }
+
+    #[test]
+    fn port_writer_filters_bits_by_mask() {
+        let registers = Registers::new(register_block());
+        let mut port = PortWriter::from_registers(&registers, PORT, 0x0f);
+
+        port.set_high(0xff);
+        assert_eq!(bits(&registers.set[PORT]), 0x0f);
+
+        port.set_low(0x3c);
+        assert_eq!(bits(&registers.clr[PORT]), 0x0c);
+
+        port.toggle(0xf0);
+        assert_eq!(bits(&registers.not[PORT]), 0x00);
+
+        port.set_level(0x81, Level::High);
+        assert_eq!(bits(&registers.set[PORT]), 0x01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn port_writer_rejects_missing_port() {
+        let registers = Registers::new(register_block());
+        PortWriter::from_registers(&registers, PORT + 1, 0x1);
+    }
+
+    #[test]
+    fn force_set_and_toggle_write_existing_port() {
+        let registers = Registers::new(register_block());
+
+        super::force_set_in(&registers, PORT, MASK, Level::High);
+        assert_eq!(bits(&registers.set[PORT]), MASK);
+
+        super::force_set_in(&registers, PORT, MASK, Level::Low);
+        assert_eq!(bits(&registers.clr[PORT]), MASK);
+
+        super::force_toggle_in(&registers, PORT, MASK);
+        assert_eq!(bits(&registers.not[PORT]), MASK);
+    }
+
+    #[test]
+    fn force_set_and_toggle_ignore_missing_port() {
+        let registers = Registers::new(register_block());
+
+        super::force_set_in(&registers, PORT + 1, MASK, Level::High);
+        super::force_set_in(&registers, usize::MAX, MASK, Level::Low);
+        super::force_toggle_in(&registers, PORT + 1, MASK);
+        super::force_toggle_in(&registers, usize::MAX, MASK);
+
+        for port in 0..=PORT {
+            assert_eq!(bits(&registers.set[port]), 0);
+            assert_eq!(bits(&registers.clr[port]), 0);
+            assert_eq!(bits(&registers.not[port]), 0);
+        }
+    }
+
+    #[test]
+    fn true_open_drain_input_pin_can_switch_to_output() {
+        let registers = Registers::new(register_block());
+        // Sound, as the register blocks only consist of `u32` values.
+        let syscon: pac::syscon::RegisterBlock = unsafe { mem::zeroed() };
+        let mut iocon: pac::iocon::RegisterBlock = unsafe { mem::zeroed() };
+
+        // Same path as `Pin::into_input_pin`.
+        super::init::<PIO0_10, direction::Input>(
+            &registers,
+            &syscon,
+            &mut iocon,
+            (),
+        );
+        assert_eq!(iocon.pio0_10.read().bits(), 0x1 << 8);
+
+        // Same path as `GpioPin::into_output`.
+        direction::Output::switch::<PIO0_10>(
+            &registers,
+            (Level::Low, OutputInitPolicy::LevelThenDir),
+        );
+        assert_eq!(iocon.pio0_10.read().bits(), 0x1 << 8);
+        assert_eq!(bits(&registers.clr[0]), 0x1 << 10);
+        assert_eq!(bits(&registers.dirset[0]), 0x1 << 10);
+
+        super::claims::release(0, 0x1 << 10);
+    }
+
+    /// Pins of a `MaskedPort`, with an unrelated pin between them
+    type MaskedPins0 = (
+        super::GpioPin<PIO0_12, direction::Output>,
+        super::GpioPin<PIO0_14, direction::Output>,
+    );
+
+    /// Apply MASK to an MPIN access, like the hardware does
+    ///
+    /// Masked bits are ignored when writing, and read as 0.
+    fn gated(gpio: &pac::gpio::RegisterBlock, port: usize, value: u32) -> u32 {
+        value & !bits(super::target::mask(gpio, port))
+    }
+
+    #[test]
+    fn mask_gates_mpin_reads_to_masked_pins() {
+        let gpio = register_block();
+        let pins = <MaskedPins0 as MaskedPins>::MASK;
+        assert_eq!(pins, 0b101 << 12);
+
+        super::set_mask(gpio, 0, pins);
+        super::write_masked(gpio, 0, !0);
+
+        assert_eq!(gated(gpio, 0, super::read_masked(gpio, 0)), pins);
+    }
+
+    #[test]
+    fn mask_gates_mpin_writes_to_masked_pins() {
+        let gpio = register_block();
+        let pins = <MaskedPins0 as MaskedPins>::MASK;
+
+        super::set_mask(gpio, 0, pins);
+        super::write_masked(gpio, 0, 0b111 << 12);
+
+        // PIO0_13 is between the pins, but not part of them.
+        assert_eq!(
+            gated(gpio, 0, bits(super::target::mpin(gpio, 0))),
+            0b101 << 12,
+        );
+    }
+
+    #[test]
+    fn unmasking_all_pins_makes_mpin_ungated() {
+        let gpio = register_block();
+
+        super::set_mask(gpio, 0, <MaskedPins0 as MaskedPins>::MASK);
+        super::set_mask(gpio, 0, !0);
+
+        assert_eq!(bits(super::target::mask(gpio, 0)), 0);
+        assert_eq!(gated(gpio, 0, !0), !0);
+    }
+
+    #[cfg(feature = "82x")]
+    #[test]
+    #[should_panic(expected = "GPIO port 1 doesn't exist on LPC82x")]
+    fn mask_of_missing_port_panics() {
+        super::set_mask(register_block(), 1, !0);
+    }
+
+    #[cfg(feature = "845")]
+    #[test]
+    fn mask_gates_mpin_of_port_1() {
+        use crate::pins::{PIO1_0, PIO1_1};
+
+        type MaskedPins1 = (
+            super::GpioPin<PIO1_0, direction::Output>,
+            super::GpioPin<PIO1_1, direction::Output>,
+        );
+
+        let gpio = register_block();
+        let pins = <MaskedPins1 as MaskedPins>::MASK;
+        assert_eq!(pins, 0b11);
+
+        super::set_mask(gpio, 1, pins);
+        super::write_masked(gpio, 1, !0);
+
+        assert_eq!(gated(gpio, 1, super::read_masked(gpio, 1)), pins);
+        assert_eq!(bits(&gpio.mpin[1]), !0);
+
+        // Port 0 is not affected.
+        assert_eq!(bits(&gpio.mask[0]), 0);
+        assert_eq!(bits(&gpio.mpin[0]), 0);
+    }
+
+    #[test]
+    fn set_and_sense_reads_low_while_line_is_held_low() {
+        let registers = Registers::new(register_block());
+
+        // Nothing sets the PIN register, so the line stays low, as if another
+        // device on the bus was pulling it low.
+        let level =
+            super::set_and_sense(&registers, PORT, MASK, Level::High, || {});
+
+        assert_eq!(bits(&registers.set[PORT]), MASK);
+        assert_eq!(level, Level::Low);
+    }
+
+    #[test]
+    fn set_and_sense_reads_level_after_settling() {
+        let registers = Registers::new(register_block());
+
+        let level =
+            super::set_and_sense(&registers, PORT, MASK, Level::High, || {
+                registers.pin[PORT].write(|w| unsafe { w.bits(MASK) })
+            });
+
+        assert_eq!(level, Level::High);
+    }
+
+    /// Records the levels it is set to
+    #[derive(Default)]
+    struct RecordingPin(Vec<bool>);
+
+    impl embedded_hal::digital::v2::OutputPin for RecordingPin {
+        type Error = void::Void;
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.push(true);
+            Ok(())
+        }
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.push(false);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn shared_pin_drives_pin_while_locked() {
+        use embedded_hal::digital::v2::OutputPin as _;
+
+        let shared = super::SharedPin::new(RecordingPin::default());
+
+        shared.lock(|pin| pin.set_high()).unwrap();
+        let driven = shared.lock(|pin| {
+            pin.set_low().unwrap();
+            pin.0.len()
+        });
+
+        assert_eq!(driven, 2);
+        assert_eq!(shared.free().0, [true, false]);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn shared_pin_rejects_nested_lock() {
+        let shared = super::SharedPin::new(RecordingPin::default());
+
+        shared.lock(|_| shared.lock(|_| ()));
+    }
+
+    #[test]
+    fn direction_state_reports_mixed_directions() {
+        let registers = Registers::new(register_block());
+
+        // PIO0_3 and PIO0_5 are outputs, PIO0_4 (between them) is an input.
+        let outputs = 0x1 << 3 | 0x1 << 5;
+        let input = 0x1 << 4;
+        registers.dir[PORT].write(|w| unsafe { w.bits(outputs) });
+
+        let state = super::direction_state_in(&registers, PORT);
+        assert_eq!(state & (outputs | input), outputs);
+
+        assert!(super::is_output(&registers, PORT, 0x1 << 3));
+        assert!(!super::is_output(&registers, PORT, input));
+        assert!(super::is_output(&registers, PORT, 0x1 << 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn direction_state_rejects_missing_port() {
+        let registers = Registers::new(register_block());
+
+        super::direction_state_in(&registers, PORT + 1);
+    }
+
+    /// Returns a sequence of levels, one per query
+    struct LevelSequence<'a>(core::cell::Cell<&'a [bool]>);
+
+    impl embedded_hal::digital::v2::InputPin for LevelSequence<'_> {
+        type Error = void::Void;
+
+        fn is_high(&self) -> Result<bool, Self::Error> {
+            let (&level, rest) = self.0.get().split_first().unwrap();
+            self.0.set(rest);
+            Ok(level)
+        }
+
+        fn is_low(&self) -> Result<bool, Self::Error> {
+            self.is_high().map(|level| !level)
+        }
+    }
+
+    #[test]
+    fn debounced_reports_level_once_it_is_stable() {
+        use embedded_hal::digital::v2::InputPin as _;
+
+        // The pin bounces after going high, then settles.
+        let levels = [
+            false, false, true, false, true, false, true, true, true, true,
+        ];
+        let button = super::Debounced::new(
+            LevelSequence(core::cell::Cell::new(&levels)),
+            MockTimer(0),
+            2,
+        );
+
+        let mut reported = [false; 10];
+        for level in reported.iter_mut() {
+            *level = button.is_high().unwrap();
+        }
+
+        assert_eq!(
+            reported,
+            [
+                false, false, false, false, false, false, false, false, true,
+                true
+            ],
+        );
+    }
+
+    #[test]
+    fn soft_pwm_toggles_pin_according_to_duty_cycle() {
+        use embedded_hal::PwmPin as _;
+
+        let mut pwm =
+            super::SoftPwm::new(RecordingPin::default(), MockTimer(0), 4);
+        pwm.set_duty(1);
+        pwm.enable();
+
+        let mut levels = [false; 8];
+        for level in levels.iter_mut() {
+            *level = *pwm.pin().0.last().unwrap();
+            pwm.update().unwrap();
+        }
+
+        assert_eq!(
+            levels,
+            [true, false, false, false, true, false, false, false]
+        );
+
+        // At full duty cycle, the pin is held high.
+        pwm.set_duty(pwm.get_max_duty());
+        pwm.update().unwrap();
+        assert_eq!(pwm.pin().0.last(), Some(&true));
+    }
+}
@@ -1,6 +1,49 @@
 use core::marker::PhantomData;
 
-use crate::syscon::{self, clock_source::PeripheralClockSelector};
+use crate::{
+    clock::Frequency,
+    syscon::{self, clock_source::PeripheralClockSelector, IOSC},
+};
+
+/// The lowest value of MSTSCLHIGH/MSTSCLLOW, in clock cycles
+const SCL_MIN: u32 = 2;
+
+/// The highest value of MSTSCLHIGH/MSTSCLLOW, in clock cycles
+const SCL_MAX: u32 = 9;
+
+/// Maximum speed (in Hz) and minimum SCL high and low times (in nanoseconds)
+/// of an I2C bus mode
+///
+/// See I2C-bus specification (UM10204), table 10.
+struct Timing {
+    max_speed: u32,
+    high: u32,
+    low: u32,
+}
+
+/// Standard-mode, up to 100 kHz
+const STANDARD_MODE: Timing = Timing {
+    max_speed: 100_000,
+    high: 4_000,
+    low: 4_700,
+};
+
+/// Fast-mode, up to 400 kHz
+const FAST_MODE: Timing = Timing {
+    max_speed: 400_000,
+    high: 600,
+    low: 1_300,
+};
+
+/// Fast-mode Plus, up to 1 MHz
+///
+/// Higher speeds are treated as Fast-mode Plus too, as long as the minimum
+/// SCL high and low times are met.
+const FAST_MODE_PLUS: Timing = Timing {
+    max_speed: u32::MAX,
+    high: 260,
+    low: 500,
+};
 
 /// Contains the clock configuration for an I2C instance
 pub struct Clock<Clock> {
@@ -27,6 +70,408 @@ where
             _clock: PhantomData,
         }
     }
+
+    /// Create the clock config for the given bus speed
+    ///
+    /// Derives the clock divider and SCL high/low times from the frequency of
+    /// `source`, such that the resulting bus speed is as close as possible to
+    /// `speed` (in Hz), without exceeding it.
+    ///
+    /// The SCL high and low times meet the minimums of the I2C bus mode that
+    /// `speed` falls into: Standard-mode (up to 100 kHz), Fast-mode (up to
+    /// 400 kHz), or Fast-mode Plus (above that). This might result in a
+    /// slower bus speed than the one requested, if the minimums can't be met
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `speed` is zero, or if `speed` can't be reached, because it
+    /// is too low for the frequency of `source`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     Peripherals,
+    ///     i2c,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    /// let syscon = p.SYSCON.split();
+    ///
+    /// // Standard-mode I2C, derived from the 12 MHz IRC/FRO.
+    /// let clock = i2c::Clock::new_with_speed(&syscon.iosc, 100_000);
+    /// ```
+    pub fn new_with_speed(source: &C, speed: u32) -> Self
+    where
+        C: Frequency,
+    {
+        let (divval, high, low) = dividers(source.hz(), speed)
+            .expect("I2C bus speed not reachable with this clock source");
+
+        Self::from_dividers(divval, high, low)
+    }
+}
+
+impl<C> Clock<C>
+where
+    C: DefaultFrequency,
+{
+    /// Create a new I2C clock configuration for 400 kHz
+    ///
+    /// Only available for clock sources that run at 12 MHz (see
+    /// [`DefaultFrequency`]), as the configuration is computed for that
+    /// frequency. Use [`Clock::new_with_speed`] for other clock sources.
+    ///
+    /// [`DefaultFrequency`]: trait.DefaultFrequency.html
+    /// [`Clock::new_with_speed`]: #method.new_with_speed
+    pub fn new_400khz() -> Self {
+        Self {
+            divval: 5,
+            mstsclhigh: 0,
+            mstscllow: 1,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Create a new I2C clock configuration for 100 kHz
+    ///
+    /// Standard-mode is the safest choice for devices whose capabilities are
+    /// unknown, and for long wires. The configuration meets the minimum SCL
+    /// high and low times of standard-mode (4.0 µs and 4.7 µs respectively),
+    /// which are considerably longer than those of fast-mode.
+    ///
+    /// Only available for clock sources that run at 12 MHz (see
+    /// [`DefaultFrequency`]), as the configuration is computed for that
+    /// frequency. Use [`Clock::new_with_speed`] for other clock sources.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{clock::Frequency, i2c, syscon::IOSC};
+    ///
+    /// struct Source;
+    ///
+    /// impl Frequency for Source {
+    ///     fn hz(&self) -> u32 { 12_000_000 }
+    /// }
+    ///
+    /// let clock = i2c::Clock::<IOSC>::new_100khz();
+    ///
+    /// assert!(clock.speed(&Source) <= 100_000);
+    /// assert!(clock.scl_high_ns(&Source) >= 4_000);
+    /// assert!(clock.scl_low_ns(&Source) >= 4_700);
+    /// ```
+    ///
+    /// [`DefaultFrequency`]: trait.DefaultFrequency.html
+    /// [`Clock::new_with_speed`]: #method.new_with_speed
+    pub fn new_100khz() -> Self {
+        let (divval, high, low) = dividers(12_000_000, 100_000)
+            .expect("100 kHz is always reachable from 12 MHz");
+
+        Self::from_dividers(divval, high, low)
+    }
+}
+
+impl<C> Clock<C> {
+    /// Encode the clock configuration into a `u32`
+    ///
+    /// This makes it possible to store the configuration somewhere that
+    /// survives deep power-down mode, like the general-purpose registers of
+    /// the PMU (see [`pmu::Handle::gp_write`]). After waking up, the
+    /// configuration can be restored using [`from_u32`], instead of
+    /// computing it again. This is the I2C equivalent of [`usart::Config`].
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{i2c, syscon::IOSC};
+    ///
+    /// let bits = i2c::Clock::<IOSC>::new_400khz().to_u32();
+    ///
+    /// let clock = i2c::Clock::<IOSC>::from_u32(bits).unwrap();
+    /// assert_eq!(clock.to_u32(), bits);
+    ///
+    /// assert!(i2c::Clock::<IOSC>::from_u32(u32::MAX).is_none());
+    /// ```
+    ///
+    /// [`pmu::Handle::gp_write`]: ../pmu/struct.Handle.html#method.gp_write
+    /// [`from_u32`]: #method.from_u32
+    /// [`usart::Config`]: ../usart/struct.Config.html
+    pub fn to_u32(&self) -> u32 {
+        self.divval as u32
+            | (self.mstsclhigh as u32) << 16
+            | (self.mstscllow as u32) << 19
+    }
+
+    /// Decode a clock configuration that was encoded using [`to_u32`]
+    ///
+    /// Returns `None`, if `bits` doesn't represent a valid configuration.
+    ///
+    /// Please note that the configuration only stores the divider values, not
+    /// the frequency of the clock source they were computed for. If the clock
+    /// configuration might have changed, use [`speed`] to check the restored
+    /// configuration, before trusting it.
+    ///
+    /// [`to_u32`]: #method.to_u32
+    /// [`speed`]: #method.speed
+    pub fn from_u32(bits: u32) -> Option<Self> {
+        if bits >> 22 != 0 {
+            return None;
+        }
+
+        let mstsclhigh = ((bits >> 16) & 0x7) as u8;
+        let mstscllow = ((bits >> 19) & 0x7) as u8;
+
+        // The fields store the number of cycles minus 2.
+        let range = 0..=(SCL_MAX - SCL_MIN) as u8;
+        if !range.contains(&mstsclhigh) || !range.contains(&mstscllow) {
+            return None;
+        }
+
+        Some(Self {
+            divval: bits as u16,
+            mstsclhigh,
+            mstscllow,
+            _clock: PhantomData,
+        })
+    }
+
+    /// Override the SCL high and low times
+    ///
+    /// By default, the SCL high and low times are computed from the requested
+    /// bus speed (or set to fixed values, depending on the constructor). Some
+    /// devices have timing requirements that the defaults don't meet, and
+    /// long cables might require stretching the clock. This method can be
+    /// used to override the defaults in those cases, leaving the clock
+    /// divider as it is.
+    ///
+    /// `high` and `low` are the number of divided clock cycles that SCL is
+    /// high or low, respectively. Both have to be between 2 and 9. Since
+    /// I2C requires the low phase to be at least as long as the high phase,
+    /// `low` must not be less than `high`.
+    ///
+    /// Since the clock divider stays the same, changing `high` and `low` also
+    /// changes the bus speed. The resulting bus speed must stay within the I2C
+    /// bus mode this configuration was created for: Standard-mode (up to
+    /// 100 kHz), Fast-mode (up to 400 kHz), or Fast-mode Plus (above that).
+    /// The resulting SCL high and low times must meet the minimums of the bus
+    /// mode that the resulting bus speed falls into. `source` must be the
+    /// clock source this configuration is used with.
+    ///
+    /// Please note that the hardware doesn't support adjusting data setup and
+    /// hold times separately. They are derived from the SCL timing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if `high` or `low` are not between 2 and 9, if `low`
+    /// is less than `high`, if the resulting bus speed is too high for the
+    /// bus mode, or if the resulting times are shorter than the minimums of
+    /// the bus mode. See [`SclTimingError`].
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     Peripherals,
+    ///     i2c,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    /// let syscon = p.SYSCON.split();
+    ///
+    /// // Stretch the low phase, for a device that needs more time.
+    /// let clock = i2c::Clock::new_with_speed(&syscon.iosc, 100_000)
+    ///     .with_scl_high_low(&syscon.iosc, 4, 8)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`SclTimingError`]: enum.SclTimingError.html
+    pub fn with_scl_high_low<F>(
+        mut self,
+        source: &F,
+        high: u8,
+        low: u8,
+    ) -> Result<Self, SclTimingError>
+    where
+        F: Frequency,
+    {
+        let range = SCL_MIN as u8..=SCL_MAX as u8;
+        if !range.contains(&high) || !range.contains(&low) {
+            return Err(SclTimingError::OutOfRange);
+        }
+        if low < high {
+            return Err(SclTimingError::LowShorterThanHigh);
+        }
+
+        let divider = self.divval as u32 + 1;
+        let speed = source.hz() / (divider * (high as u32 + low as u32));
+        if speed > timing(self.speed(source)).max_speed {
+            return Err(SclTimingError::AboveMaximumSpeed);
+        }
+
+        let timing = timing(speed);
+        if !meets(high as u32, divider, source.hz(), timing.high)
+            || !meets(low as u32, divider, source.hz(), timing.low)
+        {
+            return Err(SclTimingError::BelowMinimum);
+        }
+
+        self.mstsclhigh = high - SCL_MIN as u8;
+        self.mstscllow = low - SCL_MIN as u8;
+
+        Ok(self)
+    }
+
+    /// The bus speed that results from this configuration, in Hz
+    ///
+    /// `source` must be the clock source this configuration is used with.
+    pub fn speed<F>(&self, source: &F) -> u32
+    where
+        F: Frequency,
+    {
+        let cycles =
+            self.mstsclhigh as u32 + self.mstscllow as u32 + 2 * SCL_MIN;
+        source.hz() / ((self.divval as u32 + 1) * cycles)
+    }
+
+    fn from_dividers(divval: u16, high: u8, low: u8) -> Self {
+        Self {
+            divval,
+            mstsclhigh: high - SCL_MIN as u8,
+            mstscllow: low - SCL_MIN as u8,
+            _clock: PhantomData,
+        }
+    }
+
+    /// The SCL high time that results from this configuration, in nanoseconds
+    ///
+    /// `source` must be the clock source this configuration is used with.
+    /// The result is rounded down.
+    pub fn scl_high_ns<F>(&self, source: &F) -> u32
+    where
+        F: Frequency,
+    {
+        self.scl_ns(self.mstsclhigh, source)
+    }
+
+    /// The SCL low time that results from this configuration, in nanoseconds
+    ///
+    /// `source` must be the clock source this configuration is used with.
+    /// The result is rounded down.
+    pub fn scl_low_ns<F>(&self, source: &F) -> u32
+    where
+        F: Frequency,
+    {
+        self.scl_ns(self.mstscllow, source)
+    }
+
+    fn scl_ns<F>(&self, field: u8, source: &F) -> u32
+    where
+        F: Frequency,
+    {
+        let cycles = (field as u64 + SCL_MIN as u64) * (self.divval as u64 + 1);
+        (cycles * 1_000_000_000 / source.hz() as u64) as u32
+    }
+}
+
+/// Compute DIVVAL and the SCL high/low times for a bus speed
+///
+/// One SCL period takes `(DIVVAL + 1) * (high + low)` cycles of the source
+/// clock. Out of all combinations that don't exceed `speed` and meet the
+/// minimum SCL high/low times of the respective bus mode, the one closest to
+/// `speed` is selected. If there are multiple, the one with the lowest number
+/// of SCL cycles wins.
+fn dividers(source: u32, speed: u32) -> Option<(u16, u8, u8)> {
+    if speed == 0 {
+        return None;
+    }
+
+    let timing = timing(speed);
+
+    // Round up, to make sure we never exceed the requested speed. The
+    // frequency of a clock source is never zero.
+    let period = (source - 1) / speed + 1;
+
+    let mut best: Option<(u32, u16, u8, u8)> = None;
+
+    for cycles in 2 * SCL_MIN..=2 * SCL_MAX {
+        let divider = (period - 1) / cycles + 1;
+
+        if divider > u16::MAX as u32 + 1 {
+            continue;
+        }
+
+        let achieved = source / (divider * cycles);
+        if achieved > speed {
+            continue;
+        }
+
+        if let Some((best_speed, ..)) = best {
+            if achieved <= best_speed {
+                continue;
+            }
+        }
+
+        // Split the period evenly, if possible. Otherwise, give the low phase
+        // more cycles, as all modes require a longer low than high time.
+        let split = (SCL_MIN..=cycles / 2)
+            .rev()
+            .map(|high| (high, cycles - high))
+            .filter(|&(_, low)| low <= SCL_MAX)
+            .find(|&(high, low)| {
+                meets(high, divider, source, timing.high)
+                    && meets(low, divider, source, timing.low)
+            });
+
+        if let Some((high, low)) = split {
+            best =
+                Some((achieved, (divider - 1) as u16, high as u8, low as u8));
+        }
+    }
+
+    best.map(|(_, divval, high, low)| (divval, high, low))
+}
+
+/// The minimum SCL high and low times for a bus speed
+fn timing(speed: u32) -> Timing {
+    if speed <= STANDARD_MODE.max_speed {
+        STANDARD_MODE
+    } else if speed <= FAST_MODE.max_speed {
+        FAST_MODE
+    } else {
+        FAST_MODE_PLUS
+    }
+}
+
+/// Whether `cycles` divided clock cycles take at least `min_ns` nanoseconds
+fn meets(cycles: u32, divider: u32, source: u32, min_ns: u32) -> bool {
+    cycles as u64 * divider as u64 * 1_000_000_000
+        >= min_ns as u64 * source as u64
+}
+
+/// Indicates that SCL high and low times are invalid
+///
+/// Returned by [`Clock::with_scl_high_low`].
+///
+/// [`Clock::with_scl_high_low`]: struct.Clock.html#method.with_scl_high_low
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SclTimingError {
+    /// The high or low time is not between 2 and 9 cycles
+    OutOfRange,
+
+    /// The low time is shorter than the high time
+    LowShorterThanHigh,
+
+    /// The resulting bus speed is too high for the bus mode
+    ///
+    /// The bus mode of the configuration is determined by the bus speed it
+    /// results in, before the SCL high and low times are overridden.
+    AboveMaximumSpeed,
+
+    /// The high or low time is shorter than the minimum of the bus mode
+    BelowMinimum,
 }
 
 /// Implemented for I2C clock sources
@@ -44,49 +489,62 @@ pub trait ClockSource: private::Sealed {
         S: PeripheralClockSelector;
 }
 
+/// Implemented for I2C clock sources that run at 12 MHz
+///
+/// This is the IRC/FRO ([`IOSC`]) and, on LPC82x, `()`, which represents the
+/// main clock in its default configuration, running off the IRC.
+/// [`Clock::new_400khz`] and [`Clock::new_100khz`] are only available for
+/// these clock sources.
+///
+/// [`IOSC`]: ../syscon/struct.IOSC.html
+/// [`Clock::new_400khz`]: struct.Clock.html#method.new_400khz
+/// [`Clock::new_100khz`]: struct.Clock.html#method.new_100khz
+pub trait DefaultFrequency: ClockSource {}
+
+impl DefaultFrequency for IOSC {}
+
 #[cfg(feature = "82x")]
 mod target {
-    use core::marker::PhantomData;
+    use crate::syscon::{self, sysosc::SysOscClock, IOSC};
 
-    use crate::syscon;
+    use super::{ClockSource, DefaultFrequency};
 
-    use super::{Clock, ClockSource};
+    impl DefaultFrequency for () {}
 
-    impl super::private::Sealed for () {}
+    // On LPC82x, I2C is always clocked by the main clock. The clock source
+    // passed to the I2C API is the clock that the main clock is running off.
+    // `()` represents the main clock in its default configuration, and is
+    // supported for compatibility with earlier versions of this API.
+    macro_rules! main_clocks {
+        ($($clock:ty;)*) => {
+            $(
+                impl super::private::Sealed for $clock {}
 
-    impl ClockSource for () {
-        fn select<S>(_: &S, _: &mut syscon::Handle) {
-            // nothing to do; `()` represents the clock that is selected by
-            // default
-        }
+                impl ClockSource for $clock {
+                    fn select<S>(_: &S, _: &mut syscon::Handle) {
+                        // nothing to do; I2C is always clocked by the main
+                        // clock
+                    }
+                }
+            )*
+        };
     }
 
-    impl Clock<()> {
-        /// Create a new I2C clock configuration for 400 kHz
-        ///
-        /// Assumes the internal oscillator runs at 12 MHz.
-        pub fn new_400khz() -> Self {
-            Self {
-                divval: 5,
-                mstsclhigh: 0,
-                mstscllow: 1,
-                _clock: PhantomData,
-            }
-        }
-    }
+    main_clocks!(
+        ();
+        IOSC;
+        SysOscClock;
+    );
 }
 
 #[cfg(feature = "845")]
 mod target {
-    use core::marker::PhantomData;
-
     use crate::syscon::{
         self,
         clock_source::{PeripheralClock, PeripheralClockSelector},
-        IOSC,
     };
 
-    use super::{Clock, ClockSource};
+    use super::ClockSource;
 
     impl<T> super::private::Sealed for T where T: PeripheralClock {}
     impl<T> ClockSource for T
@@ -100,22 +558,163 @@ mod target {
             T::select(selector, handle);
         }
     }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::Frequency;
+
+    use super::{dividers, Clock, SclTimingError};
+
+    /// A clock source with a fixed frequency
+    struct Source(u32);
+
+    impl Frequency for Source {
+        fn hz(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn with_scl_high_low_accepts_standard_mode_minimums() {
+        // One divided cycle takes 2.35 µs. (2, 3) results in 85 kHz.
+        let source = Source(20_000_000);
+        let clock = Clock::<()>::from_dividers(46, 2, 3);
+
+        // The high time is 4.7 µs and the low time 9.4 µs, at 71 kHz.
+        let clock = clock.with_scl_high_low(&source, 2, 4).unwrap();
+        assert_eq!(clock.speed(&source), 70_921);
+        assert_eq!(clock.scl_high_ns(&source), 4_700);
+        assert_eq!(clock.scl_low_ns(&source), 9_400);
+    }
+
+    #[test]
+    fn with_scl_high_low_rejects_speed_above_bus_mode() {
+        // One divided cycle takes 2.35 µs. (2, 3) results in 85 kHz.
+        let source = Source(20_000_000);
+        let clock = Clock::<()>::from_dividers(46, 2, 3);
+
+        // Meets the Standard-mode minimums, but results in 106 kHz.
+        assert_eq!(
+            clock.with_scl_high_low(&source, 2, 2).err(),
+            Some(SclTimingError::AboveMaximumSpeed),
+        );
+    }
+
+    #[test]
+    fn with_scl_high_low_rejects_times_below_standard_mode_minimums() {
+        // One divided cycle takes 1.55 µs. (3, 9) results in 54 kHz.
+        let source = Source(20_000_000);
+        let clock = Clock::<()>::from_dividers(30, 3, 9);
+
+        // Results in 59 kHz, but the high time is only 3.1 µs.
+        assert_eq!(
+            clock.with_scl_high_low(&source, 2, 9).err(),
+            Some(SclTimingError::BelowMinimum),
+        );
+    }
 
-    impl Clock<IOSC> {
-        /// Create a new I2C clock configuration for 400 kHz
-        ///
-        /// Assumes the internal oscillator runs at 12 MHz.
-        pub fn new_400khz() -> Self {
-            Self {
-                divval: 5,
-                mstsclhigh: 0,
-                mstscllow: 1,
-                _clock: PhantomData,
+    #[test]
+    fn with_scl_high_low_rejects_times_below_fast_mode_minimums() {
+        // One divided cycle takes 0.25 µs. (5, 6) results in 364 kHz.
+        let source = Source(12_000_000);
+        let clock = || Clock::<()>::from_dividers(2, 5, 6);
+
+        assert!(clock().with_scl_high_low(&source, 4, 6).is_ok());
+
+        // Results in 400 kHz, but the low time is only 1.25 µs.
+        assert_eq!(
+            clock().with_scl_high_low(&source, 5, 5).err(),
+            Some(SclTimingError::BelowMinimum),
+        );
+    }
+
+    #[test]
+    fn with_scl_high_low_checks_range_and_order() {
+        let source = Source(12_000_000);
+        let clock = || Clock::<()>::from_dividers(5, 2, 3);
+
+        assert_eq!(
+            clock().with_scl_high_low(&source, 1, 3).err(),
+            Some(SclTimingError::OutOfRange),
+        );
+        assert_eq!(
+            clock().with_scl_high_low(&source, 2, 10).err(),
+            Some(SclTimingError::OutOfRange),
+        );
+        assert_eq!(
+            clock().with_scl_high_low(&source, 4, 3).err(),
+            Some(SclTimingError::LowShorterThanHigh),
+        );
+        assert!(clock().with_scl_high_low(&source, 9, 9).is_ok());
+    }
+
+    #[test]
+    fn dividers_for_standard_mode_from_12_mhz() {
+        let (divval, high, low) = dividers(12_000_000, 100_000).unwrap();
+        let clock = Clock::<()>::from_dividers(divval, high, low);
+        let source = Source(12_000_000);
+
+        assert_eq!(clock.speed(&source), 100_000);
+        assert!(clock.scl_high_ns(&source) >= 4_000);
+        assert!(clock.scl_low_ns(&source) >= 4_700);
+    }
+
+    #[test]
+    fn dividers_for_fast_mode_from_12_mhz() {
+        let (divval, high, low) = dividers(12_000_000, 400_000).unwrap();
+        let clock = Clock::<()>::from_dividers(divval, high, low);
+        let source = Source(12_000_000);
+
+        assert_eq!(clock.speed(&source), 400_000);
+        assert!(clock.scl_high_ns(&source) >= 600);
+        assert!(clock.scl_low_ns(&source) >= 1_300);
+    }
+
+    #[test]
+    fn dividers_for_fast_mode_plus_from_30_mhz() {
+        let (divval, high, low) = dividers(30_000_000, 1_000_000).unwrap();
+        let clock = Clock::<()>::from_dividers(divval, high, low);
+        let source = Source(30_000_000);
+
+        assert!(clock.speed(&source) <= 1_000_000);
+        assert!(clock.scl_high_ns(&source) >= 260);
+        assert!(clock.scl_low_ns(&source) >= 500);
+    }
+
+    #[test]
+    fn dividers_for_too_high_speed_meet_fast_mode_plus_minimums() {
+        // The minimum SCL low time alone doesn't allow 3 MHz.
+        let (divval, high, low) = dividers(12_000_000, 3_000_000).unwrap();
+        let clock = Clock::<()>::from_dividers(divval, high, low);
+        let source = Source(12_000_000);
+
+        assert_eq!(clock.speed(&source), 1_200_000);
+        assert!(clock.scl_high_ns(&source) >= 260);
+        assert!(clock.scl_low_ns(&source) >= 500);
+    }
+
+    #[test]
+    fn dividers_never_exceed_requested_speed() {
+        for &source in &[1_000_000, 12_000_000, 15_000_000, 30_000_000] {
+            for &speed in &[10_000, 99_999, 100_000, 333_333, 400_000] {
+                if let Some((divval, high, low)) = dividers(source, speed) {
+                    let clock = Clock::<()>::from_dividers(divval, high, low);
+                    assert!(clock.speed(&Source(source)) <= speed);
+                }
             }
         }
     }
-}
 
-mod private {
-    pub trait Sealed {}
+    #[test]
+    fn dividers_rejects_unreachable_speeds() {
+        assert_eq!(dividers(12_000_000, 0), None);
+
+        // Too slow: Even the largest divider results in a higher speed.
+        assert_eq!(dividers(30_000_000, 20), None);
+    }
 }
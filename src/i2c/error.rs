@@ -1,7 +1,10 @@
+use crate::pac;
+
 use super::{master, Instance};
 
 /// I2C error
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum Error {
     /// Event Timeout
@@ -48,6 +51,16 @@ pub enum Error {
 
     /// While in slave mode, an unknown state was detected
     UnknownSlaveState(u8),
+
+    /// The timeout passed to one of the master's `_timeout` methods elapsed
+    ///
+    /// The transfer was aborted by sending a stop condition.
+    Timeout,
+
+    /// No slave acknowledged the address
+    ///
+    /// The transfer was aborted by sending a stop condition.
+    AddressNack,
 }
 
 impl Error {
@@ -60,9 +73,16 @@ impl Error {
     }
 
     pub(super) fn read<I: Instance>() -> Result<(), Self> {
-        // Sound, as we're only reading from the STAT register.
+        // Sound, as we're only reading from the STAT register, and clearing
+        // error flags by writing 1 to them.
         let i2c = unsafe { &*I::REGISTERS };
 
+        Self::read_from(i2c)
+    }
+
+    pub(super) fn read_from(
+        i2c: &pac::i2c0::RegisterBlock,
+    ) -> Result<(), Self> {
         let stat = i2c.stat.read();
 
         // Check for error flags. If one is set, clear it and return the error.
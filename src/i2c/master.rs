@@ -7,19 +7,24 @@ use core::{
 };
 
 use embedded_hal::blocking::i2c;
+use embedded_hal_alpha::blocking::i2c::{
+    self as i2c_alpha, transactional, Operation,
+};
 
 use crate::{
     dma::{self, transfer::state::Ready},
     init_state::Enabled,
     pac::{
+        self,
         dma0::channel::xfercfg::{DSTINC_A, SRCINC_A},
         generic::Variant,
         i2c0::{stat::MSTSTATE_A, MSTCTL, MSTDAT},
     },
     reg_proxy::{Reg, RegProxy},
+    timer::Timer,
 };
 
-use super::{Error, Instance};
+use super::{Error, Instance, Status};
 
 /// API for the I2C master mode
 ///
@@ -32,16 +37,76 @@ use super::{Error, Instance};
 /// # `embedded-hal` traits
 /// - [`embedded_hal::blocking::i2c::Read`] for blocking reads
 /// - [`embedded_hal::blocking::i2c::Write`] for blocking writes
+/// - [`embedded_hal::blocking::i2c::WriteRead`] for blocking writes, followed
+///   by reads
+/// - [`embedded_hal_alpha::blocking::i2c::Transactional`] for blocking
+///   transactions, consisting of multiple reads and writes. Through it, the
+///   `Read`, `Write`, and `WriteRead` traits from `embedded-hal-alpha` are
+///   also implemented.
+///
+/// # Example
+///
+/// Pass the master to a driver that is written against `embedded-hal-alpha`:
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     embedded_hal_alpha::blocking::i2c::WriteRead,
+///     i2c,
+///     Peripherals,
+/// };
+///
+/// fn read_register<I>(i2c: &mut I, address: u8, register: u8) -> u8
+/// where
+///     I: WriteRead,
+///     I::Error: core::fmt::Debug,
+/// {
+///     let mut value = [0];
+///     i2c.try_write_read(address, &[register], &mut value).unwrap();
+///     value[0]
+/// }
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut swm = p.SWM.split();
+/// let mut syscon = p.SYSCON.split();
+///
+/// #[cfg(feature = "82x")]
+/// let mut swm_handle = swm.handle;
+/// #[cfg(feature = "845")]
+/// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+///
+/// let (i2c0_sda, _) = swm.fixed_functions.i2c0_sda.assign(
+///     p.pins.pio0_11.into_swm_pin(),
+///     &mut swm_handle,
+/// );
+/// let (i2c0_scl, _) = swm.fixed_functions.i2c0_scl.assign(
+///     p.pins.pio0_10.into_swm_pin(),
+///     &mut swm_handle,
+/// );
+///
+/// let mut i2c = p.I2C0
+///     .enable(
+///         &syscon.iosc,
+///         i2c0_scl,
+///         i2c0_sda,
+///         &mut syscon.handle,
+///     )
+///     .enable_master_mode(&i2c::Clock::new_400khz());
+///
+/// let id = read_register(&mut i2c.master, 0x29, 0xc0);
+/// ```
 ///
 /// [`I2C`]: ../struct.I2C.html
 /// [`embedded_hal::blocking::i2c::Read`]: #impl-Read
 /// [`embedded_hal::blocking::i2c::Write`]: #impl-Write
+/// [`embedded_hal::blocking::i2c::WriteRead`]: #impl-WriteRead
+/// [`embedded_hal_alpha::blocking::i2c::Transactional`]: #impl-Transactional
 pub struct Master<I: Instance, State, ModeState> {
     _state: PhantomData<State>,
     _mode_state: PhantomData<ModeState>,
 
-    mstctl: RegProxy<MstCtl<I>>,
-    mstdat: RegProxy<MstDat<I>>,
+    pub(super) mstctl: RegProxy<MstCtl<I>>,
+    pub(super) mstdat: RegProxy<MstDat<I>>,
 }
 
 impl<I, State, ModeState> Master<I, State, ModeState>
@@ -63,6 +128,24 @@ impl<I, C> Master<I, Enabled<PhantomData<C>>, Enabled>
 where
     I: Instance,
 {
+    /// Read the current status of the peripheral
+    ///
+    /// Decodes the master-related flags of the STAT register, which is useful
+    /// for diagnosing a misbehaving bus. While an [`Error`] describes what
+    /// went wrong, the status shows where the state machine currently is.
+    ///
+    /// This method only reads from the STAT register, so it doesn't affect a
+    /// transfer, and doesn't clear any flags. It can be called at any time,
+    /// including between transfers.
+    ///
+    /// [`Error`]: ../enum.Error.html
+    pub fn status(&self) -> Status {
+        // Sound, as we're only reading from the STAT register.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        Status::from_bits(i2c.stat.read().bits())
+    }
+
     /// Writes the provided buffer using DMA
     ///
     /// # Panics
@@ -75,8 +158,8 @@ where
         channel: dma::Channel<I::MstChannel, Enabled>,
     ) -> Result<dma::Transfer<Ready, I::MstChannel, &'static [u8], Self>, Error>
     {
-        self.start_operation(address, Rw::Write)?;
-        self.wait_for_state(State::TxReady)?;
+        self.start_operation(address, Rw::Write, &mut || false)?;
+        self.wait_for_state(State::TxReady, &mut || false)?;
         self.mstctl.modify(|_, w| w.mstdma().enabled());
         Ok(dma::Transfer::new(channel, buffer, self))
     }
@@ -95,23 +178,202 @@ where
         dma::Transfer<Ready, I::MstChannel, Self, &'static mut [u8]>,
         Error,
     > {
-        self.start_operation(address, Rw::Read)?;
+        self.start_operation(address, Rw::Read, &mut || false)?;
         self.mstctl.modify(|_, w| w.mstdma().enabled());
         Ok(dma::Transfer::new(channel, self, buffer))
     }
 
+    /// Write to the I2C bus, giving up after a timeout
+    ///
+    /// Works like the [`Write`] implementation, except that it starts `timer`
+    /// with the given number of ticks, and aborts the transfer with
+    /// [`Error::Timeout`], if the timer elapses before the transfer has
+    /// completed. Any timer that implements [`Timer`] can be used.
+    ///
+    /// When a transfer is aborted, a stop condition is sent, which should
+    /// leave the bus in a state where the next transfer can be started.
+    /// Please note that this can't help, if a slave is holding SCL low. In
+    /// that case, the next transfer will most likely time out too.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero or larger than `T::MAX_TICKS`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{i2c, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut mrt = p.MRT0.split(&mut syscon.handle).mrt0;
+    /// #
+    /// # let mut swm = p.SWM.split();
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// # let (i2c0_sda, _) = swm.fixed_functions.i2c0_sda.assign(
+    /// #     p.pins.pio0_11.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (i2c0_scl, _) = swm.fixed_functions.i2c0_scl.assign(
+    /// #     p.pins.pio0_10.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// #
+    /// # let mut i2c = p.I2C0
+    /// #     .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
+    /// #     .enable_master_mode(&i2c::Clock::new_400khz());
+    ///
+    /// // The MRT runs at 12 MHz, so this gives up after 1 ms.
+    /// let result = i2c.master.write_timeout(0x48, &[0x01], &mut mrt, 12_000);
+    /// if let Err(i2c::Error::Timeout) = result {
+    ///     // handle timeout
+    /// }
+    /// ```
+    ///
+    /// [`Write`]: #impl-Write
+    /// [`Error::Timeout`]: ../enum.Error.html#variant.Timeout
+    /// [`Timer`]: ../../timer/trait.Timer.html
+    pub fn write_timeout<T>(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        timer: &mut T,
+        ticks: u32,
+    ) -> Result<(), Error>
+    where
+        T: Timer,
+    {
+        self.exec_timeout(address, &mut [Operation::Write(data)], timer, ticks)
+    }
+
+    /// Read from the I2C bus, giving up after a timeout
+    ///
+    /// Works like the [`Read`] implementation, except that it aborts the
+    /// transfer with [`Error::Timeout`], if it hasn't completed before `timer`
+    /// elapses. Please refer to [`write_timeout`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero or larger than `T::MAX_TICKS`.
+    ///
+    /// [`Read`]: #impl-Read
+    /// [`Error::Timeout`]: ../enum.Error.html#variant.Timeout
+    /// [`write_timeout`]: #method.write_timeout
+    pub fn read_timeout<T>(
+        &mut self,
+        address: u8,
+        buffer: &mut [u8],
+        timer: &mut T,
+        ticks: u32,
+    ) -> Result<(), Error>
+    where
+        T: Timer,
+    {
+        self.exec_timeout(address, &mut [Operation::Read(buffer)], timer, ticks)
+    }
+
+    /// Write to, then read from the I2C bus, giving up after a timeout
+    ///
+    /// Works like the [`WriteRead`] implementation, except that it aborts the
+    /// transfer with [`Error::Timeout`], if it hasn't completed before `timer`
+    /// elapses. The timeout applies to the whole transfer, not to the write
+    /// and read separately. Please refer to [`write_timeout`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero or larger than `T::MAX_TICKS`.
+    ///
+    /// [`WriteRead`]: #impl-WriteRead
+    /// [`Error::Timeout`]: ../enum.Error.html#variant.Timeout
+    /// [`write_timeout`]: #method.write_timeout
+    pub fn write_read_timeout<T>(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+        timer: &mut T,
+        ticks: u32,
+    ) -> Result<(), Error>
+    where
+        T: Timer,
+    {
+        self.exec_timeout(
+            address,
+            &mut [Operation::Write(bytes), Operation::Read(buffer)],
+            timer,
+            ticks,
+        )
+    }
+
+    fn exec_timeout<T>(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation],
+        timer: &mut T,
+        ticks: u32,
+    ) -> Result<(), Error>
+    where
+        T: Timer,
+    {
+        timer.start(ticks);
+        let result =
+            self.exec(address, operations, &mut || timer.wait().is_ok());
+        timer.cancel();
+
+        result
+    }
+
     /// Wait while the peripheral is busy
     ///
     /// Once this method returns, the peripheral should either be idle or in a
     /// state that requires software interaction.
-    fn wait_for_state(&self, expected: State) -> Result<(), Error> {
+    ///
+    /// `timed_out` is checked while waiting. If it returns `true`, a stop
+    /// condition is sent and `Error::Timeout` is returned.
+    fn wait_for_state(
+        &mut self,
+        expected: State,
+        timed_out: &mut dyn FnMut() -> bool,
+    ) -> Result<(), Error> {
+        // Sound, as we're only reading from the STAT register, clearing error
+        // flags, and writing to MSTCTL, which we own.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        wait_while_busy(i2c, timed_out)?;
+
+        self.check_address_nack()?;
+        Self::check_state(expected)
+    }
+
+    /// Check whether the slave has not acknowledged its address
+    ///
+    /// If it hasn't, the master still owns the bus. Release it by sending a
+    /// stop condition, so the next transaction can start.
+    ///
+    /// Must only be called while the peripheral is not busy.
+    pub(super) fn check_address_nack(&mut self) -> Result<(), Error> {
         // Sound, as we're only reading from the STAT register.
         let i2c = unsafe { &*I::REGISTERS };
 
-        while i2c.stat.read().mstpending().is_in_progress() {
-            Error::read::<I>()?;
+        if i2c.stat.read().mststate().is_nack_address() {
+            self.mstctl.write(|w| w.mststop().stop());
+            return Err(Error::AddressNack);
         }
 
+        Ok(())
+    }
+
+    /// Check whether the peripheral is in the expected state
+    ///
+    /// Must only be called while the peripheral is not busy.
+    pub(super) fn check_state(expected: State) -> Result<(), Error> {
+        // Sound, as we're only reading from the STAT register.
+        let i2c = unsafe { &*I::REGISTERS };
+
         let actual = i2c.stat.read().mststate().variant().try_into();
         if Ok(&expected) != actual.as_ref() {
             return Err(Error::UnexpectedState { expected, actual });
@@ -120,10 +382,36 @@ where
         Ok(())
     }
 
-    fn start_operation(&mut self, address: u8, rw: Rw) -> Result<(), Error> {
+    fn start_operation(
+        &mut self,
+        address: u8,
+        rw: Rw,
+        timed_out: &mut dyn FnMut() -> bool,
+    ) -> Result<(), Error> {
         Error::check_address(address)?;
-        self.wait_for_state(State::Idle)?;
+        self.wait_for_state(State::Idle, timed_out)?;
+        self.start(address, rw);
+
+        Ok(())
+    }
+
+    /// Start a new operation, without stopping the current one first
+    ///
+    /// `state` is the state that the current operation is expected to be in.
+    fn repeated_start(
+        &mut self,
+        address: u8,
+        rw: Rw,
+        state: State,
+        timed_out: &mut dyn FnMut() -> bool,
+    ) -> Result<(), Error> {
+        self.wait_for_state(state, timed_out)?;
+        self.start(address, rw);
 
+        Ok(())
+    }
+
+    pub(super) fn start(&mut self, address: u8, rw: Rw) {
         // Write address
         let address_rw = (address << 1) | rw as u8;
         self.mstdat.write(|w| unsafe {
@@ -133,12 +421,13 @@ where
 
         // Start operation
         self.mstctl.write(|w| w.mststart().start());
-
-        Ok(())
     }
 
-    fn finish_write(&mut self) -> Result<(), Error> {
-        self.wait_for_state(State::TxReady)?;
+    fn finish_write(
+        &mut self,
+        timed_out: &mut dyn FnMut() -> bool,
+    ) -> Result<(), Error> {
+        self.wait_for_state(State::TxReady, timed_out)?;
 
         // Stop operation
         self.mstctl.write(|w| w.mststop().stop());
@@ -146,14 +435,106 @@ where
         Ok(())
     }
 
-    fn finish_read(&mut self) -> Result<(), Error> {
-        self.wait_for_state(State::RxReady)?;
+    fn finish_read(
+        &mut self,
+        timed_out: &mut dyn FnMut() -> bool,
+    ) -> Result<(), Error> {
+        self.wait_for_state(State::RxReady, timed_out)?;
 
         // Stop operation
         self.mstctl.write(|w| w.mststop().stop());
 
         Ok(())
     }
+
+    /// Execute a transaction
+    ///
+    /// This is the implementation of [`Transactional`] and the methods that
+    /// accept a timeout. See the documentation of the [`Transactional`]
+    /// implementation for details.
+    ///
+    /// [`Transactional`]: #impl-Transactional
+    fn exec(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation],
+        timed_out: &mut dyn FnMut() -> bool,
+    ) -> Result<(), Error> {
+        let mut current = None;
+
+        // Whether the byte that was last received has been read already. If
+        // so, the master needs to be told to continue, before the next byte
+        // can be received.
+        let mut received = false;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => {
+                    match current {
+                        None => {
+                            self.start_operation(address, Rw::Write, timed_out)?
+                        }
+                        Some(Rw::Write) => {}
+                        Some(Rw::Read) => self.repeated_start(
+                            address,
+                            Rw::Write,
+                            State::RxReady,
+                            timed_out,
+                        )?,
+                    }
+                    current = Some(Rw::Write);
+
+                    for &b in data.iter() {
+                        self.wait_for_state(State::TxReady, timed_out)?;
+
+                        // Write byte
+                        self.mstdat.write(|w| unsafe { w.data().bits(b) });
+
+                        // Continue transmission
+                        self.mstctl.write(|w| w.mstcontinue().continue_());
+                    }
+                }
+                Operation::Read(buffer) => {
+                    match current {
+                        None => {
+                            self.start_operation(address, Rw::Read, timed_out)?;
+                            received = false;
+                        }
+                        Some(Rw::Read) => {}
+                        Some(Rw::Write) => {
+                            self.repeated_start(
+                                address,
+                                Rw::Read,
+                                State::TxReady,
+                                timed_out,
+                            )?;
+                            received = false;
+                        }
+                    }
+                    current = Some(Rw::Read);
+
+                    for b in buffer.iter_mut() {
+                        if received {
+                            // Continue transmission
+                            self.mstctl.write(|w| w.mstcontinue().continue_());
+                        }
+
+                        self.wait_for_state(State::RxReady, timed_out)?;
+
+                        // Read received byte
+                        *b = self.mstdat.read().data().bits();
+                        received = true;
+                    }
+                }
+            }
+        }
+
+        match current {
+            Some(Rw::Write) => self.finish_write(timed_out),
+            Some(Rw::Read) => self.finish_read(timed_out),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<I, C> i2c::Write for Master<I, Enabled<PhantomData<C>>, Enabled>
@@ -166,23 +547,52 @@ where
     ///
     /// Please refer to the [embedded-hal documentation] for details.
     ///
+    /// `data` may be empty. In that case, only the address is sent, followed
+    /// by a stop condition. This can be used to check whether a device is
+    /// present on the bus, as [`Error::AddressNack`] is returned, if no device
+    /// acknowledges the address.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{i2c, prelude::*, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut swm = p.SWM.split();
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let (i2c0_sda, _) = swm
+    ///     .fixed_functions
+    ///     .i2c0_sda
+    ///     .assign(p.pins.pio0_11.into_swm_pin(), &mut swm_handle);
+    /// let (i2c0_scl, _) = swm
+    ///     .fixed_functions
+    ///     .i2c0_scl
+    ///     .assign(p.pins.pio0_10.into_swm_pin(), &mut swm_handle);
+    ///
+    /// let mut i2c = p
+    ///     .I2C0
+    ///     .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
+    ///     .enable_master_mode(&i2c::Clock::new_400khz());
+    ///
+    /// // A device is present at 0x50, but not at 0x51.
+    /// assert_eq!(i2c.master.write(0x50, &[]), Ok(()));
+    /// assert_eq!(
+    ///     i2c.master.write(0x51, &[]),
+    ///     Err(i2c::Error::AddressNack),
+    /// );
+    /// ```
+    ///
     /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.Write.html#tymethod.write
+    /// [`Error::AddressNack`]: ../enum.Error.html#variant.AddressNack
     fn write(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
-        self.start_operation(address, Rw::Write)?;
-
-        for &b in data {
-            self.wait_for_state(State::TxReady)?;
-
-            // Write byte
-            self.mstdat.write(|w| unsafe { w.data().bits(b) });
-
-            // Continue transmission
-            self.mstctl.write(|w| w.mstcontinue().continue_());
-        }
-
-        self.finish_write()?;
-
-        Ok(())
+        self.exec(address, &mut [Operation::Write(data)], &mut || false)
     }
 }
 
@@ -202,26 +612,68 @@ where
         address: u8,
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.start_operation(address, Rw::Read)?;
-
-        for (i, b) in buffer.iter_mut().enumerate() {
-            if i != 0 {
-                // Continue transmission
-                self.mstctl.write(|w| w.mstcontinue().continue_());
-            }
+        self.exec(address, &mut [Operation::Read(buffer)], &mut || false)
+    }
+}
 
-            self.wait_for_state(State::RxReady)?;
+impl<I, C> i2c::WriteRead for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
 
-            // Read received byte
-            *b = self.mstdat.read().data().bits();
-        }
+    /// Write to, then read from the I2C bus, using a repeated start
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.WriteRead.html#tymethod.write_read
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        i2c_alpha::Transactional::try_exec(
+            self,
+            address,
+            &mut [Operation::Write(bytes), Operation::Read(buffer)],
+        )
+    }
+}
 
-        self.finish_read()?;
+/// Execute a transaction, consisting of multiple reads and writes
+///
+/// Consecutive operations of the same type are merged, as required by
+/// `embedded-hal`. Between operations of different types, a repeated start
+/// condition is generated. The transaction is finished with a stop condition.
+///
+/// An empty list of operations doesn't access the bus at all. A zero-length
+/// write only addresses the device. A zero-length read is allowed too, but
+/// the hardware always receives at least one byte after addressing a device
+/// for reading, which is discarded in that case.
+impl<I, C> i2c_alpha::Transactional
+    for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
 
-        Ok(())
+    fn try_exec<'a>(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'a>],
+    ) -> Result<(), Self::Error> {
+        self.exec(address, operations, &mut || false)
     }
 }
 
+impl<I, C> transactional::Default<u8>
+    for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+}
+
 impl<I, State, ModeState> crate::private::Sealed for Master<I, State, ModeState> where
     I: Instance
 {
@@ -257,7 +709,7 @@ where
 
     fn finish(&mut self) -> nb::Result<(), Self::Error> {
         self.mstctl.modify(|_, w| w.mstdma().disabled());
-        self.finish_write()?;
+        self.finish_write(&mut || false)?;
         Ok(())
     }
 }
@@ -292,7 +744,7 @@ where
 
     fn finish(&mut self) -> nb::Result<(), Self::Error> {
         self.mstctl.modify(|_, w| w.mstdma().disabled());
-        self.finish_read()?;
+        self.finish_read(&mut || false)?;
         Ok(())
     }
 }
@@ -313,15 +765,38 @@ where
     }
 }
 
+/// Wait while the peripheral is busy, giving up once `timed_out` returns `true`
+///
+/// On timeout, a stop condition is sent and `Error::Timeout` is returned.
+fn wait_while_busy(
+    i2c: &pac::i2c0::RegisterBlock,
+    timed_out: &mut dyn FnMut() -> bool,
+) -> Result<(), Error> {
+    while i2c.stat.read().mstpending().is_in_progress() {
+        Error::read_from(i2c)?;
+
+        if timed_out() {
+            // Try to leave the bus in a usable state. If the transfer is stuck
+            // because a slave is holding SCL low, this won't help, and the bus
+            // needs to be recovered by other means.
+            i2c.mstctl.write(|w| w.mststop().stop());
+            return Err(Error::Timeout);
+        }
+    }
+
+    Ok(())
+}
+
 /// Private helper struct to model the R/W bit
 #[repr(u8)]
-enum Rw {
+pub(super) enum Rw {
     Write = 0,
     Read = 1,
 }
 
 /// The state of an I2C instance set to master mode
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum State {
     /// The peripheral is currently idle
     ///
@@ -363,7 +838,7 @@ impl TryFrom<Variant<u8, MSTSTATE_A>> for State {
     }
 }
 
-struct MstCtl<I>(PhantomData<I>);
+pub(super) struct MstCtl<I>(PhantomData<I>);
 
 // Sound, as the pointer returned is valid for the duration of the program.
 unsafe impl<I> Reg for MstCtl<I>
@@ -387,7 +862,7 @@ impl<I> fmt::Debug for MstCtl<I> {
     }
 }
 
-struct MstDat<I>(PhantomData<I>);
+pub(super) struct MstDat<I>(PhantomData<I>);
 
 // Sound, as the pointer returned is valid for the duration of the program.
 unsafe impl<I> Reg for MstDat<I>
@@ -410,3 +885,42 @@ impl<I> fmt::Debug for MstDat<I> {
         write!(f, "MstDat(...)")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use crate::pac;
+
+    use super::Error;
+
+    const MSTPENDING: u32 = 1 << 0;
+    const MSTSTOP: u32 = 1 << 2;
+
+    fn register_block() -> pac::i2c0::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        unsafe { mem::zeroed() }
+    }
+
+    #[test]
+    fn timeout_sends_stop() {
+        // MSTPENDING is cleared, so the peripheral is busy.
+        let i2c = register_block();
+
+        let result = super::wait_while_busy(&i2c, &mut || true);
+
+        assert_eq!(result, Err(Error::Timeout));
+        assert_eq!(i2c.mstctl.read().bits(), MSTSTOP);
+    }
+
+    #[test]
+    fn no_timeout_while_not_busy() {
+        let i2c = register_block();
+        i2c.stat.write(|w| unsafe { w.bits(MSTPENDING) });
+
+        let result = super::wait_while_busy(&i2c, &mut || true);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(i2c.mstctl.read().bits(), 0);
+    }
+}
@@ -0,0 +1,315 @@
+//! Async API for the I2C master mode
+
+use core::{
+    cell::RefCell,
+    future::poll_fn,
+    marker::PhantomData,
+    mem,
+    task::{Poll, Waker},
+};
+
+use cortex_m::interrupt::{self, Mutex};
+use embedded_hal_alpha::blocking::i2c::Operation;
+
+use crate::{init_state::Enabled, pac::Interrupt};
+
+use super::{
+    master::{Master, Rw, State},
+    Error, Instance,
+};
+
+type WakerSlot = Mutex<RefCell<Option<Waker>>>;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: WakerSlot = Mutex::new(RefCell::new(None));
+
+/// The wakers of the tasks waiting for I2C master interrupts, per instance
+static WAKERS: [WakerSlot; 4] = [EMPTY; 4];
+
+fn waker<I: Instance>() -> &'static WakerSlot {
+    match I::INTERRUPT {
+        Interrupt::I2C0 => &WAKERS[0],
+        Interrupt::I2C1 => &WAKERS[1],
+        Interrupt::I2C2 => &WAKERS[2],
+        Interrupt::I2C3 => &WAKERS[3],
+        _ => unreachable!(),
+    }
+}
+
+/// Handle an I2C master interrupt
+///
+/// The async methods of [`Master`] rely on this function being called from
+/// the interrupt handler of the respective I2C instance. It disables the
+/// master interrupts that were enabled by the async methods, and wakes the
+/// task that is waiting for the interrupt.
+///
+/// Please note that you also need to unmask the I2C interrupt in the NVIC.
+///
+/// This function is only available, if the `async` feature is enabled.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{i2c, pac};
+///
+/// // Register this as the handler for the I2C0 interrupt, for example using
+/// // the `interrupt` attribute from the PAC.
+/// fn i2c0_handler() {
+///     i2c::on_master_interrupt::<pac::I2C0>();
+/// }
+/// ```
+///
+/// [`Master`]: master/struct.Master.html
+pub fn on_master_interrupt<I: Instance>() {
+    // Sound, as INTENCLR only affects the bits that are written as 1, and we
+    // only clear the interrupts that the async methods have enabled.
+    let i2c = unsafe { &*I::REGISTERS };
+    i2c.intenclr.write(|w| {
+        w.mstpendingclr().set_bit();
+        w.mstarblossclr().set_bit();
+        w.mstststperrclr().set_bit()
+    });
+
+    interrupt::free(|cs| {
+        if let Some(waker) = waker::<I>().borrow(cs).borrow_mut().take() {
+            waker.wake();
+        }
+    });
+}
+
+/// Async API
+///
+/// These methods are only available, if the `async` feature is enabled. They
+/// work like their blocking counterparts, but instead of busy-waiting for the
+/// peripheral, they enable the I2C master interrupts and return control to the
+/// executor. The interrupt handler needs to call [`on_master_interrupt`] to
+/// resume them.
+///
+/// If the returned future is dropped before it completes, the transaction is
+/// aborted. This waits for the byte that is currently being transferred, if
+/// any, then sends a stop condition, leaving the master idle.
+///
+/// These methods can't be provided as an implementation of the
+/// `embedded_hal_async::i2c::I2c` trait yet, as `embedded-hal-async` depends on
+/// a version of `embedded-hal` that conflicts with the pre-release version
+/// this crate depends on.
+///
+/// [`on_master_interrupt`]: ../fn.on_master_interrupt.html
+impl<I, C> Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    /// Write to the I2C bus
+    pub async fn write_async(
+        &mut self,
+        address: u8,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        self.transaction_async(address, &mut [Operation::Write(data)])
+            .await
+    }
+
+    /// Read from the I2C bus
+    pub async fn read_async(
+        &mut self,
+        address: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        self.transaction_async(address, &mut [Operation::Read(buffer)])
+            .await
+    }
+
+    /// Write to, then read from the I2C bus, using a repeated start
+    pub async fn write_read_async(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        self.transaction_async(
+            address,
+            &mut [Operation::Write(bytes), Operation::Read(buffer)],
+        )
+        .await
+    }
+
+    /// Execute a transaction, consisting of multiple reads and writes
+    ///
+    /// Works like the implementation of [`Transactional`]. See its
+    /// documentation for details.
+    ///
+    /// [`Transactional`]: #impl-Transactional
+    pub async fn transaction_async(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Error> {
+        let guard = AbortOnDrop::<I>(PhantomData);
+        let result = self.exec_async(address, operations).await;
+        mem::forget(guard);
+
+        result
+    }
+
+    async fn exec_async(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Error> {
+        let mut current = None;
+
+        // Whether the byte that was last received has been read already. See
+        // the blocking implementation.
+        let mut received = false;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => {
+                    match current {
+                        None => {
+                            Error::check_address(address)?;
+                            self.wait_async(State::Idle).await?;
+                            self.start(address, Rw::Write);
+                        }
+                        Some(Rw::Write) => {}
+                        Some(Rw::Read) => {
+                            self.wait_async(State::RxReady).await?;
+                            self.start(address, Rw::Write);
+                        }
+                    }
+                    current = Some(Rw::Write);
+
+                    for &b in data.iter() {
+                        self.wait_async(State::TxReady).await?;
+
+                        // Write byte
+                        self.mstdat.write(|w| unsafe { w.data().bits(b) });
+
+                        // Continue transmission
+                        self.mstctl.write(|w| w.mstcontinue().continue_());
+                    }
+                }
+                Operation::Read(buffer) => {
+                    match current {
+                        None => {
+                            Error::check_address(address)?;
+                            self.wait_async(State::Idle).await?;
+                            self.start(address, Rw::Read);
+                            received = false;
+                        }
+                        Some(Rw::Read) => {}
+                        Some(Rw::Write) => {
+                            self.wait_async(State::TxReady).await?;
+                            self.start(address, Rw::Read);
+                            received = false;
+                        }
+                    }
+                    current = Some(Rw::Read);
+
+                    for b in buffer.iter_mut() {
+                        if received {
+                            // Continue transmission
+                            self.mstctl.write(|w| w.mstcontinue().continue_());
+                        }
+
+                        self.wait_async(State::RxReady).await?;
+
+                        // Read received byte
+                        *b = self.mstdat.read().data().bits();
+                        received = true;
+                    }
+                }
+            }
+        }
+
+        let expected = match current {
+            Some(Rw::Write) => State::TxReady,
+            Some(Rw::Read) => State::RxReady,
+            None => return Ok(()),
+        };
+        self.wait_async(expected).await?;
+
+        // Stop operation
+        self.mstctl.write(|w| w.mststop().stop());
+
+        Ok(())
+    }
+
+    /// Wait until the peripheral is no longer busy
+    ///
+    /// This is the async equivalent of `wait_for_state`.
+    async fn wait_async(&mut self, expected: State) -> Result<(), Error> {
+        poll_fn(|cx| {
+            // Sound, as we're only reading from the STAT register, and
+            // INTENSET only affects the bits that are written as 1.
+            let i2c = unsafe { &*I::REGISTERS };
+
+            if let Err(err) = Error::read::<I>() {
+                return Poll::Ready(Err(err));
+            }
+            if i2c.stat.read().mstpending().is_pending() {
+                return Poll::Ready(Ok(()));
+            }
+
+            interrupt::free(|cs| {
+                let mut waker = waker::<I>().borrow(cs).borrow_mut();
+                match waker.as_ref() {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    _ => *waker = Some(cx.waker().clone()),
+                }
+            });
+
+            // If the peripheral is no longer busy by now, the interrupt fires
+            // right away, so we can't miss it.
+            i2c.intenset.write(|w| {
+                w.mstpendingen().enabled();
+                w.mstarblossen().enabled();
+                w.mstststperren().enabled()
+            });
+
+            Poll::Pending
+        })
+        .await?;
+
+        self.check_address_nack()?;
+        Self::check_state(expected)
+    }
+}
+
+/// Aborts the current transaction when dropped
+///
+/// Used to clean up after a future returned by one of the async methods is
+/// dropped before it completes.
+struct AbortOnDrop<I: Instance>(PhantomData<I>);
+
+impl<I> Drop for AbortOnDrop<I>
+where
+    I: Instance,
+{
+    fn drop(&mut self) {
+        // Sound, as the dropped future had exclusive access to the master
+        // registers, and INTENCLR only affects the bits that are written as 1.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        i2c.intenclr.write(|w| {
+            w.mstpendingclr().set_bit();
+            w.mstarblossclr().set_bit();
+            w.mstststperrclr().set_bit()
+        });
+        interrupt::free(|cs| {
+            waker::<I>().borrow(cs).borrow_mut().take();
+        });
+
+        // A stop condition can only be sent, once the byte that is currently
+        // being transferred is done.
+        while i2c.stat.read().mstpending().is_in_progress() {
+            if Error::read::<I>().is_err() {
+                break;
+            }
+        }
+
+        if !i2c.stat.read().mststate().is_idle() {
+            i2c.mstctl.write(|w| w.mststop().stop());
+        }
+    }
+}
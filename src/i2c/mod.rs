@@ -39,14 +39,9 @@
 //!     &mut swm_handle,
 //! );
 //!
-//! #[cfg(feature = "82x")]
-//! let clock = &(); // I2C is always powered by system clock on LPC82x
-//! #[cfg(feature = "845")]
-//! let clock = &syscon.iosc;
-//!
 //! let mut i2c = p.I2C0
 //!     .enable(
-//!         clock,
+//!         &syscon.iosc,
 //!         i2c0_scl,
 //!         i2c0_sda,
 //!         &mut syscon.handle,
@@ -59,9 +54,23 @@
 //!     .expect("Failed to write data");
 //! ```
 //!
+//! # Multiple instances
+//!
+//! All I2C instances are available via [`Peripherals`], and can be used at the
+//! same time. The API is the same for all of them, but how their SDA and SCL
+//! functions are assigned to pins differs: I2C0 uses fixed functions (from
+//! `swm.fixed_functions`), as it has dedicated pins that support I2C
+//! Fast-mode Plus. All other instances use movable functions (from
+//! `swm.movable_functions`), which can be assigned to any pin. [`I2C::enable`]
+//! requires the respective function for each instance, as defined by
+//! [`Instance`].
+//!
 //! Please refer to the [examples in the repository] for more example code.
 //!
 //! [`I2C`]: struct.I2C.html
+//! [`Peripherals`]: ../struct.Peripherals.html
+//! [`I2C::enable`]: struct.I2C.html#method.enable
+//! [`Instance`]: trait.Instance.html
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 mod clock;
@@ -69,16 +78,26 @@ mod error;
 mod instances;
 mod interrupts;
 mod peripheral;
+mod status;
+
+#[cfg(feature = "async")]
+mod master_async;
 
 pub mod master;
+pub mod shared_bus;
 pub mod slave;
 
 pub use self::{
-    clock::{Clock, ClockSource},
+    clock::{Clock, ClockSource, DefaultFrequency, SclTimingError},
     error::Error,
     instances::Instance,
     interrupts::Interrupts,
     master::Master,
     peripheral::I2C,
+    shared_bus::{BusManager, BusProxy},
     slave::Slave,
+    status::Status,
 };
+
+#[cfg(feature = "async")]
+pub use self::master_async::on_master_interrupt;
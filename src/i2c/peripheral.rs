@@ -41,6 +41,14 @@ where
     /// Consumes this instance of `I2C` and returns another instance that has
     /// its `State` type parameter set to [`Enabled`].
     ///
+    /// Requires the SCL and SDA functions of this instance, assigned to the
+    /// pins they are going to be used on. Passing the functions of another
+    /// instance, or functions that haven't been assigned, won't compile. This
+    /// includes the functions of I2C0, which are fixed to PIO0_10 (SCL) and
+    /// PIO0_11 (SDA), and can't be assigned to any other pin. All of this
+    /// follows from the `Assigned` state of the functions, which can only be
+    /// reached by assigning them to a suitable pin.
+    ///
     /// [`Disabled`]: ../init_state/struct.Disabled.html
     /// [`Enabled`]: ../init_state/struct.Enabled.html
     pub fn enable<C, SdaPin, SclPin>(
@@ -184,6 +192,42 @@ impl<I, C, MasterMode, SlaveMode>
 where
     I: Instance,
 {
+    /// Disable this I2C instance
+    ///
+    /// This method is only available, if `I2C` is in the [`Enabled`] state.
+    /// Code that attempts to call this method when the peripheral is already
+    /// disabled will not compile.
+    ///
+    /// Disables master, slave, and monitor mode, then disables the clock of
+    /// the peripheral. Any transfer that is still in progress is interrupted.
+    ///
+    /// Consumes this instance of `I2C` and returns another instance that has
+    /// its `State` type parameter set to [`Disabled`]. The SCL and SDA
+    /// functions that were passed to [`enable`] stay assigned to their pins.
+    ///
+    /// [`Enabled`]: ../init_state/struct.Enabled.html
+    /// [`Disabled`]: ../init_state/struct.Disabled.html
+    /// [`enable`]: #method.enable
+    pub fn disable(
+        self,
+        syscon: &mut syscon::Handle,
+    ) -> I2C<I, init_state::Disabled, init_state::Disabled, init_state::Disabled>
+    {
+        self.i2c.cfg.modify(|_, w| {
+            w.msten().disabled();
+            w.slven().disabled();
+            w.monen().disabled()
+        });
+        syscon.disable_clock(&self.i2c);
+
+        I2C {
+            master: Master::new(),
+            slave: Slave::new(),
+
+            i2c: self.i2c,
+        }
+    }
+
     /// Enable interrupts
     ///
     /// Enables all interrupts set to `true` in `interrupts`. Interrupts set to
@@ -211,6 +255,92 @@ where
     pub fn read_error(&mut self) -> Result<(), Error> {
         Error::read::<I>()
     }
+
+    /// Reset the peripheral, keeping its configuration
+    ///
+    /// Resets the I2C peripheral via SYSCON, then restores the configuration
+    /// it had before the reset (clock divider, SCL timing, enabled modes, slave
+    /// addresses, timeout, and enabled interrupts). The pins stay assigned and
+    /// the type state is not affected, so the peripheral can be used right
+    /// away afterwards.
+    ///
+    /// This can be used to recover the peripheral, if it is stuck in a bad
+    /// state after an error, like a bus error or arbitration loss. It doesn't
+    /// help, if the bus itself is stuck (for example, because a slave is
+    /// holding SDA low), as the peripheral doesn't control the other devices
+    /// on the bus.
+    ///
+    /// Any ongoing transfer is aborted. Any error flags are cleared.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{i2c, prelude::*, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut swm = p.SWM.split();
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let (i2c0_sda, _) = swm.fixed_functions.i2c0_sda.assign(
+    ///     p.pins.pio0_11.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// let (i2c0_scl, _) = swm.fixed_functions.i2c0_scl.assign(
+    ///     p.pins.pio0_10.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let mut i2c = p.I2C0
+    ///     .enable(
+    ///         &syscon.iosc,
+    ///         i2c0_scl,
+    ///         i2c0_sda,
+    ///         &mut syscon.handle,
+    ///     )
+    ///     .enable_master_mode(&i2c::Clock::new_400khz());
+    ///
+    /// if i2c.master.write(0x29, &[0xc0]).is_err() {
+    ///     i2c.reset(&mut syscon.handle);
+    /// }
+    ///
+    /// // The peripheral is ready to use again.
+    /// i2c.master.write(0x29, &[0xc0]).unwrap();
+    /// ```
+    pub fn reset(&mut self, syscon: &mut syscon::Handle) {
+        let cfg = self.i2c.cfg.read().bits();
+        let intenset = self.i2c.intenset.read().bits();
+        let timeout = self.i2c.timeout.read().bits();
+        let clkdiv = self.i2c.clkdiv.read().bits();
+        let msttime = self.i2c.msttime.read().bits();
+        let slvqual0 = self.i2c.slvqual0.read().bits();
+        let mut slvadr = [0; 4];
+        for (value, reg) in slvadr.iter_mut().zip(self.i2c.slvadr.iter()) {
+            *value = reg.read().bits();
+        }
+
+        syscon.assert_reset(&self.i2c);
+        syscon.clear_reset(&self.i2c);
+
+        // Sound, as we're writing back values that were read from the same
+        // registers.
+        unsafe {
+            self.i2c.clkdiv.write(|w| w.bits(clkdiv));
+            self.i2c.msttime.write(|w| w.bits(msttime));
+            self.i2c.timeout.write(|w| w.bits(timeout));
+            self.i2c.slvqual0.write(|w| w.bits(slvqual0));
+            for (&value, reg) in slvadr.iter().zip(self.i2c.slvadr.iter()) {
+                reg.write(|w| w.bits(value));
+            }
+            self.i2c.cfg.write(|w| w.bits(cfg));
+            self.i2c.intenset.write(|w| w.bits(intenset));
+        }
+    }
 }
 
 impl<I, State, MasterMode, SlaveMode> I2C<I, State, MasterMode, SlaveMode>
@@ -228,7 +358,48 @@ where
     /// request already exists, comment on the existing issue, so we can
     /// prioritize it accordingly.
     ///
+    /// The SCL and SDA functions that were passed to [`enable`] stay assigned
+    /// to their pins. Master, slave, and monitor mode are left as they are, so
+    /// a transfer that is in progress continues on the bus. Call [`disable`]
+    /// first, to turn off all modes and the peripheral clock.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{i2c, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let (i2c0_sda, _) = swm.fixed_functions.i2c0_sda.assign(
+    ///     p.pins.pio0_11.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// let (i2c0_scl, _) = swm.fixed_functions.i2c0_scl.assign(
+    ///     p.pins.pio0_10.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let i2c = p
+    ///     .I2C0
+    ///     .enable(&syscon.iosc, i2c0_scl, i2c0_sda, &mut syscon.handle)
+    ///     .enable_master_mode(&i2c::Clock::new_400khz());
+    ///
+    /// // Disable the peripheral and get access to its registers.
+    /// let i2c0 = i2c.disable(&mut syscon.handle).free();
+    /// let cfg = i2c0.cfg.read().bits();
+    /// ```
+    ///
     /// [open an issue]: https://github.com/lpc-rs/lpc8xx-hal/issues
+    /// [`enable`]: #method.enable
+    /// [`disable`]: #method.disable
     pub fn free(self) -> I {
         self.i2c
     }
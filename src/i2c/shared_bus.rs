@@ -0,0 +1,182 @@
+//! Sharing an I2C bus between multiple drivers
+//!
+//! Drivers written against the `embedded-hal` I2C traits usually take
+//! ownership of (or a mutable reference to) the I2C master. This makes it
+//! impossible to use multiple drivers on the same bus, without some kind of
+//! synchronization.
+//!
+//! [`BusManager`] provides this synchronization. It takes ownership of the I2C
+//! master and hands out any number of [`BusProxy`] instances, which implement
+//! the `embedded-hal` I2C traits. Every operation on a proxy is executed
+//! within a critical section, which makes sure that operations from different
+//! proxies (even if they are used from interrupt handlers) never overlap.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     prelude::*,
+//!     i2c::{self, BusManager},
+//!     Peripherals,
+//! };
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut swm = p.SWM.split();
+//! let mut syscon = p.SYSCON.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! let (i2c0_sda, _) = swm.fixed_functions.i2c0_sda.assign(
+//!     p.pins.pio0_11.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (i2c0_scl, _) = swm.fixed_functions.i2c0_scl.assign(
+//!     p.pins.pio0_10.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! let i2c = p.I2C0
+//!     .enable(
+//!         &syscon.iosc,
+//!         i2c0_scl,
+//!         i2c0_sda,
+//!         &mut syscon.handle,
+//!     )
+//!     .enable_master_mode(&i2c::Clock::new_400khz());
+//!
+//! let bus = BusManager::new(i2c.master);
+//!
+//! // Each of those could be passed to a different driver.
+//! let mut proxy_a = bus.acquire();
+//! let mut proxy_b = bus.acquire();
+//!
+//! proxy_a.write(0x29, &[0x00]).unwrap();
+//! proxy_b.write(0x50, &[0x00]).unwrap();
+//! ```
+//!
+//! [`BusManager`]: struct.BusManager.html
+//! [`BusProxy`]: struct.BusProxy.html
+
+use core::cell::RefCell;
+
+use cortex_m::interrupt::{self, Mutex};
+use embedded_hal::blocking::i2c;
+
+/// Manages access to a shared I2C bus
+///
+/// Owns the I2C master (or any other type that implements the `embedded-hal`
+/// I2C traits) and hands out [`BusProxy`] instances, that can be used to
+/// access it.
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// [`BusProxy`]: struct.BusProxy.html
+/// [module documentation]: index.html
+pub struct BusManager<M> {
+    bus: Mutex<RefCell<M>>,
+}
+
+impl<M> BusManager<M> {
+    /// Create a new `BusManager`, taking ownership of the bus
+    pub fn new(bus: M) -> Self {
+        Self {
+            bus: Mutex::new(RefCell::new(bus)),
+        }
+    }
+
+    /// Create a new proxy for the bus
+    ///
+    /// Proxies are cheap. Any number of them can exist at the same time.
+    pub fn acquire(&self) -> BusProxy<'_, M> {
+        BusProxy { manager: self }
+    }
+
+    fn lock<R>(&self, f: impl FnOnce(&mut M) -> R) -> R {
+        interrupt::free(|cs| {
+            // This can't fail, as the bus is only ever borrowed within a
+            // critical section, and never across calls into user code. If a
+            // panic unwinds out of `f`, the borrow is released, so the bus
+            // can't get stuck in a borrowed state either.
+            let mut bus = self.bus.borrow(cs).borrow_mut();
+            f(&mut bus)
+        })
+    }
+}
+
+/// A proxy for a shared I2C bus
+///
+/// Can be created using [`BusManager::acquire`]. Implements the
+/// `embedded-hal` I2C traits that are implemented by the bus, executing every
+/// operation within a critical section.
+///
+/// Please note that interrupts are disabled for the whole duration of every
+/// operation. Long transfers can therefore delay interrupt handlers
+/// significantly.
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::blocking::i2c::Read`]
+/// - [`embedded_hal::blocking::i2c::Write`]
+/// - [`embedded_hal::blocking::i2c::WriteRead`]
+///
+/// [`BusManager::acquire`]: struct.BusManager.html#method.acquire
+/// [`embedded_hal::blocking::i2c::Read`]: #impl-Read
+/// [`embedded_hal::blocking::i2c::Write`]: #impl-Write
+/// [`embedded_hal::blocking::i2c::WriteRead`]: #impl-WriteRead
+pub struct BusProxy<'a, M> {
+    manager: &'a BusManager<M>,
+}
+
+impl<'a, M> Clone for BusProxy<'a, M> {
+    fn clone(&self) -> Self {
+        Self {
+            manager: self.manager,
+        }
+    }
+}
+
+impl<'a, M> i2c::Write for BusProxy<'a, M>
+where
+    M: i2c::Write,
+{
+    type Error = M::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.manager.lock(|bus| bus.write(address, bytes))
+    }
+}
+
+impl<'a, M> i2c::Read for BusProxy<'a, M>
+where
+    M: i2c::Read,
+{
+    type Error = M::Error;
+
+    fn read(
+        &mut self,
+        address: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.manager.lock(|bus| bus.read(address, buffer))
+    }
+}
+
+impl<'a, M> i2c::WriteRead for BusProxy<'a, M>
+where
+    M: i2c::WriteRead,
+{
+    type Error = M::Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.manager
+            .lock(|bus| bus.write_read(address, bytes, buffer))
+    }
+}
@@ -0,0 +1,93 @@
+use super::master;
+
+/// A snapshot of the master-related flags in the STAT register
+///
+/// Returned by [`Master::status`]. Can also be created from a raw register
+/// value using [`Status::from_bits`], which is useful, if the value has been
+/// captured elsewhere, for example by a debugger.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::i2c::{master::State, Status};
+///
+/// // Master pending, NACK received in response to the address
+/// let status = Status::from_bits(0x0000_0007);
+/// assert!(status.master_pending);
+/// assert_eq!(status.master_state, Ok(State::NackAddress));
+///
+/// // Master idle, arbitration lost, SCL timeout
+/// let status = Status::from_bits(0x0200_0011);
+/// assert_eq!(status.master_state, Ok(State::Idle));
+/// assert!(status.master_arbitration_loss);
+/// assert!(status.scl_timeout);
+/// assert!(!status.event_timeout);
+///
+/// // Reserved master state
+/// let status = Status::from_bits(0x0000_000b);
+/// assert_eq!(status.master_state, Err(5));
+/// ```
+///
+/// [`Master::status`]: master/struct.Master.html#method.status
+/// [`Status::from_bits`]: #method.from_bits
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Status {
+    /// Whether the master is waiting for software to handle the current state
+    ///
+    /// If this is `false`, the master is busy, and [`master_state`] must not
+    /// be relied upon.
+    ///
+    /// Corresponds to the MSTPENDING flag.
+    ///
+    /// [`master_state`]: #structfield.master_state
+    pub master_pending: bool,
+
+    /// The state of the master state machine
+    ///
+    /// The `Err` variant contains a reserved value of the MSTSTATE field.
+    pub master_state: Result<master::State, u8>,
+
+    /// Corresponds to the MSTARBLOSS flag
+    pub master_arbitration_loss: bool,
+
+    /// Corresponds to the MSTSTSTPERR flag
+    pub master_start_stop_error: bool,
+
+    /// Corresponds to the MONOV flag
+    pub monitor_overflow: bool,
+
+    /// Corresponds to the EVENTTIMEOUT flag
+    pub event_timeout: bool,
+
+    /// Corresponds to the SCLTIMEOUT flag
+    pub scl_timeout: bool,
+}
+
+impl Status {
+    /// Decode a raw value of the STAT register
+    ///
+    /// Flags that are not related to master mode are ignored.
+    pub fn from_bits(bits: u32) -> Self {
+        let flag = |n: u32| bits & (0x1 << n) != 0;
+
+        let master_state = match (bits >> 1) & 0x7 {
+            0x0 => Ok(master::State::Idle),
+            0x1 => Ok(master::State::RxReady),
+            0x2 => Ok(master::State::TxReady),
+            0x3 => Ok(master::State::NackAddress),
+            0x4 => Ok(master::State::NackData),
+            state => Err(state as u8),
+        };
+
+        Self {
+            master_pending: flag(0),
+            master_state,
+            master_arbitration_loss: flag(4),
+            master_start_stop_error: flag(6),
+            monitor_overflow: flag(17),
+            event_timeout: flag(24),
+            scl_timeout: flag(25),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! API for device identification, using the In-Application Programming (IAP)
+//! interface of the boot ROM
+//!
+//! The boot ROM provides the IAP interface, which, among other things, can
+//! read the unique serial number of the device. This module wraps the IAP
+//! commands that read information, which is useful for provisioning and
+//! telemetry. It doesn't provide any commands that write to the flash memory.
+//!
+//! The part ID doesn't require IAP, as it is also available in the DEVICE_ID
+//! register of the SYSCON peripheral. [`part_id`] reads it from there.
+//!
+//! The IAP interface is described in the chapter on flash ISP and IAP
+//! programming, in the user manuals of both LPC82x and LPC84x.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::iap;
+//!
+//! let part_id = iap::part_id();
+//! let serial = iap::unique_id().unwrap();
+//! ```
+//!
+//! [`part_id`]: fn.part_id.html
+
+use core::mem;
+
+use cortex_m::interrupt;
+
+use crate::pac;
+
+/// The entry point of the IAP interface in the boot ROM
+///
+/// The lowest bit is set, as this is a Thumb function. The boot ROM is located
+/// at 0x1fff_0000 on LPC82x, and at 0x0f00_0000 on LPC84x.
+#[cfg(feature = "82x")]
+const ENTRY: usize = 0x1fff_1ff1;
+#[cfg(feature = "845")]
+const ENTRY: usize = 0x0f00_1ff1;
+
+/// IAP command: Read Part Identification number
+const READ_PART_ID: u32 = 54;
+
+/// IAP command: Read UID
+const READ_UID: u32 = 58;
+
+/// IAP status code: CMD_SUCCESS
+const CMD_SUCCESS: u32 = 0;
+
+/// Read the part identification number
+///
+/// Reads the DEVICE_ID register of the SYSCON peripheral, which contains the
+/// same value that the IAP command "Read Part Identification number" returns.
+/// Please refer to the user manual for the meaning of the values.
+pub fn part_id() -> u32 {
+    // Sound, as we only read from a read-only register.
+    let syscon = unsafe { &*pac::SYSCON::ptr() };
+    syscon.device_id.read().bits()
+}
+
+/// Read the part identification number, using the IAP interface
+///
+/// Returns the same value as [`part_id`]. This exists mostly for checking
+/// that the IAP interface works. Prefer [`part_id`], which doesn't need to
+/// call into the boot ROM.
+///
+/// Please refer to [`unique_id`] for the requirements of calling into the
+/// IAP interface.
+///
+/// [`part_id`]: fn.part_id.html
+/// [`unique_id`]: fn.unique_id.html
+pub fn part_id_from_iap() -> Result<u32, Error> {
+    read_part_id(rom)
+}
+
+/// Read the unique serial number of the device
+///
+/// Calls the IAP command "Read UID", which returns a 128-bit serial number
+/// that is unique to each device.
+///
+/// Interrupts are disabled during the call, as the boot ROM code must not be
+/// interrupted by code that could call into it again. The call only takes a
+/// few microseconds.
+///
+/// The IAP code uses up to 128 bytes of the stack, which must be available.
+/// This function doesn't write to the flash memory, so the top 32 bytes of
+/// RAM, which are used by the IAP commands that do, are not affected.
+///
+/// # Errors
+///
+/// Returns the status code, if the IAP command fails. This is not expected
+/// to happen.
+pub fn unique_id() -> Result<UniqueId, Error> {
+    read_uid(rom)
+}
+
+fn read_part_id(
+    entry: impl FnMut(&[u32; 5], &mut [u32; 5]),
+) -> Result<u32, Error> {
+    let result = call_with(entry, READ_PART_ID)?;
+    Ok(result[1])
+}
+
+fn read_uid(
+    entry: impl FnMut(&[u32; 5], &mut [u32; 5]),
+) -> Result<UniqueId, Error> {
+    let result = call_with(entry, READ_UID)?;
+    Ok(UniqueId([result[1], result[2], result[3], result[4]]))
+}
+
+/// Execute an IAP command using the given entry point
+///
+/// The entry point is a parameter, so the handling of commands and results
+/// can be tested without the boot ROM.
+fn call_with(
+    mut entry: impl FnMut(&[u32; 5], &mut [u32; 5]),
+    command: u32,
+) -> Result<[u32; 5], Error> {
+    let command = [command, 0, 0, 0, 0];
+    let mut result = [0; 5];
+
+    entry(&command, &mut result);
+
+    if result[0] != CMD_SUCCESS {
+        return Err(Error(result[0]));
+    }
+
+    Ok(result)
+}
+
+/// Call into the IAP interface of the boot ROM
+fn rom(command: &[u32; 5], result: &mut [u32; 5]) {
+    // Sound, as `ENTRY` is the documented address of the IAP function, which
+    // has this signature.
+    let iap: extern "C" fn(*const u32, *mut u32) =
+        unsafe { mem::transmute(ENTRY) };
+
+    interrupt::free(|_| iap(command.as_ptr(), result.as_mut_ptr()));
+}
+
+/// The unique serial number of a device
+///
+/// Returned by [`unique_id`]. Contains the four words returned by the IAP
+/// command, with the least significant word first.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::iap::UniqueId;
+///
+/// let id = UniqueId([0x33221100, 0x77665544, 0xbbaa9988, 0xffeeddcc]);
+///
+/// assert_eq!(id.as_u128(), 0xffeeddcc_bbaa9988_77665544_33221100);
+/// assert_eq!(id.to_bytes()[..4], [0x00, 0x11, 0x22, 0x33]);
+/// ```
+///
+/// [`unique_id`]: fn.unique_id.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UniqueId(pub [u32; 4]);
+
+impl UniqueId {
+    /// Returns the serial number as a single number
+    pub fn as_u128(&self) -> u128 {
+        self.0
+            .iter()
+            .rev()
+            .fold(0, |acc, &word| acc << 32 | word as u128)
+    }
+
+    /// Returns the serial number as bytes, least significant byte first
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.as_u128().to_le_bytes()
+    }
+}
+
+/// An IAP command failed
+///
+/// Contains the status code that was returned by the boot ROM. Please refer to
+/// the user manual for the meaning of the status codes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Error(pub u32);
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, UniqueId, READ_PART_ID, READ_UID};
+
+    #[test]
+    fn read_uid_passes_command_and_returns_words() {
+        let mut calls = 0;
+
+        let id = super::read_uid(|command, result| {
+            calls += 1;
+            assert_eq!(command, &[READ_UID, 0, 0, 0, 0]);
+            *result = [0, 1, 2, 3, 4];
+        });
+
+        assert_eq!(id, Ok(UniqueId([1, 2, 3, 4])));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn read_part_id_returns_second_word() {
+        let part_id = super::read_part_id(|command, result| {
+            assert_eq!(command[0], READ_PART_ID);
+            *result = [0, 0x0000_8442, 0, 0, 0];
+        });
+
+        assert_eq!(part_id, Ok(0x0000_8442));
+    }
+
+    #[test]
+    fn failed_command_returns_status_code() {
+        // 1 is INVALID_COMMAND
+        let id = super::read_uid(|_, result| *result = [1, 1, 2, 3, 4]);
+
+        assert_eq!(id, Err(Error(1)));
+    }
+}
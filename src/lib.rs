@@ -104,6 +104,8 @@ pub extern crate cortex_m_rt;
 pub extern crate embedded_hal;
 pub extern crate embedded_hal_alpha;
 pub extern crate embedded_time;
+#[cfg(feature = "fugit")]
+pub extern crate fugit;
 pub extern crate nb;
 pub extern crate void;
 
@@ -118,7 +120,9 @@ pub mod delay;
 pub mod dma;
 pub mod gpio;
 pub mod i2c;
+pub mod iap;
 pub mod mrt;
+pub mod onewire;
 #[cfg(feature = "845")]
 pub mod pinint;
 pub mod pins;
@@ -127,9 +131,13 @@ pub mod sleep;
 pub mod spi;
 pub mod swm;
 pub mod syscon;
+pub mod timer;
 pub mod usart;
 pub mod wkt;
 
+#[cfg(test)]
+mod test_support;
+
 /// Re-exports various traits that are required to use lpc8xx-hal
 ///
 /// The purpose of this module is to improve convenience, by not requiring the
@@ -227,14 +235,16 @@ pub struct Peripherals {
     /// General-purpose I/O (GPIO)
     ///
     /// By default, the GPIO peripheral is enabled on the LPC82x and disabled on
-    /// the LPC845.
+    /// the LPC845. `GPIO::enable` can be called on both, to write code that
+    /// works on all targets.
     #[cfg(feature = "82x")]
     pub GPIO: GPIO<init_state::Enabled>,
 
     /// General-purpose I/O (GPIO)
     ///
     /// By default, the GPIO peripheral is enabled on the LPC82x and disabled on
-    /// the LPC845.
+    /// the LPC845. `GPIO::enable` can be called on both, to write code that
+    /// works on all targets.
     #[cfg(feature = "845")]
     pub GPIO: GPIO<init_state::Disabled>,
 
@@ -378,6 +388,11 @@ pub struct Peripherals {
     /// A HAL API for this peripheral has not been implemented yet. In the
     /// meantime, this field provides you with the raw register mappings, which
     /// allow you full, unprotected access to the peripheral.
+    ///
+    /// The flash wait states can be configured using
+    /// [`syscon::flash::set_wait_states`].
+    ///
+    /// [`syscon::flash::set_wait_states`]: syscon/flash/fn.set_wait_states.html
     pub FLASH_CTRL: pac::FLASH_CTRL,
 
     /// Input multiplexing
@@ -497,6 +512,26 @@ impl Peripherals {
     ///
     /// Since there are no means within this API to forcibly change type state,
     /// you will need to resort to something like [`core::mem::transmute`].
+    ///
+    /// # Example
+    ///
+    /// Unlike [`Peripherals::take`], this method can be called after the
+    /// peripherals have already been taken. If you only need a single
+    /// peripheral, you can just move it out of the returned struct and drop
+    /// the rest.
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let p = Peripherals::take().unwrap();
+    /// drop(p);
+    ///
+    /// // `take` won't return the peripherals a second time, but `steal` will.
+    /// assert!(Peripherals::take().is_none());
+    /// let wkt = unsafe { Peripherals::steal() }.WKT;
+    /// ```
+    ///
+    /// [`Peripherals::take`]: #method.take
     pub unsafe fn steal() -> Self {
         Self::new(pac::Peripherals::steal())
     }
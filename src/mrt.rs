@@ -1,15 +1,26 @@
 //! API for the MRT (Multi-Rate Timer) peripheral
 //!
 //! Please be aware that this doesn't try to abstract everything, it only
-//! implements the embedded-hal `Timer` functionality.
+//! implements the embedded-hal `Timer` functionality, as well as basic
+//! interrupt handling.
 //!
 //! The MRT consists of 4 channels, which are mostly separate and can each act
-//! as a run-of-the-mill timer.
+//! as a run-of-the-mill timer. Each channel can run in repeat mode (the
+//! default), or in one-shot mode (see [`Mode`]).
+//!
+//! All channels share the same interrupt (`MRT0`). Use [`Interrupts::pending`]
+//! in the interrupt handler, to find out which channels have fired.
+//!
+//! [`Mode`]: enum.Mode.html
+//! [`Interrupts::pending`]: struct.Interrupts.html#method.pending
 
 use core::convert::TryFrom;
 
 use crate::{
-    pac::{self, mrt0::CHANNEL},
+    pac::{
+        self,
+        mrt0::{channel::ctrl::MODE_A, CHANNEL, IRQ_FLAG},
+    },
     reg_proxy::{Reg, RegProxy},
     syscon,
 };
@@ -66,7 +77,10 @@ impl MRT {
 }
 
 /// The maximum timer value
-pub const MAX_VALUE: Ticks = Ticks(0x7fff_ffff - 1);
+pub const MAX_VALUE: Ticks = Ticks(MAX_TICKS);
+
+/// The maximum timer value, as a plain number of ticks
+pub(crate) const MAX_TICKS: u32 = 0x7fff_ffff - 1;
 
 /// Represents a MRT0 channel
 ///
@@ -92,14 +106,19 @@ where
     pub fn start(&mut self, reload: Ticks) {
         // This stops the timer, to prevent race conditions when resetting the
         // interrupt bit
+        self.stop();
+        self.0
+            .intval
+            .write(|w| unsafe { w.ivalue().bits(reload.0 + 1) });
+    }
+
+    /// Stops the timer and resets the interrupt flag
+    pub(crate) fn stop(&mut self) {
         self.0.intval.write(|w| {
             w.load().set_bit();
             unsafe { w.ivalue().bits(0) }
         });
         self.0.stat.write(|w| w.intflag().set_bit());
-        self.0
-            .intval
-            .write(|w| unsafe { w.ivalue().bits(reload.0 + 1) });
     }
 
     /// Indicates whether the timer is running
@@ -112,16 +131,71 @@ where
         self.0.timer.read().value().bits()
     }
 
+    /// Returns the number of ticks remaining until the count down finishes
+    ///
+    /// Returns zero, if the timer is not running, and while the interrupt flag
+    /// is set. In repeat mode, this is the case from the moment the count down
+    /// finishes, until the interrupt flag is cleared.
+    pub(crate) fn remaining(&self) -> u32 {
+        remaining(&self.0)
+    }
+
     /// Returns the reload value of the timer
     pub fn reload_value(&self) -> u32 {
         self.0.intval.read().ivalue().bits()
     }
 
+    /// Select the timer mode
+    ///
+    /// Please note that the [`Periodic`] trait is implemented regardless of
+    /// the mode. If you pass a channel in one-shot mode to code that expects a
+    /// periodic timer, it will only fire once.
+    ///
+    /// [`Periodic`]: embedded_hal::timer::Periodic
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.0.ctrl.modify(|_, w| w.mode().variant(mode.into()));
+    }
+
+    /// Returns the current timer mode
+    pub fn mode(&self) -> Mode {
+        if self.0.ctrl.read().mode().is_one_shot_interrupt_mode() {
+            Mode::OneShot
+        } else {
+            Mode::Repeat
+        }
+    }
+
+    /// Enable the interrupt for this channel
+    ///
+    /// Once enabled, the `MRT0` interrupt fires whenever the timer of this
+    /// channel reaches zero. The interrupt still needs to be unmasked in the
+    /// NVIC.
+    pub fn enable_interrupt(&mut self) {
+        self.0.ctrl.modify(|_, w| w.inten().enabled());
+    }
+
+    /// Disable the interrupt for this channel
+    pub fn disable_interrupt(&mut self) {
+        self.0.ctrl.modify(|_, w| w.inten().disabled());
+    }
+
+    /// Indicates whether the interrupt flag of this channel is set
+    ///
+    /// The interrupt flag is set when the timer reaches zero, regardless of
+    /// whether the interrupt is enabled.
+    pub fn is_interrupt_pending(&self) -> bool {
+        self.0.stat.read().intflag().is_pending_interrupt()
+    }
+
+    /// Clear the interrupt flag of this channel
+    pub fn clear_interrupt(&mut self) {
+        self.0.stat.write(|w| w.intflag().set_bit());
+    }
+
     /// Non-blockingly "waits" until the count down finishes
     fn wait(&mut self) -> nb::Result<(), Void> {
-        if self.0.stat.read().intflag().is_pending_interrupt() {
-            // Reset the interrupt flag
-            self.0.stat.write(|w| w.intflag().set_bit());
+        if self.is_interrupt_pending() {
+            self.clear_interrupt();
             Ok(())
         } else {
             Err(nb::Error::WouldBlock)
@@ -213,6 +287,7 @@ where
 ///
 /// [`CountDown`]: embedded_hal::timer::CountDown
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Ticks(u32);
 
 impl Ticks {
@@ -287,11 +362,71 @@ impl TryFrom<Seconds> for Ticks {
 }
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// Indicates that a conversion to [`Tick`] failed
 ///
 /// This is the case when the resulting value is larger than [`MAX_VALUE`].
 pub struct TickConversionError;
 
+/// The mode of an MRT channel
+///
+/// Used by [`Channel::set_mode`].
+///
+/// [`Channel::set_mode`]: struct.Channel.html#method.set_mode
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mode {
+    /// The timer is reloaded every time it reaches zero
+    ///
+    /// This is the default.
+    Repeat,
+
+    /// The timer stops after reaching zero
+    ///
+    /// The timer can be started again using [`Channel::start`].
+    ///
+    /// [`Channel::start`]: struct.Channel.html#method.start
+    OneShot,
+}
+
+impl From<Mode> for MODE_A {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Repeat => MODE_A::REPEAT_INTERRUPT_MODE,
+            Mode::OneShot => MODE_A::ONE_SHOT_INTERRUPT_MODE,
+        }
+    }
+}
+
+/// Provides access to the interrupt flags of all channels
+///
+/// Since all channels share the same interrupt, this can be used in the
+/// interrupt handler to find out which channels have fired, without requiring
+/// access to all of them.
+pub struct Interrupts(RegProxy<IRQ_FLAG>);
+
+impl Interrupts {
+    fn new() -> Self {
+        Self(RegProxy::new())
+    }
+
+    /// Returns the channels whose interrupt flag is set
+    pub fn pending(&self) -> Pending {
+        Pending::from_bits(self.0.read().bits())
+    }
+
+    /// Clear the interrupt flags of the given channels
+    ///
+    /// Only clears the flags that are set in `pending`. Flags that were set in
+    /// the meantime are left alone, so they won't get lost.
+    pub fn clear(&mut self, pending: Pending) {
+        // Sound, as we only write 1 to flag bits, which clears them.
+        self.0.write(|w| unsafe { w.bits(pending.bits()) });
+    }
+}
+
+reg!(IRQ_FLAG, IRQ_FLAG, pac::MRT0, irq_flag);
+
 /// Implemented for types that identify MRT channels
 pub trait Trait: Reg<Target = CHANNEL> + sealed::Sealed {}
 
@@ -303,14 +438,55 @@ macro_rules! channels {
                 #[allow(missing_docs)]
                 pub $field: Channel<$channel>,
             )*
+
+            /// The interrupt flags of all channels
+            pub interrupts: Interrupts,
         }
 
         impl Channels {
             fn new() -> Self {
                 Self {
                     $($field: Channel::new(),)*
+                    interrupts: Interrupts::new(),
+                }
+            }
+        }
+
+        /// The channels whose interrupt flag is set
+        ///
+        /// Returned by [`Interrupts::pending`].
+        ///
+        /// [`Interrupts::pending`]: struct.Interrupts.html#method.pending
+        #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        pub struct Pending {
+            $(
+                #[allow(missing_docs)]
+                pub $field: bool,
+            )*
+        }
+
+        impl Pending {
+            /// Indicates whether the interrupt flag of any channel is set
+            pub fn any(&self) -> bool {
+                false $(|| self.$field)*
+            }
+
+            fn from_bits(bits: u32) -> Self {
+                Self {
+                    $($field: bits & (0x1 << $index) != 0,)*
                 }
             }
+
+            fn bits(&self) -> u32 {
+                let mut bits = 0;
+                $(
+                    if self.$field {
+                        bits |= 0x1 << $index;
+                    }
+                )*
+                bits
+            }
         }
 
         $(
@@ -337,3 +513,55 @@ channels!(
 mod sealed {
     pub trait Sealed {}
 }
+
+fn remaining(channel: &CHANNEL) -> u32 {
+    // Read the timer first. If it reaches zero after that, the interrupt flag
+    // is set.
+    let value = channel.timer.read().value().bits();
+
+    let stat = channel.stat.read();
+    if stat.intflag().is_pending_interrupt() || !stat.run().is_running() {
+        return 0;
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{mem, ptr};
+
+    use crate::pac::mrt0::CHANNEL;
+
+    const INTFLAG: u32 = 1 << 0;
+    const RUN: u32 = 1 << 1;
+
+    fn channel(stat: u32, timer: u32) -> CHANNEL {
+        // Sound, as the register block only consists of `u32` values.
+        let mut channel: CHANNEL = unsafe { mem::zeroed() };
+        channel.stat.write(|w| unsafe { w.bits(stat) });
+
+        // TIMER is read-only. Sound, as a register only consists of a `u32`
+        // value.
+        let register = ptr::addr_of_mut!(channel.timer) as *mut u32;
+        unsafe { ptr::write_volatile(register, timer) };
+
+        channel
+    }
+
+    #[test]
+    fn remaining_is_timer_value_while_running() {
+        assert_eq!(super::remaining(&channel(RUN, 42)), 42);
+    }
+
+    #[test]
+    fn remaining_is_zero_once_finished() {
+        // In repeat mode, the timer has already been reloaded.
+        assert_eq!(super::remaining(&channel(RUN | INTFLAG, 99)), 0);
+    }
+
+    #[test]
+    fn remaining_is_zero_while_stopped() {
+        assert_eq!(super::remaining(&channel(0, 42)), 0);
+    }
+}
@@ -0,0 +1,342 @@
+//! Bit-banged 1-Wire bus master
+//!
+//! The entry point to this API is [`OneWire`]. It implements the 1-Wire
+//! protocol on top of a GPIO pin in dynamic mode, as used by sensors like the
+//! DS18B20. This is not a peripheral, just a thin layer on top of the [`gpio`]
+//! and [`delay`] APIs.
+//!
+//! The bus is driven low by switching the pin to output (with its level set to
+//! LOW), and released by switching it to input. This emulates an open-drain
+//! output, which means an external pull-up resistor (typically 4.7 kΩ) is
+//! required.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     delay::Delay,
+//!     gpio::Level,
+//!     onewire::OneWire,
+//!     pac::CorePeripherals,
+//!     pins::DynamicPinDirection,
+//!     Peripherals,
+//! };
+//!
+//! let cp = CorePeripherals::take().unwrap();
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//!
+//! let gpio = p.GPIO.enable(&mut syscon.handle);
+//!
+//! let pin = p.pins.pio0_1.into_dynamic_pin(
+//!     gpio.tokens.pio0_1,
+//!     Level::Low,
+//!     DynamicPinDirection::Input,
+//! );
+//!
+//! let mut bus = OneWire::new(pin, Delay::new(cp.SYST));
+//!
+//! if bus.reset() {
+//!     bus.write_byte(0xcc); // Skip ROM
+//!     bus.write_byte(0x44); // Start temperature conversion
+//! }
+//! ```
+//!
+//! [`OneWire`]: struct.OneWire.html
+//! [`gpio`]: ../gpio/index.html
+//! [`delay`]: ../delay/index.html
+
+use cortex_m::interrupt;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_time::duration::Nanoseconds;
+
+use crate::{
+    delay::Resolution,
+    gpio::{direction, GpioPin, Level},
+    pins,
+};
+
+/// How long the bus is held low to reset all devices, in µs
+pub const RESET_LOW_US: u16 = 480;
+
+/// How long to wait after a reset before sampling the presence pulse, in µs
+pub const PRESENCE_SAMPLE_US: u16 = 70;
+
+/// How long to wait after sampling the presence pulse, in µs
+///
+/// Together with [`PRESENCE_SAMPLE_US`], this makes up the minimum time of
+/// 480 µs, that the bus needs to be released after a reset.
+///
+/// [`PRESENCE_SAMPLE_US`]: constant.PRESENCE_SAMPLE_US.html
+pub const PRESENCE_RELEASE_US: u16 = 410;
+
+/// How long the bus is held low to start a slot, in µs
+///
+/// This applies to writing a 1 and to reading. This is the nominal value,
+/// which is shortened according to the resolution of the delay provider. See
+/// [`READ_WINDOW_US`].
+///
+/// [`READ_WINDOW_US`]: constant.READ_WINDOW_US.html
+pub const SLOT_START_US: u16 = 6;
+
+/// How long the bus is held low to write a 0, in µs
+pub const WRITE_ZERO_LOW_US: u16 = 60;
+
+/// How long to wait after starting a read slot, before sampling, in µs
+///
+/// This is the nominal value, which is shortened according to the resolution
+/// of the delay provider. See [`READ_WINDOW_US`].
+///
+/// [`READ_WINDOW_US`]: constant.READ_WINDOW_US.html
+pub const READ_SAMPLE_US: u16 = 9;
+
+/// The latest time after the start of a read slot, at which the bus must be
+/// sampled, in µs
+///
+/// Every delay can take up to one resolution step of the delay provider longer
+/// than requested. To make sure that the bus is sampled in time,
+/// [`SLOT_START_US`] and [`READ_SAMPLE_US`] are each shortened by the
+/// resolution (rounded up to whole microseconds), but not below 1 µs. If the
+/// worst-case sampling time still exceeds this window, the delay provider is
+/// too coarse to be used.
+///
+/// All other delays are long enough, that a delay provider that is accurate
+/// enough for this window is accurate enough for them too.
+///
+/// [`SLOT_START_US`]: constant.SLOT_START_US.html
+/// [`READ_SAMPLE_US`]: constant.READ_SAMPLE_US.html
+pub const READ_WINDOW_US: u16 = 15;
+
+/// The duration of a complete time slot, including recovery time, in µs
+pub const SLOT_US: u16 = 70;
+
+/// A bit-banged 1-Wire bus master
+///
+/// Requires a [`GpioPin`] in dynamic mode and a delay provider that reports its
+/// [`Resolution`]. The timing of the 1-Wire protocol is defined by the
+/// constants in this module, and the time-critical ones are adjusted to the
+/// resolution of the delay provider, as described in [`READ_WINDOW_US`].
+/// [`Delay`] and [`DelayCycles`] are fine enough for this. [`WktDelay`] is
+/// too, when using the 750 kHz IRC/FRO-derived clock, but not with the 10 kHz
+/// low-power clock.
+///
+/// Interrupts are disabled during each time slot, as an interrupt handler
+/// could otherwise stretch the timing beyond what the protocol allows.
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// [`GpioPin`]: ../gpio/struct.GpioPin.html
+/// [`Resolution`]: ../delay/trait.Resolution.html
+/// [`READ_WINDOW_US`]: constant.READ_WINDOW_US.html
+/// [`Delay`]: ../delay/struct.Delay.html
+/// [`DelayCycles`]: ../delay/struct.DelayCycles.html
+/// [`WktDelay`]: ../delay/struct.WktDelay.html
+/// [module documentation]: index.html
+pub struct OneWire<T, D> {
+    pin: GpioPin<T, direction::Dynamic>,
+    delay: D,
+    timing: Timing,
+}
+
+impl<T, D> OneWire<T, D>
+where
+    T: pins::Trait,
+    D: DelayUs<u16> + Resolution,
+{
+    /// Create a new instance of `OneWire`
+    ///
+    /// Releases the bus, by switching `pin` to input.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the resolution of `delay` is too coarse to meet the timing of
+    /// the 1-Wire protocol. See [`READ_WINDOW_US`].
+    ///
+    /// [`READ_WINDOW_US`]: constant.READ_WINDOW_US.html
+    pub fn new(mut pin: GpioPin<T, direction::Dynamic>, delay: D) -> Self {
+        let timing = Timing::new(delay.resolution())
+            .expect("Delay provider too coarse for 1-Wire timing");
+
+        pin.switch_to_input();
+        pin.set_low();
+
+        Self { pin, delay, timing }
+    }
+
+    /// Reset all devices on the bus
+    ///
+    /// Returns `true`, if at least one device answered with a presence pulse.
+    pub fn reset(&mut self) -> bool {
+        self.pull_low();
+        self.delay.delay_us(RESET_LOW_US);
+
+        let present = interrupt::free(|_| {
+            self.release();
+            self.delay.delay_us(PRESENCE_SAMPLE_US);
+            self.pin.is_low()
+        });
+
+        self.delay.delay_us(PRESENCE_RELEASE_US);
+
+        present
+    }
+
+    /// Write a single bit to the bus
+    pub fn write_bit(&mut self, bit: bool) {
+        let low = if bit {
+            self.timing.slot_start
+        } else {
+            WRITE_ZERO_LOW_US
+        };
+
+        interrupt::free(|_| {
+            self.pull_low();
+            self.delay.delay_us(low);
+            self.release();
+            self.delay.delay_us(SLOT_US - low);
+        });
+    }
+
+    /// Read a single bit from the bus
+    pub fn read_bit(&mut self) -> bool {
+        interrupt::free(|_| {
+            self.pull_low();
+            self.delay.delay_us(self.timing.slot_start);
+            self.release();
+            self.delay.delay_us(self.timing.read_sample);
+
+            let bit = self.pin.is_high();
+
+            self.delay.delay_us(
+                SLOT_US - self.timing.slot_start - self.timing.read_sample,
+            );
+
+            bit
+        })
+    }
+
+    /// Write a byte to the bus, least significant bit first
+    pub fn write_byte(&mut self, byte: u8) {
+        for i in 0..8 {
+            self.write_bit(byte & (0x1 << i) != 0);
+        }
+    }
+
+    /// Read a byte from the bus, least significant bit first
+    pub fn read_byte(&mut self) -> u8 {
+        let mut byte = 0;
+
+        for i in 0..8 {
+            if self.read_bit() {
+                byte |= 0x1 << i;
+            }
+        }
+
+        byte
+    }
+
+    /// Release the pin and the delay provider
+    pub fn free(self) -> (GpioPin<T, direction::Dynamic>, D) {
+        (self.pin, self.delay)
+    }
+
+    fn pull_low(&mut self) {
+        self.pin.switch_to_output(Level::Low);
+    }
+
+    fn release(&mut self) {
+        self.pin.switch_to_input();
+    }
+}
+
+/// The time-critical delays, adjusted to the resolution of a delay provider
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Timing {
+    slot_start: u16,
+    read_sample: u16,
+}
+
+impl Timing {
+    /// Returns `None`, if the resolution is too coarse
+    fn new(resolution: Nanoseconds<u32>) -> Option<Self> {
+        let step = resolution.0.div_ceil(1_000);
+        let shorten = |us: u16| (us as u32).saturating_sub(step).max(1);
+
+        let slot_start = shorten(SLOT_START_US);
+        let read_sample = shorten(READ_SAMPLE_US);
+
+        // Each of the two delays can take up to one resolution step longer
+        // than requested.
+        let latest_sample_ns =
+            (slot_start + read_sample) as u64 * 1_000 + 2 * resolution.0 as u64;
+        if latest_sample_ns > READ_WINDOW_US as u64 * 1_000 {
+            return None;
+        }
+
+        Some(Self {
+            slot_start: slot_start as u16,
+            read_sample: read_sample as u16,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_time::duration::Nanoseconds;
+
+    use super::{Timing, READ_SAMPLE_US, SLOT_START_US};
+
+    #[test]
+    fn exact_delay_uses_nominal_timing() {
+        assert_eq!(
+            Timing::new(Nanoseconds(0)),
+            Some(Timing {
+                slot_start: SLOT_START_US,
+                read_sample: READ_SAMPLE_US,
+            })
+        );
+    }
+
+    #[test]
+    fn timing_is_shortened_by_resolution() {
+        // SysTick at 12 MHz
+        assert_eq!(
+            Timing::new(Nanoseconds(84)),
+            Some(Timing {
+                slot_start: 5,
+                read_sample: 8,
+            })
+        );
+
+        // WKT with the 750 kHz IRC/FRO-derived clock
+        assert_eq!(
+            Timing::new(Nanoseconds(1_334)),
+            Some(Timing {
+                slot_start: 4,
+                read_sample: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn timing_is_never_shorter_than_1_us() {
+        assert_eq!(
+            Timing::new(Nanoseconds(5_000)),
+            Some(Timing {
+                slot_start: 1,
+                read_sample: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn coarse_delay_is_rejected() {
+        // 1 µs + 1 µs + 2 * 6.6 µs > 15 µs
+        assert_eq!(Timing::new(Nanoseconds(6_600)), None);
+
+        // WKT with the 10 kHz low-power clock
+        assert_eq!(Timing::new(Nanoseconds(100_000)), None);
+        assert_eq!(Timing::new(Nanoseconds(u32::MAX)), None);
+    }
+}
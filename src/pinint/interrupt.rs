@@ -1,6 +1,10 @@
 use core::marker::PhantomData;
 
-use crate::{init_state::Enabled, pac, pins, syscon};
+use crate::{
+    init_state::Enabled,
+    pac::{self, NVIC},
+    pins, syscon,
+};
 
 use super::traits::Trait;
 
@@ -163,4 +167,124 @@ where
             // interrupts.
             unsafe { w.cenaf().bits(I::MASK) });
     }
+
+    /// Enable wake-up from deep-sleep and power-down modes
+    ///
+    /// Enables this pin interrupt in the start logic (STARTERP0 register of
+    /// the SYSCON), so an edge on the pin wakes up the microcontroller from
+    /// deep-sleep or power-down mode. This is not required for waking up from
+    /// the regular sleep mode. See [`sleep::WithHandler::deep_sleep_until`]
+    /// for a way to sleep until the pin changes.
+    ///
+    /// The start logic doesn't have its own edge configuration. Which edges
+    /// wake up the microcontroller is determined by which edges are enabled
+    /// for this interrupt, using [`enable_rising_edge`] and
+    /// [`enable_falling_edge`]. The interrupt also needs to be unmasked in the
+    /// NVIC, and its handler needs to clear the edge flags, as usual.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics, if neither rising nor falling edges are
+    /// enabled for this interrupt, as the microcontroller would never wake up.
+    ///
+    /// [`sleep::WithHandler::deep_sleep_until`]: ../sleep/struct.WithHandler.html#method.deep_sleep_until
+    /// [`enable_rising_edge`]: #method.enable_rising_edge
+    /// [`enable_falling_edge`]: #method.enable_falling_edge
+    pub fn enable_wakeup(&mut self, syscon: &mut syscon::Handle) {
+        // Sound, as we're only reading.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        let edges =
+            pint.ienr.read().enrl().bits() | pint.ienf.read().enaf().bits();
+        debug_assert!(
+            edges & I::MASK != 0,
+            "No edge enabled for pin interrupt {}",
+            I::INDEX,
+        );
+
+        syscon.enable_pin_interrupt_wakeup(I::MASK);
+    }
+
+    /// Disable wake-up from deep-sleep and power-down modes
+    pub fn disable_wakeup(&mut self, syscon: &mut syscon::Handle) {
+        syscon.disable_pin_interrupt_wakeup(I::MASK);
+    }
+
+    /// The interrupt in the NVIC that this pin interrupt raises
+    ///
+    /// Pin interrupts 5 to 7 share their interrupt with other peripherals
+    /// (DAC1, USART3, and USART4, respectively).
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{
+    ///     pac::Interrupt,
+    ///     pinint::{Trait, PININT0, PININT4, PININT5, PININT7},
+    /// };
+    ///
+    /// assert!(matches!(PININT0::INTERRUPT, Interrupt::PIN_INT0));
+    /// assert!(matches!(PININT4::INTERRUPT, Interrupt::PIN_INT4));
+    /// assert!(matches!(PININT5::INTERRUPT, Interrupt::PIN_INT5_DAC1));
+    /// assert!(matches!(PININT7::INTERRUPT, Interrupt::PIN_INT7_USART4));
+    /// ```
+    pub fn nvic_interrupt(&self) -> pac::Interrupt {
+        I::INTERRUPT
+    }
+
+    /// Set the priority of this pin interrupt in the NVIC
+    ///
+    /// Lower values mean higher priority. The Cortex-M0+ only implements the
+    /// two most significant bits of the priority, so only `0x00`, `0x40`,
+    /// `0x80`, and `0xc0` result in distinct priorities.
+    ///
+    /// # Safety
+    ///
+    /// Changing priority levels can break priority-based critical sections
+    /// (like the resources of RTIC), and compromise memory safety. See
+    /// `cortex_m::peripheral::NVIC::set_priority`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics, if any of the bits that aren't implemented
+    /// are set in `priority`, as those would be silently ignored.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{pins::PIO0_4, CorePeripherals, Peripherals};
+    ///
+    /// let mut cp = CorePeripherals::take().unwrap();
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let pinint = p.PININT.enable(&mut syscon.handle);
+    ///
+    /// let mut interrupt = pinint
+    ///     .interrupts
+    ///     .pinint0
+    ///     .select::<PIO0_4>(&mut syscon.handle);
+    ///
+    /// // Make this input preempt interrupts of a lower priority.
+    /// unsafe { interrupt.set_nvic_priority(&mut cp.NVIC, 0x40) };
+    /// assert_eq!(interrupt.nvic_priority(), 0x40);
+    /// ```
+    pub unsafe fn set_nvic_priority(&mut self, nvic: &mut NVIC, priority: u8) {
+        debug_assert!(
+            priority & 0x3f == 0,
+            "Priority {:#x} uses unimplemented bits",
+            priority,
+        );
+
+        nvic.set_priority(I::INTERRUPT, priority);
+    }
+
+    /// The priority of this pin interrupt in the NVIC
+    ///
+    /// See [`set_nvic_priority`].
+    ///
+    /// [`set_nvic_priority`]: #method.set_nvic_priority
+    pub fn nvic_priority(&self) -> u8 {
+        NVIC::get_priority(I::INTERRUPT)
+    }
 }
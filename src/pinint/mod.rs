@@ -1,13 +1,21 @@
 //! Interface to the pin interrupts/pattern matching engine
 //!
 //! This API is currently limited. It exposes a subset of the pin interrupts
-//! functionality, and none of the pattern matching functionality.
+//! functionality, and a basic interface to the pattern matching engine (see
+//! [`PatternMatch`]).
+//!
+//! [`PatternMatch`]: struct.PatternMatch.html
 
 mod gen;
 mod interrupt;
+mod pattern;
 mod peripheral;
 mod traits;
 
 pub use self::{
-    gen::*, interrupt::Interrupt, peripheral::PININT, traits::Trait,
+    gen::*,
+    interrupt::Interrupt,
+    pattern::{Condition, PatternMatch, Slice},
+    peripheral::PININT,
+    traits::Trait,
 };
@@ -0,0 +1,212 @@
+use core::marker::PhantomData;
+
+use crate::{init_state::Enabled, pac};
+
+use super::traits::Trait;
+
+/// API for controlling the pattern match engine
+///
+/// The pattern match engine evaluates a boolean expression over the inputs of
+/// the pin interrupts. The expression is built from up to 8 bit slices. Each
+/// slice takes one of the pin interrupt inputs as its source, and evaluates a
+/// [`Condition`] on it. Consecutive slices form a product term (that is, the
+/// conditions of all slices in the term are combined using AND), which ends at
+/// a slice that has been marked as an endpoint. Multiple product terms are
+/// combined using OR.
+///
+/// Since slice 7 is always an endpoint, the slices following the last
+/// endpoint you configured form a product term too. All slices are configured
+/// as [`Condition::Always`] after reset, so make sure to configure at least
+/// one slice of that trailing term as [`Condition::Never`]. Otherwise that
+/// term will always match.
+///
+/// If the product term ending at slice `n` matches, pin interrupt `n` is
+/// raised in the NVIC, if pattern match mode has been selected using
+/// [`enable`]. Optionally, the match can also be signalled to the CPU via the
+/// RXEV signal (see [`enable_rxev`]), which wakes the CPU up from
+/// `cortex_m::asm::wfe`.
+///
+/// The inputs of the slices are the same inputs used by the regular pin
+/// interrupts. Use [`Interrupt::select`] to select the pin that is connected to
+/// an input.
+///
+/// You can gain access to an instance of this struct via [`PININT`].
+///
+/// [`Condition`]: enum.Condition.html
+/// [`Condition::Always`]: enum.Condition.html#variant.Always
+/// [`Condition::Never`]: enum.Condition.html#variant.Never
+/// [`enable`]: #method.enable
+/// [`enable_rxev`]: #method.enable_rxev
+/// [`Interrupt::select`]: struct.Interrupt.html#method.select
+/// [`PININT`]: struct.PININT.html
+pub struct PatternMatch<State> {
+    _state: PhantomData<State>,
+}
+
+impl<State> PatternMatch<State> {
+    pub(super) fn new() -> Self {
+        Self {
+            _state: PhantomData,
+        }
+    }
+}
+
+impl PatternMatch<Enabled> {
+    /// Configure a bit slice
+    ///
+    /// Selects the pin interrupt input `I` as the source of `slice`, and sets
+    /// the condition that the slice evaluates on that input. If `endpoint` is
+    /// `true`, `slice` ends a product term.
+    ///
+    /// [`Slice::Slice7`] is always an endpoint, regardless of the value of
+    /// `endpoint`.
+    ///
+    /// Please note that configuring any slice clears the sticky edge detection
+    /// of all slices.
+    ///
+    /// [`Slice::Slice7`]: enum.Slice.html#variant.Slice7
+    pub fn configure_slice<I>(
+        &mut self,
+        slice: Slice,
+        condition: Condition,
+        endpoint: bool,
+    ) where
+        I: Trait,
+    {
+        let shift = 8 + 3 * slice as u32;
+
+        // Sound, as this `PatternMatch` instance is the only one accessing
+        // these registers, and we only modify the bits of the selected slice.
+        // All written values are valid for their respective fields.
+        self.pint().pmsrc.modify(|r, w| unsafe {
+            w.bits(r.bits() & !(0b111 << shift) | (I::INDEX as u32) << shift)
+        });
+        self.pint().pmcfg.modify(|r, w| {
+            let mut bits =
+                r.bits() & !(0b111 << shift) | (condition as u32) << shift;
+
+            if slice != Slice::Slice7 {
+                let endpoint_bit = 0x1 << slice as u32;
+                if endpoint {
+                    bits |= endpoint_bit;
+                } else {
+                    bits &= !endpoint_bit;
+                }
+            }
+
+            unsafe { w.bits(bits) }
+        });
+    }
+
+    /// Select pattern match mode
+    ///
+    /// From now on, the pin interrupts are raised by the pattern match engine,
+    /// instead of by the regular pin interrupt configuration.
+    pub fn enable(&mut self) {
+        self.pint()
+            .pmctrl
+            .modify(|_, w| w.sel_pmatch().pattern_match());
+    }
+
+    /// Select regular pin interrupt mode
+    ///
+    /// From now on, the pin interrupts are raised according to the regular pin
+    /// interrupt configuration again. This is the default.
+    pub fn disable(&mut self) {
+        self.pint()
+            .pmctrl
+            .modify(|_, w| w.sel_pmatch().pin_interrupt());
+    }
+
+    /// Enable the RXEV signal
+    ///
+    /// If enabled, the RXEV signal is asserted whenever any product term
+    /// matches. This wakes up the CPU, if it is waiting for an event
+    /// (`cortex_m::asm::wfe`).
+    pub fn enable_rxev(&mut self) {
+        self.pint().pmctrl.modify(|_, w| w.ena_rxev().enabled());
+    }
+
+    /// Disable the RXEV signal
+    pub fn disable_rxev(&mut self) {
+        self.pint().pmctrl.modify(|_, w| w.ena_rxev().disabled());
+    }
+
+    /// Returns which product terms currently match
+    ///
+    /// If bit `n` of the returned value is set, the product term ending at
+    /// slice `n` is currently matching.
+    pub fn matches(&self) -> u8 {
+        self.pint().pmctrl.read().pmat().bits()
+    }
+
+    /// Returns whether the product term ending at `slice` currently matches
+    pub fn is_match(&self, slice: Slice) -> bool {
+        self.matches() & (0x1 << slice as u8) != 0
+    }
+
+    fn pint(&self) -> &pac::pint::RegisterBlock {
+        // Sound, as this `PatternMatch` instance is the only one accessing the
+        // pattern match registers.
+        unsafe { &*pac::PINT::ptr() }
+    }
+}
+
+/// Identifies one of the bit slices of the pattern match engine
+///
+/// See [`PatternMatch`].
+///
+/// [`PatternMatch`]: struct.PatternMatch.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(missing_docs)]
+pub enum Slice {
+    Slice0 = 0,
+    Slice1 = 1,
+    Slice2 = 2,
+    Slice3 = 3,
+    Slice4 = 4,
+    Slice5 = 5,
+    Slice6 = 6,
+    Slice7 = 7,
+}
+
+/// The condition a bit slice evaluates on its input
+///
+/// See [`PatternMatch::configure_slice`].
+///
+/// [`PatternMatch::configure_slice`]: struct.PatternMatch.html#method.configure_slice
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Condition {
+    /// The slice always matches
+    Always = 0,
+
+    /// A rising edge has occurred since the slice was configured
+    StickyRisingEdge = 1,
+
+    /// A falling edge has occurred since the slice was configured
+    StickyFallingEdge = 2,
+
+    /// Any edge has occurred since the slice was configured
+    StickyAnyEdge = 3,
+
+    /// The input is high
+    High = 4,
+
+    /// The input is low
+    Low = 5,
+
+    /// The slice never matches
+    ///
+    /// Use this to disable unused slices.
+    Never = 6,
+
+    /// An edge has just been detected
+    ///
+    /// This is the non-sticky variant of [`StickyAnyEdge`]. The slice only
+    /// matches for one clock cycle after the edge.
+    ///
+    /// [`StickyAnyEdge`]: #variant.StickyAnyEdge
+    Event = 7,
+}
@@ -5,13 +5,16 @@ use crate::{
     pac, syscon,
 };
 
-use super::gen::Interrupts;
+use super::{gen::Interrupts, pattern::PatternMatch};
 
 /// Entry point to the PININT API
 pub struct PININT<State> {
     /// Provides access to the pin interrupts
     pub interrupts: Interrupts<State>,
 
+    /// Provides access to the pattern match engine
+    pub pattern_match: PatternMatch<State>,
+
     pinint: pac::PINT,
     _state: PhantomData<State>,
 }
@@ -20,6 +23,7 @@ impl PININT<Disabled> {
     pub(crate) fn new(pinint: pac::PINT) -> Self {
         Self {
             interrupts: Interrupts::new(),
+            pattern_match: PatternMatch::new(),
             pinint,
             _state: PhantomData,
         }
@@ -31,6 +35,7 @@ impl PININT<Disabled> {
 
         PININT {
             interrupts: Interrupts::new(),
+            pattern_match: PatternMatch::new(),
             pinint: self.pinint,
             _state: PhantomData,
         }
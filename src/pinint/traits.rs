@@ -1,3 +1,5 @@
+use crate::pac;
+
 /// Implemented by types that identify pin interrupts
 ///
 /// This trait is an internal implementation detail and should neither be
@@ -13,4 +15,9 @@ pub trait Trait {
     ///
     /// Used in various registers.
     const MASK: u8;
+
+    /// The interrupt in the NVIC that this pin interrupt raises
+    ///
+    /// Some of these interrupts are shared with other peripherals.
+    const INTERRUPT: pac::Interrupt;
 }
@@ -6,6 +6,7 @@ use super::{pin::Pin, state, traits::Trait};
 
 macro_rules! pins {
     ($(
+        $(#[$attr:meta])*
         $field:ident,
         $type:ident,
         $port:expr,
@@ -17,23 +18,34 @@ macro_rules! pins {
         /// You can get access to an instance of this struct through
         ///[`Peripherals`].
         ///
+        /// If a specific part has been selected using the target hardware
+        /// features (for example `845m301jhi33`), only the pins that are
+        /// bonded out on its package are available. Code that attempts to use
+        /// any other pin will not compile.
+        ///
         /// # Limitations
         ///
-        /// This struct currently provides access to all pins that can be
-        /// available on an LPC8xx part. Please make sure that you are aware of
-        /// which pins are actually available on your specific part, and only
-        /// use those.
+        /// The 20-pin package of the LPC82x is not taken into account, and
+        /// neither are parts that were selected using a family feature (like
+        /// `845`). In those cases, this struct provides access to all pins that
+        /// can be available on an LPC8xx part. Please make sure that you are
+        /// aware of which pins are actually available on your specific part,
+        /// and only use those.
         ///
         /// [`Peripherals`]: ../struct.Peripherals.html
         #[allow(missing_docs)]
         pub struct Pins {
-            $(pub $field: Pin<$type, $default_state_ty>,)*
+            $(
+                $(#[$attr])*
+                pub $field: Pin<$type, $default_state_ty>,
+            )*
         }
 
         impl Pins {
             pub(crate) fn new() -> Self {
                 Pins {
                     $(
+                        $(#[$attr])*
                         $field: Pin {
                             ty:     $type(()),
                             _state: <$default_state_ty>::new(),
@@ -71,6 +83,7 @@ macro_rules! pins {
         pub struct Tokens<State> {
             $(
                 /// A token representing a pin
+                $(#[$attr])*
                 pub $field: Token<$type, State>,
             )*
         }
@@ -79,6 +92,7 @@ macro_rules! pins {
             pub(crate) fn new() -> Self {
                 Self {
                     $(
+                        $(#[$attr])*
                         $field: Token($type(()), PhantomData),
                     )*
                 }
@@ -91,6 +105,7 @@ macro_rules! pins {
             pub(crate) fn switch_state<NewState>(self) -> Tokens<NewState> {
                 Tokens {
                     $(
+                        $(#[$attr])*
                         $field: Token(self.$field.0, PhantomData),
                     )*
                 }
@@ -171,29 +186,54 @@ pins!(
     pio0_26, PIO0_26, 0, 0x1a, state::Unused;
     pio0_27, PIO0_27, 0, 0x1b, state::Unused;
     pio0_28, PIO0_28, 0, 0x1c, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio0_29, PIO0_29, 0, 0x1d, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio0_30, PIO0_30, 0, 0x1e, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio0_31, PIO0_31, 0, 0x1f, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_0 , PIO1_0 , 1, 0x00, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_1 , PIO1_1 , 1, 0x01, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_2 , PIO1_2 , 1, 0x02, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_3 , PIO1_3 , 1, 0x03, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_4 , PIO1_4 , 1, 0x04, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_5 , PIO1_5 , 1, 0x05, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_6 , PIO1_6 , 1, 0x06, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_7 , PIO1_7 , 1, 0x07, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_8 , PIO1_8 , 1, 0x08, state::Unused;
+    #[cfg(not(feature = "33"))]
     pio1_9 , PIO1_9 , 1, 0x09, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_10, PIO1_10, 1, 0x0a, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_11, PIO1_11, 1, 0x0b, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_12, PIO1_12, 1, 0x0c, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_13, PIO1_13, 1, 0x0d, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_14, PIO1_14, 1, 0x0e, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_15, PIO1_15, 1, 0x0f, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_16, PIO1_16, 1, 0x10, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_17, PIO1_17, 1, 0x11, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_18, PIO1_18, 1, 0x12, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_19, PIO1_19, 1, 0x13, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_20, PIO1_20, 1, 0x14, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
     pio1_21, PIO1_21, 1, 0x15, state::Unused;
 );
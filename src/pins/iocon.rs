@@ -0,0 +1,670 @@
+//! Per-pin access to the I/O configuration (IOCON)
+//!
+//! The entry point to this API is [`IoconPin`], which can be accessed using
+//! [`Pin::iocon`]. It provides access to the IOCON register of a single pin.
+//!
+//! Please note that the IOCON clock needs to be enabled, for the IOCON
+//! registers to be accessible:
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     Peripherals,
+//!     pins::iocon::Pull,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! syscon.handle.enable_clock(&p.IOCON);
+//!
+//! p.pins.pio0_4.iocon().set_pull(Pull::Up);
+//! ```
+//!
+//! [`IoconPin`]: struct.IoconPin.html
+//! [`Pin::iocon`]: ../struct.Pin.html#method.iocon
+
+use core::{marker::PhantomData, ptr};
+
+use cortex_m::interrupt;
+
+use crate::pac;
+
+use super::{Trait, TrueOpenDrain};
+
+const MODE_SHIFT: u32 = 3;
+const MODE_MASK: u32 = 0x3 << MODE_SHIFT;
+const INV: u32 = 0x1 << 6;
+const I2CMODE_SHIFT: u32 = 8;
+const I2CMODE_MASK: u32 = 0x3 << I2CMODE_SHIFT;
+const OD: u32 = 0x1 << 10;
+const S_MODE_SHIFT: u32 = 11;
+const S_MODE_MASK: u32 = 0x3 << S_MODE_SHIFT;
+const CLK_DIV_SHIFT: u32 = 13;
+const CLK_DIV_MASK: u32 = 0x7 << CLK_DIV_SHIFT;
+
+/// Provides access to the IOCON register of a pin
+///
+/// Can be accessed using [`Pin::iocon`]. Borrows the pin, which makes sure
+/// that only one `IoconPin` instance for a given pin can exist at a time.
+///
+/// Provides methods for the commonly used IOCON settings, as well as
+/// [`IoconPin::read`] and [`IoconPin::modify`] for raw access to the register.
+/// The raw access methods can be used to change settings the HAL doesn't
+/// support otherwise.
+///
+/// Not all pins have all settings. Most notably, the true open-drain I2C pins
+/// (PIO0_10 and PIO0_11) don't have pull resistors or a pseudo open-drain
+/// mode. Instead, they have an I2C mode setting, which is only available for
+/// them (see [`IoconPin::set_i2c_mode`]). Please refer to the user manual for
+/// details.
+///
+/// There is no IOCON setting for analog mode on this platform. Analog
+/// functions (like ADC inputs) are enabled by assigning the respective fixed
+/// function to the pin, using the switch matrix.
+///
+/// [`Pin::iocon`]: ../struct.Pin.html#method.iocon
+/// [`IoconPin::read`]: #method.read
+/// [`IoconPin::modify`]: #method.modify
+/// [`IoconPin::set_i2c_mode`]: #method.set_i2c_mode
+pub struct IoconPin<'pin, T> {
+    iocon: *mut pac::iocon::RegisterBlock,
+    _pin: PhantomData<&'pin mut T>,
+}
+
+impl<'pin, T> IoconPin<'pin, T>
+where
+    T: Trait,
+{
+    /// The offset of the pin's register within the IOCON register block
+    ///
+    /// The IOCON registers are not ordered by pin number, which is why this
+    /// mapping is required.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lpc8xx_hal::pins::{iocon::IoconPin, PIO0_0, PIO0_17, PIO0_18};
+    ///
+    /// assert_eq!(IoconPin::<PIO0_17>::OFFSET, 0x00);
+    /// assert_eq!(IoconPin::<PIO0_0>::OFFSET, 0x44);
+    /// assert_eq!(IoconPin::<PIO0_18>::OFFSET, 0x78);
+    /// ```
+    pub const OFFSET: usize = offset(T::PORT, T::ID);
+
+    pub(crate) fn new() -> Self {
+        Self::at(pac::IOCON::ptr() as *mut _)
+    }
+
+    /// Access the pin's register within the given IOCON register block
+    fn at(iocon: *mut pac::iocon::RegisterBlock) -> Self {
+        Self {
+            iocon,
+            _pin: PhantomData,
+        }
+    }
+
+    /// Read the raw value of the pin's IOCON register
+    pub fn read(&self) -> u32 {
+        // Sound, as we're only reading from a register that belongs to our
+        // pin.
+        unsafe { ptr::read_volatile(self.register()) }
+    }
+
+    /// Modify the raw value of the pin's IOCON register
+    ///
+    /// Passes the current value of the register to `f` and writes back the
+    /// value that `f` returns.
+    pub fn modify<F>(&mut self, f: F)
+    where
+        F: FnOnce(u32) -> u32,
+    {
+        let value = f(self.read());
+
+        // Sound, as the register belongs to our pin, and we have exclusive
+        // access to it.
+        unsafe { ptr::write_volatile(self.register(), value) }
+    }
+
+    /// Select the pull resistor configuration
+    ///
+    /// Writes the MODE field of the pin's IOCON register. See [`Pull`] for
+    /// the available modes.
+    ///
+    /// The true open-drain pins (PIO0_10 and PIO0_11) don't have pull
+    /// resistors, and the MODE field is reserved for them. Don't use this
+    /// method with those pins.
+    ///
+    /// [`Pull`]: enum.Pull.html
+    pub fn set_pull(&mut self, pull: Pull) {
+        self.modify(|r| (r & !MODE_MASK) | pull.bits() << MODE_SHIFT)
+    }
+
+    /// Returns the current pull resistor configuration
+    pub fn pull(&self) -> Pull {
+        Pull::from_bits((self.read() & MODE_MASK) >> MODE_SHIFT)
+    }
+
+    /// Enable or disable input inversion
+    ///
+    /// If enabled, the input level of the pin is inverted in hardware, before
+    /// it reaches the GPIO and pin interrupt peripherals.
+    pub fn set_input_inverted(&mut self, inverted: bool) {
+        self.modify(|r| if inverted { r | INV } else { r & !INV })
+    }
+
+    /// Indicates whether input inversion is enabled
+    pub fn is_input_inverted(&self) -> bool {
+        self.read() & INV != 0
+    }
+
+    /// Enable or disable pseudo open-drain mode
+    ///
+    /// In open-drain mode, the pin is only driven low. Setting the output high
+    /// leaves the pin floating (or pulled up, depending on the pull resistor
+    /// configuration).
+    pub fn set_open_drain(&mut self, open_drain: bool) {
+        self.modify(|r| if open_drain { r | OD } else { r & !OD })
+    }
+
+    /// Configure the input glitch filter
+    ///
+    /// `clock_divider` selects one of the IOCON clock dividers (IOCONCLKDIV0
+    /// to IOCONCLKDIV6) that provides the clock used for sampling. It is
+    /// ignored, if the filter is bypassed.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `clock_divider` is larger than 6.
+    pub fn set_filter(&mut self, filter: Filter, clock_divider: u8) {
+        assert!(clock_divider <= 6);
+
+        self.modify(|r| {
+            (r & !(S_MODE_MASK | CLK_DIV_MASK))
+                | (filter as u32) << S_MODE_SHIFT
+                | (clock_divider as u32) << CLK_DIV_SHIFT
+        })
+    }
+
+    fn register(&self) -> *mut u32 {
+        register(self.iocon, Self::OFFSET)
+    }
+}
+
+impl<'pin, T> IoconPin<'pin, T>
+where
+    T: TrueOpenDrain,
+{
+    /// Select the I2C mode of a true open-drain pin
+    ///
+    /// This method is only available for PIO0_10 and PIO0_11, which are the
+    /// only pins that have this setting. After reset, they are configured for
+    /// standard/fast-mode I2C, which includes an input glitch filter and slew
+    /// rate control meant for I2C. [`Pin::into_output_pin`] and
+    /// [`Pin::into_dynamic_pin`] select [`I2cMode::Gpio`] automatically, so
+    /// this method is only needed to select a different mode.
+    ///
+    /// Please note that these pins can't drive a high level in any mode. An
+    /// external pull-up resistor is required to pull the line high.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{pins::iocon::I2cMode, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// syscon.handle.enable_clock(&p.IOCON);
+    ///
+    /// p.pins.pio0_10.iocon().set_i2c_mode(I2cMode::FastModePlusI2c);
+    /// p.pins.pio0_11.iocon().set_i2c_mode(I2cMode::FastModePlusI2c);
+    /// assert_eq!(p.pins.pio0_10.iocon().i2c_mode(), I2cMode::FastModePlusI2c);
+    /// ```
+    ///
+    /// [`Pin::into_output_pin`]: ../struct.Pin.html#method.into_output_pin
+    /// [`Pin::into_dynamic_pin`]: ../struct.Pin.html#method.into_dynamic_pin
+    /// [`I2cMode::Gpio`]: enum.I2cMode.html#variant.Gpio
+    pub fn set_i2c_mode(&mut self, mode: I2cMode) {
+        self.modify(|r| (r & !I2CMODE_MASK) | (mode as u32) << I2CMODE_SHIFT)
+    }
+
+    /// Returns the I2C mode of a true open-drain pin
+    ///
+    /// See [`set_i2c_mode`].
+    ///
+    /// [`set_i2c_mode`]: #method.set_i2c_mode
+    pub fn i2c_mode(&self) -> I2cMode {
+        match (self.read() & I2CMODE_MASK) >> I2CMODE_SHIFT {
+            0 => I2cMode::StandardI2c,
+            1 => I2cMode::Gpio,
+            // 3 is reserved and never written by the HAL.
+            _ => I2cMode::FastModePlusI2c,
+        }
+    }
+}
+
+/// The pull resistor configuration of a pin
+///
+/// Used by [`IoconPin::set_pull`] and [`GpioPin::set_pull`]. Each variant
+/// corresponds to one of the four values of the MODE field in the pin's IOCON
+/// register.
+///
+/// The pull resistors are active regardless of the pin's function or
+/// direction. For a pin that drives its output push-pull, they have no
+/// visible effect. They do matter for inputs, and for outputs in open-drain
+/// mode, while they're not driving the pin low.
+///
+/// # Example
+///
+/// ```
+/// use lpc8xx_hal::pins::iocon::Pull;
+///
+/// assert_eq!(Pull::None.bits(), 0b00);
+/// assert_eq!(Pull::Down.bits(), 0b01);
+/// assert_eq!(Pull::Up.bits(), 0b10);
+/// assert_eq!(Pull::Repeater.bits(), 0b11);
+///
+/// for &pull in &[Pull::None, Pull::Down, Pull::Up, Pull::Repeater] {
+///     assert_eq!(Pull::from_bits(pull.bits()), pull);
+/// }
+/// ```
+///
+/// [`IoconPin::set_pull`]: struct.IoconPin.html#method.set_pull
+/// [`GpioPin::set_pull`]: ../../gpio/struct.GpioPin.html#method.set_pull
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Pull {
+    /// No pull resistor
+    None = 0,
+
+    /// Pull-down resistor enabled
+    Down = 1,
+
+    /// Pull-up resistor enabled
+    Up = 2,
+
+    /// Repeater mode
+    ///
+    /// Retains the last level the pin was driven to, by enabling the pull-up
+    /// resistor, if the pin is high, and the pull-down resistor, if it is low.
+    ///
+    /// Which resistor is enabled depends on the level that the pin input
+    /// currently reads, not on the pin's configuration. Once whoever drove the
+    /// line stops driving it, the line is weakly held at its last level,
+    /// instead of floating. This is useful for bus lines that can be left
+    /// undriven (like a tri-stated data bus), as it prevents a floating input
+    /// from toggling randomly and wasting power.
+    ///
+    /// The pin can still be driven to the other level by anything that is
+    /// stronger than the pull resistor, after which the repeater holds that
+    /// level instead. This means repeater mode can't be used to define a
+    /// default level for a line, like a pull-up or pull-down can.
+    Repeater = 3,
+}
+
+impl Pull {
+    /// Create a `Pull` from the value of the MODE field
+    ///
+    /// Only the lowest two bits of `bits` are considered.
+    pub fn from_bits(bits: u32) -> Self {
+        match bits & 0x3 {
+            0 => Pull::None,
+            1 => Pull::Down,
+            2 => Pull::Up,
+            _ => Pull::Repeater,
+        }
+    }
+
+    /// Returns the value of the MODE field for this configuration
+    pub fn bits(self) -> u32 {
+        self as u32
+    }
+}
+
+/// The I2C mode of a true open-drain pin
+///
+/// Used by [`IoconPin::set_i2c_mode`], which is only available for PIO0_10
+/// and PIO0_11.
+///
+/// [`IoconPin::set_i2c_mode`]: struct.IoconPin.html#method.set_i2c_mode
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum I2cMode {
+    /// Standard-mode/fast-mode I2C
+    ///
+    /// This is the default after reset.
+    StandardI2c = 0,
+
+    /// Standard GPIO functionality
+    ///
+    /// The pin still can't drive a high level, so an external pull-up
+    /// resistor is required, if it is used as an output.
+    Gpio = 1,
+
+    /// Fast-mode Plus I2C
+    FastModePlusI2c = 2,
+}
+
+/// The input glitch filter configuration of a pin
+///
+/// Used by [`IoconPin::set_filter`]. Input pulses shorter than the selected
+/// number of filter clock cycles are rejected.
+///
+/// [`IoconPin::set_filter`]: struct.IoconPin.html#method.set_filter
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Filter {
+    /// The input filter is bypassed
+    Bypass = 0,
+
+    /// Reject pulses shorter than 1 filter clock cycle
+    Cycles1 = 1,
+
+    /// Reject pulses shorter than 2 filter clock cycles
+    Cycles2 = 2,
+
+    /// Reject pulses shorter than 3 filter clock cycles
+    Cycles3 = 3,
+}
+
+/// Select GPIO mode, if `T` is a true open-drain pin
+///
+/// Called when a pin is transitioned into the GPIO state, regardless of its
+/// direction, as an input pin can later be switched to output. Does nothing
+/// for all other pins.
+///
+/// The caller must own pin `T`. `syscon` and `iocon` are the SYSCON and IOCON
+/// register blocks. Only the IOCON clock bit and the IOCON register of pin `T`
+/// are touched.
+pub(crate) fn select_gpio_mode<T>(
+    syscon: &pac::syscon::RegisterBlock,
+    iocon: *mut pac::iocon::RegisterBlock,
+) where
+    T: Trait,
+{
+    if !T::TRUE_OPEN_DRAIN {
+        return;
+    }
+
+    interrupt::free(|_| {
+        select_gpio_mode_in(syscon, iocon, IoconPin::<T>::OFFSET)
+    });
+}
+
+/// Select GPIO mode for the true open-drain pin with the given register offset
+///
+/// The IOCON clock is enabled while the register is written, as the write
+/// would be ignored otherwise. It is restored to its previous state afterwards,
+/// so this doesn't interfere with the clock configuration done through
+/// `syscon::Handle`.
+///
+/// Must be called with interrupts disabled.
+fn select_gpio_mode_in(
+    syscon: &pac::syscon::RegisterBlock,
+    iocon: *mut pac::iocon::RegisterBlock,
+    offset: usize,
+) {
+    #[cfg(feature = "82x")]
+    let sysahbclkctrl = &syscon.sysahbclkctrl;
+    #[cfg(feature = "845")]
+    let sysahbclkctrl = &syscon.sysahbclkctrl0;
+
+    let clock_enabled = sysahbclkctrl.read().iocon().bit_is_set();
+    if !clock_enabled {
+        sysahbclkctrl.modify(|_, w| w.iocon().set_bit());
+    }
+
+    let register = register(iocon, offset);
+
+    // Sound, as the caller makes sure that `offset` identifies the register of
+    // a pin it owns.
+    unsafe {
+        let value = ptr::read_volatile(register);
+        ptr::write_volatile(
+            register,
+            (value & !I2CMODE_MASK) | (I2cMode::Gpio as u32) << I2CMODE_SHIFT,
+        );
+    }
+
+    if !clock_enabled {
+        sysahbclkctrl.modify(|_, w| w.iocon().clear_bit());
+    }
+}
+
+/// Returns a pointer to the register at `offset` within `iocon`
+fn register(iocon: *mut pac::iocon::RegisterBlock, offset: usize) -> *mut u32 {
+    (iocon as *mut u8).wrapping_add(offset) as *mut u32
+}
+
+/// The offset of a pin's register in the IOCON register block
+///
+/// The layout is the same on LPC82x and LPC84x, except that LPC84x has
+/// additional registers for the pins that don't exist on LPC82x.
+const fn offset(port: usize, id: u8) -> usize {
+    match (port, id) {
+        (0, 17) => 0x00,
+        (0, 13) => 0x04,
+        (0, 12) => 0x08,
+        (0, 5) => 0x0c,
+        (0, 4) => 0x10,
+        (0, 3) => 0x14,
+        (0, 2) => 0x18,
+        (0, 11) => 0x1c,
+        (0, 10) => 0x20,
+        (0, 16) => 0x24,
+        (0, 15) => 0x28,
+        (0, 1) => 0x2c,
+        (0, 9) => 0x34,
+        (0, 8) => 0x38,
+        (0, 7) => 0x3c,
+        (0, 6) => 0x40,
+        (0, 0) => 0x44,
+        (0, 14) => 0x48,
+        (0, 28) => 0x50,
+        (0, 27) => 0x54,
+        (0, 26) => 0x58,
+        (0, 25) => 0x5c,
+        (0, 24) => 0x60,
+        (0, 23) => 0x64,
+        (0, 22) => 0x68,
+        (0, 21) => 0x6c,
+        (0, 20) => 0x70,
+        (0, 19) => 0x74,
+        (0, 18) => 0x78,
+        (1, 8) => 0x7c,
+        (1, 9) => 0x80,
+        (1, 12) => 0x84,
+        (1, 13) => 0x88,
+        (0, 31) => 0x8c,
+        (1, 0) => 0x90,
+        (1, 1) => 0x94,
+        (1, 2) => 0x98,
+        (1, 14) => 0x9c,
+        (1, 15) => 0xa0,
+        (1, 3) => 0xa4,
+        (1, 4) => 0xa8,
+        (1, 5) => 0xac,
+        (1, 16) => 0xb0,
+        (1, 17) => 0xb4,
+        (1, 6) => 0xb8,
+        (1, 18) => 0xbc,
+        (1, 19) => 0xc0,
+        (1, 7) => 0xc4,
+        (0, 29) => 0xc8,
+        (0, 30) => 0xcc,
+        (1, 20) => 0xd0,
+        (1, 21) => 0xd4,
+        (1, 11) => 0xd8,
+        (1, 10) => 0xdc,
+        _ => panic!("pin has no IOCON register"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use crate::{
+        pac,
+        pins::{Trait as _, PIO0_10, PIO0_11, PIO0_12},
+    };
+
+    use super::{select_gpio_mode_in, Filter, IoconPin, Pull};
+
+    const MODE_UP: u32 = 0x2 << 3;
+    const INV: u32 = 0x1 << 6;
+    const OD: u32 = 0x1 << 10;
+    const I2CMODE_GPIO: u32 = 0x1 << 8;
+    const IOCON_CLOCK: u32 = 0x1 << 18;
+
+    fn syscon() -> pac::syscon::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        unsafe { mem::zeroed() }
+    }
+
+    fn iocon() -> pac::iocon::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        unsafe { mem::zeroed() }
+    }
+
+    #[cfg(feature = "82x")]
+    fn sysahbclkctrl(syscon: &pac::syscon::RegisterBlock) -> u32 {
+        syscon.sysahbclkctrl.read().bits()
+    }
+
+    #[cfg(feature = "845")]
+    fn sysahbclkctrl(syscon: &pac::syscon::RegisterBlock) -> u32 {
+        syscon.sysahbclkctrl0.read().bits()
+    }
+
+    #[test]
+    fn only_true_open_drain_pins_are_flagged() {
+        assert!(PIO0_10::TRUE_OPEN_DRAIN);
+        assert!(PIO0_11::TRUE_OPEN_DRAIN);
+        assert!(!PIO0_12::TRUE_OPEN_DRAIN);
+    }
+
+    #[test]
+    fn select_gpio_mode_sets_i2cmode_of_pin() {
+        let syscon = syscon();
+
+        let mut iocon = iocon();
+        // Standard I2C mode, input inverted
+        iocon.pio0_10.write(|w| unsafe { w.bits(0x1 << 6) });
+
+        select_gpio_mode_in(&syscon, &mut iocon, IoconPin::<PIO0_10>::OFFSET);
+        assert_eq!(iocon.pio0_10.read().bits(), I2CMODE_GPIO | 0x1 << 6);
+        assert_eq!(iocon.pio0_11.read().bits(), 0);
+
+        select_gpio_mode_in(&syscon, &mut iocon, IoconPin::<PIO0_11>::OFFSET);
+        assert_eq!(iocon.pio0_11.read().bits(), I2CMODE_GPIO);
+    }
+
+    #[test]
+    fn select_gpio_mode_replaces_fast_mode_plus() {
+        let syscon = syscon();
+
+        let mut iocon = iocon();
+        iocon.pio0_11.write(|w| unsafe { w.bits(0x2 << 8) });
+
+        select_gpio_mode_in(&syscon, &mut iocon, IoconPin::<PIO0_11>::OFFSET);
+        assert_eq!(iocon.pio0_11.read().bits(), I2CMODE_GPIO);
+    }
+
+    #[test]
+    fn select_gpio_mode_leaves_iocon_clock_disabled() {
+        let syscon = syscon();
+        let mut iocon = iocon();
+
+        select_gpio_mode_in(&syscon, &mut iocon, IoconPin::<PIO0_10>::OFFSET);
+        assert_eq!(sysahbclkctrl(&syscon), 0);
+    }
+
+    #[test]
+    fn select_gpio_mode_leaves_iocon_clock_enabled() {
+        let syscon = syscon();
+        let mut iocon = iocon();
+
+        #[cfg(feature = "82x")]
+        syscon
+            .sysahbclkctrl
+            .write(|w| unsafe { w.bits(IOCON_CLOCK) });
+        #[cfg(feature = "845")]
+        syscon
+            .sysahbclkctrl0
+            .write(|w| unsafe { w.bits(IOCON_CLOCK) });
+
+        select_gpio_mode_in(&syscon, &mut iocon, IoconPin::<PIO0_10>::OFFSET);
+        assert_eq!(sysahbclkctrl(&syscon), IOCON_CLOCK);
+    }
+
+    #[test]
+    fn set_input_inverted_writes_inv() {
+        let mut iocon = iocon();
+        iocon.pio0_12.write(|w| unsafe { w.bits(MODE_UP) });
+
+        let mut pin = IoconPin::<PIO0_12>::at(&mut iocon);
+        pin.set_input_inverted(true);
+        assert!(pin.is_input_inverted());
+        assert_eq!(iocon.pio0_12.read().bits(), MODE_UP | INV);
+
+        let mut pin = IoconPin::<PIO0_12>::at(&mut iocon);
+        pin.set_input_inverted(false);
+        assert!(!pin.is_input_inverted());
+        assert_eq!(iocon.pio0_12.read().bits(), MODE_UP);
+    }
+
+    #[test]
+    fn set_pull_writes_mode() {
+        let mut iocon = iocon();
+        iocon.pio0_12.write(|w| unsafe { w.bits(INV | OD) });
+
+        for &pull in &[Pull::Down, Pull::Up, Pull::Repeater, Pull::None] {
+            let mut pin = IoconPin::<PIO0_12>::at(&mut iocon);
+            pin.set_pull(pull);
+            assert_eq!(pin.pull(), pull);
+            assert_eq!(
+                iocon.pio0_12.read().bits(),
+                INV | OD | (pull as u32) << 3
+            );
+        }
+    }
+
+    #[test]
+    fn set_open_drain_writes_od() {
+        let mut iocon = iocon();
+        iocon.pio0_12.write(|w| unsafe { w.bits(MODE_UP) });
+
+        IoconPin::<PIO0_12>::at(&mut iocon).set_open_drain(true);
+        assert_eq!(iocon.pio0_12.read().bits(), MODE_UP | OD);
+
+        IoconPin::<PIO0_12>::at(&mut iocon).set_open_drain(false);
+        assert_eq!(iocon.pio0_12.read().bits(), MODE_UP);
+    }
+
+    #[test]
+    fn set_filter_writes_s_mode_and_clk_div() {
+        let mut iocon = iocon();
+        iocon.pio0_12.write(|w| unsafe { w.bits(MODE_UP | OD) });
+
+        IoconPin::<PIO0_12>::at(&mut iocon).set_filter(Filter::Cycles3, 6);
+        assert_eq!(
+            iocon.pio0_12.read().bits(),
+            MODE_UP | OD | 0x3 << 11 | 0x6 << 13
+        );
+
+        IoconPin::<PIO0_12>::at(&mut iocon).set_filter(Filter::Cycles1, 2);
+        assert_eq!(
+            iocon.pio0_12.read().bits(),
+            MODE_UP | OD | 0x1 << 11 | 0x2 << 13
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_filter_rejects_missing_clock_divider() {
+        let mut iocon = iocon();
+
+        IoconPin::<PIO0_12>::at(&mut iocon).set_filter(Filter::Cycles1, 7);
+    }
+}
@@ -9,8 +9,13 @@ mod gen;
 mod pin;
 mod traits;
 
+pub mod iocon;
 pub mod state;
 
 pub use self::{
-    gen::*, pin::DynamicPinDirection, pin::Pin, state::State, traits::Trait,
+    gen::*,
+    pin::DynamicPinDirection,
+    pin::Pin,
+    state::State,
+    traits::{Trait, TrueOpenDrain},
 };
@@ -1,10 +1,11 @@
 use crate::{
-    gpio::{direction, GpioPin, Level},
+    gpio::{direction, GpioPin, Level, OutputInitPolicy},
     init_state,
 };
 
 use super::{
     gen::Token,
+    iocon::IoconPin,
     state::{self, State},
     traits::Trait,
 };
@@ -79,9 +80,6 @@ use super::{
 /// #     &mut swm_handle,
 /// # );
 /// #
-/// # #[cfg(feature = "82x")]
-/// # let gpio = p.GPIO;
-/// # #[cfg(feature = "845")]
 /// # let gpio = p.GPIO.enable(&mut syscon.handle);
 ///
 /// let (clkout, pio0_12) = clkout.unassign(pio0_12, &mut swm_handle);
@@ -176,7 +174,20 @@ pub struct Pin<T: Trait, S: State> {
 }
 
 /// Marks the current directin of a Dynamic Pin.
-#[derive(Debug, PartialEq, Copy, Clone)]
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::pins::DynamicPinDirection;
+///
+/// let direction = DynamicPinDirection::Input;
+///
+/// assert!(direction.is_input());
+/// assert_eq!(direction.opposite(), DynamicPinDirection::Output);
+/// assert_eq!(format!("{:?}", direction), "Input");
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DynamicPinDirection {
     /// Pin is currently Input
     Input,
@@ -185,6 +196,67 @@ pub enum DynamicPinDirection {
     Output,
 }
 
+impl DynamicPinDirection {
+    /// Indicates whether this is the input direction
+    pub fn is_input(self) -> bool {
+        self == DynamicPinDirection::Input
+    }
+
+    /// Indicates whether this is the output direction
+    pub fn is_output(self) -> bool {
+        self == DynamicPinDirection::Output
+    }
+
+    /// Returns the opposite direction
+    pub fn opposite(self) -> Self {
+        match self {
+            DynamicPinDirection::Input => DynamicPinDirection::Output,
+            DynamicPinDirection::Output => DynamicPinDirection::Input,
+        }
+    }
+}
+
+impl<T, S> Pin<T, S>
+where
+    T: Trait,
+    S: State,
+{
+    /// Access the IOCON register of this pin
+    ///
+    /// Returns an [`IoconPin`], which can be used to configure the pull
+    /// resistors, open-drain mode, and input filter of the pin. This method is
+    /// available in every state, as the IOCON settings are independent of the
+    /// pin's function.
+    ///
+    /// # Limitations
+    ///
+    /// The IOCON clock must be enabled, before the returned [`IoconPin`] is
+    /// used. This can be done using `syscon.handle.enable_clock(&p.IOCON)`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     Peripherals,
+    ///     pins::iocon::{Filter, Pull},
+    /// };
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// syscon.handle.enable_clock(&p.IOCON);
+    ///
+    /// let mut iocon = p.pins.pio0_4.iocon();
+    /// iocon.set_pull(Pull::Up);
+    /// iocon.set_filter(Filter::Cycles3, 0);
+    /// ```
+    ///
+    /// [`IoconPin`]: iocon/struct.IoconPin.html
+    pub fn iocon(&mut self) -> IoconPin<'_, T> {
+        IoconPin::new()
+    }
+}
+
 impl<T> Pin<T, state::Unused>
 where
     T: Trait,
@@ -203,6 +275,10 @@ where
     /// that the GPIO peripheral is enabled, and stays enabled while the pin is
     /// in the GPIO mode.
     ///
+    /// Like [`into_output_pin`], this method selects GPIO mode in IOCON for
+    /// the true open-drain pins, PIO0_10 and PIO0_11, so they can be switched
+    /// to output later.
+    ///
     /// # Example
     ///
     /// ``` no_run
@@ -214,9 +290,6 @@ where
     /// let mut syscon = p.SYSCON.split();
     /// let swm = p.SWM.split();
     ///
-    /// #[cfg(feature = "82x")]
-    /// let gpio = p.GPIO;
-    /// #[cfg(feature = "845")]
     /// let gpio = p.GPIO.enable(&mut syscon.handle);
     ///
     /// // Transition pin into GPIO state, then set it to output
@@ -234,11 +307,12 @@ where
     /// [State Management]: #state-management
     /// [`GpioPin`]: ../gpio/struct.GpioPin.html
     /// [`GPIO`]: ../gpio/struct.GPIO.html
+    /// [`into_output_pin`]: #method.into_output_pin
     pub fn into_input_pin(
         self,
         token: Token<T, init_state::Enabled>,
     ) -> GpioPin<T, direction::Input> {
-        GpioPin::new(token, ())
+        GpioPin::new(self.ty, token, ())
     }
 
     /// Transition pin to GPIO output mode
@@ -255,6 +329,18 @@ where
     /// that the GPIO peripheral is enabled, and stays enabled while the pin is
     /// in the GPIO mode.
     ///
+    /// # True open-drain pins
+    ///
+    /// PIO0_10 and PIO0_11, the pins of the I2C0 fixed functions, are true
+    /// open-drain pins. They can only pull the line low. Setting the output
+    /// high releases the line, which then needs an external pull-up resistor
+    /// to actually go high. [`GpioPin::is_set_high`] only reflects the
+    /// commanded level, so use [`GpioPin::is_high`] to check the level on the
+    /// line.
+    ///
+    /// After reset, these pins are configured for I2C in IOCON. This method
+    /// selects [`I2cMode::Gpio`] for them (see [`IoconPin::set_i2c_mode`]).
+    ///
     /// # Example
     ///
     /// ``` no_run
@@ -269,9 +355,6 @@ where
     /// let mut syscon = p.SYSCON.split();
     /// let swm = p.SWM.split();
     ///
-    /// #[cfg(feature = "82x")]
-    /// let gpio = p.GPIO;
-    /// #[cfg(feature = "845")]
     /// let gpio = p.GPIO.enable(&mut syscon.handle);
     ///
     /// // Transition pin into GPIO state, then set it to output
@@ -288,12 +371,16 @@ where
     /// [State Management]: #state-management
     /// [`GpioPin`]: ../gpio/struct.GpioPin.html
     /// [`GPIO`]: ../gpio/struct.GPIO.html
+    /// [`GpioPin::is_set_high`]: ../gpio/struct.GpioPin.html#method.is_set_high
+    /// [`GpioPin::is_high`]: ../gpio/struct.GpioPin.html#method.is_high
+    /// [`I2cMode::Gpio`]: iocon/enum.I2cMode.html#variant.Gpio
+    /// [`IoconPin::set_i2c_mode`]: iocon/struct.IoconPin.html#method.set_i2c_mode
     pub fn into_output_pin(
         self,
         token: Token<T, init_state::Enabled>,
         initial: Level,
     ) -> GpioPin<T, direction::Output> {
-        GpioPin::new(token, initial)
+        GpioPin::new(self.ty, token, (initial, OutputInitPolicy::LevelThenDir))
     }
 
     /// Transition pin to Dynamic mode, i.e. GPIO direction switchable at runtime
@@ -310,6 +397,10 @@ where
     /// that the GPIO peripheral is enabled, and stays enabled while the pin is
     /// in the GPIO mode.
     ///
+    /// PIO0_10 and PIO0_11 are true open-drain pins, which can only pull the
+    /// line low. Like [`into_output_pin`], this method selects GPIO mode for
+    /// them in IOCON. Please refer to [`into_output_pin`] for details.
+    ///
     /// # Example
     ///
     /// ``` no_run
@@ -325,9 +416,6 @@ where
     /// let mut syscon = p.SYSCON.split();
     /// let swm = p.SWM.split();
     ///
-    /// #[cfg(feature = "82x")]
-    /// let gpio = p.GPIO;
-    /// #[cfg(feature = "845")]
     /// let gpio = p.GPIO.enable(&mut syscon.handle);
     ///
     /// // Transition pin into GPIO state, then set it to output
@@ -356,13 +444,14 @@ where
     /// [State Management]: #state-management
     /// [`GpioPin`]: ../gpio/struct.GpioPin.html
     /// [`GPIO`]: ../gpio/struct.GPIO.html
+    /// [`into_output_pin`]: #method.into_output_pin
     pub fn into_dynamic_pin(
         self,
         token: Token<T, init_state::Enabled>,
         level: Level,
         direction: DynamicPinDirection,
     ) -> GpioPin<T, direction::Dynamic> {
-        GpioPin::new(token, (level, direction))
+        GpioPin::new(self.ty, token, (level, direction))
     }
 
     /// Transition pin to SWM mode
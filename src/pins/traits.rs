@@ -1,3 +1,5 @@
+use super::{PIO0_10, PIO0_11};
+
 /// Implemented by types that identify pins
 ///
 /// This trait is an internal implementation detail and should neither be
@@ -34,4 +36,29 @@ pub trait Trait {
     /// [`PIO0_1`]: struct.PIO0_1.html
     /// [`PIO0_2`]: struct.PIO0_2.html
     const MASK: u32;
+
+    /// Whether this is one of the true open-drain pins
+    ///
+    /// This is `true` for exactly the pins that implement [`TrueOpenDrain`].
+    ///
+    /// [`TrueOpenDrain`]: trait.TrueOpenDrain.html
+    const TRUE_OPEN_DRAIN: bool =
+        Self::PORT == 0 && (Self::ID == 10 || Self::ID == 11);
 }
+
+/// Implemented by the true open-drain pins
+///
+/// These are PIO0_10 and PIO0_11, which are also used by the fixed functions
+/// of I2C0. Unlike all other pins, they can't drive a high level, only pull
+/// the line low. They have their own IOCON settings, which can be accessed
+/// using [`IoconPin::set_i2c_mode`].
+///
+/// This trait is an internal implementation detail and should neither be
+/// implemented nor used outside of LPC8xx HAL. Any changes to this trait won't
+/// be considered breaking changes.
+///
+/// [`IoconPin::set_i2c_mode`]: iocon/struct.IoconPin.html#method.set_i2c_mode
+pub trait TrueOpenDrain: Trait {}
+
+impl TrueOpenDrain for PIO0_10 {}
+impl TrueOpenDrain for PIO0_11 {}
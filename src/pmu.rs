@@ -25,13 +25,66 @@
 //! pmu.handle.enter_sleep_mode(&mut cp.SCB);
 //! ```
 //!
+//! Use the self-wake-up timer to wake up from deep power-down mode:
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     Peripherals,
+//!     clock::Ticks,
+//!     pac::CorePeripherals,
+//! };
+//!
+//! let mut cp = CorePeripherals::take().unwrap();
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut pmu = p.PMU.split();
+//! let mut syscon = p.SYSCON.split();
+//! let mut wkt = p.WKT.enable(&mut syscon.handle);
+//!
+//! if pmu.handle.deep_power_down_flag() {
+//!     // We've woken up from deep power-down mode.
+//!     pmu.handle.clear_deep_power_down_flag();
+//! }
+//!
+//! let clock = pmu.low_power_clock.enable(&mut pmu.handle);
+//!
+//! // Wake up after 5 seconds
+//! let interval = Ticks { value: 50_000, clock: &clock };
+//! pmu.handle.arm_wakeup_timer(&mut wkt, interval);
+//!
+//! // This will never return, as waking up resets the microcontroller.
+//! pmu.handle.enter_deep_power_down_mode(&mut cp.SCB);
+//! ```
+//!
+//! Retain a value across a deep power-down wake-up:
+//!
+//! ``` no_run
+//! use lpc8xx_hal::Peripherals;
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut pmu = p.PMU.split();
+//!
+//! let boot_count = pmu.handle.gp_read(0);
+//! pmu.handle.gp_write(0, boot_count + 1);
+//!
+//! assert_eq!(pmu.handle.gp_read(0), boot_count + 1);
+//! ```
+//!
 //! Please refer to the [examples in the repository] for more example code.
 //!
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 use cortex_m::{asm, interrupt};
+use embedded_hal::prelude::*;
 
-use crate::{clock, init_state, pac};
+use crate::{
+    clock::{self, Ticks},
+    init_state, pac,
+    syscon::clocks::measured_hz,
+    timer,
+    wkt::WKT,
+};
 
 /// Entry point to the PMU API
 ///
@@ -116,6 +169,75 @@ pub struct Handle {
 }
 
 impl Handle {
+    /// Sleep until an interrupt occurs
+    ///
+    /// Makes sure that regular sleep mode (not deep-sleep mode) is selected,
+    /// then executes the `WFI` instruction. The core sleeps until an
+    /// NVIC-enabled interrupt occurs.
+    ///
+    /// Unlike [`enter_sleep_mode`], this method doesn't disable interrupts, so
+    /// the interrupt handler runs right after waking up, before this method
+    /// returns.
+    ///
+    /// # `WFI` vs `WFE`
+    ///
+    /// `WFI` (see this method) waits for an interrupt. `WFE` (see
+    /// [`wait_for_event`]) waits for an event, which includes interrupts, but
+    /// also other events, like the `SEV` instruction or the RXEV signal from
+    /// peripherals. `WFE` returns immediately, if an event has occurred since
+    /// the last `WFE`, which makes it suitable for loops that check a
+    /// condition before going to sleep, without missing events that occur in
+    /// between. Use [`wait_for_interrupt`] for the simple case of an event
+    /// loop that is driven by interrupts.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{pac::CorePeripherals, Peripherals};
+    ///
+    /// let mut cp = CorePeripherals::take().unwrap();
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut pmu = p.PMU.split();
+    ///
+    /// loop {
+    ///     // Do nothing until something happens. Interrupt handlers do the
+    ///     // actual work.
+    ///     pmu.handle.wait_for_interrupt(&mut cp.SCB);
+    /// }
+    /// ```
+    ///
+    /// [`enter_sleep_mode`]: #method.enter_sleep_mode
+    /// [`wait_for_event`]: #method.wait_for_event
+    /// [`wait_for_interrupt`]: #method.wait_for_interrupt
+    pub fn wait_for_interrupt(&mut self, scb: &mut pac::SCB) {
+        self.select_sleep_mode(scb);
+        asm::wfi();
+    }
+
+    /// Sleep until an event occurs
+    ///
+    /// Makes sure that regular sleep mode (not deep-sleep mode) is selected,
+    /// then executes the `WFE` instruction. See [`wait_for_interrupt`] for an
+    /// explanation of the difference between `WFI` and `WFE`.
+    ///
+    /// [`wait_for_interrupt`]: #method.wait_for_interrupt
+    pub fn wait_for_event(&mut self, scb: &mut pac::SCB) {
+        self.select_sleep_mode(scb);
+        asm::wfe();
+    }
+
+    fn select_sleep_mode(&mut self, scb: &mut pac::SCB) {
+        // Default power mode indicates active or sleep mode.
+        self.pmu.pcon.modify(|_, w| w.pm().default());
+
+        // A previous call to any of the deep-sleep methods might have left the
+        // SLEEPDEEP bit set. See user manual, section 6.7.4.2.
+        scb.clear_sleepdeep();
+
+        asm::dsb();
+    }
+
     /// Enter sleep mode
     ///
     /// The microcontroller will wake up from sleep mode, if an NVIC-enabled
@@ -146,7 +268,10 @@ impl Handle {
     ///
     /// If you intend to wake up from this mode again, you need to configure the
     /// STARTERP0 and STARTERP1 registers of the SYSCON appropriately. See user
-    /// manual, section 6.5.1.
+    /// manual, section 6.5.1. Most clocks are stopped in this mode, which
+    /// limits the peripherals that can wake up the microcontroller. Use
+    /// [`syscon::Handle::configure_deep_sleep`] to keep the watchdog oscillator
+    /// or brown-out detector running.
     ///
     /// # Safety
     ///
@@ -159,6 +284,8 @@ impl Handle {
     /// Please make sure that the peripheral states configured in PDAWAKECFG
     /// match the peripheral states as tracked by the API before calling this
     /// method.
+    ///
+    /// [`syscon::Handle::configure_deep_sleep`]: ../syscon/struct.Handle.html#method.configure_deep_sleep
     pub unsafe fn enter_deep_sleep_mode(&mut self, scb: &mut pac::SCB) {
         interrupt::free(|_| {
             self.pmu.pcon.modify(|_, w| w.pm().deep_sleep_mode());
@@ -209,8 +336,187 @@ impl Handle {
             asm::wfi();
         })
     }
+
+    /// Enter deep power-down mode
+    ///
+    /// The microcontroller will wake up from deep power-down mode, if the
+    /// WAKEUP pin is pulled low (unless it has been disabled using
+    /// [`disable_wakeup_pin`]) or if the self-wake-up timer fires (if it has
+    /// been armed using [`arm_wakeup_timer`]). See user manual, section
+    /// 6.7.7.3.
+    ///
+    /// Waking up from deep power-down mode resets the microcontroller. This
+    /// method will therefore never return. After the reset,
+    /// [`deep_power_down_flag`] can be used to find out whether the
+    /// microcontroller has been in deep power-down mode. Please note that the
+    /// reset cause returned by [`syscon::Handle::reset_cause`] can't be relied
+    /// upon for this, as there is no reset cause flag dedicated to waking up
+    /// from deep power-down mode.
+    ///
+    /// The contents of RAM are lost in deep power-down mode. Data can be
+    /// retained in the general-purpose registers of the PMU.
+    ///
+    /// # Limitations
+    ///
+    /// If the WAKEUP pin is disabled and the self-wake-up timer has not been
+    /// armed, the microcontroller will stay in deep power-down mode, until the
+    /// next power-on reset.
+    ///
+    /// [`disable_wakeup_pin`]: #method.disable_wakeup_pin
+    /// [`arm_wakeup_timer`]: #method.arm_wakeup_timer
+    /// [`deep_power_down_flag`]: #method.deep_power_down_flag
+    /// [`syscon::Handle::reset_cause`]: ../syscon/struct.Handle.html#method.reset_cause
+    pub fn enter_deep_power_down_mode(&mut self, scb: &mut pac::SCB) -> ! {
+        interrupt::free(|_| {
+            self.pmu.pcon.modify(|_, w| w.pm().deep_power_down_mode());
+
+            // The SLEEPDEEP bit must be set for entering deep power-down mode.
+            // See user manual, section 6.7.7.2.
+            scb.set_sleepdeep();
+
+            asm::dsb();
+            asm::wfi();
+        });
+
+        // Waking up from deep power-down mode causes a reset, so we should
+        // never get here.
+        loop {
+            asm::wfi();
+        }
+    }
+
+    /// Enable the WAKEUP pin as a wake-up source for deep power-down mode
+    ///
+    /// The WAKEUP pin is enabled by default. The microcontroller wakes up when
+    /// the pin is pulled low. The hardware doesn't support an active-high
+    /// WAKEUP pin.
+    ///
+    /// If `hysteresis` is `true`, the hysteresis of the WAKEUP pin's input
+    /// buffer is enabled.
+    pub fn enable_wakeup_pin(&mut self, hysteresis: bool) {
+        self.pmu.dpdctrl.modify(|_, w| {
+            w.wakeuphys().bit(hysteresis);
+            w.wakepad_disable().enabled()
+        });
+    }
+
+    /// Disable the WAKEUP pin as a wake-up source for deep power-down mode
+    ///
+    /// This frees the pin to be used for other purposes, while the
+    /// microcontroller is in deep power-down mode. According to the user
+    /// manual, the WAKEUP pin should only be disabled, if the self-wake-up
+    /// timer has been armed using [`arm_wakeup_timer`].
+    ///
+    /// [`arm_wakeup_timer`]: #method.arm_wakeup_timer
+    pub fn disable_wakeup_pin(&mut self) {
+        self.pmu
+            .dpdctrl
+            .modify(|_, w| w.wakepad_disable().disabled());
+    }
+
+    /// Arm the self-wake-up timer to wake up from deep power-down mode
+    ///
+    /// Selects the low-power clock as the clock source of the self-wake-up
+    /// timer (WKT), keeps the low-power oscillator running in deep power-down
+    /// mode, and starts the timer with the given interval. The
+    /// microcontroller will wake up from deep power-down mode (and be reset),
+    /// once the timer runs out.
+    ///
+    /// The low-power clock runs at a nominal 10 kHz, so one tick corresponds to
+    /// 100 µs.
+    ///
+    /// The timer starts counting immediately, so this method should be called
+    /// right before [`enter_deep_power_down_mode`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `interval` is zero. The timer would never run out, and the
+    /// microcontroller would never wake up.
+    ///
+    /// [`enter_deep_power_down_mode`]: #method.enter_deep_power_down_mode
+    pub fn arm_wakeup_timer<'clock>(
+        &mut self,
+        wkt: &mut WKT,
+        interval: Ticks<'clock, LowPowerClock<init_state::Enabled>>,
+    ) {
+        assert!(interval.value > 0);
+
+        self.pmu.dpdctrl.modify(|_, w| w.lposcdpden().enabled());
+
+        wkt.select_clock::<LowPowerClock>();
+        wkt.start(interval.value);
+    }
+
+    /// Disarm the self-wake-up timer as a wake-up source
+    ///
+    /// Stops the low-power oscillator from running in deep power-down mode. The
+    /// self-wake-up timer itself is not stopped.
+    pub fn disarm_wakeup_timer(&mut self) {
+        self.pmu.dpdctrl.modify(|_, w| w.lposcdpden().disabled());
+    }
+
+    /// Indicates whether the microcontroller has been in deep power-down mode
+    ///
+    /// Since waking up from deep power-down mode resets the microcontroller,
+    /// this flag can be checked after startup to find out whether the reset
+    /// was caused by a wake-up from deep power-down mode. The flag is not
+    /// cleared automatically. Use [`clear_deep_power_down_flag`] for that.
+    ///
+    /// [`clear_deep_power_down_flag`]: #method.clear_deep_power_down_flag
+    pub fn deep_power_down_flag(&self) -> bool {
+        self.pmu.pcon.read().dpdflag().bit_is_set()
+    }
+
+    /// Clear the deep power-down flag
+    ///
+    /// See [`deep_power_down_flag`].
+    ///
+    /// [`deep_power_down_flag`]: #method.deep_power_down_flag
+    pub fn clear_deep_power_down_flag(&mut self) {
+        clear_deep_power_down_flag(&self.pmu);
+    }
+
+    /// Read from a general-purpose register
+    ///
+    /// The PMU has [`GP_REGISTERS`] general-purpose registers, that retain
+    /// their contents in deep power-down mode and across resets. They are only
+    /// reset, if all power is removed from the microcontroller. This makes
+    /// them useful for storing data that needs to survive a deep power-down
+    /// wake-up, like a boot reason or a counter. See user manual, section
+    /// 6.6.2.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is not smaller than [`GP_REGISTERS`].
+    ///
+    /// [`GP_REGISTERS`]: constant.GP_REGISTERS.html
+    pub fn gp_read(&self, index: usize) -> u32 {
+        gp_read(&self.pmu, index)
+    }
+
+    /// Write to a general-purpose register
+    ///
+    /// See [`gp_read`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is not smaller than [`GP_REGISTERS`].
+    ///
+    /// [`gp_read`]: #method.gp_read
+    /// [`GP_REGISTERS`]: constant.GP_REGISTERS.html
+    pub fn gp_write(&mut self, index: usize, value: u32) {
+        gp_write(&self.pmu, index, value);
+    }
 }
 
+/// The number of general-purpose registers in the PMU
+///
+/// See [`Handle::gp_read`] and [`Handle::gp_write`].
+///
+/// [`Handle::gp_read`]: struct.Handle.html#method.gp_read
+/// [`Handle::gp_write`]: struct.Handle.html#method.gp_write
+pub const GP_REGISTERS: usize = 4;
+
 /// The 10 kHz low-power clock
 ///
 /// This is one of the clocks that can be used to run the self-wake-up timer
@@ -278,6 +584,110 @@ impl LowPowerClock<init_state::Enabled> {
     }
 }
 
+impl LowPowerClock<init_state::Enabled> {
+    /// Measure the actual frequency of the low-power clock
+    ///
+    /// The low-power oscillator runs at a nominal 10 kHz, but its actual
+    /// frequency can vary considerably between devices, and with temperature
+    /// and supply voltage. This method measures it against a more accurate
+    /// reference clock, like the IRC/FRO, which must be running during the
+    /// measurement.
+    ///
+    /// Runs the self-wake-up timer (WKT) from the low-power clock, while
+    /// `reference` counts down `reference_ticks` ticks of `reference_clock`.
+    /// The number of low-power clock ticks counted in that time determines the
+    /// result. The longer the reference interval, the more accurate the
+    /// result. An interval of 100 ms is a good starting point.
+    ///
+    /// The low-power oscillator might not be running yet, if it has just been
+    /// enabled. To account for that, the measurement only starts once the WKT
+    /// has started counting. The time this takes counts against the reference
+    /// interval, so the measurement might take up to twice as long.
+    ///
+    /// The returned [`Calibration`] can be used to correct tick counts for the
+    /// low-power clock, before passing them to [`Handle::arm_wakeup_timer`].
+    ///
+    /// The WKT is left halted, with the low-power clock selected. Any count
+    /// down it was running before is lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::NotRunning`], if the low-power clock didn't
+    /// start ticking within the reference interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `reference_ticks` is zero or larger than `T::MAX_TICKS`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{clock::Ticks, CorePeripherals, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    /// let mut cp = CorePeripherals::take().unwrap();
+    ///
+    /// let mut pmu    = p.PMU.split();
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut wkt    = p.WKT.enable(&mut syscon.handle);
+    ///
+    /// let clock = pmu.low_power_clock.enable(&mut pmu.handle);
+    ///
+    /// // SysTick runs at 12 MHz by default, so this is a 100 ms interval.
+    /// let calibration = clock
+    ///     .calibrate(&mut wkt, &mut cp.SYST, &syscon.iosc, 1_200_000)
+    ///     .unwrap();
+    ///
+    /// let interval = calibration.correct(Ticks::from_ms(5_000, &clock));
+    /// pmu.handle.arm_wakeup_timer(&mut wkt, interval);
+    /// ```
+    ///
+    /// [`Calibration`]: struct.Calibration.html
+    /// [`Handle::arm_wakeup_timer`]: struct.Handle.html#method.arm_wakeup_timer
+    /// [`CalibrationError::NotRunning`]: enum.CalibrationError.html#variant.NotRunning
+    pub fn calibrate<T, C>(
+        &self,
+        wkt: &mut WKT,
+        reference: &mut T,
+        reference_clock: &C,
+        reference_ticks: u32,
+    ) -> Result<Calibration, CalibrationError>
+    where
+        T: timer::Timer,
+        C: clock::Frequency,
+    {
+        wkt.select_enabled_clock(self);
+        wkt.start(u32::MAX);
+
+        // Wait for the oscillator to start up. Once the count has changed, it
+        // is running, and we're synchronized to the start of a tick.
+        reference.start(reference_ticks);
+        loop {
+            if wkt.count() != u32::MAX {
+                break;
+            }
+            if reference.wait().is_ok() {
+                wkt.clear_counter();
+                return Err(CalibrationError::NotRunning);
+            }
+        }
+
+        let start = wkt.count();
+        reference.start(reference_ticks);
+        while reference.wait().is_err() {}
+        let end = wkt.count();
+
+        reference.cancel();
+        wkt.clear_counter();
+
+        Ok(Calibration::from_measurement(
+            start - end,
+            reference_ticks,
+            reference_clock.hz(),
+        ))
+    }
+}
+
 impl<State> clock::Frequency for LowPowerClock<State> {
     fn hz(&self) -> u32 {
         10_000
@@ -285,3 +695,202 @@ impl<State> clock::Frequency for LowPowerClock<State> {
 }
 
 impl clock::Enabled for LowPowerClock<init_state::Enabled> {}
+
+/// The measured frequency of the low-power clock
+///
+/// Can be acquired by measuring the low-power clock using
+/// [`LowPowerClock::calibrate`]. If the measurement has been stored somewhere
+/// (for example in one of the general-purpose registers of the PMU, to keep it
+/// across deep power-down mode), it can be restored using [`from_hz`].
+///
+/// [`LowPowerClock::calibrate`]: struct.LowPowerClock.html#method.calibrate
+/// [`from_hz`]: #method.from_hz
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Calibration {
+    hz: u32,
+}
+
+impl Calibration {
+    /// Create a calibration from a measurement
+    ///
+    /// `ticks` is the number of low-power clock ticks that were counted, while
+    /// `reference_ticks` ticks of a reference clock running at `reference_hz`
+    /// elapsed.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `reference_ticks` is zero.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::pmu::Calibration;
+    ///
+    /// // 1_150 ticks counted in 100 ms of a 12 MHz reference clock
+    /// let calibration = Calibration::from_measurement(1_150, 1_200_000, 12_000_000);
+    /// assert_eq!(calibration.hz(), 11_500);
+    ///
+    /// // The result is rounded to the nearest Hz.
+    /// let calibration =
+    ///     Calibration::from_measurement(1_151, 1_200_001, 12_000_000);
+    /// assert_eq!(calibration.hz(), 11_510);
+    /// ```
+    pub fn from_measurement(
+        ticks: u32,
+        reference_ticks: u32,
+        reference_hz: u32,
+    ) -> Self {
+        Self {
+            hz: measured_hz(ticks, reference_ticks, reference_hz),
+        }
+    }
+
+    /// Create a calibration from a previously measured frequency
+    pub fn from_hz(hz: u32) -> Self {
+        Self { hz }
+    }
+
+    /// The measured frequency in Hz
+    pub fn hz(&self) -> u32 {
+        self.hz
+    }
+
+    /// Correct a tick count, based on the measured frequency
+    ///
+    /// `ticks` is assumed to have been computed based on the nominal frequency
+    /// of its clock (for the low-power clock, that's 10 kHz). Returns a tick
+    /// count that represents the same duration at the measured frequency. The
+    /// result is rounded to the nearest tick, and saturates at `u32::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::{
+    ///     clock::{Frequency, Ticks},
+    ///     pmu::Calibration,
+    /// };
+    ///
+    /// struct Clock;
+    ///
+    /// impl Frequency for Clock {
+    ///     fn hz(&self) -> u32 { 10_000 }
+    /// }
+    ///
+    /// // The clock runs 15% fast, so 1 s takes 11_500 ticks instead of 10_000.
+    /// let calibration = Calibration::from_hz(11_500);
+    /// let ticks = calibration.correct(Ticks::from_ms(1_000, &Clock));
+    /// assert_eq!(ticks.value, 11_500);
+    ///
+    /// // A slow clock needs fewer ticks.
+    /// let calibration = Calibration::from_hz(8_000);
+    /// let ticks = calibration.correct(Ticks::from_ms(1_000, &Clock));
+    /// assert_eq!(ticks.value, 8_000);
+    ///
+    /// let ticks = calibration.correct(Ticks { value: 3, clock: &Clock });
+    /// assert_eq!(ticks.value, 2);
+    /// ```
+    pub fn correct<'clock, C>(
+        &self,
+        ticks: Ticks<'clock, C>,
+    ) -> Ticks<'clock, C>
+    where
+        C: clock::Frequency,
+    {
+        let nominal = ticks.clock.hz() as u64;
+        let value =
+            (ticks.value as u64 * self.hz as u64 + nominal / 2) / nominal;
+        let value = if value > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            value as u32
+        };
+
+        Ticks {
+            value,
+            clock: ticks.clock,
+        }
+    }
+}
+
+/// An error that can occur while calibrating the low-power clock
+///
+/// Returned by [`LowPowerClock::calibrate`].
+///
+/// [`LowPowerClock::calibrate`]: struct.LowPowerClock.html#method.calibrate
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CalibrationError {
+    /// The low-power clock didn't start ticking within the reference interval
+    NotRunning,
+}
+
+/// Clear the deep power-down flag, leaving the sleep flag untouched
+///
+/// Both flags are cleared by writing a one to them, so a read-modify-write
+/// would clear the sleep flag too. Writing zero to the other fields is
+/// harmless: PM is set again before entering any power mode, and NODPD can
+/// only be cleared by a power-on reset.
+fn clear_deep_power_down_flag(pmu: &pac::pmu::RegisterBlock) {
+    pmu.pcon.write(|w| w.dpdflag().set_bit());
+}
+
+fn gp_read(pmu: &pac::pmu::RegisterBlock, index: usize) -> u32 {
+    assert!(index < GP_REGISTERS);
+    pmu.gpreg[index].read().gpdata().bits()
+}
+
+fn gp_write(pmu: &pac::pmu::RegisterBlock, index: usize, value: u32) {
+    assert!(index < GP_REGISTERS);
+    pmu.gpreg[index].write(|w|
+        // Sound, as all values are valid for this field.
+        unsafe { w.gpdata().bits(value) });
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use crate::pac;
+
+    const SLEEPFLAG: u32 = 1 << 8;
+    const DPDFLAG: u32 = 1 << 11;
+
+    #[test]
+    fn clear_deep_power_down_flag_only_writes_dpdflag() {
+        // Sound, as the register block only consists of `u32` values.
+        let pmu: pac::pmu::RegisterBlock = unsafe { mem::zeroed() };
+        pmu.pcon.write(|w| unsafe { w.bits(SLEEPFLAG | DPDFLAG) });
+
+        super::clear_deep_power_down_flag(&pmu);
+
+        // Writing back SLEEPFLAG as one would clear it.
+        assert_eq!(pmu.pcon.read().bits(), DPDFLAG);
+    }
+
+    #[test]
+    fn gp_registers_round_trip() {
+        // Sound, as the register block only consists of `u32` values.
+        let pmu: pac::pmu::RegisterBlock = unsafe { mem::zeroed() };
+
+        for index in 0..super::GP_REGISTERS {
+            super::gp_write(&pmu, index, 0xdead_0000 | index as u32);
+        }
+        for index in 0..super::GP_REGISTERS {
+            assert_eq!(super::gp_read(&pmu, index), 0xdead_0000 | index as u32);
+            assert_eq!(
+                pmu.gpreg[index].read().bits(),
+                0xdead_0000 | index as u32
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn gp_read_rejects_missing_register() {
+        // Sound, as the register block only consists of `u32` values.
+        let pmu: pac::pmu::RegisterBlock = unsafe { mem::zeroed() };
+
+        super::gp_read(&pmu, super::GP_REGISTERS);
+    }
+}
@@ -5,13 +5,29 @@
 //!
 //! Both sleeping via busy waiting and via regular sleep mode are supported.
 //! Please refer to [`sleep::Busy`] and [`sleep::Regular`] for more details.
+//! [`sleep::Periodic`] builds on top of [`sleep::Regular`], to wake up at a
+//! fixed rate. [`sleep::WithHandler`] supports applications that use the WKT
+//! with their own interrupt handler. [`sleep::Delay`] makes any of the sleep
+//! modes usable with drivers that expect an `embedded-hal` delay.
 //!
 //! [`sleep::Busy`]: struct.Busy.html
 //! [`sleep::Regular`]: struct.Regular.html
+//! [`sleep::Periodic`]: struct.Periodic.html
+//! [`sleep::WithHandler`]: struct.WithHandler.html
+//! [`sleep::Delay`]: struct.Delay.html
+
+use core::marker::PhantomData;
 
 use cortex_m::{asm, interrupt};
-use embedded_hal::prelude::*;
+use embedded_hal::{
+    blocking::delay::{DelayMs, DelayUs},
+    prelude::*,
+};
+use embedded_hal_alpha::blocking::delay::{
+    DelayMs as DelayMsAlpha, DelayUs as DelayUsAlpha,
+};
 use nb;
+use void::Void;
 
 use crate::{
     clock::{self, Ticks},
@@ -29,6 +45,11 @@ where
     Clock: clock::Enabled,
 {
     /// Puts the processor to sleep for the given number of ticks of the clock
+    ///
+    /// Returns immediately, if the number of ticks is zero. Use
+    /// [`Ticks::new`] to catch that case when creating the `Ticks` instance.
+    ///
+    /// [`Ticks::new`]: ../clock/struct.Ticks.html#method.new
     fn sleep<'clock, T>(&mut self, ticks: T)
     where
         Clock: 'clock,
@@ -189,6 +210,12 @@ where
         self.wkt.select_clock::<Clock>();
         self.wkt.start(ticks.value);
 
+        self.wait_for_alarm();
+    }
+}
+
+impl<'r> Regular<'r> {
+    fn wait_for_alarm(&mut self) {
         // Within the this closure, interrupts are enabled, but interrupt
         // handlers won't run. This means that we'll exit sleep mode when the
         // WKT interrupt is fired, but there won't be an interrupt handler that
@@ -210,3 +237,539 @@ where
         });
     }
 }
+
+/// A sleep mode as a delay provider
+///
+/// Wraps any [`Sleep`] implementation, together with the clock it uses, and
+/// implements the `embedded-hal` delay traits for it. This makes it possible
+/// to pass [`sleep::Busy`] or [`sleep::Regular`] to drivers that expect a
+/// delay, just like [`delay::Delay`] or [`delay::WktDelay`].
+///
+/// Durations are converted to ticks of the clock, rounding up. If the number
+/// of ticks for a single call to `delay_us` doesn't fit into a `u32`, the
+/// delay saturates at the longest possible one. `delay_ms` with a `u32`
+/// argument waits in chunks, so it isn't affected by that.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     delay::{Delay, WktDelay},
+///     embedded_hal::blocking::delay::DelayMs,
+///     pac::CorePeripherals,
+///     sleep, Peripherals,
+/// };
+///
+/// // A driver that works with any delay provider
+/// fn blink(delay: &mut dyn DelayMs<u16>) {
+///     delay.delay_ms(500);
+/// }
+///
+/// let cp = CorePeripherals::take().unwrap();
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let mut wkt = p.WKT.enable(&mut syscon.handle);
+///
+/// let clock = syscon.iosc_derived_clock;
+///
+/// blink(&mut Delay::new(cp.SYST));
+/// blink(&mut sleep::Delay::new(sleep::Busy::prepare(&mut wkt), &clock));
+/// blink(&mut WktDelay::new(wkt, &clock));
+/// ```
+///
+/// [`sleep::Busy`]: struct.Busy.html
+/// [`sleep::Regular`]: struct.Regular.html
+/// [`delay::Delay`]: ../delay/struct.Delay.html
+/// [`delay::WktDelay`]: ../delay/struct.WktDelay.html
+pub struct Delay<'clock, S, Clock> {
+    sleep: S,
+    clock: &'clock Clock,
+}
+
+impl<'clock, S, Clock> Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Create a new instance of `sleep::Delay`
+    ///
+    /// `clock` must be the clock that `sleep` uses to measure the time.
+    pub fn new(sleep: S, clock: &'clock Clock) -> Self {
+        Self { sleep, clock }
+    }
+
+    /// Return the wrapped sleep mode
+    pub fn free(self) -> S {
+        self.sleep
+    }
+}
+
+impl<'clock, S, Clock> DelayMs<u32> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, mut ms: u32) {
+        // The number of ticks might not fit into a `u32`, so we wait in chunks
+        // that are small enough for any supported clock.
+        const MAX_MS: u32 = 0x0000_FFFF;
+        while ms != 0 {
+            let current_ms = if ms <= MAX_MS { ms } else { MAX_MS };
+            self.sleep.sleep(Ticks::from_ms(current_ms, self.clock));
+            ms -= current_ms;
+        }
+    }
+}
+
+impl<'clock, S, Clock> DelayMsAlpha<u32> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `ms` milliseconds
+    fn try_delay_ms(&mut self, ms: u32) -> Result<(), Self::Error> {
+        self.delay_ms(ms);
+        Ok(())
+    }
+}
+
+impl<'clock, S, Clock> DelayMs<u16> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u16) {
+        self.sleep.sleep(Ticks::from_ms(ms as u32, self.clock));
+    }
+}
+
+impl<'clock, S, Clock> DelayMsAlpha<u16> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `ms` milliseconds
+    fn try_delay_ms(&mut self, ms: u16) -> Result<(), Self::Error> {
+        self.delay_ms(ms);
+        Ok(())
+    }
+}
+
+impl<'clock, S, Clock> DelayMs<u8> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u8) {
+        self.delay_ms(ms as u16);
+    }
+}
+
+impl<'clock, S, Clock> DelayMsAlpha<u8> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `ms` milliseconds
+    fn try_delay_ms(&mut self, ms: u8) -> Result<(), Self::Error> {
+        self.delay_ms(ms);
+        Ok(())
+    }
+}
+
+impl<'clock, S, Clock> DelayUs<u32> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Pauses execution for `us` microseconds
+    ///
+    /// The delay is rounded up to the next tick of the clock.
+    fn delay_us(&mut self, us: u32) {
+        self.sleep.sleep(Ticks::from_us(us, self.clock));
+    }
+}
+
+impl<'clock, S, Clock> DelayUsAlpha<u32> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u32) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+impl<'clock, S, Clock> DelayUs<u16> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Pauses execution for `us` microseconds
+    fn delay_us(&mut self, us: u16) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl<'clock, S, Clock> DelayUsAlpha<u16> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u16) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+impl<'clock, S, Clock> DelayUs<u8> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    /// Pauses execution for `us` microseconds
+    fn delay_us(&mut self, us: u8) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl<'clock, S, Clock> DelayUsAlpha<u8> for Delay<'clock, S, Clock>
+where
+    S: Sleep<Clock>,
+    Clock: clock::Enabled + clock::Frequency,
+{
+    type Error = Void;
+
+    /// Pauses execution for `us` microseconds
+    fn try_delay_us(&mut self, us: u8) -> Result<(), Self::Error> {
+        self.delay_us(us);
+        Ok(())
+    }
+}
+
+/// Periodic sleep mode
+///
+/// Wraps [`sleep::Regular`] to wake the microcontroller up at a fixed rate.
+/// This is useful for duty-cycled applications that need to wake up, do some
+/// work, and go back to sleep, for example to sample a sensor.
+///
+/// Unlike calling [`Sleep::sleep`] in a loop, which would add the time spent
+/// working to every period, `sleep::Periodic` restarts the [WKT] as soon as
+/// a period ends. The work done between calls to [`Periodic::wait_next`]
+/// overlaps with the next period, which means the time between wake-ups does
+/// not depend on how long the work takes.
+///
+/// Every period is still slightly longer than `interval`, as the WKT is only
+/// restarted after waking up, which takes some time. This error is small and
+/// bounded for each period, but it accumulates. Don't rely on
+/// `sleep::Periodic` for keeping time over long durations.
+///
+/// If the work takes longer than the period, [`Periodic::wait_next`] doesn't
+/// sleep, but returns [`Overrun`] instead. See its documentation for details.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     Peripherals,
+///     clock::Ticks,
+///     pac::CorePeripherals,
+///     sleep,
+/// };
+///
+/// let mut cp = CorePeripherals::take().unwrap();
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut pmu    = p.PMU.split();
+/// let mut syscon = p.SYSCON.split();
+/// let mut wkt    = p.WKT.enable(&mut syscon.handle);
+///
+/// let clock = syscon.iosc_derived_clock;
+///
+/// let regular = sleep::Regular::prepare(
+///     &mut pmu.handle,
+///     &mut cp.SCB,
+///     &mut wkt,
+/// );
+/// let interval = Ticks { value: 75_000, clock: &clock }; // 100 ms
+/// let mut periodic = sleep::Periodic::new(regular, interval);
+///
+/// loop {
+///     if let Err(sleep::Overrun) = periodic.wait_next() {
+///         // The last period was missed.
+///     }
+///
+///     // Do some work here
+/// }
+/// ```
+///
+/// [`sleep::Regular`]: struct.Regular.html
+/// [`Periodic::wait_next`]: #method.wait_next
+/// [`Overrun`]: struct.Overrun.html
+pub struct Periodic<'r, Clock> {
+    regular: Regular<'r>,
+    interval: u32,
+    running: bool,
+    _clock: PhantomData<Clock>,
+}
+
+impl<'r, Clock> Periodic<'r, Clock>
+where
+    Clock: clock::Enabled + wkt::Clock,
+{
+    /// Create a new instance of `sleep::Periodic`
+    ///
+    /// The first period starts with the first call to
+    /// [`Periodic::wait_next`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `interval` is zero.
+    ///
+    /// [`Periodic::wait_next`]: #method.wait_next
+    pub fn new<'clock, T>(regular: Regular<'r>, interval: T) -> Self
+    where
+        Clock: 'clock,
+        T: Into<Ticks<'clock, Clock>>,
+    {
+        let interval: Ticks<Clock> = interval.into();
+
+        // If we try to sleep for zero cycles, we'll never wake up again.
+        assert!(interval.value > 0);
+
+        Periodic {
+            regular,
+            interval: interval.value,
+            running: false,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Sleep until the end of the current period
+    ///
+    /// Puts the microcontroller into sleep mode, until the current period
+    /// ends. The next period is started right after waking up, before this
+    /// method returns.
+    ///
+    /// If the current period has already ended when this method is called,
+    /// it returns [`Overrun`] immediately, without sleeping. In that case, the
+    /// next period is started from the time of the call, as the WKT can't
+    /// measure how much time has passed since the end of the period.
+    ///
+    /// [`Overrun`]: struct.Overrun.html
+    pub fn wait_next(&mut self) -> Result<(), Overrun> {
+        if !self.running {
+            self.restart();
+            self.running = true;
+        }
+
+        if self.regular.wkt.wait().is_ok() {
+            self.restart();
+            return Err(Overrun);
+        }
+
+        self.regular.wait_for_alarm();
+        self.restart();
+
+        Ok(())
+    }
+
+    /// Stop waking up periodically and return the wrapped `sleep::Regular`
+    pub fn free(self) -> Regular<'r> {
+        self.regular
+    }
+
+    fn restart(&mut self) {
+        self.regular.wkt.select_clock::<Clock>();
+        self.regular.wkt.start(self.interval);
+
+        // The WKT interrupt is still pending from the end of the last period.
+        // If we don't clear it, it would wake us up right away, the next time
+        // we go to sleep. We need to do this after starting the timer, or the
+        // interrupt would become pending again immediately.
+        NVIC::unpend(Interrupt::WKT);
+    }
+}
+
+/// Returned by [`Periodic::wait_next`], if a period has been missed
+///
+/// [`Periodic::wait_next`]: struct.Periodic.html#method.wait_next
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Overrun;
+
+/// Regular sleep mode, cooperating with an interrupt handler
+///
+/// [`sleep::Regular`] relies on there not being a WKT interrupt handler, as it
+/// uses the WKT's alarm flag to find out whether it is time to wake up. This
+/// doesn't work, if the application uses the WKT interrupt itself, as the
+/// interrupt handler needs to reset the alarm flag.
+///
+/// `sleep::WithHandler` doesn't access the WKT (or any other timer) at all.
+/// Instead, it puts the microcontroller to sleep, until a user-provided
+/// condition is met. The condition will typically check a flag that is set
+/// by an interrupt handler. This works with any interrupt, not just the WKT.
+///
+/// # Sequencing
+///
+/// [`WithHandler::sleep_until`] does the following, in a loop:
+///
+/// 1. Disable interrupts.
+/// 2. Check the condition. If it is met, enable interrupts and return.
+/// 3. Enter sleep mode. Since interrupts are disabled, interrupt handlers
+///    won't run, but the microcontroller still wakes up, if an interrupt
+///    becomes pending.
+/// 4. Enable interrupts. Any pending interrupt handler runs now.
+///
+/// Because the condition is checked with interrupts disabled, an interrupt
+/// that happens right after the check can't get lost: It becomes pending, and
+/// either prevents the microcontroller from going to sleep, or wakes it up
+/// right away.
+///
+/// The interrupt that is supposed to wake up the microcontroller must be
+/// unmasked in the NVIC, and its interrupt handler must clear the interrupt
+/// flag of the peripheral. Otherwise, the interrupt handler would be called
+/// again right away.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use core::sync::atomic::{AtomicBool, Ordering};
+///
+/// use lpc8xx_hal::{
+///     Peripherals,
+///     pac::CorePeripherals,
+///     sleep,
+/// };
+///
+/// // Set by the interrupt handler
+/// static WOKE_UP: AtomicBool = AtomicBool::new(false);
+///
+/// let mut cp = CorePeripherals::take().unwrap();
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut pmu = p.PMU.split();
+///
+/// let mut sleep = sleep::WithHandler::prepare(&mut pmu.handle, &mut cp.SCB);
+///
+/// // Sleep until the interrupt handler has run.
+/// // The condition is checked with interrupts disabled, so it's fine to load
+/// // and reset the flag separately. Cortex-M0+ doesn't support atomic swaps.
+/// sleep.sleep_until(|| {
+///     let woke_up = WOKE_UP.load(Ordering::Acquire);
+///     WOKE_UP.store(false, Ordering::Release);
+///     woke_up
+/// });
+/// ```
+///
+/// Please refer to the [examples in the repository] for a full example.
+///
+/// [`sleep::Regular`]: struct.Regular.html
+/// [`WithHandler::sleep_until`]: #method.sleep_until
+/// [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
+pub struct WithHandler<'r> {
+    pmu: &'r mut pmu::Handle,
+    scb: &'r mut pac::SCB,
+}
+
+impl<'r> WithHandler<'r> {
+    /// Prepare regular sleep mode, cooperating with an interrupt handler
+    ///
+    /// Requires references to the PMU handle and the SCB, which will be
+    /// borrowed for as long as the `sleep::WithHandler` instance exists.
+    pub fn prepare(pmu: &'r mut pmu::Handle, scb: &'r mut pac::SCB) -> Self {
+        WithHandler { pmu, scb }
+    }
+
+    /// Sleep until `condition` returns `true`
+    ///
+    /// `condition` is called with interrupts disabled, so it must be short.
+    /// If it returns `true` right away, this method returns without sleeping.
+    ///
+    /// Please refer to the [struct documentation] for details.
+    ///
+    /// [struct documentation]: struct.WithHandler.html
+    pub fn sleep_until<F>(&mut self, mut condition: F)
+    where
+        F: FnMut() -> bool,
+    {
+        loop {
+            let done = interrupt::free(|_| {
+                if condition() {
+                    return true;
+                }
+
+                // Interrupt handlers won't run in here, but a pending
+                // interrupt still wakes us up.
+                self.pmu.enter_sleep_mode(self.scb);
+
+                false
+            });
+
+            if done {
+                return;
+            }
+
+            // The critical section has ended, so the interrupt handler that
+            // woke us up has run by now. Check the condition again.
+        }
+    }
+
+    /// Enter deep-sleep mode until `condition` returns `true`
+    ///
+    /// Works like [`sleep_until`], but uses deep-sleep mode instead of the
+    /// regular sleep mode, which saves more power. Only interrupts that have
+    /// been enabled in the start logic can wake up the microcontroller from
+    /// deep-sleep mode. For pin interrupts, use
+    /// [`pinint::Interrupt::enable_wakeup`]. For other interrupts, use
+    /// [`syscon::Handle::enable_interrupt_wakeup`].
+    ///
+    /// # Safety
+    ///
+    /// This method calls [`pmu::Handle::enter_deep_sleep_mode`]. Please refer
+    /// to its documentation for the requirements that need to be met.
+    ///
+    /// [`sleep_until`]: #method.sleep_until
+    /// [`pinint::Interrupt::enable_wakeup`]: ../pinint/struct.Interrupt.html#method.enable_wakeup
+    /// [`syscon::Handle::enable_interrupt_wakeup`]: ../syscon/struct.Handle.html#method.enable_interrupt_wakeup
+    /// [`pmu::Handle::enter_deep_sleep_mode`]: ../pmu/struct.Handle.html#method.enter_deep_sleep_mode
+    pub unsafe fn deep_sleep_until<F>(&mut self, mut condition: F)
+    where
+        F: FnMut() -> bool,
+    {
+        loop {
+            let done = interrupt::free(|_| {
+                if condition() {
+                    return true;
+                }
+
+                // Sound, as the caller guarantees that the requirements of
+                // `enter_deep_sleep_mode` are met.
+                unsafe { self.pmu.enter_deep_sleep_mode(self.scb) };
+
+                false
+            });
+
+            if done {
+                return;
+            }
+        }
+    }
+}
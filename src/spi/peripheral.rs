@@ -384,8 +384,10 @@ pub struct Slave;
 
 /// Receiver Overrun Error
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct RxOverrunError;
 
 /// Transmitter Underrun Error
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct TxUnderrunError;
@@ -92,9 +92,19 @@ impl<T> Function<T, Unassigned> {
     /// );
     /// ```
     ///
+    /// Fixed functions can only be assigned to their respective pin. Trying to
+    /// assign them to any other pin won't compile.
+    ///
+    /// Movable functions can be assigned to any pin, but not all pins are
+    /// bonded out on the smaller packages. If a specific part has been
+    /// selected using the target hardware features (for example
+    /// `845m301jhi33`), the pins that are missing on its package are not
+    /// available (see [`Pins`]), so they can't be passed here.
+    ///
     /// [`Unassigned`]: state/struct.Unassigned.html
     /// [`Pin`]: ../pins/struct.Pin.html
     /// [`pins::state::Swm`]: ../pins/state/struct.Swm.html
+    /// [`Pins`]: ../pins/struct.Pins.html
     pub fn assign<P, S>(
         mut self,
         mut pin: Pin<P, S>,
@@ -200,6 +210,95 @@ impl<T, P> Function<T, Assigned<P>> {
     }
 }
 
+/// Implemented for tuples of functions and pins that can be assigned at once
+///
+/// This trait is implemented for tuples of up to six `(function, pin)` pairs,
+/// where each function can be assigned to the pin it is paired with. Please
+/// refer to [`swm::Handle::assign_all`] for the public API that uses this
+/// trait.
+///
+/// [`swm::Handle::assign_all`]: struct.Handle.html#method.assign_all
+pub trait AssignAll {
+    /// The result of the assignment
+    ///
+    /// A tuple with one `(function, pin)` pair for each pair that was passed
+    /// in, with the states of the function and pin updated, like the return
+    /// value of [`Function::assign`].
+    ///
+    /// [`Function::assign`]: struct.Function.html#method.assign
+    type Assigned;
+
+    /// Assign all functions to their respective pins
+    fn assign_all(self, swm: &mut Handle) -> Self::Assigned;
+}
+
+macro_rules! assign_all {
+    ($($function:ident, $pin:ident, $state:ident, $f:ident, $p:ident;)*) => {
+        impl<$($function, $pin, $state,)*> AssignAll
+            for ($((Function<$function, Unassigned>, Pin<$pin, $state>),)*)
+        where
+            $(
+                $function: FunctionTrait<$pin>,
+                $pin: pins::Trait,
+                $state: pins::State,
+                Pin<$pin, $state>: AssignFunction<
+                    $function,
+                    <$function as FunctionTrait<$pin>>::Kind,
+                >,
+            )*
+        {
+            type Assigned = ($(
+                (
+                    Function<$function, Assigned<$pin>>,
+                    <Pin<$pin, $state> as AssignFunction<
+                        $function,
+                        <$function as FunctionTrait<$pin>>::Kind,
+                    >>::Assigned,
+                ),
+            )*);
+
+            fn assign_all(self, swm: &mut Handle) -> Self::Assigned {
+                let ($(($f, $p),)*) = self;
+                ($($f.assign($p, swm),)*)
+            }
+        }
+    };
+}
+
+assign_all!(
+    F1, P1, S1, f1, p1;
+);
+assign_all!(
+    F1, P1, S1, f1, p1;
+    F2, P2, S2, f2, p2;
+);
+assign_all!(
+    F1, P1, S1, f1, p1;
+    F2, P2, S2, f2, p2;
+    F3, P3, S3, f3, p3;
+);
+assign_all!(
+    F1, P1, S1, f1, p1;
+    F2, P2, S2, f2, p2;
+    F3, P3, S3, f3, p3;
+    F4, P4, S4, f4, p4;
+);
+assign_all!(
+    F1, P1, S1, f1, p1;
+    F2, P2, S2, f2, p2;
+    F3, P3, S3, f3, p3;
+    F4, P4, S4, f4, p4;
+    F5, P5, S5, f5, p5;
+);
+assign_all!(
+    F1, P1, S1, f1, p1;
+    F2, P2, S2, f2, p2;
+    F3, P3, S3, f3, p3;
+    F4, P4, S4, f4, p4;
+    F5, P5, S5, f5, p5;
+    F6, P6, S6, f6, p6;
+);
+
 /// Implemented for all fixed and movable functions
 ///
 /// This trait is an internal implementation detail and should neither be
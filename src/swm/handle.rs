@@ -2,6 +2,8 @@ use core::marker::PhantomData;
 
 use crate::{init_state, pac, syscon};
 
+use super::functions::AssignAll;
+
 /// Handle to the SWM peripheral
 ///
 /// Can be used to enable and disable the switch matrix. It is also required by
@@ -80,4 +82,46 @@ impl Handle<init_state::Enabled> {
             _state: PhantomData,
         }
     }
+
+    /// Assign multiple functions to pins at once
+    ///
+    /// Accepts a tuple of `(function, pin)` pairs and assigns each function to
+    /// the pin it is paired with, as if [`Function::assign`] was called for
+    /// each pair. Returns a tuple of the same shape, containing the assigned
+    /// functions and the updated pins. Up to six pairs are supported.
+    ///
+    /// The same requirements as for [`Function::assign`] apply to each pair.
+    /// Since every pin is moved into this method, each pin can only appear
+    /// once. Code that attempts to pass the same pin twice will not compile.
+    /// To assign multiple functions to the same pin, use [`Function::assign`]
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let swm = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let ((u0_rxd, _), (u0_txd, _)) = swm_handle.assign_all((
+    ///     (swm.movable_functions.u0_rxd, p.pins.pio0_0.into_swm_pin()),
+    ///     (swm.movable_functions.u0_txd, p.pins.pio0_4.into_swm_pin()),
+    /// ));
+    /// ```
+    ///
+    /// [`Function::assign`]: struct.Function.html#method.assign
+    pub fn assign_all<A>(&mut self, assignments: A) -> A::Assigned
+    where
+        A: AssignAll,
+    {
+        assignments.assign_all(self)
+    }
 }
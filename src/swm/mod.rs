@@ -23,7 +23,7 @@ pub use self::{
     assignment::{AssignFunction, UnassignFunction},
     fixed_functions::*,
     function_kind::{Analog, FunctionKind, Input, Output},
-    functions::{Function, FunctionTrait, NotAvailable},
+    functions::{AssignAll, Function, FunctionTrait, NotAvailable},
     handle::Handle,
     movable_functions::*,
     peripheral::{Parts, SWM},
@@ -0,0 +1,312 @@
+//! API for querying the configured clock frequencies
+//!
+//! See [`Clocks`]. To verify the clock configuration on real hardware, clock
+//! frequencies can also be measured, using [`syscon::Handle::measure_clock`].
+//!
+//! [`Clocks`]: struct.Clocks.html
+//! [`syscon::Handle::measure_clock`]: ../struct.Handle.html#method.measure_clock
+
+use crate::{
+    clock::{self, Ticks},
+    timer,
+};
+
+use super::{main_clock::MainClock, Handle};
+
+/// A snapshot of the configured clock frequencies
+///
+/// Summarizes the frequencies of the main clock and the system clock, which
+/// the core and most peripherals run at. Drivers that need to know these
+/// frequencies can take a `&Clocks`, instead of having to be told each
+/// frequency separately.
+///
+/// Once the clock tree has been set up, use [`syscon::Handle::freeze`] to
+/// create an instance. Please note that `Clocks` is a snapshot: It doesn't
+/// borrow any of the clocks, and won't be updated, if the clock configuration
+/// is changed afterwards. Call [`syscon::Handle::freeze`] again in that case.
+///
+/// The clocks of peripherals that have their own clock selection (like the
+/// USARTs) are not covered. Those are configured using the APIs of the
+/// respective peripherals.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::{clock::Frequency, syscon::Clocks};
+///
+/// // Main clock running at 12 MHz, system clock divided by 2
+/// let clocks = Clocks::new(12_000_000, 2);
+///
+/// assert_eq!(clocks.main_hz(), 12_000_000);
+/// assert_eq!(clocks.system_hz(), 6_000_000);
+/// assert_eq!(clocks.core_hz(), 6_000_000);
+/// assert_eq!(clocks.core().hz(), 6_000_000);
+/// ```
+///
+/// [`syscon::Handle::freeze`]: ../struct.Handle.html#method.freeze
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Clocks {
+    main: u32,
+    system_div: u8,
+}
+
+impl Clocks {
+    /// Create a `Clocks` instance from known settings
+    ///
+    /// `main_hz` is the frequency of the main clock. `system_div` is the value
+    /// of the system clock divider (SYSAHBCLKDIV), which divides the main clock
+    /// to produce the system clock. A divider of zero means the system clock
+    /// is disabled.
+    ///
+    /// Usually you want to use [`syscon::Handle::freeze`] instead, which reads
+    /// the divider from the hardware.
+    ///
+    /// [`syscon::Handle::freeze`]: ../struct.Handle.html#method.freeze
+    pub fn new(main_hz: u32, system_div: u8) -> Self {
+        Self {
+            main: main_hz,
+            system_div,
+        }
+    }
+
+    /// The frequency of the main clock in Hz
+    pub fn main_hz(&self) -> u32 {
+        self.main
+    }
+
+    /// The frequency of the system clock in Hz
+    ///
+    /// The system clock drives the core, the memories, and the peripheral bus.
+    /// Returns zero, if the system clock is disabled.
+    pub fn system_hz(&self) -> u32 {
+        self.main.checked_div(self.system_div as u32).unwrap_or(0)
+    }
+
+    /// The frequency of the core in Hz
+    ///
+    /// The core runs at the system clock, so this returns the same value as
+    /// [`system_hz`].
+    ///
+    /// [`system_hz`]: #method.system_hz
+    pub fn core_hz(&self) -> u32 {
+        self.system_hz()
+    }
+
+    /// The core clock, for use with APIs that require a [`clock::Frequency`]
+    ///
+    /// This can be passed to [`DelayCycles`], for example.
+    ///
+    /// [`clock::Frequency`]: ../../clock/trait.Frequency.html
+    /// [`DelayCycles`]: ../../delay/struct.DelayCycles.html
+    pub fn core(&self) -> CoreClock {
+        CoreClock { hz: self.core_hz() }
+    }
+}
+
+/// The frequency of the core, as captured by [`Clocks`]
+///
+/// Returned by [`Clocks::core`].
+///
+/// [`Clocks`]: struct.Clocks.html
+/// [`Clocks::core`]: struct.Clocks.html#method.core
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoreClock {
+    hz: u32,
+}
+
+impl clock::Frequency for CoreClock {
+    fn hz(&self) -> u32 {
+        self.hz
+    }
+}
+
+impl Handle {
+    /// Capture the configured clock frequencies
+    ///
+    /// Call this once the clock tree has been set up, passing the [`MainClock`]
+    /// returned by [`set_main_clock`]. The system clock divider is read from
+    /// the hardware.
+    ///
+    /// The returned [`Clocks`] is a snapshot. It won't reflect any changes
+    /// that are made to the clock configuration afterwards.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{delay::DelayCycles, prelude::*, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// let main_clock = syscon.handle.set_main_clock(&syscon.iosc);
+    /// let clocks = syscon.handle.freeze(&main_clock);
+    ///
+    /// assert_eq!(clocks.main_hz(), 12_000_000);
+    ///
+    /// let core = clocks.core();
+    /// let mut delay = DelayCycles::new(&core);
+    /// delay.delay_us(5_u8);
+    /// ```
+    ///
+    /// [`MainClock`]: main_clock/struct.MainClock.html
+    /// [`set_main_clock`]: #method.set_main_clock
+    /// [`Clocks`]: clocks/struct.Clocks.html
+    pub fn freeze<C>(&self, main_clock: &MainClock<'_, C>) -> Clocks
+    where
+        C: clock::Frequency,
+    {
+        let system_div = self.sysahbclkdiv.read().div().bits();
+        Clocks::new(clock::Frequency::hz(main_clock), system_div)
+    }
+}
+
+impl Handle {
+    /// Measure the frequency of a clock, using two timers
+    ///
+    /// Counts ticks of the target clock, using `counter`, while
+    /// `reference_timer` counts down `interval`. Returns the measured frequency
+    /// of the target clock in Hz. This is useful during bring-up, to confirm
+    /// that the clock configuration (an external crystal, for example) actually
+    /// produces the expected frequency.
+    ///
+    /// `counter` must be a timer that counts ticks of the target clock (for
+    /// example, [`SYST`] or an MRT channel count ticks of the system clock),
+    /// while `reference_timer` must count ticks of the clock that `interval`
+    /// refers to. `target` is the frequency the target clock is expected to
+    /// run at. The measurement is only as accurate as the reference clock.
+    ///
+    /// This method blocks until the interval has elapsed.
+    ///
+    /// # Choosing the interval
+    ///
+    /// The measurement has a resolution of one tick of the target clock, so
+    /// longer intervals yield more accurate results. At the same time, the
+    /// counter must not run out during the interval. At the expected
+    /// frequency, `interval` must not be longer than half the time it takes
+    /// `counter` to count down its [`Timer::MAX_TICKS`]. This leaves room for
+    /// a target clock that runs faster than expected.
+    ///
+    /// SysTick, for example, can count `2^24` ticks, which takes about 1.4 s at
+    /// 12 MHz. An interval of 500 ms is fine in that case, and results in a
+    /// resolution of less than 1 ppm.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the counter could run out at the expected frequency, as
+    /// described above, or if `interval` is not valid for `reference_timer`.
+    ///
+    /// Returns `u32::MAX`, if the counter did run out anyway, which means the
+    /// target clock runs at more than twice its expected frequency.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{clock::Ticks, Peripherals, CorePeripherals};
+    ///
+    /// let cp = CorePeripherals::take().unwrap();
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut wkt = p.WKT.enable(&mut syscon.handle);
+    /// let mut syst = cp.SYST;
+    ///
+    /// let main_clock = syscon.handle.set_main_clock(&syscon.iosc);
+    /// let core = syscon.handle.freeze(&main_clock).core();
+    ///
+    /// // By default, the WKT counts ticks of the IRC-derived clock.
+    /// let wkt_clock = syscon.iosc_derived_clock;
+    ///
+    /// // Measure for 100 ms.
+    /// let interval = Ticks::from_ms(100, &wkt_clock);
+    ///
+    /// let hz = syscon
+    ///     .handle
+    ///     .measure_clock(&core, &mut syst, &mut wkt, interval);
+    /// ```
+    ///
+    /// [`SYST`]: ../pac/struct.SYST.html
+    /// [`Timer::MAX_TICKS`]: ../timer/trait.Timer.html#associatedconstant.MAX_TICKS
+    pub fn measure_clock<C, T, R, RC>(
+        &self,
+        target: &C,
+        counter: &mut T,
+        reference_timer: &mut R,
+        interval: Ticks<'_, RC>,
+    ) -> u32
+    where
+        C: clock::Frequency,
+        T: timer::Timer,
+        R: timer::Timer,
+        RC: clock::Frequency,
+    {
+        let reference_hz = interval.clock.hz();
+
+        let expected = measured_hz(target.hz(), reference_hz, interval.value);
+        assert!(expected <= T::MAX_TICKS / 2);
+
+        counter.start(T::MAX_TICKS);
+        reference_timer.start(interval.value);
+
+        while let Err(nb::Error::WouldBlock) = reference_timer.wait() {}
+
+        let remaining = counter.remaining();
+        let ran_out = counter.wait().is_ok();
+
+        counter.cancel();
+        reference_timer.cancel();
+
+        if ran_out {
+            return u32::MAX;
+        }
+
+        measured_hz(T::MAX_TICKS - remaining, interval.value, reference_hz)
+    }
+}
+
+/// Compute a frequency from a number of counted ticks
+///
+/// Returns the frequency in Hz of a clock, of which `count` ticks have been
+/// counted over an interval of `interval` ticks of a reference clock that runs
+/// at `reference_hz`. The result is rounded to the nearest Hz, and saturates at
+/// `u32::MAX`.
+///
+/// This is used by [`syscon::Handle::measure_clock`], but can also be used
+/// with counts acquired by other means.
+///
+/// # Panics
+///
+/// Panics, if `interval` is zero.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::syscon::clocks::measured_hz;
+///
+/// // 1_200_000 ticks counted during 75_000 ticks of a 750 kHz clock (100 ms)
+/// assert_eq!(measured_hz(1_200_000, 75_000, 750_000), 12_000_000);
+///
+/// // The result is rounded to the nearest Hz.
+/// assert_eq!(measured_hz(1, 3, 2), 1);
+/// assert_eq!(measured_hz(1, 3, 1), 0);
+///
+/// // Large intermediate results don't overflow, but the result saturates.
+/// assert_eq!(measured_hz(u32::MAX, u32::MAX, u32::MAX), u32::MAX);
+/// assert_eq!(measured_hz(u32::MAX, 1, 2), u32::MAX);
+/// ```
+///
+/// [`syscon::Handle::measure_clock`]: ../struct.Handle.html#method.measure_clock
+pub fn measured_hz(count: u32, interval: u32, reference_hz: u32) -> u32 {
+    assert!(interval > 0);
+
+    let interval = interval as u64;
+    let hz = (count as u64 * reference_hz as u64 + interval / 2) / interval;
+
+    if hz > u32::MAX as u64 {
+        return u32::MAX;
+    }
+
+    hz as u32
+}
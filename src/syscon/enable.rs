@@ -0,0 +1,119 @@
+use crate::{dma::DMA, gpio::GPIO, init_state, swm, wkt::WKT};
+
+#[cfg(feature = "845")]
+use crate::pinint::PININT;
+
+use super::Handle;
+
+/// Implemented for peripherals that only need the SYSCON handle to be enabled
+///
+/// Please refer to [`syscon::Handle::enable_all`] for the public API that uses
+/// this trait.
+///
+/// This trait is also implemented for tuples of such peripherals, which are
+/// enabled one after the other, and returned as a tuple in the same order.
+///
+/// [`syscon::Handle::enable_all`]: struct.Handle.html#method.enable_all
+pub trait Enable {
+    /// The enabled peripheral
+    type Enabled;
+
+    /// Enable the peripheral
+    ///
+    /// This is equivalent to calling the peripheral's own `enable` method.
+    fn enable(self, syscon: &mut Handle) -> Self::Enabled;
+}
+
+impl Handle {
+    /// Enable multiple peripherals at once
+    ///
+    /// Accepts a peripheral that implements [`Enable`], or a tuple of up to 6
+    /// of them. Returns the enabled peripherals in the same form.
+    ///
+    /// This saves you from passing `&mut syscon.handle` to every single
+    /// peripheral you need to enable during setup. The handle is only
+    /// borrowed for the duration of the call, so it can still be used for
+    /// anything else afterwards.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let swm = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let (wkt, dma) = syscon.handle.enable_all((p.WKT, p.DMA));
+    /// #[cfg(feature = "845")]
+    /// let (wkt, dma, gpio, swm_handle) = syscon
+    ///     .handle
+    ///     .enable_all((p.WKT, p.DMA, p.GPIO, swm.handle));
+    /// ```
+    ///
+    /// [`Enable`]: trait.Enable.html
+    pub fn enable_all<P>(&mut self, peripherals: P) -> P::Enabled
+    where
+        P: Enable,
+    {
+        peripherals.enable(self)
+    }
+}
+
+macro_rules! impl_enable {
+    ($($ty:ty => $enabled:ty;)*) => {
+        $(
+            impl Enable for $ty {
+                type Enabled = $enabled;
+
+                fn enable(self, syscon: &mut Handle) -> Self::Enabled {
+                    <$ty>::enable(self, syscon)
+                }
+            }
+        )*
+    };
+}
+
+impl_enable!(
+    DMA<init_state::Disabled> => DMA<init_state::Enabled>;
+    GPIO<init_state::Disabled> => GPIO<init_state::Enabled>;
+    swm::Handle<init_state::Disabled> => swm::Handle<init_state::Enabled>;
+    WKT<init_state::Disabled> => WKT<init_state::Enabled>;
+);
+
+// GPIO is enabled by default on LPC82x. `GPIO::enable` is available anyway,
+// to allow for code that works on all targets.
+#[cfg(feature = "82x")]
+impl_enable!(
+    GPIO<init_state::Enabled> => GPIO<init_state::Enabled>;
+);
+
+#[cfg(feature = "845")]
+impl_enable!(
+    PININT<init_state::Disabled> => PININT<init_state::Enabled>;
+);
+
+macro_rules! impl_enable_tuple {
+    ($($name:ident),*) => {
+        impl<$($name,)*> Enable for ($($name,)*)
+        where
+            $($name: Enable,)*
+        {
+            type Enabled = ($($name::Enabled,)*);
+
+            #[allow(non_snake_case)]
+            fn enable(self, syscon: &mut Handle) -> Self::Enabled {
+                let ($($name,)*) = self;
+                ($($name.enable(syscon),)*)
+            }
+        }
+    };
+}
+
+impl_enable_tuple!(A, B);
+impl_enable_tuple!(A, B, C);
+impl_enable_tuple!(A, B, C, D);
+impl_enable_tuple!(A, B, C, D, E);
+impl_enable_tuple!(A, B, C, D, E, F);
@@ -0,0 +1,120 @@
+//! API for configuring the flash access time
+//!
+//! The flash memory needs more time to respond at higher system clock
+//! frequencies. The number of additional system clocks that the core waits for
+//! every flash access (the wait states) is configured in the FLASHCFG register
+//! of the flash controller, using [`set_wait_states`].
+//!
+//! The wait states need to be increased before the system clock is raised, and
+//! can be decreased after it has been lowered. [`wait_states_for`] computes the
+//! minimum number of wait states for a given frequency.
+//!
+//! [`set_wait_states`]: fn.set_wait_states.html
+//! [`wait_states_for`]: fn.wait_states_for.html
+
+use crate::pac;
+
+/// The highest frequency that can be used with 0 wait states, in Hz
+pub const MAX_FREQUENCY_0_WAIT_STATES: u32 = 24_000_000;
+
+/// The highest frequency that can be used with 1 wait state, in Hz
+pub const MAX_FREQUENCY_1_WAIT_STATE: u32 = 30_000_000;
+
+/// The highest number of wait states supported by the flash controller
+#[cfg(feature = "82x")]
+pub const MAX_WAIT_STATES: u8 = 1;
+
+/// The highest number of wait states supported by the flash controller
+#[cfg(feature = "845")]
+pub const MAX_WAIT_STATES: u8 = 2;
+
+/// Set the number of flash wait states
+///
+/// Every flash access takes `wait_states + 1` system clocks. Use
+/// [`wait_states_for`] to compute the minimum number of wait states for a
+/// given system clock frequency.
+///
+/// The FLASHCFG register belongs to the flash controller, not to SYSCON, so
+/// this function doesn't need a [`syscon::Handle`]. Exclusive access to the
+/// register is guaranteed by requiring a mutable reference to `FLASH_CTRL`,
+/// which can only be obtained by whoever owns it.
+///
+/// Please note that running the system clock at a frequency that requires
+/// more wait states than are configured can cause the microcontroller to read
+/// garbage from the flash and crash. Make sure to increase the wait states
+/// before increasing the system clock frequency.
+///
+/// # Panics
+///
+/// Panics, if `wait_states` is larger than [`MAX_WAIT_STATES`].
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     Peripherals,
+///     syscon::flash::{self, wait_states_for},
+/// };
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// flash::set_wait_states(&mut p.FLASH_CTRL, wait_states_for(30_000_000));
+/// assert_eq!(flash::wait_states(&p.FLASH_CTRL), 1);
+///
+/// // It's safe to raise the system clock to 30 MHz now.
+/// ```
+///
+/// [`wait_states_for`]: fn.wait_states_for.html
+/// [`syscon::Handle`]: ../struct.Handle.html
+/// [`MAX_WAIT_STATES`]: constant.MAX_WAIT_STATES.html
+pub fn set_wait_states(flash_ctrl: &mut pac::FLASH_CTRL, wait_states: u8) {
+    assert!(wait_states <= MAX_WAIT_STATES);
+
+    // Sound, as we just checked that the value is valid.
+    flash_ctrl
+        .flashcfg
+        .modify(|_, w| unsafe { w.flashtim().bits(wait_states) });
+}
+
+/// Returns the number of flash wait states that is currently configured
+///
+/// See [`set_wait_states`].
+///
+/// [`set_wait_states`]: fn.set_wait_states.html
+pub fn wait_states(flash_ctrl: &pac::FLASH_CTRL) -> u8 {
+    flash_ctrl.flashcfg.read().flashtim().bits()
+}
+
+/// Compute the minimum number of flash wait states for a system clock frequency
+///
+/// `frequency` is the system clock frequency in Hz. The result can be passed to
+/// [`set_wait_states`].
+///
+/// Frequencies above [`MAX_FREQUENCY_1_WAIT_STATE`] are outside of the
+/// specification. For those, [`MAX_WAIT_STATES`] is returned, which is the best
+/// that can be done, but there is no guarantee that the flash will work
+/// reliably.
+///
+/// # Example
+///
+/// ```
+/// use lpc8xx_hal::syscon::flash::wait_states_for;
+///
+/// assert_eq!(wait_states_for(12_000_000), 0);
+/// assert_eq!(wait_states_for(24_000_000), 0);
+/// assert_eq!(wait_states_for(24_000_001), 1);
+/// assert_eq!(wait_states_for(30_000_000), 1);
+/// ```
+///
+/// [`set_wait_states`]: fn.set_wait_states.html
+/// [`MAX_FREQUENCY_1_WAIT_STATE`]: constant.MAX_FREQUENCY_1_WAIT_STATE.html
+/// [`MAX_WAIT_STATES`]: constant.MAX_WAIT_STATES.html
+pub fn wait_states_for(frequency: u32) -> u8 {
+    if frequency <= MAX_FREQUENCY_0_WAIT_STATES {
+        0
+    } else if frequency <= MAX_FREQUENCY_1_WAIT_STATE {
+        1
+    } else {
+        MAX_WAIT_STATES
+    }
+}
@@ -0,0 +1,103 @@
+//! API for trimming the internal RC oscillator (IRC)
+//!
+//! The IRC is factory-trimmed to 12 MHz, with an accuracy of ±1.5% over the
+//! full temperature range. Where that is not good enough (for example, to
+//! achieve accurate USART baud rates without an external crystal), the trim
+//! value in the IRCCTRL register can be adjusted, using
+//! [`syscon::Handle::set_irc_trim`]. Raising the trim value raises the
+//! frequency.
+//!
+//! This is intended for closed-loop calibration against an external timebase,
+//! like a 32.768 kHz crystal or a signal from a host: Measure the IRC
+//! frequency against the reference, adjust the trim value, repeat.
+//!
+//! This API is only available on LPC82x. On LPC84x, the FRO is trimmed by the
+//! boot ROM, according to the configuration in the FAIM.
+//!
+//! [`syscon::Handle::set_irc_trim`]: ../struct.Handle.html#method.set_irc_trim
+
+use crate::pac::syscon::IRCCTRL;
+
+use super::Handle;
+
+/// The value of the trim field after reset
+///
+/// Please note that the boot ROM overwrites this with the factory trim value,
+/// which is specific to each device.
+pub const RESET_TRIM: u8 = 0x80;
+
+impl Handle {
+    /// Return the current IRC trim value
+    ///
+    /// Reads the TRIM field of the IRCCTRL register. Right after startup, this
+    /// is the factory trim value, as written by the boot ROM. It's a good idea
+    /// to keep that value around, before starting calibration, to be able to
+    /// restore it.
+    pub fn irc_trim(&self) -> u8 {
+        read_trim(&self.ircctrl)
+    }
+
+    /// Set the IRC trim value
+    ///
+    /// Writes the TRIM field of the IRCCTRL register. The new value takes
+    /// effect immediately, changing the frequency of the IRC, and with it the
+    /// frequency of every clock derived from it.
+    ///
+    /// The user manual doesn't document the step size, or which range of
+    /// values is safe to use. Values far from the factory trim value can
+    /// result in an unstable clock, or one that is outside of the range the
+    /// microcontroller is specified for. Adjust the value in small steps, and
+    /// verify the result against a reference.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// let factory_trim = syscon.handle.irc_trim();
+    ///
+    /// // The IRC is a bit slow on this device, according to our reference.
+    /// syscon.handle.set_irc_trim(factory_trim + 1);
+    /// assert_eq!(syscon.handle.irc_trim(), factory_trim + 1);
+    /// ```
+    pub fn set_irc_trim(&mut self, trim: u8) {
+        write_trim(&self.ircctrl, trim);
+    }
+}
+
+fn read_trim(ircctrl: &IRCCTRL) -> u8 {
+    ircctrl.read().trim().bits()
+}
+
+fn write_trim(ircctrl: &IRCCTRL, trim: u8) {
+    // Sound, as all `u8` values are valid for this field.
+    ircctrl.write(|w| unsafe { w.trim().bits(trim) });
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use crate::pac;
+
+    fn register_block() -> pac::syscon::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        unsafe { mem::zeroed() }
+    }
+
+    #[test]
+    fn trim_round_trips() {
+        let syscon = register_block();
+
+        for &trim in &[super::RESET_TRIM, 0x00, 0x7f, 0x81, 0xff] {
+            super::write_trim(&syscon.ircctrl, trim);
+
+            assert_eq!(super::read_trim(&syscon.ircctrl), trim);
+            assert_eq!(syscon.ircctrl.read().bits(), trim as u32);
+        }
+    }
+}
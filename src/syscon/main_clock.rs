@@ -0,0 +1,287 @@
+//! API for selecting the main clock
+//!
+//! The main clock drives the core, memories, and most peripherals. It can be
+//! switched to any clock that implements [`MainClockSource`], using
+//! [`syscon::Handle::set_main_clock`]:
+//!
+//! - The IRC/FRO ([`IOSC`]), which is the default after reset
+//! - The system oscillator ([`SysOscClock`])
+//! - The watchdog oscillator ([`WdtOscClock`])
+//!
+//! Running the main clock from the system PLL is not supported yet.
+//!
+//! [`MainClockSource`]: trait.MainClockSource.html
+//! [`syscon::Handle::set_main_clock`]: ../struct.Handle.html#method.set_main_clock
+//! [`IOSC`]: ../struct.IOSC.html
+//! [`SysOscClock`]: ../sysosc/struct.SysOscClock.html
+//! [`WdtOscClock`]: ../wdtosc/struct.WdtOscClock.html
+
+use crate::clock;
+
+use super::{sysosc::SysOscClock, wdtosc::WdtOscClock, Handle, IOSC};
+
+/// The main clock, running off a specific source
+///
+/// Returned by [`syscon::Handle::set_main_clock`]. Implements
+/// [`clock::Frequency`], returning the frequency of the source.
+///
+/// Keeps a shared reference to the source. This prevents the source from
+/// being consumed while the `MainClock` exists, so methods like
+/// [`SysOscClock::disable`] and [`WdtOscClock::disable`] can't be called.
+///
+/// This is not a complete guarantee that the main clock keeps running off the
+/// source. APIs that don't take ownership of the source can still interfere,
+/// for example by selecting a different main clock, or by powering down the
+/// source's analog block using [`syscon::Handle::power_down`]. The frequency
+/// reported by `MainClock` is only accurate, if nothing like that happens.
+///
+/// [`syscon::Handle::set_main_clock`]: ../struct.Handle.html#method.set_main_clock
+/// [`clock::Frequency`]: ../../clock/trait.Frequency.html
+/// [`SysOscClock::disable`]: ../sysosc/struct.SysOscClock.html#method.disable
+/// [`WdtOscClock::disable`]: ../wdtosc/struct.WdtOscClock.html#method.disable
+/// [`syscon::Handle::power_down`]: ../struct.Handle.html#method.power_down
+#[derive(Debug)]
+pub struct MainClock<'source, C> {
+    source: &'source C,
+}
+
+impl<'source, C> MainClock<'source, C> {
+    /// Return a reference to the source of the main clock
+    pub fn source(&self) -> &'source C {
+        self.source
+    }
+}
+
+impl<'source, C> clock::Frequency for MainClock<'source, C>
+where
+    C: clock::Frequency,
+{
+    fn hz(&self) -> u32 {
+        self.source.hz()
+    }
+}
+
+impl<'source, C> clock::Enabled for MainClock<'source, C> where
+    C: clock::Frequency
+{
+}
+
+/// Implemented for clocks that can be selected as the main clock
+///
+/// Please refer to [`syscon::Handle::set_main_clock`] for the public API that
+/// uses this trait.
+///
+/// [`syscon::Handle::set_main_clock`]: ../struct.Handle.html#method.set_main_clock
+pub trait MainClockSource: clock::Frequency + private::Sealed {
+    /// Internal method to select the clock in MAINCLKSEL
+    ///
+    /// This is an internal method, to be called by the SYSCON API. It only
+    /// writes the selection, but doesn't apply it. Users generally shouldn't
+    /// need to call this. This method is exempt from any guarantees of API
+    /// stability.
+    fn select(&self, syscon: &mut Handle);
+}
+
+impl Handle {
+    /// Switch the main clock to another source
+    ///
+    /// Selects `source` in MAINCLKSEL, then toggles MAINCLKUEN from 0 to 1,
+    /// which is required for the new selection to take effect. On LPC84x, the
+    /// PLL is also bypassed, so the main clock runs off the source directly.
+    ///
+    /// Returns a [`MainClock`], which borrows `source` for as long as it
+    /// exists.
+    ///
+    /// Please note that the clocks of some peripherals are derived from the
+    /// main clock. The configuration of those might need to be adapted after
+    /// changing the main clock.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     prelude::*,
+    ///     Peripherals,
+    ///     syscon::wdtosc::WdtOscClock,
+    /// };
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// let wdtosc = WdtOscClock::enable(syscon.wdtosc, &mut syscon.handle, 1, 0);
+    ///
+    /// let main_clock = syscon.handle.set_main_clock(&wdtosc);
+    /// assert_eq!(main_clock.hz(), 300_000);
+    ///
+    /// // Switch back to the IRC/FRO
+    /// let main_clock = syscon.handle.set_main_clock(&syscon.iosc);
+    /// assert_eq!(main_clock.hz(), 12_000_000);
+    /// ```
+    ///
+    /// [`MainClock`]: main_clock/struct.MainClock.html
+    pub fn set_main_clock<'source, C>(
+        &mut self,
+        source: &'source C,
+    ) -> MainClock<'source, C>
+    where
+        C: MainClockSource,
+    {
+        switch_main_clock(self, source);
+        target::bypass_pll(self);
+
+        MainClock { source }
+    }
+}
+
+fn switch_main_clock<C>(syscon: &mut impl MainClockWrites, source: &C)
+where
+    C: MainClockSource,
+{
+    syscon.write_selection(source);
+
+    // The new selection only takes effect after MAINCLKUEN has been toggled
+    // from 0 to 1. Without this, the old source stays selected.
+    syscon.write_update_enable(false);
+    syscon.write_update_enable(true);
+}
+
+/// The register writes that switch the main clock
+///
+/// Only implemented by `Handle`. Exists, so the tests can record the order of
+/// the writes.
+trait MainClockWrites {
+    /// Select the source in MAINCLKSEL
+    fn write_selection<C>(&mut self, source: &C)
+    where
+        C: MainClockSource;
+
+    /// Write the ENA bit of MAINCLKUEN
+    fn write_update_enable(&mut self, ena: bool);
+}
+
+impl MainClockWrites for Handle {
+    fn write_selection<C>(&mut self, source: &C)
+    where
+        C: MainClockSource,
+    {
+        source.select(self);
+    }
+
+    fn write_update_enable(&mut self, ena: bool) {
+        self.mainclkuen.write(|w| w.ena().bit(ena));
+    }
+}
+
+impl private::Sealed for IOSC {}
+impl private::Sealed for SysOscClock {}
+impl private::Sealed for WdtOscClock {}
+
+impl MainClockSource for IOSC {
+    fn select(&self, syscon: &mut Handle) {
+        target::select_iosc(syscon);
+    }
+}
+
+impl MainClockSource for SysOscClock {
+    fn select(&self, syscon: &mut Handle) {
+        target::select_sysosc(self, syscon);
+    }
+}
+
+impl MainClockSource for WdtOscClock {
+    fn select(&self, syscon: &mut Handle) {
+        syscon.mainclksel.write(|w| w.sel().wdtosc());
+    }
+}
+
+#[cfg(feature = "82x")]
+mod target {
+    use crate::syscon::{sysosc::SysOscClock, Handle};
+
+    pub fn select_iosc(syscon: &mut Handle) {
+        syscon.mainclksel.write(|w| w.sel().irc_osc());
+    }
+
+    pub fn select_sysosc(sysosc: &SysOscClock, syscon: &mut Handle) {
+        // On LPC82x, the system oscillator can only reach the main clock
+        // through the PLL input.
+        sysosc.select_as_pll_input(syscon);
+        syscon.mainclksel.write(|w| w.sel().pll_in());
+    }
+
+    pub fn bypass_pll(_: &mut Handle) {
+        // nothing to do; on LPC82x, MAINCLKSEL selects between the PLL output
+        // and the other sources directly
+    }
+}
+
+#[cfg(feature = "845")]
+mod target {
+    use crate::syscon::{sysosc::SysOscClock, Handle};
+
+    pub fn select_iosc(syscon: &mut Handle) {
+        syscon.mainclksel.write(|w| w.sel().fro());
+    }
+
+    pub fn select_sysosc(_: &SysOscClock, syscon: &mut Handle) {
+        syscon.extclksel.write(|w| w.sel().sys_osc());
+        syscon.mainclksel.write(|w| w.sel().ext_clk());
+    }
+
+    pub fn bypass_pll(syscon: &mut Handle) {
+        syscon.mainclkpllsel.write(|w| w.sel().main_clk_pre_pll());
+        syscon.mainclkplluen.write(|w| w.ena().clear_bit());
+        syscon.mainclkplluen.write(|w| w.ena().set_bit());
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syscon::IOSC;
+
+    use super::{MainClockSource, MainClockWrites};
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Write {
+        MainClkSel,
+        MainClkUen(bool),
+    }
+
+    /// Records the register writes, in the order they are done in
+    #[derive(Default)]
+    struct WriteLog(Vec<Write>);
+
+    impl MainClockWrites for WriteLog {
+        fn write_selection<C>(&mut self, _: &C)
+        where
+            C: MainClockSource,
+        {
+            self.0.push(Write::MainClkSel);
+        }
+
+        fn write_update_enable(&mut self, ena: bool) {
+            self.0.push(Write::MainClkUen(ena));
+        }
+    }
+
+    #[test]
+    fn switch_main_clock_selects_before_toggling_update_enable() {
+        let mut log = WriteLog::default();
+
+        super::switch_main_clock(&mut log, &IOSC(()));
+
+        assert_eq!(
+            log.0,
+            [
+                Write::MainClkSel,
+                Write::MainClkUen(false),
+                Write::MainClkUen(true),
+            ],
+        );
+    }
+}
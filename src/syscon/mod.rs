@@ -15,20 +15,34 @@ pub mod frg;
 #[cfg(feature = "845")]
 pub use self::frg::FRG;
 
+pub use self::{clocks::Clocks, enable::Enable};
+
 pub mod clock_source;
+pub mod clocks;
+pub mod flash;
+#[cfg(feature = "82x")]
+pub mod irc;
+pub mod main_clock;
+pub mod sysosc;
+pub mod wdtosc;
+
+mod enable;
 
 #[cfg(feature = "82x")]
 use crate::pac::syscon::{
     pdruncfg, presetctrl as presetctrl0, starterp1,
-    sysahbclkctrl as sysahbclkctrl0, PDRUNCFG, PRESETCTRL as PRESETCTRL0,
-    STARTERP1, SYSAHBCLKCTRL as SYSAHBCLKCTRL0, UARTCLKDIV, UARTFRGDIV,
-    UARTFRGMULT,
+    sysahbclkctrl as sysahbclkctrl0, IRCCTRL, MAINCLKSEL, MAINCLKUEN, PDRUNCFG,
+    PDSLEEPCFG, PRESETCTRL as PRESETCTRL0, STARTERP1,
+    SYSAHBCLKCTRL as SYSAHBCLKCTRL0, SYSAHBCLKDIV, SYSOSCCTRL, SYSPLLCLKSEL,
+    SYSPLLCLKUEN, SYSRSTSTAT, UARTCLKDIV, UARTFRGDIV, UARTFRGMULT, WDTOSCCTRL,
 };
 
 #[cfg(feature = "845")]
 use crate::pac::syscon::{
-    pdruncfg, presetctrl0, starterp1, sysahbclkctrl0, FCLKSEL, PDRUNCFG,
-    PRESETCTRL0, STARTERP1, SYSAHBCLKCTRL0,
+    pdruncfg, presetctrl0, starterp1, sysahbclkctrl0, EXTCLKSEL, FCLKSEL,
+    MAINCLKPLLSEL, MAINCLKPLLUEN, MAINCLKSEL, MAINCLKUEN, PDRUNCFG, PDSLEEPCFG,
+    PRESETCTRL0, STARTERP0, STARTERP1, SYSAHBCLKCTRL0, SYSAHBCLKDIV,
+    SYSOSCCTRL, SYSPLLCLKSEL, SYSPLLCLKUEN, SYSRSTSTAT, WDTOSCCTRL,
 };
 
 use crate::{clock, init_state, pac, reg_proxy::RegProxy};
@@ -68,11 +82,30 @@ impl SYSCON {
         Parts {
             handle: Handle {
                 pdruncfg: RegProxy::new(),
+                pdsleepcfg: RegProxy::new(),
                 presetctrl0: RegProxy::new(),
+                #[cfg(feature = "845")]
+                starterp0: RegProxy::new(),
                 starterp1: RegProxy::new(),
                 sysahbclkctrl: RegProxy::new(),
+                sysahbclkdiv: RegProxy::new(),
+                sysrststat: RegProxy::new(),
+                sysoscctrl: RegProxy::new(),
+                wdtoscctrl: RegProxy::new(),
+                mainclksel: RegProxy::new(),
+                mainclkuen: RegProxy::new(),
+                syspllclksel: RegProxy::new(),
+                syspllclkuen: RegProxy::new(),
+                #[cfg(feature = "845")]
+                extclksel: RegProxy::new(),
+                #[cfg(feature = "845")]
+                mainclkpllsel: RegProxy::new(),
+                #[cfg(feature = "845")]
+                mainclkplluen: RegProxy::new(),
                 #[cfg(feature = "845")]
                 fclksel: RegProxy::new(),
+                #[cfg(feature = "82x")]
+                ircctrl: RegProxy::new(),
             },
 
             bod: BOD(()),
@@ -84,6 +117,7 @@ impl SYSCON {
             rom: ROM(()),
             sysosc: SYSOSC(()),
             syspll: SYSPLL(()),
+            wdtosc: WDTOSC(()),
 
             #[cfg(feature = "82x")]
             uartfrg: UARTFRG {
@@ -154,6 +188,9 @@ pub struct Parts {
     /// PLL
     pub syspll: SYSPLL,
 
+    /// Watchdog oscillator
+    pub wdtosc: WDTOSC,
+
     #[cfg(feature = "82x")]
     /// UART Fractional Baud Rate Generator
     pub uartfrg: UARTFRG,
@@ -182,11 +219,30 @@ pub struct Parts {
 /// [module documentation]: index.html
 pub struct Handle {
     pdruncfg: RegProxy<PDRUNCFG>,
+    pdsleepcfg: RegProxy<PDSLEEPCFG>,
     presetctrl0: RegProxy<PRESETCTRL0>,
+    #[cfg(feature = "845")]
+    starterp0: RegProxy<STARTERP0>,
     starterp1: RegProxy<STARTERP1>,
     sysahbclkctrl: RegProxy<SYSAHBCLKCTRL0>,
+    sysahbclkdiv: RegProxy<SYSAHBCLKDIV>,
+    sysrststat: RegProxy<SYSRSTSTAT>,
+    pub(crate) sysoscctrl: RegProxy<SYSOSCCTRL>,
+    pub(crate) wdtoscctrl: RegProxy<WDTOSCCTRL>,
+    pub(crate) mainclksel: RegProxy<MAINCLKSEL>,
+    pub(crate) mainclkuen: RegProxy<MAINCLKUEN>,
+    pub(crate) syspllclksel: RegProxy<SYSPLLCLKSEL>,
+    pub(crate) syspllclkuen: RegProxy<SYSPLLCLKUEN>,
+    #[cfg(feature = "845")]
+    pub(crate) extclksel: RegProxy<EXTCLKSEL>,
+    #[cfg(feature = "845")]
+    pub(crate) mainclkpllsel: RegProxy<MAINCLKPLLSEL>,
+    #[cfg(feature = "845")]
+    pub(crate) mainclkplluen: RegProxy<MAINCLKPLLUEN>,
     #[cfg(feature = "845")]
     pub(crate) fclksel: RegProxy<FCLKSEL>,
+    #[cfg(feature = "82x")]
+    pub(crate) ircctrl: RegProxy<IRCCTRL>,
 }
 
 impl Handle {
@@ -232,6 +288,52 @@ impl Handle {
         self.pdruncfg.modify(|_, w| peripheral.power_down(w));
     }
 
+    /// Configure what stays powered in deep-sleep and power-down modes
+    ///
+    /// Writes the PDSLEEPCFG register, which determines whether the brown-out
+    /// detector and the watchdog oscillator keep running while the
+    /// microcontroller is in deep-sleep or power-down mode. Please refer to
+    /// [`DeepSleepConfig`] for details on what this means for the peripherals
+    /// that can wake up the microcontroller.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{syscon::DeepSleepConfig, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// // Keep the watchdog oscillator running, so the watchdog timer keeps
+    /// // counting and can wake up the microcontroller.
+    /// syscon.handle.configure_deep_sleep(DeepSleepConfig {
+    ///     wdtosc: true,
+    ///     ..DeepSleepConfig::default()
+    /// });
+    /// ```
+    ///
+    /// [`DeepSleepConfig`]: struct.DeepSleepConfig.html
+    pub fn configure_deep_sleep(&mut self, config: DeepSleepConfig) {
+        // Only write the two fields. The other bits are reserved and must keep
+        // their reset value.
+        self.pdsleepcfg.modify(|_, w| {
+            w.bod_pd().bit(!config.bod);
+            w.wdtosc_pd().bit(!config.wdtosc)
+        });
+    }
+
+    /// Read what stays powered in deep-sleep and power-down modes
+    ///
+    /// Returns the configuration that was last written using
+    /// [`configure_deep_sleep`], or the reset value, where nothing stays
+    /// powered.
+    ///
+    /// [`configure_deep_sleep`]: #method.configure_deep_sleep
+    pub fn deep_sleep_config(&self) -> DeepSleepConfig {
+        DeepSleepConfig::from_bits(self.pdsleepcfg.read().bits())
+    }
+
     /// Enable interrupt wake-up from deep-sleep and power-down modes
     ///
     /// To use an interrupt for waking up the system from the deep-sleep and
@@ -253,6 +355,212 @@ impl Handle {
     {
         self.starterp1.modify(|_, w| I::disable(w));
     }
+
+    /// Enable pin interrupt wake-up from deep-sleep and power-down modes
+    ///
+    /// Sets the bits in `mask` in the STARTERP0 register. Used by the PININT
+    /// API, which knows which bit belongs to which pin interrupt.
+    #[cfg(feature = "845")]
+    pub(crate) fn enable_pin_interrupt_wakeup(&mut self, mask: u8) {
+        // Sound, as bits 0 to 7 of STARTERP0 enable the wake-up for pin
+        // interrupts 0 to 7, and we only set the bits in `mask`.
+        self.starterp0
+            .modify(|r, w| unsafe { w.bits(r.bits() | mask as u32) });
+    }
+
+    /// Disable pin interrupt wake-up from deep-sleep and power-down modes
+    ///
+    /// Clears the bits in `mask` in the STARTERP0 register.
+    #[cfg(feature = "845")]
+    pub(crate) fn disable_pin_interrupt_wakeup(&mut self, mask: u8) {
+        // Sound, as we only clear bits that enable pin interrupt wake-up.
+        self.starterp0
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(mask as u32)) });
+    }
+
+    /// Read the cause of the last reset
+    ///
+    /// Reads the system reset status register (SYSRSTSTAT). The flags in this
+    /// register are not cleared by reading them, and they accumulate across
+    /// resets, until they're explicitly cleared. Use
+    /// [`syscon::Handle::clear_reset_cause`] to do that.
+    ///
+    /// [`syscon::Handle::clear_reset_cause`]: #method.clear_reset_cause
+    pub fn reset_cause(&self) -> ResetCause {
+        ResetCause::from_bits(self.sysrststat.read().bits() as u8)
+    }
+
+    /// Clear the reset cause flags
+    ///
+    /// Clears all flags in the system reset status register (SYSRSTSTAT) that
+    /// are set in `cause`. This is not done automatically by
+    /// [`syscon::Handle::reset_cause`], to make sure that the flags can still
+    /// be observed by other means (a debugger, for example), unless they are
+    /// explicitly cleared.
+    ///
+    /// Pass the value returned by [`syscon::Handle::reset_cause`] to clear the
+    /// flags that have been observed, without accidentally clearing a flag
+    /// that was set in the meantime.
+    ///
+    /// [`syscon::Handle::reset_cause`]: #method.reset_cause
+    pub fn clear_reset_cause(&mut self, cause: ResetCause) {
+        // The flags are cleared by writing a one to them.
+        self.sysrststat.write(|w| {
+            w.por().bit(cause.power_on);
+            w.extrst().bit(cause.external);
+            w.wdt().bit(cause.watchdog);
+            w.bod().bit(cause.brown_out);
+            w.sysrst().bit(cause.system)
+        });
+    }
+}
+
+/// The cause of a reset
+///
+/// Returned by [`syscon::Handle::reset_cause`]. Multiple flags can be set at
+/// the same time, as they accumulate until they are cleared.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::syscon::ResetCause;
+///
+/// // Power-on reset and watchdog reset
+/// let cause = ResetCause::from_bits(0b00101);
+///
+/// assert!(cause.power_on);
+/// assert!(!cause.external);
+/// assert!(cause.watchdog);
+/// assert!(!cause.brown_out);
+/// assert!(!cause.system);
+///
+/// assert_eq!(cause.bits(), 0b00101);
+/// ```
+///
+/// [`syscon::Handle::reset_cause`]: struct.Handle.html#method.reset_cause
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResetCause {
+    /// Power-on reset (POR)
+    pub power_on: bool,
+
+    /// Reset from the external reset pin (EXTRST)
+    pub external: bool,
+
+    /// Reset from the watchdog timer (WDT)
+    pub watchdog: bool,
+
+    /// Brown-out detection reset (BOD)
+    pub brown_out: bool,
+
+    /// System reset requested by software (SYSRST)
+    pub system: bool,
+}
+
+impl ResetCause {
+    /// Decode the reset cause from the contents of SYSRSTSTAT
+    ///
+    /// Bits that don't correspond to a reset cause are ignored.
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            power_on: bits & 0x01 != 0,
+            external: bits & 0x02 != 0,
+            watchdog: bits & 0x04 != 0,
+            brown_out: bits & 0x08 != 0,
+            system: bits & 0x10 != 0,
+        }
+    }
+
+    /// Encode the reset cause in the format of SYSRSTSTAT
+    pub fn bits(&self) -> u8 {
+        (self.power_on as u8)
+            | (self.external as u8) << 1
+            | (self.watchdog as u8) << 2
+            | (self.brown_out as u8) << 3
+            | (self.system as u8) << 4
+    }
+}
+
+/// Determines what stays powered in deep-sleep and power-down modes
+///
+/// Passed to [`syscon::Handle::configure_deep_sleep`]. Corresponds to the
+/// PDSLEEPCFG register.
+///
+/// In deep-sleep and power-down modes, all clocks that are derived from the
+/// IRC/FRO or the system oscillator are stopped, regardless of this
+/// configuration. This means a peripheral that needs its clock to detect an
+/// event can't wake up the microcontroller. The following wake-up sources
+/// still work:
+///
+/// - Pin interrupts and pattern match, and the WAKEUP pin.
+/// - The WKT, if it runs from the low-power oscillator.
+/// - The watchdog timer, with `wdtosc` set to `true`.
+/// - A brown-out detection interrupt or reset, with `bod` set to `true`.
+/// - USART in synchronous slave mode, and I2C and SPI in slave mode, as they
+///   are clocked by the bus master. In asynchronous mode, the USART can't
+///   receive, but a start bit can wake up the microcontroller via a pin
+///   interrupt on the RX pin.
+///
+/// The wake-up interrupt also needs to be enabled, using
+/// [`syscon::Handle::enable_interrupt_wakeup`].
+///
+/// Every block that stays powered increases the current consumption in
+/// deep-sleep and power-down modes. Please refer to the datasheet for the
+/// current draw with and without the watchdog oscillator and the brown-out
+/// detector powered. The default value powers everything down.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::syscon::DeepSleepConfig;
+///
+/// // Nothing stays powered after reset.
+/// let config = DeepSleepConfig::from_bits(0xffff);
+/// assert_eq!(config, DeepSleepConfig::default());
+///
+/// // A set bit powers down the block, so a cleared one keeps it running.
+/// let config = DeepSleepConfig {
+///     wdtosc: true,
+///     ..DeepSleepConfig::default()
+/// };
+/// assert_eq!(config.bits(), 0b000_1000);
+///
+/// let config = DeepSleepConfig {
+///     bod: true,
+///     wdtosc: true,
+/// };
+/// assert_eq!(config.bits(), 0b000_0000);
+/// ```
+///
+/// [`syscon::Handle::configure_deep_sleep`]: struct.Handle.html#method.configure_deep_sleep
+/// [`syscon::Handle::enable_interrupt_wakeup`]: struct.Handle.html#method.enable_interrupt_wakeup
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeepSleepConfig {
+    /// Keep the brown-out detector powered (BOD_PD)
+    pub bod: bool,
+
+    /// Keep the watchdog oscillator running (WDTOSC_PD)
+    pub wdtosc: bool,
+}
+
+impl DeepSleepConfig {
+    /// Decode the configuration from the contents of PDSLEEPCFG
+    ///
+    /// Bits that don't correspond to a field are ignored.
+    pub fn from_bits(bits: u32) -> Self {
+        Self {
+            bod: bits & 0x08 == 0,
+            wdtosc: bits & 0x40 == 0,
+        }
+    }
+
+    /// Encode the configuration in the format of PDSLEEPCFG
+    ///
+    /// Only includes the BOD_PD and WDTOSC_PD bits. All other bits are zero.
+    pub fn bits(&self) -> u32 {
+        (!self.bod as u32) << 3 | (!self.wdtosc as u32) << 6
+    }
 }
 
 /// Brown-out detection
@@ -333,6 +641,16 @@ pub struct SYSOSC(());
 #[derive(Debug)]
 pub struct SYSPLL(());
 
+/// Watchdog oscillator
+///
+/// Can be used to control the watchdog oscillator using various methods on
+/// [`syscon::Handle`]. See [`WdtOscClock`] for a higher-level API.
+///
+/// [`syscon::Handle`]: struct.Handle.html
+/// [`WdtOscClock`]: wdtosc/struct.WdtOscClock.html
+#[derive(Debug)]
+pub struct WDTOSC(());
+
 #[cfg(feature = "82x")]
 /// UART Fractional Baud Rate Generator
 ///
@@ -617,6 +935,7 @@ impl_analog_block!(BOD, bod_pd);
 impl_analog_block!(pac::ADC0, adc_pd);
 impl_analog_block!(SYSOSC, sysosc_pd);
 impl_analog_block!(pac::WWDT, wdtosc_pd);
+impl_analog_block!(WDTOSC, wdtosc_pd);
 impl_analog_block!(SYSPLL, syspll_pd);
 impl_analog_block!(pac::ACOMP, acmp);
 
@@ -670,6 +989,16 @@ impl IoscDerivedClock<init_state::Disabled> {
     }
 }
 
+/// The IRC/FRO runs at 12 MHz
+///
+/// This is the case after reset. On LPC84x, the FRO can be configured to run
+/// at different frequencies, which this API doesn't support yet.
+impl clock::Frequency for IOSC {
+    fn hz(&self) -> u32 {
+        12_000_000
+    }
+}
+
 impl<State> clock::Frequency for IoscDerivedClock<State> {
     fn hz(&self) -> u32 {
         750_000
@@ -735,17 +1064,36 @@ wakeup_interrupt!(I2c2Wakeup, i2c2);
 wakeup_interrupt!(I2c3Wakeup, i2c3);
 
 reg!(PDRUNCFG, PDRUNCFG, pac::SYSCON, pdruncfg);
+reg!(PDSLEEPCFG, PDSLEEPCFG, pac::SYSCON, pdsleepcfg);
 #[cfg(feature = "82x")]
 reg!(PRESETCTRL0, PRESETCTRL0, pac::SYSCON, presetctrl);
 #[cfg(feature = "845")]
 reg!(PRESETCTRL0, PRESETCTRL0, pac::SYSCON, presetctrl0);
+#[cfg(feature = "845")]
+reg!(STARTERP0, STARTERP0, pac::SYSCON, starterp0);
 reg!(STARTERP1, STARTERP1, pac::SYSCON, starterp1);
+reg!(SYSAHBCLKDIV, SYSAHBCLKDIV, pac::SYSCON, sysahbclkdiv);
+reg!(SYSRSTSTAT, SYSRSTSTAT, pac::SYSCON, sysrststat);
+reg!(SYSOSCCTRL, SYSOSCCTRL, pac::SYSCON, sysoscctrl);
+reg!(WDTOSCCTRL, WDTOSCCTRL, pac::SYSCON, wdtoscctrl);
+reg!(MAINCLKSEL, MAINCLKSEL, pac::SYSCON, mainclksel);
+reg!(MAINCLKUEN, MAINCLKUEN, pac::SYSCON, mainclkuen);
+reg!(SYSPLLCLKSEL, SYSPLLCLKSEL, pac::SYSCON, syspllclksel);
+reg!(SYSPLLCLKUEN, SYSPLLCLKUEN, pac::SYSCON, syspllclkuen);
+#[cfg(feature = "845")]
+reg!(EXTCLKSEL, EXTCLKSEL, pac::SYSCON, extclksel);
+#[cfg(feature = "845")]
+reg!(MAINCLKPLLSEL, MAINCLKPLLSEL, pac::SYSCON, mainclkpllsel);
+#[cfg(feature = "845")]
+reg!(MAINCLKPLLUEN, MAINCLKPLLUEN, pac::SYSCON, mainclkplluen);
 #[cfg(feature = "82x")]
 reg!(SYSAHBCLKCTRL0, SYSAHBCLKCTRL0, pac::SYSCON, sysahbclkctrl);
 #[cfg(feature = "845")]
 reg!(SYSAHBCLKCTRL0, SYSAHBCLKCTRL0, pac::SYSCON, sysahbclkctrl0);
 #[cfg(feature = "845")]
 reg!(FCLKSEL, [FCLKSEL; 11], pac::SYSCON, fclksel);
+#[cfg(feature = "82x")]
+reg!(IRCCTRL, IRCCTRL, pac::SYSCON, ircctrl);
 
 #[cfg(feature = "82x")]
 reg!(UARTCLKDIV, UARTCLKDIV, pac::SYSCON, uartclkdiv);
@@ -0,0 +1,221 @@
+//! API for the system oscillator (SYSOSC)
+//!
+//! The system oscillator can be driven by an external crystal connected to the
+//! XTALIN and XTALOUT pins, or by an external clock signal connected to XTALIN
+//! (bypass mode).
+
+use crate::{
+    clock, swm,
+    syscon::{Handle, SYSOSC},
+};
+
+/// The lowest supported crystal frequency
+pub const MIN_FREQUENCY: u32 = 1_000_000;
+
+/// The highest supported crystal frequency
+pub const MAX_FREQUENCY: u32 = 25_000_000;
+
+/// The highest frequency that is still served by the low frequency range
+const LOW_RANGE_MAX: u32 = 20_000_000;
+
+/// The number of cycles to wait for the oscillator to stabilize
+///
+/// The user manual recommends waiting approximately 500 µs after powering up
+/// the system oscillator. This value is based on the highest frequency the
+/// main clock can run at by default (30 MHz on LPC84x).
+const STARTUP_CYCLES: u32 = 15_000;
+
+/// The system oscillator, running off an external crystal or clock signal
+///
+/// Can be created using [`SysOscClock::enable`] or
+/// [`SysOscClock::enable_bypass`]. Implements [`clock::Frequency`], returning
+/// the frequency that was passed on creation.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     Peripherals,
+///     syscon::sysosc::SysOscClock,
+/// };
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut swm = p.SWM.split();
+/// let mut syscon = p.SYSCON.split();
+///
+/// #[cfg(feature = "82x")]
+/// let mut swm_handle = swm.handle;
+/// #[cfg(feature = "845")]
+/// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+///
+/// let (xtalin, _) = swm.fixed_functions.xtalin.assign(
+///     p.pins.pio0_8.into_swm_pin(),
+///     &mut swm_handle,
+/// );
+/// let (xtalout, _) = swm.fixed_functions.xtalout.assign(
+///     p.pins.pio0_9.into_swm_pin(),
+///     &mut swm_handle,
+/// );
+///
+/// let sysosc = SysOscClock::enable(
+///     syscon.sysosc,
+///     &mut syscon.handle,
+///     12_000_000,
+///     &xtalin,
+///     &xtalout,
+/// );
+/// sysosc.select_as_main_clock(&mut syscon.handle);
+///
+/// assert_eq!(sysosc.hz(), 12_000_000);
+/// ```
+///
+/// [`SysOscClock::enable`]: #method.enable
+/// [`SysOscClock::enable_bypass`]: #method.enable_bypass
+/// [`clock::Frequency`]: ../../clock/trait.Frequency.html
+#[derive(Debug)]
+pub struct SysOscClock {
+    sysosc: SYSOSC,
+    frequency: u32,
+}
+
+impl SysOscClock {
+    /// Enable the system oscillator, driven by an external crystal
+    ///
+    /// `frequency` is the frequency of the crystal in Hz. It is used to select
+    /// the correct frequency range and is returned by [`clock::Frequency`].
+    /// Requires the XTALIN and XTALOUT functions to be assigned, to make sure
+    /// the crystal is actually connected to the oscillator.
+    ///
+    /// Powers up the system oscillator and blocks until it has had time to
+    /// stabilize. This assumes the main clock is not faster than 30 MHz.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `frequency` is not between [`MIN_FREQUENCY`] and
+    /// [`MAX_FREQUENCY`].
+    ///
+    /// [`clock::Frequency`]: ../../clock/trait.Frequency.html
+    /// [`MIN_FREQUENCY`]: constant.MIN_FREQUENCY.html
+    /// [`MAX_FREQUENCY`]: constant.MAX_FREQUENCY.html
+    pub fn enable<I, O>(
+        sysosc: SYSOSC,
+        syscon: &mut Handle,
+        frequency: u32,
+        _xtalin: &swm::Function<swm::XTALIN, swm::state::Assigned<I>>,
+        _xtalout: &swm::Function<swm::XTALOUT, swm::state::Assigned<O>>,
+    ) -> Self {
+        Self::configure(sysosc, syscon, frequency, false)
+    }
+
+    /// Enable the system oscillator in bypass mode
+    ///
+    /// In bypass mode, the system oscillator is driven by an external clock
+    /// signal connected to XTALIN, instead of a crystal. `frequency` is the
+    /// frequency of that signal in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `frequency` is not between [`MIN_FREQUENCY`] and
+    /// [`MAX_FREQUENCY`].
+    ///
+    /// [`MIN_FREQUENCY`]: constant.MIN_FREQUENCY.html
+    /// [`MAX_FREQUENCY`]: constant.MAX_FREQUENCY.html
+    pub fn enable_bypass<I>(
+        sysosc: SYSOSC,
+        syscon: &mut Handle,
+        frequency: u32,
+        _xtalin: &swm::Function<swm::XTALIN, swm::state::Assigned<I>>,
+    ) -> Self {
+        Self::configure(sysosc, syscon, frequency, true)
+    }
+
+    fn configure(
+        sysosc: SYSOSC,
+        syscon: &mut Handle,
+        frequency: u32,
+        bypass: bool,
+    ) -> Self {
+        assert!((MIN_FREQUENCY..=MAX_FREQUENCY).contains(&frequency));
+
+        // The frequency range must be configured before the oscillator is
+        // powered up.
+        syscon.sysoscctrl.write(|w| {
+            w.bypass().bit(bypass);
+            target::freq_range(w).bit(frequency > LOW_RANGE_MAX)
+        });
+        syscon.power_up(&sysosc);
+
+        cortex_m::asm::delay(STARTUP_CYCLES);
+
+        Self { sysosc, frequency }
+    }
+
+    /// Select the system oscillator as the main clock
+    ///
+    /// Please note that the clocks of some peripherals are derived from the
+    /// main clock. The configuration of those might need to be adapted after
+    /// changing the main clock.
+    pub fn select_as_main_clock(&self, syscon: &mut Handle) {
+        syscon.set_main_clock(self);
+    }
+
+    /// Select the system oscillator as the input of the system PLL
+    pub fn select_as_pll_input(&self, syscon: &mut Handle) {
+        target::select_as_pll_input(syscon);
+    }
+
+    /// Power down the system oscillator
+    ///
+    /// Returns the [`SYSOSC`] handle that was consumed on creation.
+    ///
+    /// Make sure the system oscillator is not used as the main clock, or as the
+    /// input of a running system PLL, before calling this method.
+    ///
+    /// [`SYSOSC`]: ../struct.SYSOSC.html
+    pub fn disable(self, syscon: &mut Handle) -> SYSOSC {
+        syscon.power_down(&self.sysosc);
+        self.sysosc
+    }
+}
+
+impl clock::Frequency for SysOscClock {
+    fn hz(&self) -> u32 {
+        self.frequency
+    }
+}
+
+impl clock::Enabled for SysOscClock {}
+
+#[cfg(feature = "82x")]
+mod target {
+    use crate::{pac::syscon::sysoscctrl, syscon::Handle};
+
+    pub fn freq_range(w: &mut sysoscctrl::W) -> sysoscctrl::FREQ_RANGE_W<'_> {
+        w.freq_range()
+    }
+
+    pub fn select_as_pll_input(syscon: &mut Handle) {
+        syscon.syspllclksel.write(|w| w.sel().sysosc());
+        syscon.syspllclkuen.write(|w| w.ena().clear_bit());
+        syscon.syspllclkuen.write(|w| w.ena().set_bit());
+    }
+}
+
+#[cfg(feature = "845")]
+mod target {
+    use crate::{pac::syscon::sysoscctrl, syscon::Handle};
+
+    pub fn freq_range(w: &mut sysoscctrl::W) -> sysoscctrl::FREQRANGE_W<'_> {
+        w.freqrange()
+    }
+
+    pub fn select_as_pll_input(syscon: &mut Handle) {
+        syscon.extclksel.write(|w| w.sel().sys_osc());
+
+        syscon.syspllclksel.write(|w| w.sel().ext_clk());
+        syscon.syspllclkuen.write(|w| w.ena().clear_bit());
+        syscon.syspllclkuen.write(|w| w.ena().set_bit());
+    }
+}
@@ -0,0 +1,165 @@
+//! API for the watchdog oscillator (WDTOSC)
+//!
+//! The watchdog oscillator is a low-power oscillator, that can be used as the
+//! main clock, or as the clock of the windowed watchdog timer. Its analog
+//! output frequency (Fclkana) can be selected between 0.6 MHz and 4.6 MHz, and
+//! is divided by a configurable divider, resulting in frequencies between
+//! 9.4 kHz and 2.3 MHz.
+//!
+//! Please note that the watchdog oscillator is very imprecise. Its frequency
+//! can deviate from the nominal value by up to ±40%, depending on process,
+//! voltage, and temperature. Don't use it for anything that requires accurate
+//! timing.
+
+use crate::{
+    clock,
+    syscon::{Handle, WDTOSC},
+};
+
+/// The nominal analog output frequencies (Fclkana), by FREQSEL value
+///
+/// The first entry corresponds to a FREQSEL value of 1. A value of 0 is not
+/// valid while the oscillator is running.
+pub const FREQUENCIES: [u32; 15] = [
+    600_000, 1_050_000, 1_400_000, 1_750_000, 2_100_000, 2_400_000, 2_700_000,
+    3_000_000, 3_250_000, 3_500_000, 3_750_000, 4_000_000, 4_200_000,
+    4_400_000, 4_600_000,
+];
+
+/// The highest valid DIVSEL value
+pub const MAX_DIVSEL: u8 = 0x1f;
+
+/// The watchdog oscillator
+///
+/// Can be created using [`WdtOscClock::enable`]. Implements
+/// [`clock::Frequency`], returning the nominal frequency of the selected
+/// configuration. Since the actual frequency can deviate from that by up to
+/// ±40%, the value returned by [`clock::Frequency`] is only a rough estimate.
+///
+/// # Limitations
+///
+/// The watchdog oscillator can't be used to run the self-wake-up timer (WKT).
+/// The WKT only supports the IRC/FRO-derived clock, the low-power clock, and
+/// an external clock on the WKTCLKIN pin. To keep the WKT running while the
+/// IRC/FRO is powered down, use the low-power clock ([`LowPowerClock`]).
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     Peripherals,
+///     syscon::wdtosc::WdtOscClock,
+/// };
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// // 0.6 MHz / 64 = 9.375 kHz
+/// let wdtosc = WdtOscClock::enable(syscon.wdtosc, &mut syscon.handle, 1, 31);
+/// wdtosc.select_as_main_clock(&mut syscon.handle);
+///
+/// assert_eq!(wdtosc.hz(), 9_375);
+/// ```
+///
+/// [`WdtOscClock::enable`]: #method.enable
+/// [`clock::Frequency`]: ../../clock/trait.Frequency.html
+/// [`LowPowerClock`]: ../../pmu/struct.LowPowerClock.html
+#[derive(Debug)]
+pub struct WdtOscClock {
+    wdtosc: WDTOSC,
+    frequency: u32,
+}
+
+impl WdtOscClock {
+    /// Configure and power up the watchdog oscillator
+    ///
+    /// `freqsel` selects the analog output frequency (see [`FREQUENCIES`]).
+    /// `divsel` selects the divider, which divides the analog output frequency
+    /// by `2 * (1 + divsel)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `freqsel` is not between 1 and 15, or if `divsel` is larger
+    /// than [`MAX_DIVSEL`].
+    ///
+    /// [`FREQUENCIES`]: constant.FREQUENCIES.html
+    /// [`MAX_DIVSEL`]: constant.MAX_DIVSEL.html
+    pub fn enable(
+        wdtosc: WDTOSC,
+        syscon: &mut Handle,
+        freqsel: u8,
+        divsel: u8,
+    ) -> Self {
+        let frequency = nominal_frequency(freqsel, divsel);
+
+        // The frequency must be configured before the oscillator is powered
+        // up, as FREQSEL 0 is invalid while it is running.
+        //
+        // Sound, as `nominal_frequency` checked that both values are valid.
+        syscon.wdtoscctrl.write(|w| unsafe {
+            w.freqsel().bits(freqsel).divsel().bits(divsel)
+        });
+        syscon.power_up(&wdtosc);
+
+        Self { wdtosc, frequency }
+    }
+
+    /// Select the watchdog oscillator as the main clock
+    ///
+    /// Please note that the clocks of some peripherals are derived from the
+    /// main clock. The configuration of those might need to be adapted after
+    /// changing the main clock.
+    pub fn select_as_main_clock(&self, syscon: &mut Handle) {
+        syscon.set_main_clock(self);
+    }
+
+    /// Power down the watchdog oscillator
+    ///
+    /// Returns the [`WDTOSC`] handle that was consumed on creation.
+    ///
+    /// Make sure the watchdog oscillator is not used as the main clock, or by
+    /// the windowed watchdog timer, before calling this method.
+    ///
+    /// [`WDTOSC`]: ../struct.WDTOSC.html
+    pub fn disable(self, syscon: &mut Handle) -> WDTOSC {
+        syscon.power_down(&self.wdtosc);
+        self.wdtosc
+    }
+}
+
+impl clock::Frequency for WdtOscClock {
+    fn hz(&self) -> u32 {
+        self.frequency
+    }
+}
+
+impl clock::Enabled for WdtOscClock {}
+
+/// Compute the nominal frequency for a configuration
+///
+/// Returns the nominal frequency, in Hz, that the watchdog oscillator runs at
+/// with the given FREQSEL and DIVSEL values.
+///
+/// # Panics
+///
+/// Panics, if `freqsel` is not between 1 and 15, or if `divsel` is larger than
+/// [`MAX_DIVSEL`].
+///
+/// # Example
+///
+/// ```
+/// use lpc8xx_hal::syscon::wdtosc::nominal_frequency;
+///
+/// assert_eq!(nominal_frequency(1, 31), 9_375);
+/// assert_eq!(nominal_frequency(15, 0), 2_300_000);
+/// ```
+///
+/// [`MAX_DIVSEL`]: constant.MAX_DIVSEL.html
+pub fn nominal_frequency(freqsel: u8, divsel: u8) -> u32 {
+    assert!((1..=FREQUENCIES.len() as u8).contains(&freqsel));
+    assert!(divsel <= MAX_DIVSEL);
+
+    FREQUENCIES[freqsel as usize - 1] / (2 * (1 + divsel as u32))
+}
@@ -0,0 +1,20 @@
+//! Support code for unit tests that run on the host
+//!
+//! The `cortex-m` crate implements its register accessors in an assembly
+//! library, which is only linked when building for a Cortex-M target. The
+//! functions here stand in for the ones that are needed by code under test,
+//! most notably `interrupt::free`. There are no interrupts on the host, so
+//! they don't need to do anything.
+
+#[no_mangle]
+extern "C" fn __cpsid() {}
+
+#[no_mangle]
+extern "C" fn __cpsie() {}
+
+#[no_mangle]
+extern "C" fn __primask_r() -> u32 {
+    // Interrupts are reported as disabled, so `interrupt::free` doesn't try to
+    // enable them afterwards.
+    1
+}
@@ -0,0 +1,694 @@
+//! Common interface for the timer peripherals
+//!
+//! This module provides the [`Timer`] trait, which abstracts over the
+//! different timers that are available on the microcontroller. It is
+//! implemented for the following timers:
+//!
+//! - The self-wake-up timer ([`WKT`])
+//! - The system timer ([`SYST`])
+//! - The channels of the multi-rate timer ([`mrt::Channel`])
+//!
+//! Code that only needs to wait for a number of timer ticks can be written
+//! generically over [`Timer`], which makes it possible to switch the timer
+//! backing it, without changing the code itself.
+//!
+//! [`AlarmSet`] builds on top of [`Timer`], to provide multiple independent
+//! alarms that are driven by a single timer.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     Peripherals,
+//!     timer::Timer,
+//! };
+//!
+//! fn wait<T: Timer>(timer: &mut T, ticks: u32) {
+//!     timer.start(ticks);
+//!     while let Err(nb::Error::WouldBlock) = timer.wait() {}
+//! }
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut wkt = p.WKT.enable(&mut syscon.handle);
+//! let mut mrt = p.MRT0.split(&mut syscon.handle).mrt0;
+//!
+//! // The WKT runs at 750 kHz by default, the MRT at 12 MHz. Both of these
+//! // wait for 10 ms.
+//! wait(&mut wkt, 7_500);
+//! wait(&mut mrt, 120_000);
+//! ```
+//!
+//! [`Timer`]: trait.Timer.html
+//! [`AlarmSet`]: struct.AlarmSet.html
+//! [`WKT`]: ../wkt/struct.WKT.html
+//! [`SYST`]: ../pac/struct.SYST.html
+//! [`mrt::Channel`]: ../mrt/struct.Channel.html
+
+use cortex_m::peripheral::syst::{self, SystClkSource};
+use embedded_hal::timer::CountDown;
+use void::Void;
+
+use crate::{
+    clock, init_state,
+    mrt::{self, Channel},
+    pac::SYST,
+    reg_proxy::Reg,
+    wkt::{self, WKT},
+};
+
+/// A timer that counts down a number of ticks
+///
+/// Please note that all timers count ticks of their respective clock. Which
+/// clock that is, and therefore how long a tick takes, depends on the timer
+/// and its configuration. Please refer to the documentation of the respective
+/// implementation for details.
+pub trait Timer {
+    /// The highest number of ticks that can be passed to [`Timer::start`]
+    ///
+    /// [`Timer::start`]: #tymethod.start
+    const MAX_TICKS: u32;
+
+    /// Start counting down the given number of ticks
+    ///
+    /// If the timer is already running, it is restarted.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero or larger than [`Timer::MAX_TICKS`].
+    ///
+    /// [`Timer::MAX_TICKS`]: #associatedconstant.MAX_TICKS
+    fn start(&mut self, ticks: u32);
+
+    /// Non-blockingly "waits" until the count down finishes
+    ///
+    /// Returns `Ok(())` once the ticks passed to [`Timer::start`] have
+    /// elapsed. Never finishes, if the timer is not running.
+    ///
+    /// [`Timer::start`]: #tymethod.start
+    fn wait(&mut self) -> nb::Result<(), Void>;
+
+    /// Stop the timer
+    ///
+    /// After calling this method, [`Timer::remaining`] returns zero and
+    /// [`Timer::wait`] won't finish until the timer is started again.
+    ///
+    /// [`Timer::remaining`]: #tymethod.remaining
+    /// [`Timer::wait`]: #tymethod.wait
+    fn cancel(&mut self);
+
+    /// Returns the number of ticks remaining until the count down finishes
+    ///
+    /// Returns zero, if the timer is not running, and once the count down has
+    /// finished. Timers that restart the count down by themselves keep
+    /// returning zero until [`Timer::wait`] has returned `Ok(())`, then return
+    /// the ticks remaining of the next count down.
+    ///
+    /// [`Timer::wait`]: #tymethod.wait
+    fn remaining(&self) -> u32;
+}
+
+/// The WKT counts ticks of the clock selected via [`WKT::select_clock`]
+///
+/// Once the count down has finished, the WKT stops and
+/// [`Timer::remaining`] returns zero.
+///
+/// [`WKT::select_clock`]: ../wkt/struct.WKT.html#method.select_clock
+/// [`Timer::remaining`]: trait.Timer.html#tymethod.remaining
+impl Timer for WKT<init_state::Enabled> {
+    const MAX_TICKS: u32 = u32::MAX;
+
+    fn start(&mut self, ticks: u32) {
+        assert!(ticks > 0);
+        CountDown::start(self, ticks);
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        CountDown::wait(self)
+    }
+
+    fn cancel(&mut self) {
+        self.clear_counter();
+    }
+
+    fn remaining(&self) -> u32 {
+        WKT::remaining(self)
+    }
+}
+
+/// SysTick counts ticks of the system clock
+///
+/// SysTick is used as a one-shot timer. It is stopped once the count down has
+/// finished, and [`Timer::remaining`] returns zero from then on.
+///
+/// The SysTick registers have unknown values after reset. Please call
+/// [`Timer::start`] or [`Timer::cancel`] before using the other methods.
+///
+/// [`Timer::remaining`]: trait.Timer.html#tymethod.remaining
+/// [`Timer::start`]: trait.Timer.html#tymethod.start
+/// [`Timer::cancel`]: trait.Timer.html#tymethod.cancel
+impl Timer for SYST {
+    const MAX_TICKS: u32 = 0x0100_0000;
+
+    fn start(&mut self, ticks: u32) {
+        assert!((1..=Self::MAX_TICKS).contains(&ticks));
+
+        self.disable_counter();
+        self.set_clock_source(SystClkSource::Core);
+
+        // The counter counts from the reload value down to zero, so the count
+        // down takes one tick longer than the reload value. A reload value of
+        // zero would never finish, so a single tick is rounded up to two. This
+        // also keeps the reload value from looking like a cancelled timer.
+        self.set_reload((ticks - 1).max(1));
+
+        // This also clears the COUNTFLAG bit.
+        self.clear_current();
+        self.enable_counter();
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        match syst_state(self) {
+            SystState::Finished => Ok(()),
+            SystState::Running | SystState::Cancelled => {
+                Err(nb::Error::WouldBlock)
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        syst_cancel(self);
+    }
+
+    fn remaining(&self) -> u32 {
+        syst_remaining(self)
+    }
+}
+
+const SYST_COUNTER_MASK: u32 = 0x00ff_ffff;
+const SYST_CSR_ENABLE: u32 = 1 << 0;
+const SYST_CSR_COUNTFLAG: u32 = 1 << 16;
+
+#[derive(Debug, Eq, PartialEq)]
+enum SystState {
+    Running,
+    Finished,
+    Cancelled,
+}
+
+// SysTick doesn't stop by itself, once the count down has finished. It reloads
+// the counter and sets COUNTFLAG, which is cleared by reading CSR. Therefore
+// SysTick is stopped as soon as COUNTFLAG is seen, and a stopped SysTick with a
+// non-zero reload value is considered finished. `syst_cancel` sets the reload
+// value to zero, which `Timer::start` never does.
+fn syst_state(syst: &syst::RegisterBlock) -> SystState {
+    let csr = syst.csr.read();
+
+    if csr & SYST_CSR_ENABLE == 0 {
+        if syst.rvr.read() == 0 {
+            return SystState::Cancelled;
+        }
+        return SystState::Finished;
+    }
+    if csr & SYST_CSR_COUNTFLAG == 0 {
+        return SystState::Running;
+    }
+
+    // Sound, as we only clear the ENABLE bit. COUNTFLAG is read-only.
+    unsafe { syst.csr.write(csr & !SYST_CSR_ENABLE) };
+    SystState::Finished
+}
+
+fn syst_remaining(syst: &syst::RegisterBlock) -> u32 {
+    // Read the counter first. If it wraps after that, `syst_state` sees it.
+    let current = syst.cvr.read() & SYST_COUNTER_MASK;
+
+    match syst_state(syst) {
+        SystState::Running => current,
+        SystState::Finished | SystState::Cancelled => 0,
+    }
+}
+
+fn syst_cancel(syst: &syst::RegisterBlock) {
+    // Sound, as the timer stays stopped, and writing any value to CVR clears
+    // it.
+    unsafe {
+        syst.csr.modify(|csr| csr & !SYST_CSR_ENABLE);
+        syst.rvr.write(0);
+        syst.cvr.write(0);
+    }
+}
+
+/// MRT channels count ticks of the system clock
+///
+/// MRT channels run in repeat mode. Once the count down has finished, the
+/// channel restarts it. [`Timer::remaining`] returns zero until
+/// [`Timer::wait`] has returned `Ok(())`, then counts down from the beginning.
+///
+/// [`Timer::remaining`]: trait.Timer.html#tymethod.remaining
+/// [`Timer::wait`]: trait.Timer.html#tymethod.wait
+impl<T> Timer for Channel<T>
+where
+    T: Reg + mrt::Trait,
+{
+    const MAX_TICKS: u32 = mrt::MAX_TICKS;
+
+    fn start(&mut self, ticks: u32) {
+        assert!((1..=Self::MAX_TICKS).contains(&ticks));
+
+        // Sound, as we just checked that `ticks` is within the valid range.
+        Channel::start(self, unsafe { mrt::Ticks::from_u32(ticks) });
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        CountDown::wait(self)
+    }
+
+    fn cancel(&mut self) {
+        self.stop();
+    }
+
+    fn remaining(&self) -> u32 {
+        Channel::remaining(self)
+    }
+}
+
+/// Multiple software alarms, driven by a single timer
+///
+/// Keeps track of up to `N` alarms, and always programs the timer for the
+/// alarm that is due next. This makes it possible to have multiple
+/// independent timeouts, while only using a single timer, like the [`WKT`].
+///
+/// Alarms are scheduled using [`AlarmSet::schedule`], which returns an
+/// [`AlarmId`] that identifies the alarm. [`AlarmSet::wait`] returns the
+/// [`AlarmId`] of each alarm, once it has elapsed, and re-arms the timer for
+/// the next one. It needs to be called regularly, at least once per count down
+/// of the timer.
+///
+/// All times are measured in ticks of the timer. Internally, time is tracked
+/// using a 64-bit counter, so wraparound of the timer's counter is not a
+/// concern. Please note that every time the timer is re-armed, a few clock
+/// cycles elapse between reading the timer and restarting it, which are not
+/// accounted for.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{timer::AlarmSet, Peripherals};
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let wkt = p.WKT.enable(&mut syscon.handle);
+///
+/// // The WKT runs at 750 kHz by default.
+/// let mut alarms = AlarmSet::<_, 4>::from_timer(wkt);
+///
+/// let blink = alarms.schedule(375_000).unwrap(); // 500 ms
+/// let timeout = alarms.schedule(7_500_000).unwrap(); // 10 s
+///
+/// loop {
+///     let alarm = nb::block!(alarms.wait()).unwrap();
+///
+///     if alarm == blink {
+///         // toggle an LED, then schedule the next blink
+///         alarms.schedule(375_000).unwrap();
+///     }
+///     if alarm == timeout {
+///         break;
+///     }
+/// }
+/// ```
+///
+/// [`WKT`]: ../wkt/struct.WKT.html
+/// [`AlarmSet::schedule`]: #method.schedule
+/// [`AlarmId`]: struct.AlarmId.html
+/// [`AlarmSet::wait`]: #method.wait
+pub struct AlarmSet<T, const N: usize> {
+    timer: T,
+
+    /// The time at which the timer was last started, or last finished
+    now: u64,
+
+    /// The ticks the timer is currently counting down, if it's running
+    armed: Option<u32>,
+
+    /// The deadlines of the scheduled alarms
+    deadlines: [Option<u64>; N],
+
+    /// Incremented whenever a slot is reused, to detect stale alarm IDs
+    generations: [u32; N],
+}
+
+impl<const N: usize> AlarmSet<WKT<init_state::Enabled>, N> {
+    /// Create an `AlarmSet` that is driven by the self-wake-up timer
+    ///
+    /// Selects `clock` as the clock of the WKT. All ticks passed to
+    /// [`AlarmSet::schedule`] are ticks of that clock.
+    ///
+    /// [`AlarmSet::schedule`]: #method.schedule
+    pub fn new<C>(mut wkt: WKT<init_state::Enabled>, clock: &C) -> Self
+    where
+        C: wkt::Clock + clock::Enabled,
+    {
+        wkt.select_enabled_clock(clock);
+        Self::from_timer(wkt)
+    }
+}
+
+impl<T, const N: usize> AlarmSet<T, N>
+where
+    T: Timer,
+{
+    /// Create an `AlarmSet` that is driven by any timer
+    ///
+    /// The timer is cancelled, if it is currently running.
+    pub fn from_timer(mut timer: T) -> Self {
+        timer.cancel();
+
+        Self {
+            timer,
+            now: 0,
+            armed: None,
+            deadlines: [None; N],
+            generations: [0; N],
+        }
+    }
+
+    /// Schedule an alarm that elapses after `ticks` ticks
+    ///
+    /// If the new alarm is due before the one the timer is currently counting
+    /// down for, the timer is re-armed. Alarms that elapse at the same time
+    /// are returned by [`AlarmSet::wait`] one after the other.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AlarmSetFull`], if `N` alarms are already scheduled.
+    ///
+    /// [`AlarmSet::wait`]: #method.wait
+    /// [`AlarmSetFull`]: struct.AlarmSetFull.html
+    pub fn schedule(&mut self, ticks: u32) -> Result<AlarmId, AlarmSetFull> {
+        let index = self
+            .deadlines
+            .iter()
+            .position(|deadline| deadline.is_none())
+            .ok_or(AlarmSetFull)?;
+
+        self.update();
+        let elapsed = self
+            .armed
+            .map_or(0, |armed| armed.saturating_sub(self.timer.remaining()));
+        let deadline = self.now + elapsed as u64 + ticks as u64;
+        self.deadlines[index] = Some(deadline);
+        self.generations[index] = self.generations[index].wrapping_add(1);
+
+        let is_next = match self.armed {
+            Some(armed) => deadline < self.now + armed as u64,
+            None => true,
+        };
+        if is_next {
+            self.rearm();
+        }
+
+        Ok(self.id(index))
+    }
+
+    /// Cancel an alarm
+    ///
+    /// Does nothing, if the alarm has already elapsed or was cancelled. This
+    /// is also true, if its slot has since been reused by another alarm.
+    pub fn cancel(&mut self, id: AlarmId) {
+        if self.is_current(id) {
+            self.deadlines[id.index] = None;
+        }
+    }
+
+    /// Returns whether an alarm is still scheduled
+    ///
+    /// Returns `false` once the alarm has been returned by
+    /// [`AlarmSet::wait`], or after it was cancelled.
+    ///
+    /// [`AlarmSet::wait`]: #method.wait
+    pub fn is_scheduled(&self, id: AlarmId) -> bool {
+        self.is_current(id) && self.deadlines[id.index].is_some()
+    }
+
+    /// Non-blockingly "waits" until the next alarm elapses
+    ///
+    /// Returns the [`AlarmId`] of an alarm that has elapsed, and removes the
+    /// alarm from the set. If multiple alarms have elapsed, they are returned
+    /// by consecutive calls, in the order of their deadlines. Re-arms the
+    /// timer for the next alarm, whenever the timer has finished.
+    ///
+    /// [`AlarmId`]: struct.AlarmId.html
+    pub fn wait(&mut self) -> nb::Result<AlarmId, Void> {
+        self.update();
+
+        let now = self.now;
+        let (index, _) = self
+            .deadlines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, deadline)| deadline.map(|d| (i, d)))
+            .filter(|&(_, deadline)| deadline <= now)
+            .min_by_key(|&(_, deadline)| deadline)
+            .ok_or(nb::Error::WouldBlock)?;
+
+        self.deadlines[index] = None;
+        Ok(self.id(index))
+    }
+
+    /// Release the timer
+    ///
+    /// All alarms are discarded, and the timer is cancelled.
+    pub fn free(mut self) -> T {
+        self.timer.cancel();
+        self.timer
+    }
+
+    /// Returns the ID of the alarm that currently occupies a slot
+    fn id(&self, index: usize) -> AlarmId {
+        AlarmId {
+            index,
+            generation: self.generations[index],
+        }
+    }
+
+    /// Returns whether an ID refers to the alarm that currently occupies its
+    /// slot, as opposed to one that previously occupied it
+    fn is_current(&self, id: AlarmId) -> bool {
+        self.generations[id.index] == id.generation
+    }
+
+    /// Account for a finished count down, and re-arm the timer
+    fn update(&mut self) {
+        if let Some(armed) = self.armed {
+            if self.timer.wait().is_ok() {
+                self.now += armed as u64;
+                self.armed = None;
+            }
+        }
+
+        if self.armed.is_none() {
+            self.rearm();
+        }
+    }
+
+    /// Start the timer for the next alarm that is not due yet
+    fn rearm(&mut self) {
+        if let Some(armed) = self.armed.take() {
+            let elapsed = armed.saturating_sub(self.timer.remaining());
+            self.now += elapsed as u64;
+            self.timer.cancel();
+        }
+
+        let now = self.now;
+        let next = self
+            .deadlines
+            .iter()
+            .filter_map(|&deadline| deadline)
+            .filter(|&deadline| deadline > now)
+            .min();
+
+        if let Some(next) = next {
+            let ticks = (next - now).min(T::MAX_TICKS as u64) as u32;
+            self.timer.start(ticks);
+            self.armed = Some(ticks);
+        }
+    }
+}
+
+/// Identifies an alarm of an [`AlarmSet`]
+///
+/// Returned by [`AlarmSet::schedule`].
+///
+/// [`AlarmSet`]: struct.AlarmSet.html
+/// [`AlarmSet::schedule`]: struct.AlarmSet.html#method.schedule
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmId {
+    index: usize,
+    generation: u32,
+}
+
+/// Indicates that no more alarms can be scheduled
+///
+/// Returned by [`AlarmSet::schedule`], if all slots of the [`AlarmSet`] are
+/// in use.
+///
+/// [`AlarmSet::schedule`]: struct.AlarmSet.html#method.schedule
+/// [`AlarmSet`]: struct.AlarmSet.html
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmSetFull;
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use void::Void;
+
+    use super::Timer;
+
+    /// A timer that ticks once per call to `wait`
+    ///
+    /// Stands in for a real timer in the tests of code that is generic over
+    /// [`Timer`]. The field is the number of ticks remaining.
+    pub struct MockTimer(pub u32);
+
+    impl Timer for MockTimer {
+        const MAX_TICKS: u32 = u32::MAX;
+
+        fn start(&mut self, ticks: u32) {
+            self.0 = ticks;
+        }
+
+        fn wait(&mut self) -> nb::Result<(), Void> {
+            match self.0 {
+                // Not running; never finishes.
+                0 => Err(nb::Error::WouldBlock),
+                1 => {
+                    self.0 = 0;
+                    Ok(())
+                }
+                _ => {
+                    self.0 -= 1;
+                    Err(nb::Error::WouldBlock)
+                }
+            }
+        }
+
+        fn cancel(&mut self) {
+            self.0 = 0;
+        }
+
+        fn remaining(&self) -> u32 {
+            self.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use cortex_m::peripheral::syst;
+
+    use super::{
+        mock::MockTimer, AlarmSet, SystState, SYST_CSR_COUNTFLAG,
+        SYST_CSR_ENABLE,
+    };
+
+    #[test]
+    fn alarms_fire_in_order_of_their_deadlines() {
+        let mut alarms = AlarmSet::<_, 4>::from_timer(MockTimer(0));
+
+        let a = alarms.schedule(300).unwrap();
+        let b = alarms.schedule(100).unwrap();
+        let c = alarms.schedule(200).unwrap();
+
+        let mut fired = [a; 3];
+        for id in fired.iter_mut() {
+            *id = nb::block!(alarms.wait()).unwrap();
+        }
+
+        assert_eq!(fired, [b, c, a]);
+    }
+
+    #[test]
+    fn stale_id_does_not_affect_reused_slot() {
+        let mut alarms = AlarmSet::<_, 1>::from_timer(MockTimer(0));
+
+        let stale = alarms.schedule(10).unwrap();
+        assert_eq!(nb::block!(alarms.wait()).unwrap(), stale);
+
+        let current = alarms.schedule(10).unwrap();
+        assert_ne!(current, stale);
+        assert!(!alarms.is_scheduled(stale));
+
+        alarms.cancel(stale);
+        assert!(alarms.is_scheduled(current));
+        assert_eq!(nb::block!(alarms.wait()).unwrap(), current);
+    }
+
+    #[test]
+    fn cancel_after_reuse_of_cancelled_slot() {
+        let mut alarms = AlarmSet::<_, 1>::from_timer(MockTimer(0));
+
+        let cancelled = alarms.schedule(10).unwrap();
+        alarms.cancel(cancelled);
+
+        let current = alarms.schedule(20).unwrap();
+        alarms.cancel(cancelled);
+
+        assert!(!alarms.is_scheduled(cancelled));
+        assert!(alarms.is_scheduled(current));
+    }
+
+    fn syst(csr: u32, rvr: u32, cvr: u32) -> syst::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        let syst: syst::RegisterBlock = unsafe { mem::zeroed() };
+
+        // Sound, as nothing else accesses this register block.
+        unsafe {
+            syst.csr.write(csr);
+            syst.rvr.write(rvr);
+            syst.cvr.write(cvr);
+        }
+
+        syst
+    }
+
+    #[test]
+    fn syst_remaining_counts_down_while_running() {
+        let syst = syst(SYST_CSR_ENABLE, 99, 42);
+
+        assert_eq!(super::syst_remaining(&syst), 42);
+        assert_eq!(super::syst_state(&syst), SystState::Running);
+    }
+
+    #[test]
+    fn syst_remaining_is_zero_once_finished() {
+        // After the count down, the counter has been reloaded.
+        let syst = syst(SYST_CSR_ENABLE | SYST_CSR_COUNTFLAG, 99, 99);
+
+        assert_eq!(super::syst_remaining(&syst), 0);
+        assert_eq!(syst.csr.read() & SYST_CSR_ENABLE, 0);
+
+        // In hardware, reading CSR has cleared COUNTFLAG. The count down must
+        // still be recognized as finished.
+        // Sound, as nothing else accesses this register block.
+        unsafe { syst.csr.write(0) };
+        assert_eq!(super::syst_remaining(&syst), 0);
+        assert_eq!(super::syst_state(&syst), SystState::Finished);
+    }
+
+    #[test]
+    fn syst_remaining_is_zero_after_cancel() {
+        let syst = syst(SYST_CSR_ENABLE, 99, 42);
+
+        super::syst_cancel(&syst);
+
+        assert_eq!(super::syst_remaining(&syst), 0);
+        assert_eq!(super::syst_state(&syst), SystState::Cancelled);
+    }
+}
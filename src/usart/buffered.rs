@@ -0,0 +1,424 @@
+use void::Void;
+
+use crate::embedded_hal::serial::{Read, Write};
+
+use super::{
+    flags::Interrupts,
+    instances::Instance,
+    rx::{Error, Rx},
+    state::Enabled,
+    tx::Tx,
+};
+
+type EnabledTx<I, Mode, Throttle> = Tx<I, Enabled<u8, Mode>, Throttle>;
+
+impl<I, Mode> Rx<I, Enabled<u8, Mode>>
+where
+    I: Instance,
+{
+    /// Receive into a ring buffer from the interrupt handler
+    ///
+    /// Returns a [`BufferedRx`], which uses `buffer` as backing storage for
+    /// received words. The size of the buffer is entirely up to the caller,
+    /// which means applications that need to absorb large bursts can use a
+    /// large buffer, while small applications can keep it small.
+    ///
+    /// Since the buffer has a `'static` lifetime, it is guaranteed to outlive
+    /// both the returned [`BufferedRx`] and the interrupt handler that fills
+    /// it. This method enables the RXRDY interrupt. Please refer to
+    /// [`BufferedRx`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0.
+    ///
+    /// [`BufferedRx`]: struct.BufferedRx.html
+    pub fn into_buffered(
+        mut self,
+        buffer: &'static mut [u8],
+    ) -> BufferedRx<I, Mode> {
+        self.enable_interrupts(Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        });
+
+        BufferedRx {
+            rx: self,
+            buffer: RingBuffer::new(buffer),
+            error: None,
+        }
+    }
+}
+
+impl<I, Mode, Throttle> Tx<I, Enabled<u8, Mode>, Throttle>
+where
+    I: Instance,
+{
+    /// Transmit from a ring buffer in the interrupt handler
+    ///
+    /// Returns a [`BufferedTx`], which uses `buffer` as backing storage for
+    /// words that are waiting to be sent. As with
+    /// [`Rx::into_buffered`], the size of the buffer is chosen by the caller,
+    /// and its `'static` lifetime guarantees that it outlives the interrupt
+    /// handler. Please refer to [`BufferedTx`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0.
+    ///
+    /// [`BufferedTx`]: struct.BufferedTx.html
+    /// [`Rx::into_buffered`]: struct.Rx.html#method.into_buffered
+    pub fn into_buffered(
+        self,
+        buffer: &'static mut [u8],
+    ) -> BufferedTx<I, Mode, Throttle> {
+        BufferedTx {
+            tx: self,
+            buffer: RingBuffer::new(buffer),
+        }
+    }
+}
+
+/// A USART receiver that buffers received words in memory
+///
+/// Created by [`Rx::into_buffered`]. Received words are moved from the
+/// peripheral into the buffer by [`on_interrupt`], which needs to be called
+/// from the interrupt handler of the USART instance. They can then be read
+/// at leisure, using the [`embedded_hal::serial::Read`] implementation.
+///
+/// Typically, a `BufferedRx` is put into a static `Mutex<RefCell<...>>`, so
+/// it can be accessed from both the interrupt handler and the main program.
+/// The interrupt still needs to be enabled in the NVIC, using
+/// [`USART::enable_in_nvic`].
+///
+/// # Errors
+///
+/// If a word is received while the buffer is full, the word is discarded and
+/// the next call to `read` returns [`Error::Overrun`], just like it would, if
+/// the peripheral itself had overflowed. Words that were received with a
+/// framing, parity, or noise error are discarded, and the error is returned
+/// from `read` instead. Errors are only returned once all words that are
+/// already in the buffer have been read.
+///
+/// [`Rx::into_buffered`]: struct.Rx.html#method.into_buffered
+/// [`on_interrupt`]: #method.on_interrupt
+/// [`embedded_hal::serial::Read`]: #impl-Read<u8>
+/// [`USART::enable_in_nvic`]: struct.USART.html#method.enable_in_nvic
+/// [`Error::Overrun`]: enum.Error.html#variant.Overrun
+pub struct BufferedRx<I, Mode> {
+    rx: Rx<I, Enabled<u8, Mode>>,
+    buffer: RingBuffer,
+    error: Option<Error>,
+}
+
+impl<I, Mode> BufferedRx<I, Mode>
+where
+    I: Instance,
+{
+    /// Move all received words into the buffer
+    ///
+    /// Must be called from the interrupt handler of the USART instance.
+    pub fn on_interrupt(&mut self) {
+        while let Ok((word, status)) = self.rx.read_with_status() {
+            // Words were lost before this one, but the word itself is fine.
+            if status.overrun {
+                self.record_error(Error::Overrun);
+            }
+
+            if status.framing {
+                self.record_error(Error::Framing);
+            } else if status.parity {
+                self.record_error(Error::Parity);
+            } else if status.noise {
+                self.record_error(Error::Noise);
+            } else if !self.buffer.push(word) {
+                self.record_error(Error::Overrun);
+            }
+        }
+    }
+
+    /// Returns the number of words that can be buffered
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns the number of words that are currently buffered
+    pub fn len(&self) -> usize {
+        self.buffer.len
+    }
+
+    /// Indicates whether no words are currently buffered
+    pub fn is_empty(&self) -> bool {
+        self.buffer.len == 0
+    }
+
+    /// Return the receiver and the buffer
+    ///
+    /// Disables the RXRDY interrupt. Any words that are still in the buffer
+    /// are discarded.
+    pub fn free(mut self) -> (Rx<I, Enabled<u8, Mode>>, &'static mut [u8]) {
+        self.rx.disable_interrupts(Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        });
+
+        (self.rx, self.buffer.storage)
+    }
+
+    fn record_error(&mut self, error: Error) {
+        // Only keep the first error. Later ones are most likely caused by it.
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl<I, Mode> Read<u8> for BufferedRx<I, Mode>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        if let Some(word) = self.buffer.pop() {
+            return Ok(word);
+        }
+
+        match self.error.take() {
+            Some(error) => Err(nb::Error::Other(error)),
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+}
+
+/// A USART transmitter that buffers words to be sent in memory
+///
+/// Created by [`Tx::into_buffered`]. Writing to it, using the
+/// [`embedded_hal::serial::Write`] implementation, only puts words into the
+/// buffer and enables the TXRDY interrupt. [`on_interrupt`], which needs to
+/// be called from the interrupt handler of the USART instance, then moves the
+/// words to the peripheral, as it becomes ready for them.
+///
+/// As with [`BufferedRx`], it's typical to put a `BufferedTx` into a static
+/// `Mutex<RefCell<...>>`, and the interrupt needs to be enabled in the NVIC.
+///
+/// [`Tx::into_buffered`]: struct.Tx.html#method.into_buffered
+/// [`embedded_hal::serial::Write`]: #impl-Write<u8>
+/// [`on_interrupt`]: #method.on_interrupt
+/// [`BufferedRx`]: struct.BufferedRx.html
+pub struct BufferedTx<I, Mode, Throttle> {
+    tx: EnabledTx<I, Mode, Throttle>,
+    buffer: RingBuffer,
+}
+
+impl<I, Mode, Throttle> BufferedTx<I, Mode, Throttle>
+where
+    I: Instance,
+{
+    /// Move as many buffered words to the peripheral as it can accept
+    ///
+    /// Must be called from the interrupt handler of the USART instance.
+    /// Disables the TXRDY interrupt, once the buffer is empty.
+    pub fn on_interrupt(&mut self) {
+        while let Some(word) = self.buffer.peek() {
+            match self.tx.write(word) {
+                Ok(()) => {
+                    self.buffer.pop();
+                }
+                Err(nb::Error::WouldBlock) => return,
+                Err(nb::Error::Other(void)) => match void {},
+            }
+        }
+
+        self.tx.disable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+    }
+
+    /// Returns the number of words that can be buffered
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns the number of words that are waiting to be sent
+    pub fn len(&self) -> usize {
+        self.buffer.len
+    }
+
+    /// Indicates whether no words are waiting to be sent
+    pub fn is_empty(&self) -> bool {
+        self.buffer.len == 0
+    }
+
+    /// Return the transmitter and the buffer
+    ///
+    /// Disables the TXRDY interrupt. Any words that are still in the buffer
+    /// are discarded. Call [`flush`] first, if you don't want that.
+    ///
+    /// [`flush`]: #impl-Write<u8>
+    pub fn free(mut self) -> (EnabledTx<I, Mode, Throttle>, &'static mut [u8]) {
+        self.tx.disable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+
+        (self.tx, self.buffer.storage)
+    }
+}
+
+impl<I, Mode, Throttle> Write<u8> for BufferedTx<I, Mode, Throttle>
+where
+    I: Instance,
+{
+    type Error = Void;
+
+    /// Puts a word into the buffer
+    ///
+    /// Returns `WouldBlock`, if the buffer is full.
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        if !self.buffer.push(word) {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.tx.enable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+
+        Ok(())
+    }
+
+    /// Returns `Ok`, once the buffer is empty and all words have been sent
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        if !self.buffer.is_empty() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.tx.flush()
+    }
+}
+
+struct RingBuffer {
+    storage: &'static mut [u8],
+    start: usize,
+    len: usize,
+}
+
+impl RingBuffer {
+    fn new(storage: &'static mut [u8]) -> Self {
+        assert!(!storage.is_empty());
+
+        Self {
+            storage,
+            start: 0,
+            len: 0,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.storage.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push(&mut self, word: u8) -> bool {
+        if self.len == self.capacity() {
+            return false;
+        }
+
+        let i = (self.start + self.len) % self.capacity();
+        self.storage[i] = word;
+        self.len += 1;
+
+        true
+    }
+
+    fn peek(&self) -> Option<u8> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.storage[self.start])
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        let word = self.peek()?;
+
+        self.start = (self.start + 1) % self.capacity();
+        self.len -= 1;
+
+        Some(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RingBuffer;
+
+    fn buffer(capacity: usize) -> RingBuffer {
+        RingBuffer::new(Box::leak(vec![0; capacity].into_boxed_slice()))
+    }
+
+    #[test]
+    fn pop_returns_words_in_order() {
+        let mut buffer = buffer(4);
+        assert_eq!(buffer.pop(), None);
+
+        assert!(buffer.push(1));
+        assert!(buffer.push(2));
+        assert!(buffer.push(3));
+        assert_eq!(buffer.len, 3);
+
+        assert_eq!(buffer.peek(), Some(1));
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn push_rejects_words_when_full() {
+        let mut buffer = buffer(2);
+
+        assert!(buffer.push(1));
+        assert!(buffer.push(2));
+        assert!(!buffer.push(3));
+        assert_eq!(buffer.len, 2);
+
+        assert_eq!(buffer.pop(), Some(1));
+        assert!(buffer.push(3));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+    }
+
+    #[test]
+    fn words_wrap_around_end_of_storage() {
+        let mut buffer = buffer(3);
+
+        for word in 0..10 {
+            assert!(buffer.push(word));
+            assert!(buffer.push(word + 100));
+            assert_eq!(buffer.pop(), Some(word));
+            assert_eq!(buffer.pop(), Some(word + 100));
+        }
+        assert!(buffer.is_empty());
+
+        assert!(buffer.push(1));
+        assert!(buffer.push(2));
+        assert!(buffer.push(3));
+        assert!(!buffer.push(4));
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_empty_storage() {
+        buffer(0);
+    }
+}
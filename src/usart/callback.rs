@@ -0,0 +1,121 @@
+use super::{
+    flags::Interrupts,
+    instances::Instance,
+    rx::{FrameStatus, Rx},
+    state::{Enabled, Word},
+};
+
+impl<I, W, Mode> Rx<I, Enabled<W, Mode>>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Pass every received word to a callback, from the interrupt handler
+    ///
+    /// Returns a [`CallbackRx`], which calls `callback` with each received
+    /// word and its [`FrameStatus`]. No buffer is involved, which makes this
+    /// the lowest-latency way to receive data. It is well suited for feeding
+    /// state machine parsers.
+    ///
+    /// This method enables the RXRDY interrupt. Please refer to
+    /// [`CallbackRx`] for more information.
+    ///
+    /// [`CallbackRx`]: struct.CallbackRx.html
+    /// [`FrameStatus`]: struct.FrameStatus.html
+    pub fn into_callback<F>(mut self, callback: F) -> CallbackRx<I, W, Mode, F>
+    where
+        F: FnMut(W, FrameStatus) + 'static,
+    {
+        self.enable_interrupts(Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        });
+
+        CallbackRx { rx: self, callback }
+    }
+}
+
+/// A USART receiver that passes each received word to a callback
+///
+/// Created by [`Rx::into_callback`]. [`on_interrupt`], which needs to be
+/// called from the interrupt handler of the USART instance, reads all words
+/// that have been received and passes each one to the callback, together with
+/// its [`FrameStatus`].
+///
+/// The callback runs in the interrupt context, so it should be short. As the
+/// `CallbackRx` needs to be accessed from the interrupt handler, it is
+/// typically put into a static `Mutex<RefCell<...>>`. This requires a type
+/// that can be named, which is why a function pointer (`fn(u8, FrameStatus)`)
+/// is the most practical choice for the callback. The interrupt still needs
+/// to be enabled in the NVIC, using [`USART::enable_in_nvic`].
+///
+/// # Overrun
+///
+/// If the callback takes longer than it takes to receive a word, the
+/// peripheral will overflow, and words will be lost. This is reported to the
+/// callback, by setting [`FrameStatus::overrun`] for the first word that is
+/// received after the lost ones. A parser should treat this as a loss of
+/// synchronization, for example by discarding the current line.
+///
+/// [`Rx::into_callback`]: struct.Rx.html#method.into_callback
+/// [`on_interrupt`]: #method.on_interrupt
+/// [`FrameStatus`]: struct.FrameStatus.html
+/// [`FrameStatus::overrun`]: struct.FrameStatus.html#structfield.overrun
+/// [`USART::enable_in_nvic`]: struct.USART.html#method.enable_in_nvic
+pub struct CallbackRx<I, W: Word, Mode, F> {
+    rx: Rx<I, Enabled<W, Mode>>,
+    callback: F,
+}
+
+impl<I, W, Mode, F> CallbackRx<I, W, Mode, F>
+where
+    I: Instance,
+    W: Word,
+    F: FnMut(W, FrameStatus),
+{
+    /// Pass all received words to the callback
+    ///
+    /// Must be called from the interrupt handler of the USART instance.
+    pub fn on_interrupt(&mut self) {
+        while let Ok((word, status)) = self.rx.read_with_status() {
+            (self.callback)(word, status);
+        }
+    }
+
+    /// Enable the RXRDY interrupt
+    ///
+    /// The interrupt is enabled when the `CallbackRx` is created. This method
+    /// only needs to be called, to re-enable it after a call to
+    /// [`disable_interrupt`].
+    ///
+    /// [`disable_interrupt`]: #method.disable_interrupt
+    pub fn enable_interrupt(&mut self) {
+        self.rx.enable_interrupts(Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        });
+    }
+
+    /// Disable the RXRDY interrupt
+    ///
+    /// The callback won't be called anymore, until the interrupt is enabled
+    /// again using [`enable_interrupt`]. The peripheral keeps receiving in
+    /// the meantime, so it will overflow, if more than one word arrives. The
+    /// callback is informed about that, once the interrupt is enabled again.
+    ///
+    /// [`enable_interrupt`]: #method.enable_interrupt
+    pub fn disable_interrupt(&mut self) {
+        self.rx.disable_interrupts(Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        });
+    }
+
+    /// Return the receiver and the callback
+    ///
+    /// Disables the RXRDY interrupt.
+    pub fn free(mut self) -> (Rx<I, Enabled<W, Mode>>, F) {
+        self.disable_interrupt();
+        (self.rx, self.callback)
+    }
+}
@@ -1,6 +1,9 @@
 use core::marker::PhantomData;
 
-use crate::syscon::{self, clock_source::PeripheralClockSelector};
+use crate::{
+    syscon::{self, clock_source::PeripheralClockSelector},
+    usart::state::AsyncMode,
+};
 
 /// Defines the clock configuration for a USART instance
 ///
@@ -39,6 +42,23 @@ where
     }
 }
 
+impl<T> Clock<T, AsyncMode> {
+    /// The oversampling factor of this configuration
+    ///
+    /// Every bit is sampled this many times.
+    pub fn oversampling(&self) -> u8 {
+        self.osrval + 1
+    }
+}
+
+/// Indicates that a baud rate can't be reached from a given clock
+///
+/// Returned by `Clock::new_with_baudrate_from`, if the requested baud rate
+/// can't be reached with sufficient accuracy.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnreachableBaudrate;
+
 /// Implemented for USART clock sources
 pub trait ClockSource: private::Sealed {
     /// Select the clock source
@@ -54,11 +74,59 @@ pub trait ClockSource: private::Sealed {
         S: PeripheralClockSelector;
 }
 
+/// The maximum deviation from the requested baud rate, in parts per million
+const MAX_DEVIATION: u32 = 30_000;
+
+/// Find the divider and oversampling factor that get closest to `baudrate`
+///
+/// Returns the divider, the oversampling factor (between 5 and 16), and the
+/// baud rate that is achieved. If several combinations are equally close, the
+/// highest oversampling factor is selected.
+fn best_divider(frequency: u32, baudrate: u32) -> Option<(u32, u8, u32)> {
+    // (divider, oversampling, achieved baud rate, error)
+    let mut best: Option<(u32, u8, u32, u32)> = None;
+
+    for oversampling in (5..=16u8).rev() {
+        let cycles = match baudrate.checked_mul(oversampling as u32) {
+            Some(cycles) if cycles > 0 && cycles <= frequency => cycles,
+            _ => continue,
+        };
+
+        // Round to the nearest divider.
+        let divider =
+            ((frequency + cycles / 2) / cycles).min(u16::MAX as u32 + 1);
+        let achieved = frequency / (divider * oversampling as u32);
+        let error = achieved.abs_diff(baudrate);
+
+        match best {
+            Some((_, _, _, best_error)) if best_error <= error => {}
+            _ => best = Some((divider, oversampling, achieved, error)),
+        }
+    }
+
+    best.map(|(divider, oversampling, achieved, _)| {
+        (divider, oversampling, achieved)
+    })
+}
+
+/// The deviation of `achieved` from `baudrate`, in parts per million
+fn deviation(achieved: u32, baudrate: u32) -> u32 {
+    (achieved.abs_diff(baudrate) as u64 * 1_000_000 / baudrate as u64) as u32
+}
+
 #[cfg(feature = "82x")]
 mod target {
-    use crate::syscon::{self, UARTFRG};
+    use core::marker::PhantomData;
 
-    use super::ClockSource;
+    use crate::{
+        syscon::{self, UARTFRG},
+        usart::state::AsyncMode,
+    };
+
+    use super::{
+        best_divider, deviation, Clock, ClockSource, UnreachableBaudrate,
+        MAX_DEVIATION,
+    };
 
     impl super::private::Sealed for UARTFRG {}
 
@@ -67,13 +135,207 @@ mod target {
             // nothing to do; selected by default
         }
     }
+
+    /// A configuration of the UART fractional baud rate generator (UARTFRG)
+    ///
+    /// On LPC82x, all USART instances are clocked by UARTFRG, which means they
+    /// can't be configured independently. Every instance has its own baud
+    /// rate generator though, which divides the common clock further.
+    /// [`FrgConfig::for_baudrates`] makes use of that, by searching for a
+    /// common clock from which all requested baud rates can be derived.
+    ///
+    /// The UARTFRG output frequency (U_PCLK) is
+    /// `main_clock / clkdiv / (1 + frgmult / 256)`.
+    ///
+    /// [`FrgConfig::for_baudrates`]: #method.for_baudrates
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct FrgConfig {
+        /// The value of the UART clock divider (UARTCLKDIV)
+        ///
+        /// Must not be zero, as that disables the clock.
+        pub clkdiv: u8,
+
+        /// The value of the fractional generator multiplier (UARTFRGMULT)
+        pub frgmult: u8,
+    }
+
+    impl FrgConfig {
+        /// Find a common configuration for multiple baud rates
+        ///
+        /// Searches for a UARTFRG configuration from which all of `baudrates`
+        /// can be derived, using the baud rate generator of each USART
+        /// instance. `main_clock` is the frequency of the main clock in Hz,
+        /// which is 12 MHz after reset.
+        ///
+        /// Returns the UARTFRG configuration, which must be applied using
+        /// [`apply`], together with a USART clock configuration for each baud
+        /// rate, and the baud rate that it actually achieves. The results are
+        /// in the same order as `baudrates`.
+        ///
+        /// The search derives its candidates for the common clock from the
+        /// highest baud rate, and selects the one that minimizes the largest
+        /// relative deviation of any baud rate. It stops early, if all baud
+        /// rates can be reached exactly. The search takes a while on the
+        /// device, so it should be done once, during initialization.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`UnreachableBaudrate`], if no common configuration can be
+        /// found, for which each achieved baud rate deviates from the
+        /// requested one by 3% or less. This can be the case, even if each
+        /// baud rate could be reached on its own.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use lpc8xx_hal::usart::{FrgConfig, UnreachableBaudrate};
+        ///
+        /// let (frg, [(_, fast), (_, slow)]) =
+        ///     FrgConfig::for_baudrates(12_000_000, [115_200, 9600]).unwrap();
+        /// assert_eq!(frg, FrgConfig { clkdiv: 4, frgmult: 47 });
+        /// assert_eq!(frg.u_pclk(12_000_000), 2_534_653);
+        /// assert_eq!(fast, 115_211);
+        /// assert_eq!(slow, 9600);
+        ///
+        /// // 2.4 Mbaud can only be reached with a U_PCLK of 12 MHz, from
+        /// // which 1.9 Mbaud can't be derived.
+        /// let result =
+        ///     FrgConfig::for_baudrates(12_000_000, [2_400_000, 1_900_000]);
+        /// assert!(matches!(result, Err(UnreachableBaudrate)));
+        /// ```
+        ///
+        /// [`apply`]: #method.apply
+        /// [`UnreachableBaudrate`]: struct.UnreachableBaudrate.html
+        pub fn for_baudrates<const N: usize>(
+            main_clock: u32,
+            baudrates: [u32; N],
+        ) -> Result<(Self, Clocks<N>), UnreachableBaudrate> {
+            let highest =
+                baudrates.iter().copied().max().ok_or(UnreachableBaudrate)?;
+            if highest == 0 {
+                return Err(UnreachableBaudrate);
+            }
+
+            // (configuration, largest deviation)
+            let mut best: Option<(Self, u32)> = None;
+
+            'search: for oversampling in (5..=16).rev() {
+                for divider in 1..=MAX_CANDIDATE_DIVIDER {
+                    let target =
+                        match highest.checked_mul(oversampling * divider) {
+                            Some(target) if target <= main_clock => target,
+                            _ => break,
+                        };
+
+                    let config = match Self::approximate(main_clock, target) {
+                        Some(config) => config,
+                        None => continue,
+                    };
+
+                    let u_pclk = config.u_pclk(main_clock);
+                    let worst = baudrates.iter().try_fold(0, |worst, &baud| {
+                        let (_, _, achieved) = best_divider(u_pclk, baud)?;
+                        Some(worst.max(deviation(achieved, baud)))
+                    });
+                    let worst = match worst {
+                        Some(worst) => worst,
+                        None => continue,
+                    };
+
+                    match best {
+                        Some((_, best_worst)) if best_worst <= worst => {}
+                        _ => best = Some((config, worst)),
+                    }
+
+                    if worst == 0 {
+                        break 'search;
+                    }
+                }
+            }
+
+            let config = match best {
+                Some((config, worst)) if worst <= MAX_DEVIATION => config,
+                _ => return Err(UnreachableBaudrate),
+            };
+
+            let u_pclk = config.u_pclk(main_clock);
+            let clocks = baudrates.map(|baudrate| {
+                // Can't fail, as this has been checked during the search.
+                let (divider, oversampling, achieved) =
+                    best_divider(u_pclk, baudrate).unwrap();
+
+                let clock = Clock {
+                    psc: (divider - 1) as u16,
+                    osrval: oversampling - 1,
+                    _clock: PhantomData,
+                    _mode: PhantomData,
+                };
+
+                (clock, achieved)
+            });
+
+            Ok((config, clocks))
+        }
+
+        /// The UARTFRG output frequency (U_PCLK) for this configuration
+        ///
+        /// `main_clock` is the frequency of the main clock in Hz.
+        pub fn u_pclk(&self, main_clock: u32) -> u32 {
+            (main_clock as u64 * 256
+                / (self.clkdiv as u64 * (256 + self.frgmult as u64)))
+                as u32
+        }
+
+        /// Apply this configuration to UARTFRG
+        ///
+        /// This affects all USART instances, which is why it should be done
+        /// before any of them are enabled.
+        pub fn apply(&self, uartfrg: &mut UARTFRG) {
+            uartfrg.set_clkdiv(self.clkdiv);
+            uartfrg.set_frgmult(self.frgmult);
+            // The fractional divider must always be set to 256.
+            uartfrg.set_frgdiv(0xff);
+        }
+
+        /// Find the configuration that gets closest to `target`
+        fn approximate(main_clock: u32, target: u32) -> Option<Self> {
+            let clkdiv = main_clock / target;
+            if clkdiv == 0 || clkdiv > u8::MAX as u32 {
+                return None;
+            }
+
+            // main_clock / (clkdiv * target) = 1 + frgmult / 256
+            let divisor = clkdiv as u64 * target as u64;
+            let frgmult = (main_clock as u64 * 256 + divisor / 2) / divisor;
+            let frgmult = frgmult.saturating_sub(256).min(u8::MAX as u64);
+
+            Some(Self {
+                clkdiv: clkdiv as u8,
+                frgmult: frgmult as u8,
+            })
+        }
+    }
+
+    /// USART clock configurations, each with the baud rate it achieves
+    type Clocks<const N: usize> = [(Clock<UARTFRG, AsyncMode>, u32); N];
+
+    /// The highest baud rate generator divider that is tried for U_PCLK
+    ///
+    /// Limits the number of candidates that [`FrgConfig::for_baudrates`]
+    /// tries, which keeps the search fast enough for low baud rates.
+    const MAX_CANDIDATE_DIVIDER: u32 = 16;
 }
 
+#[cfg(feature = "82x")]
+pub use self::target::FrgConfig;
+
 #[cfg(feature = "845")]
 mod target {
     use core::marker::PhantomData;
 
     use crate::{
+        clock,
         syscon::{
             self,
             clock_source::{PeripheralClock, PeripheralClockSelector},
@@ -81,30 +343,180 @@ mod target {
         usart::state::AsyncMode,
     };
 
-    use super::{Clock, ClockSource};
+    use super::{
+        best_divider, deviation, Clock, ClockSource, UnreachableBaudrate,
+        MAX_DEVIATION,
+    };
 
     impl Clock<syscon::IOSC, AsyncMode> {
         /// Create a new configuration with a specified baudrate
         ///
-        /// Assumes the internal oscillator runs at 12 MHz.
+        /// Assumes the internal oscillator runs at 12 MHz. This is a wrapper
+        /// around [`new_with_baudrate_from`]. Please use that, if you need to
+        /// know the baud rate that was actually achieved.
+        ///
+        /// # Panics
+        ///
+        /// Panics, if `baudrate` can't be reached from 12 MHz. See
+        /// [`new_with_baudrate_from`] for details.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use lpc8xx_hal::usart;
+        ///
+        /// let clock = usart::Clock::new_with_baudrate(115_200);
+        /// assert_eq!(clock.oversampling(), 13);
+        /// assert_eq!(clock.baudrate(), 115_384);
+        ///
+        /// let clock = usart::Clock::new_with_baudrate(9600);
+        /// assert_eq!(clock.baudrate(), 9600);
+        /// ```
+        ///
+        /// [`new_with_baudrate_from`]: #method.new_with_baudrate_from
         pub fn new_with_baudrate(baudrate: u32) -> Self {
-            // We want something with 5% tolerance
-            let calc = baudrate * 20;
-            let mut osrval = 5;
-            for i in (5..=16).rev() {
-                if calc * (i as u32) < 12_000_000 {
-                    osrval = i;
-                }
-            }
-            let psc = (12_000_000 / (baudrate * osrval as u32) - 1) as u16;
-            let osrval = osrval - 1;
+            let (clock, _) = Self::from_frequency(12_000_000, baudrate)
+                .expect("Baud rate can't be reached from 12 MHz");
+            clock
+        }
+
+        /// Create a new configuration with a baudrate and oversampling factor
+        ///
+        /// Every bit is sampled `oversampling` times. Higher values improve
+        /// noise immunity, but lower values make it possible to reach higher
+        /// baud rates, or get closer to the requested one. The baud rate
+        /// generator is configured to get as close as possible to `baudrate`,
+        /// given the selected oversampling factor.
+        ///
+        /// Oversampling only applies to asynchronous mode, which is why this
+        /// constructor is not available for synchronous mode.
+        ///
+        /// Assumes the internal oscillator runs at 12 MHz.
+        ///
+        /// # Panics
+        ///
+        /// Panics, if `oversampling` is not between 5 and 16, if `baudrate` is
+        /// zero, or if `baudrate` can't be reached with the given oversampling
+        /// factor.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use lpc8xx_hal::usart;
+        ///
+        /// let clock = usart::Clock::new_with_baudrate_and_oversampling(
+        ///     115_200,
+        ///     13,
+        /// );
+        /// assert_eq!(clock.oversampling(), 13);
+        /// assert_eq!(clock.baudrate(), 115_384);
+        ///
+        /// // 1 Mbaud is out of reach with 16x oversampling, but works with a
+        /// // lower factor.
+        /// let clock = usart::Clock::new_with_baudrate_and_oversampling(
+        ///     1_000_000,
+        ///     6,
+        /// );
+        /// assert_eq!(clock.baudrate(), 1_000_000);
+        /// ```
+        pub fn new_with_baudrate_and_oversampling(
+            baudrate: u32,
+            oversampling: u8,
+        ) -> Self {
+            assert!((5..=16).contains(&oversampling));
+            assert!(baudrate > 0, "Baud rate must not be zero");
+
+            let cycles = baudrate
+                .checked_mul(oversampling as u32)
+                .filter(|&cycles| cycles <= 12_000_000)
+                .expect("Baud rate too high for oversampling factor");
+
+            // Round to the nearest divider.
+            let divider = (12_000_000 + cycles / 2) / cycles;
+            assert!(divider <= u16::MAX as u32 + 1);
+
             Self {
-                psc,
-                osrval,
+                psc: (divider - 1) as u16,
+                osrval: oversampling - 1,
                 _clock: PhantomData,
                 _mode: PhantomData,
             }
         }
+
+        /// The baud rate resulting from this configuration
+        ///
+        /// Assumes the internal oscillator runs at 12 MHz.
+        pub fn baudrate(&self) -> u32 {
+            12_000_000 / ((self.psc as u32 + 1) * self.oversampling() as u32)
+        }
+    }
+
+    impl<T> Clock<T, AsyncMode>
+    where
+        T: PeripheralClock + clock::Frequency,
+    {
+        /// Create a new configuration with a specified baudrate
+        ///
+        /// Computes the baud rate generator settings from the actual
+        /// frequency of `source`, which makes this constructor suitable for
+        /// clock configurations other than the default one.
+        ///
+        /// Selects the combination of oversampling factor (between 5 and 16)
+        /// and divider that gets closest to `baudrate`. If several
+        /// combinations are equally close, the highest oversampling factor is
+        /// selected.
+        ///
+        /// Returns the configuration, together with the baud rate that it
+        /// actually achieves.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`UnreachableBaudrate`], if the achieved baud rate would
+        /// deviate from `baudrate` by more than 3%. This is typically the case
+        /// for low source frequencies, which can't be divided finely enough.
+        ///
+        /// # Example
+        ///
+        /// ``` no_run
+        /// use lpc8xx_hal::{usart, Peripherals};
+        ///
+        /// let p = Peripherals::take().unwrap();
+        /// let syscon = p.SYSCON.split();
+        ///
+        /// let (clock, baudrate) =
+        ///     usart::Clock::new_with_baudrate_from(&syscon.iosc, 115_200)
+        ///         .unwrap();
+        /// assert_eq!(baudrate, 115_384);
+        /// ```
+        ///
+        /// [`UnreachableBaudrate`]: ../struct.UnreachableBaudrate.html
+        pub fn new_with_baudrate_from(
+            source: &T,
+            baudrate: u32,
+        ) -> Result<(Self, u32), UnreachableBaudrate> {
+            Self::from_frequency(source.hz(), baudrate)
+        }
+
+        fn from_frequency(
+            frequency: u32,
+            baudrate: u32,
+        ) -> Result<(Self, u32), UnreachableBaudrate> {
+            let (divider, oversampling, achieved) =
+                best_divider(frequency, baudrate)
+                    .filter(|&(_, _, achieved)| {
+                        deviation(achieved, baudrate) <= MAX_DEVIATION
+                    })
+                    .ok_or(UnreachableBaudrate)?;
+
+            let clock = Self {
+                psc: (divider - 1) as u16,
+                osrval: oversampling - 1,
+                _clock: PhantomData,
+                _mode: PhantomData,
+            };
+
+            Ok((clock, achieved))
+        }
     }
 
     impl<T> super::private::Sealed for T where T: PeripheralClock {}
@@ -120,6 +532,109 @@ mod target {
             T::select(selector, handle);
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{
+            syscon::IOSC,
+            usart::{clock::UnreachableBaudrate, state::AsyncMode},
+        };
+
+        type Clock = super::Clock<IOSC, AsyncMode>;
+
+        #[test]
+        fn new_with_baudrate_and_oversampling_at_limit() {
+            let clock = Clock::new_with_baudrate_and_oversampling(750_000, 16);
+            assert_eq!(clock.psc, 0);
+            assert_eq!(clock.baudrate(), 750_000);
+        }
+
+        #[test]
+        #[should_panic(expected = "Baud rate too high")]
+        fn new_with_baudrate_and_oversampling_rejects_too_high_baudrate() {
+            Clock::new_with_baudrate_and_oversampling(750_001, 16);
+        }
+
+        #[test]
+        #[should_panic(expected = "Baud rate too high")]
+        fn new_with_baudrate_and_oversampling_rejects_overflow() {
+            // 268_500_000 * 16 wraps around to 1_032_704 in a `u32`.
+            Clock::new_with_baudrate_and_oversampling(268_500_000, 16);
+        }
+
+        #[test]
+        #[should_panic(expected = "Baud rate must not be zero")]
+        fn new_with_baudrate_and_oversampling_rejects_zero() {
+            Clock::new_with_baudrate_and_oversampling(0, 16);
+        }
+
+        fn from_frequency(
+            frequency: u32,
+            baudrate: u32,
+        ) -> Result<(u16, u8, u32), UnreachableBaudrate> {
+            Clock::from_frequency(frequency, baudrate).map(
+                |(clock, achieved)| (clock.psc, clock.oversampling(), achieved),
+            )
+        }
+
+        #[test]
+        fn from_frequency_with_various_sources() {
+            assert_eq!(
+                from_frequency(12_000_000, 115_200),
+                Ok((7, 13, 115_384))
+            );
+            assert_eq!(
+                from_frequency(30_000_000, 115_200),
+                Ok((19, 13, 115_384))
+            );
+            assert_eq!(from_frequency(24_000_000, 9_600), Ok((249, 10, 9_600)));
+        }
+
+        #[test]
+        fn from_frequency_oversampling_bounds() {
+            // Only reachable with the lowest oversampling factor
+            assert_eq!(
+                from_frequency(12_000_000, 2_400_000),
+                Ok((0, 5, 2_400_000))
+            );
+
+            // Reachable with the highest oversampling factor
+            assert_eq!(
+                from_frequency(12_000_000, 750_000),
+                Ok((0, 16, 750_000))
+            );
+
+            // 10x and 15x are equally good. The higher factor is selected.
+            assert_eq!(
+                from_frequency(30_000_000, 1_000_000),
+                Ok((1, 15, 1_000_000))
+            );
+        }
+
+        #[test]
+        fn from_frequency_unreachable_baudrate() {
+            // Faster than the source clock divided by the lowest oversampling
+            // factor
+            assert_eq!(
+                from_frequency(12_000_000, 3_000_000),
+                Err(UnreachableBaudrate)
+            );
+
+            // Closest is 2 Mbaud, which deviates by more than 3%
+            assert_eq!(
+                from_frequency(12_000_000, 1_900_000),
+                Err(UnreachableBaudrate)
+            );
+
+            // Source clock too slow to be divided finely enough
+            assert_eq!(
+                from_frequency(100_000, 9_600),
+                Err(UnreachableBaudrate)
+            );
+
+            assert_eq!(from_frequency(12_000_000, 0), Err(UnreachableBaudrate));
+        }
+    }
 }
 
 mod private {
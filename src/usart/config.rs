@@ -0,0 +1,146 @@
+use crate::clock::Frequency;
+
+use super::{
+    clock::Clock,
+    settings::Settings,
+    state::{AsyncMode, Word},
+};
+
+/// A complete USART configuration for asynchronous mode
+///
+/// Captures the register values that result from a clock configuration and
+/// [`Settings`], so they can be stored and later re-applied using
+/// [`USART::enable_async_from_config`]. This is useful after a wake-up from
+/// deep power-down mode, which resets the microcontroller: The configuration
+/// can be stored in one of the general-purpose registers of the PMU (see
+/// [`pmu::Handle::gp_write`]), and re-applied after the wake-up, without
+/// having to compute it again.
+///
+/// A `Config` can be created from a clock configuration and [`Settings`]
+/// using [`Config::new`], or captured from an enabled USART using
+/// [`USART::config`].
+///
+/// Please note that the configuration only stores the divider values, not
+/// the frequency of the clock they were computed for. If the clock
+/// configuration might have changed, use [`Config::baudrate`] to check the
+/// restored configuration, before trusting it.
+///
+/// # Example
+///
+/// ``` rust
+/// # #[cfg(feature = "845")]
+/// # {
+/// use lpc8xx_hal::usart;
+///
+/// let clock = usart::Clock::new_with_baudrate(115_200);
+/// let settings = usart::Settings::default().parity_even();
+///
+/// let config = usart::Config::new(&clock, &settings);
+///
+/// // Store `bits` somewhere that survives deep power-down...
+/// let bits = config.to_u32();
+///
+/// // ...and restore it after waking up.
+/// let restored = usart::Config::<u8>::from_u32(bits).unwrap();
+/// assert_eq!(restored, config);
+///
+/// // A configuration for 9-bit words can't be restored as an 8-bit one.
+/// let settings = usart::Settings::default().data_len_9();
+/// let bits = usart::Config::new(&clock, &settings).to_u32();
+/// assert_eq!(usart::Config::<u8>::from_u32(bits), None);
+/// # }
+/// ```
+///
+/// [`Settings`]: struct.Settings.html
+/// [`USART::enable_async_from_config`]: struct.USART.html#method.enable_async_from_config
+/// [`pmu::Handle::gp_write`]: ../pmu/struct.Handle.html#method.gp_write
+/// [`Config::new`]: #method.new
+/// [`USART::config`]: struct.USART.html#method.config
+/// [`Config::baudrate`]: #method.baudrate
+#[derive(Debug, PartialEq)]
+pub struct Config<W = u8> {
+    pub(super) psc: u16,
+    pub(super) osrval: u8,
+    pub(super) settings: Settings<W>,
+}
+
+impl<W> Config<W>
+where
+    W: Word,
+{
+    /// Create a configuration from a clock configuration and settings
+    pub fn new<C>(clock: &Clock<C, AsyncMode>, settings: &Settings<W>) -> Self {
+        Self {
+            psc: clock.psc,
+            osrval: clock.osrval,
+            settings: *settings,
+        }
+    }
+
+    /// Encode the configuration into a `u32`
+    ///
+    /// The result can be decoded again using [`from_u32`].
+    ///
+    /// [`from_u32`]: #method.from_u32
+    pub fn to_u32(self) -> u32 {
+        let cfg = self.settings.to_cfg_bits();
+
+        // Pack the CFG fields into 9 bits: DATALEN, PARITYSEL, and STOPLEN
+        // (bits 2 to 6), CLKPOL (bit 12), LOOP (bit 15), RXPOL and TXPOL
+        // (bits 22 and 23).
+        let settings = (cfg >> 2) & 0x1f
+            | ((cfg >> 12) & 0x1) << 5
+            | ((cfg >> 15) & 0x1) << 6
+            | ((cfg >> 22) & 0x3) << 7;
+
+        self.psc as u32 | (self.osrval as u32) << 16 | settings << 20
+    }
+
+    /// Decode a configuration that was encoded using [`to_u32`]
+    ///
+    /// Returns `None`, if `bits` doesn't represent a valid configuration, or
+    /// if the configured data length doesn't match the word type `W`.
+    ///
+    /// [`to_u32`]: #method.to_u32
+    pub fn from_u32(bits: u32) -> Option<Self> {
+        if bits >> 29 != 0 {
+            return None;
+        }
+
+        let osrval = ((bits >> 16) & 0xf) as u8;
+        if osrval < 4 {
+            return None;
+        }
+
+        let settings = bits >> 20;
+        let cfg = (settings & 0x1f) << 2
+            | ((settings >> 5) & 0x1) << 12
+            | ((settings >> 6) & 0x1) << 15
+            | ((settings >> 7) & 0x3) << 22;
+
+        Some(Self {
+            psc: bits as u16,
+            osrval,
+            settings: Settings::from_cfg_bits(cfg)?,
+        })
+    }
+
+    /// The baud rate that results from this configuration
+    ///
+    /// `source` is the clock that drives the USART.
+    pub fn baudrate<F>(&self, source: &F) -> u32
+    where
+        F: Frequency,
+    {
+        source.hz() / ((self.psc as u32 + 1) * (self.osrval as u32 + 1))
+    }
+}
+
+// Can't be derived, as that would require `W: Clone`.
+impl<W> Clone for Config<W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<W> Copy for Config<W> {}
@@ -67,13 +67,31 @@
 //!
 //! Please refer to the [examples in the repository] for more example code.
 //!
+//! # Interrupt load
+//!
+//! The USART peripherals on LPC8xx don't have a FIFO. Receiver and transmitter
+//! are each buffered by a single data register (RXDAT and TXDAT), which means
+//! the RXRDY and TXRDY interrupts fire once per word, and there is no
+//! threshold that could be configured to make them fire less often.
+//!
+//! If the interrupt load becomes a problem, for example when streaming data at
+//! high baud rates, consider using DMA instead (see [`Rx::read_all`] and
+//! [`Tx::write_all`]). This results in a single interrupt per transfer, at the
+//! cost of having to know the length of the transfer up front.
+//!
 //! [`USART`]: struct.USART.html
+//! [`Rx::read_all`]: struct.Rx.html#method.read_all
+//! [`Tx::write_all`]: struct.Tx.html#method.write_all
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
+mod buffered;
+mod callback;
 mod clock;
+mod config;
 mod flags;
 mod instances;
 mod peripheral;
+mod rs485;
 mod rx;
 mod settings;
 mod tx;
@@ -81,11 +99,18 @@ mod tx;
 pub mod state;
 
 pub use self::{
-    clock::{Clock, ClockSource},
+    buffered::{BufferedRx, BufferedTx},
+    callback::CallbackRx,
+    clock::{Clock, ClockSource, UnreachableBaudrate},
+    config::Config,
     flags::{Flag, Interrupts},
     instances::Instance,
-    peripheral::USART,
-    rx::{Error, Rx},
+    peripheral::{protected_identifier, AutoBaudError, USART},
+    rs485::Rs485,
+    rx::{Error, FrameStatus, Rx},
     settings::Settings,
-    tx::Tx,
+    tx::{Tx, WriteHandle},
 };
+
+#[cfg(feature = "82x")]
+pub use self::clock::FrgConfig;
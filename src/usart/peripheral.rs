@@ -7,13 +7,16 @@ use embedded_hal::{
 use void::Void;
 
 use crate::{
+    clock::Frequency,
     init_state::Disabled,
     pac::{usart0::cfg, NVIC},
     swm, syscon,
+    timer::Timer,
 };
 
 use super::{
     clock::{Clock, ClockSource},
+    config::Config,
     flags::{Flag, Interrupts},
     instances::Instance,
     rx::{Error, Rx},
@@ -28,8 +31,9 @@ use super::{
 /// this struct.
 ///
 /// You can either use this struct as-is, if you need to send and receive in the
-/// same place, or you can move the `rx` and `tx` fields out of this struct, to
-/// use the sender and receiver from different contexts.
+/// same place, or you can split it into the sender and receiver using
+/// [`USART::split`] (or by moving the `rx` and `tx` fields out of this struct),
+/// to use them from different contexts.
 ///
 /// Please refer to the [module documentation] for more information.
 ///
@@ -38,12 +42,21 @@ use super::{
 /// - [`embedded_hal::serial::Write`] for non-blocking writes
 /// - [`embedded_hal::blocking::serial::Write`] for blocking writes
 ///
+/// # Formatted output
+///
+/// If the word size is `u8`, `USART` implements [`core::fmt::Write`], which
+/// means it can be used with `write!` and `writeln!`. Please refer to the
+/// documentation of [`Tx`] for details.
+///
 ///
 /// [`Peripherals`]: ../struct.Peripherals.html
+/// [`USART::split`]: #method.split
 /// [module documentation]: index.html
 /// [`embedded_hal::serial::Read`]: #impl-Read<W>
 /// [`embedded_hal::serial::Write`]: #impl-Write<W>
 /// [`embedded_hal::blocking::serial::Write`]: #impl-Write<Word>
+/// [`core::fmt::Write`]: #impl-Write
+/// [`Tx`]: struct.Tx.html#formatted-output
 pub struct USART<I, State> {
     /// The USART Receiver
     pub rx: Rx<I, State>,
@@ -54,6 +67,10 @@ pub struct USART<I, State> {
     usart: I,
 }
 
+/// The transmitter and receiver of an enabled USART, as returned by `split`
+type Split<I, W, Mode> =
+    (Tx<I, Enabled<W, Mode>, NoThrottle>, Rx<I, Enabled<W, Mode>>);
+
 impl<I> USART<I, Disabled>
 where
     I: Instance,
@@ -95,13 +112,93 @@ where
     /// [`BaudRate`]: struct.BaudRate.html
     /// [module documentation]: index.html
     pub fn enable_async<RxPin, TxPin, CLOCK, W>(
-        mut self,
+        self,
         clock: &Clock<CLOCK, AsyncMode>,
         syscon: &mut syscon::Handle,
         _: swm::Function<I::Rx, swm::state::Assigned<RxPin>>,
         _: swm::Function<I::Tx, swm::state::Assigned<TxPin>>,
         settings: Settings<W>,
     ) -> USART<I, Enabled<W, AsyncMode>>
+    where
+        CLOCK: ClockSource,
+        W: Word,
+    {
+        self.enable_async_inner::<CLOCK, W>(
+            &Config::new(clock, &settings),
+            syscon,
+        )
+    }
+
+    /// Enable the USART in asynchronous mode, using a stored configuration
+    ///
+    /// Works like [`enable_async`], except that the clock configuration and
+    /// settings are taken from `config`. See [`Config`] for details.
+    ///
+    /// The clock source `CLOCK` is selected, when the USART is enabled. It
+    /// has to be specified explicitly, as `config` only stores the divider
+    /// values. Nothing checks that these values were computed for `CLOCK`, so
+    /// please make sure that is the case.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{syscon, usart, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut pmu = p.PMU.split();
+    /// let swm = p.SWM.split();
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let ((u0_rxd, _), (u0_txd, _)) = swm_handle.assign_all((
+    ///     (swm.movable_functions.u0_rxd, p.pins.pio0_0.into_swm_pin()),
+    ///     (swm.movable_functions.u0_txd, p.pins.pio0_4.into_swm_pin()),
+    /// ));
+    ///
+    /// // Restore a configuration that was stored before entering deep
+    /// // power-down mode.
+    /// let config = usart::Config::<u8>::from_u32(pmu.handle.gp_read(0))
+    ///     .expect("Invalid configuration");
+    ///
+    /// #[cfg(feature = "82x")]
+    /// type Clock = syscon::UARTFRG;
+    /// #[cfg(feature = "845")]
+    /// type Clock = syscon::IOSC;
+    ///
+    /// let serial = p.USART0.enable_async_from_config::<Clock, _, _, _>(
+    ///     &config,
+    ///     &mut syscon.handle,
+    ///     u0_rxd,
+    ///     u0_txd,
+    /// );
+    /// ```
+    ///
+    /// [`enable_async`]: #method.enable_async
+    /// [`Config`]: struct.Config.html
+    pub fn enable_async_from_config<CLOCK, W, RxPin, TxPin>(
+        self,
+        config: &Config<W>,
+        syscon: &mut syscon::Handle,
+        _: swm::Function<I::Rx, swm::state::Assigned<RxPin>>,
+        _: swm::Function<I::Tx, swm::state::Assigned<TxPin>>,
+    ) -> USART<I, Enabled<W, AsyncMode>>
+    where
+        CLOCK: ClockSource,
+        W: Word,
+    {
+        self.enable_async_inner::<CLOCK, W>(config, syscon)
+    }
+
+    fn enable_async_inner<CLOCK, W>(
+        mut self,
+        config: &Config<W>,
+        syscon: &mut syscon::Handle,
+    ) -> USART<I, Enabled<W, AsyncMode>>
     where
         CLOCK: ClockSource,
         W: Word,
@@ -110,10 +207,10 @@ where
 
         self.usart
             .brg
-            .write(|w| unsafe { w.brgval().bits(clock.psc) });
+            .write(|w| unsafe { w.brgval().bits(config.psc) });
         self.usart
             .osr
-            .write(|w| unsafe { w.osrval().bits(clock.osrval) });
+            .write(|w| unsafe { w.osrval().bits(config.osrval) });
 
         // We are not allowed to send or receive data when writing to CFG. This
         // is ensured by type state, so no need to do anything here.
@@ -121,7 +218,7 @@ where
         self.usart.cfg.modify(|_, w| {
             w.syncen().asynchronous_mode();
             Self::apply_general_config(w);
-            settings.apply(w);
+            config.settings.apply(w);
             w
         });
 
@@ -271,9 +368,6 @@ where
         // Disable CTS; can be enabled by the user later.
         w.ctsen().disabled();
 
-        // No loopback mode; currently it's not supported.
-        w.loop_().normal();
-
         // Enable automatic address matching. This makes no difference until we
         // set a separate bit in CTL, and address detection without automatic
         // matching is currently not supported by this API.
@@ -281,20 +375,364 @@ where
     }
 }
 
+impl<I, W> USART<I, Enabled<W, AsyncMode>>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Capture the current configuration
+    ///
+    /// Reads the clock configuration and settings back from the peripheral,
+    /// including any changes made since it was enabled (for example by
+    /// [`auto_baud`]). The result can be stored and later re-applied using
+    /// [`enable_async_from_config`]. See [`Config`] for details.
+    ///
+    /// [`auto_baud`]: #method.auto_baud
+    /// [`enable_async_from_config`]: #method.enable_async_from_config
+    /// [`Config`]: struct.Config.html
+    pub fn config(&self) -> Config<W> {
+        let settings = Settings::from_cfg_bits(self.usart.cfg.read().bits())
+            .expect("USART configuration doesn't match its type state");
+
+        Config {
+            psc: self.usart.brg.read().brgval().bits(),
+            osrval: self.usart.osr.read().osrval().bits(),
+            settings,
+        }
+    }
+
+    /// Detect the baud rate from an incoming character
+    ///
+    /// Enables the autobaud mode of the USART, which measures the start bit of
+    /// the next character that is received, and sets up the baud rate
+    /// generator to match it. Blocks until the measurement has been completed,
+    /// the hardware reports an error, or `timer` elapses, whichever comes
+    /// first.
+    ///
+    /// The measurement relies on the start bit being followed by a 1 bit, so
+    /// the other side should send a character whose least significant bit is
+    /// set, like `0x55` (`b'U'`). The character that was used for the
+    /// measurement should not be relied upon to be received correctly.
+    ///
+    /// `clock` is the clock that drives the USART. It is required to compute
+    /// the baud rate that was detected, which is returned on success. The
+    /// oversampling factor that was configured when enabling the USART is
+    /// kept.
+    ///
+    /// The receiver must be idle when calling this method.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AutoBaudError::Measurement`], if the hardware reports an
+    /// error during the measurement, which can happen if the character
+    /// doesn't meet the requirements above. Returns [`AutoBaudError::Timeout`],
+    /// if nothing was received before `timer` elapsed. In both cases, autobaud
+    /// mode is disabled again, but the baud rate generator might not be set to
+    /// a useful value anymore. You can call this method again to retry.
+    ///
+    /// Returns [`AutoBaudError::Break`] without starting the measurement, if
+    /// the receiver currently detects a break. A measurement would fail
+    /// anyway in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero or larger than `T::MAX_TICKS`.
+    ///
+    /// [`AutoBaudError::Measurement`]: enum.AutoBaudError.html#variant.Measurement
+    /// [`AutoBaudError::Timeout`]: enum.AutoBaudError.html#variant.Timeout
+    /// [`AutoBaudError::Break`]: enum.AutoBaudError.html#variant.Break
+    pub fn auto_baud<C, T>(
+        &mut self,
+        clock: &C,
+        timer: &mut T,
+        ticks: u32,
+    ) -> Result<u32, AutoBaudError>
+    where
+        C: Frequency,
+        T: Timer,
+    {
+        // A break (or an idle line of the wrong polarity) looks like a start
+        // bit that never ends.
+        if self.usart.stat.read().rxbrk().bit_is_set() {
+            return Err(AutoBaudError::Break);
+        }
+
+        // Clear a leftover error from an earlier attempt.
+        self.usart.stat.write(|w| w.aberr().set_bit());
+
+        self.usart.ctl.modify(|_, w| w.autobaud().enabled());
+        timer.start(ticks);
+
+        // The hardware clears AUTOBAUD, once the measurement is complete, or
+        // if an error occurred.
+        let result = loop {
+            if self.usart.stat.read().aberr().bit_is_set() {
+                self.usart.stat.write(|w| w.aberr().set_bit());
+                break Err(AutoBaudError::Measurement);
+            }
+            if self.usart.ctl.read().autobaud().is_disabled() {
+                break Ok(());
+            }
+            if timer.wait().is_ok() {
+                self.usart.ctl.modify(|_, w| w.autobaud().disabled());
+                break Err(AutoBaudError::Timeout);
+            }
+        };
+        timer.cancel();
+        result?;
+
+        let brgval = self.usart.brg.read().brgval().bits() as u32 + 1;
+        let osrval = self.usart.osr.read().osrval().bits() as u32 + 1;
+
+        Ok(clock.hz() / (brgval * osrval))
+    }
+}
+
+impl<I> USART<I, Enabled<u8, AsyncMode>>
+where
+    I: Instance,
+{
+    /// Send a LIN header
+    ///
+    /// Sends a break that lasts for `break_bits` bit times, followed by a
+    /// break delimiter of one bit time, the sync byte (`0x55`), and the
+    /// protected identifier `pid`. Blocks until the header has been sent
+    /// completely. A LIN master sends a break of at least 13 bit times.
+    ///
+    /// `pid` is sent as-is. Use [`protected_identifier`] to compute it from a
+    /// frame identifier.
+    ///
+    /// The length of a bit time is derived from the baud rate the USART is
+    /// currently configured for, so this works after [`auto_baud`] too.
+    /// `clock` is the clock that drives the USART. `timer` is used to time the
+    /// break and the delimiter, and `timer_clock` is the clock that drives
+    /// `timer`.
+    ///
+    /// Any words that have been written before are sent first, as the break
+    /// can only be started once the transmitter is idle.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `break_bits` bit times don't fit into `T::MAX_TICKS` ticks
+    /// of `timer`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// # #[cfg(feature = "845")]
+    /// # {
+    /// use lpc8xx_hal::{
+    ///     nb::block, prelude::*, usart, CorePeripherals, Peripherals,
+    /// };
+    ///
+    /// let cp = CorePeripherals::take().unwrap();
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm = p.SWM.split();
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// let mut syst = cp.SYST;
+    ///
+    /// let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+    ///     p.pins.pio0_0.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+    ///     p.pins.pio0_4.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// // Loop the transmitter back to the receiver, so we can check what has
+    /// // been sent.
+    /// let mut serial = p.USART0.enable_async(
+    ///     &usart::Clock::new_with_baudrate(19_200),
+    ///     &mut syscon.handle,
+    ///     u0_rxd,
+    ///     u0_txd,
+    ///     usart::Settings::default().loopback_enabled(),
+    /// );
+    ///
+    /// // Both the USART and SYST run at 12 MHz.
+    /// let pid = usart::protected_identifier(0x3c);
+    /// serial.send_lin_header(pid, 13, &syscon.iosc, &mut syst, &syscon.iosc);
+    ///
+    /// // The break is received as a zero with a framing error.
+    /// let _ = block!(serial.read());
+    /// assert_eq!(block!(serial.read()).unwrap(), 0x55);
+    /// assert_eq!(block!(serial.read()).unwrap(), pid);
+    /// # }
+    /// ```
+    ///
+    /// [`protected_identifier`]: fn.protected_identifier.html
+    /// [`auto_baud`]: #method.auto_baud
+    pub fn send_lin_header<C, T, F>(
+        &mut self,
+        pid: u8,
+        break_bits: u32,
+        clock: &C,
+        timer: &mut T,
+        timer_clock: &F,
+    ) where
+        C: Frequency,
+        T: Timer,
+        F: Frequency,
+    {
+        let brgval = self.usart.brg.read().brgval().bits() as u64 + 1;
+        let osrval = self.usart.osr.read().osrval().bits() as u64 + 1;
+
+        // Converts a number of bit times into timer ticks. Rounds up, so the
+        // break is never shorter than requested.
+        let ticks = |bits: u32| {
+            let bit_clocks = bits as u64 * brgval * osrval;
+            let ticks = (bit_clocks * timer_clock.hz() as u64)
+                .div_ceil(clock.hz() as u64);
+            assert!(ticks <= T::MAX_TICKS as u64);
+            (ticks as u32).max(1)
+        };
+        let break_ticks = ticks(break_bits);
+        let delimiter_ticks = ticks(1);
+
+        self.wait_tx_complete();
+
+        // Disabling the transmitter before enabling the break makes sure the
+        // break starts on a clean character boundary.
+        self.usart.ctl.modify(|_, w| w.txdis().disabled());
+        while self.usart.stat.read().txdisstat().bit_is_clear() {}
+
+        self.usart.ctl.modify(|_, w| w.txbrken().continous());
+        timer.start(break_ticks);
+        while timer.wait().is_err() {}
+
+        self.usart.ctl.modify(|_, w| w.txbrken().normal());
+        timer.start(delimiter_ticks);
+        while timer.wait().is_err() {}
+
+        self.usart.ctl.modify(|_, w| w.txdis().enabled());
+
+        for &word in &[0x55, pid] {
+            while let Err(nb::Error::WouldBlock) = self.write(word) {}
+        }
+        self.wait_tx_complete();
+    }
+}
+
+/// Compute the protected identifier of a LIN frame
+///
+/// Adds the two parity bits defined by the LIN specification to the 6-bit
+/// frame identifier `id`. The result can be passed to
+/// [`USART::send_lin_header`].
+///
+/// # Panics
+///
+/// Panics, if `id` is larger than `0x3f`.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::usart;
+///
+/// assert_eq!(usart::protected_identifier(0x00), 0x80);
+/// assert_eq!(usart::protected_identifier(0x3c), 0x3c);
+/// assert_eq!(usart::protected_identifier(0x3d), 0x7d);
+/// ```
+///
+/// [`USART::send_lin_header`]: struct.USART.html#method.send_lin_header
+pub fn protected_identifier(id: u8) -> u8 {
+    assert!(id <= 0x3f);
+
+    let bit = |n: u8| (id >> n) & 0x1;
+    let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+    let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 0x1;
+
+    id | p0 << 6 | p1 << 7
+}
+
 impl<I, W, Mode> USART<I, Enabled<W, Mode>>
 where
     I: Instance,
     W: Word,
 {
+    /// Split the USART into its sender and receiver
+    ///
+    /// Consumes this instance of `USART` and returns the sender ([`Tx`]) and
+    /// the receiver ([`Rx`]), which can then be moved into different contexts,
+    /// for example the receiver into an interrupt handler and the sender into
+    /// the main loop. Both implement the respective `embedded-hal` traits.
+    ///
+    /// This is sound, as the sender and receiver never access the same
+    /// registers, except for those that don't require synchronization: STAT
+    /// is only read, or written to clear flags, in which case only the bits of
+    /// the respective flags are written as 1. INTENSET and INTENCLR only
+    /// affect the bits that are written as 1.
+    ///
+    /// Please note that the USART can't be disabled after it has been split,
+    /// as the raw peripheral is dropped.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{nb::block, prelude::*, usart, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let swm = p.SWM.split();
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let clock_config = {
+    ///     syscon.uartfrg.set_clkdiv(6);
+    ///     syscon.uartfrg.set_frgmult(22);
+    ///     syscon.uartfrg.set_frgdiv(0xff);
+    ///     usart::Clock::new(&syscon.uartfrg, 0, 16)
+    /// };
+    /// #[cfg(feature = "845")]
+    /// let clock_config = usart::Clock::new_with_baudrate(115200);
+    ///
+    /// let ((u0_rxd, _), (u0_txd, _)) = swm_handle.assign_all((
+    ///     (swm.movable_functions.u0_rxd, p.pins.pio0_0.into_swm_pin()),
+    ///     (swm.movable_functions.u0_txd, p.pins.pio0_4.into_swm_pin()),
+    /// ));
+    ///
+    /// let serial = p.USART0.enable_async(
+    ///     &clock_config,
+    ///     &mut syscon.handle,
+    ///     u0_rxd,
+    ///     u0_txd,
+    ///     usart::Settings::default().loopback_enabled(),
+    /// );
+    ///
+    /// let (mut tx, mut rx) = serial.split();
+    ///
+    /// // In loopback mode, everything that's sent is received again.
+    /// block!(tx.write(b'a')).unwrap();
+    /// assert_eq!(block!(rx.read()).unwrap(), b'a');
+    /// ```
+    ///
+    /// [`Tx`]: struct.Tx.html
+    /// [`Rx`]: struct.Rx.html
+    pub fn split(self) -> Split<I, W, Mode> {
+        (self.tx, self.rx)
+    }
+
     /// Disable the USART
     ///
     /// This method is only available, if `USART` is in the [`Enabled`] state.
     /// Code that attempts to call this method when the peripheral is already
     /// disabled will not compile.
     ///
+    /// Disables the clock of the USART, which stops it immediately. Words that
+    /// are still waiting in the transmitter are not sent. Call
+    /// [`wait_tx_complete`] first, if that matters.
+    ///
     /// Consumes this instance of `USART` and returns another instance that has
     /// its `State` type parameter set to [`Disabled`].
     ///
+    /// [`wait_tx_complete`]: #method.wait_tx_complete
     /// [`Enabled`]: state/struct.Enabled.html
     /// [`Disabled`]: ../init_state/struct.Disabled.html
     pub fn disable(self, syscon: &mut syscon::Handle) -> USART<I, Disabled> {
@@ -314,6 +752,15 @@ where
         flag.is_set::<I>()
     }
 
+    /// Wait until all previously written words have been sent
+    ///
+    /// Please refer to [`Tx::wait_tx_complete`] for details.
+    ///
+    /// [`Tx::wait_tx_complete`]: struct.Tx.html#method.wait_tx_complete
+    pub fn wait_tx_complete(&mut self) {
+        self.tx.wait_tx_complete()
+    }
+
     /// Enable interrupts for this instance in the NVIC
     ///
     /// This only enables the interrupts in the NVIC. It doesn't enable any
@@ -476,7 +923,14 @@ where
     /// request already exists, comment on the existing issue, so we can
     /// prioritize it accordingly.
     ///
+    /// The RX and TX (and SCLK, in synchronous mode) functions that were
+    /// passed when enabling the USART stay assigned to their pins, so the raw
+    /// peripheral keeps driving the TX pin. If the USART is still enabled, so
+    /// is its clock, and the baud rate and frame format stay as configured.
+    /// Call [`disable`] first, to turn the clock off.
+    ///
     /// [open an issue]: https://github.com/lpc-rs/lpc8xx-hal/issues
+    /// [`disable`]: #method.disable
     pub fn free(self) -> I {
         self.usart
     }
@@ -525,8 +979,53 @@ where
     Self: BlockingWriteDefault<u8>,
     I: Instance,
 {
-    /// Writes a string slice into this writer, returning whether the write succeeded.
+    /// Writes a string slice, blocking until it has been sent completely
+    ///
+    /// Never returns an error, as writing to the USART can't fail.
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.tx.write_str(s)
     }
 }
+
+/// An error that occurred during baud rate detection
+///
+/// Returned by [`USART::auto_baud`].
+///
+/// [`USART::auto_baud`]: struct.USART.html#method.auto_baud
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AutoBaudError {
+    /// The hardware reported an error during the measurement
+    ///
+    /// Corresponds to the ABERR flag in the STAT register.
+    Measurement,
+
+    /// No character was received before the timeout elapsed
+    Timeout,
+
+    /// The receiver detected a break before the measurement started
+    ///
+    /// Corresponds to the RXBRK flag in the STAT register. If the line is
+    /// supposed to be idle, this usually means that the RX polarity doesn't
+    /// match the line (see [`Settings::rx_inverted`]).
+    ///
+    /// [`Settings::rx_inverted`]: struct.Settings.html#method.rx_inverted
+    Break,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pac;
+
+    use super::USART;
+
+    #[test]
+    fn free_returns_raw_peripheral() {
+        // Sound, as the peripheral is only compared, never accessed.
+        let usart = unsafe { pac::Peripherals::steal() }.USART0;
+
+        let usart = USART::new(usart).free();
+
+        assert_eq!(&*usart as *const _, pac::USART0::ptr());
+    }
+}
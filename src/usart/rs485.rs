@@ -0,0 +1,153 @@
+use embedded_hal::{
+    blocking::serial::Write as BlockingWrite, digital::v2::OutputPin,
+};
+use void::{ResultVoidExt as _, Void};
+
+use super::{
+    instances::Instance,
+    state::{Enabled, Word},
+    tx::Tx,
+};
+
+/// Controls the direction of a half-duplex RS-485 transceiver
+///
+/// Wraps a USART transmitter and the GPIO output that is connected to the
+/// transceiver's driver enable (DE) and receiver enable (RE) pins. The
+/// transceiver is switched to transmitting for the duration of each write,
+/// and switched back to receiving right after the last bit has been sent.
+///
+/// The driver is only disabled once the transmitter is idle, meaning the last
+/// word has left the shift register (see [`Tx::wait_tx_complete`]). Disabling
+/// it any earlier would cut off the end of the last word, while disabling it
+/// later than necessary risks colliding with the reply of another device.
+///
+/// The DE pin is driven high to enable the driver. If your transceiver needs
+/// a low level instead, wrap the pin in [`gpio::Inverted`].
+///
+/// The receiver is not affected by this wrapper, and can be used as usual.
+/// Please note that most transceivers don't receive while transmitting, or
+/// echo the transmitted data back, depending on how DE and RE are wired.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::Level,
+///     nb::block,
+///     prelude::*,
+///     usart::{self, Rs485},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let mut swm = p.SWM.split();
+///
+/// #[cfg(feature = "82x")]
+/// let mut swm_handle = swm.handle;
+/// #[cfg(feature = "845")]
+/// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+///
+/// #[cfg(feature = "82x")]
+/// let clock_config = {
+///     syscon.uartfrg.set_clkdiv(6);
+///     syscon.uartfrg.set_frgmult(22);
+///     syscon.uartfrg.set_frgdiv(0xff);
+///     usart::Clock::new(&syscon.uartfrg, 0, 16)
+/// };
+/// #[cfg(feature = "845")]
+/// let clock_config = usart::Clock::new_with_baudrate(115200);
+///
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+///     p.pins.pio0_0.into_swm_pin(),
+///     &mut swm_handle,
+/// );
+/// let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+///     p.pins.pio0_4.into_swm_pin(),
+///     &mut swm_handle,
+/// );
+///
+/// let usart = p.USART0.enable_async(
+///     &clock_config,
+///     &mut syscon.handle,
+///     u0_rxd,
+///     u0_txd,
+///     usart::Settings::default(),
+/// );
+///
+/// let de = p
+///     .pins
+///     .pio0_12
+///     .into_output_pin(gpio.tokens.pio0_12, Level::Low);
+///
+/// let mut rx = usart.rx;
+/// let mut rs485 = Rs485::new(usart.tx, de);
+///
+/// // The driver is enabled during the write, and disabled again once the
+/// // last bit has been sent. We can listen for the reply right away.
+/// rs485.write_all(b"ping");
+/// let reply = block!(rx.read());
+/// ```
+///
+/// [`Tx::wait_tx_complete`]: struct.Tx.html#method.wait_tx_complete
+/// [`gpio::Inverted`]: ../gpio/struct.Inverted.html
+pub struct Rs485<I, W: Word, Mode, Throttle, DePin> {
+    tx: Tx<I, Enabled<W, Mode>, Throttle>,
+    de: DePin,
+}
+
+impl<I, W, Mode, Throttle, DePin> Rs485<I, W, Mode, Throttle, DePin>
+where
+    I: Instance,
+    W: Word,
+    DePin: OutputPin<Error = Void>,
+{
+    /// Create a new instance of `Rs485`
+    ///
+    /// Disables the driver right away, so the transceiver starts out
+    /// receiving.
+    pub fn new(tx: Tx<I, Enabled<W, Mode>, Throttle>, mut de: DePin) -> Self {
+        de.set_low().void_unwrap();
+        Self { tx, de }
+    }
+
+    /// Write all words, then switch back to receiving
+    ///
+    /// Blocks until all words have been sent completely.
+    pub fn write_all(&mut self, words: &[W])
+    where
+        W: Clone,
+    {
+        self.transmit(|tx| tx.bwrite_all(words).void_unwrap())
+    }
+
+    /// Enable the driver for the duration of the closure
+    ///
+    /// Enables the driver, calls `f` with the transmitter, waits until
+    /// everything written by `f` has been sent completely, then disables the
+    /// driver again. This can be used to send data in multiple parts, for
+    /// example using `write!`, without releasing the bus in between.
+    pub fn transmit<R>(
+        &mut self,
+        f: impl FnOnce(&mut Tx<I, Enabled<W, Mode>, Throttle>) -> R,
+    ) -> R {
+        self.de.set_high().void_unwrap();
+
+        let result = f(&mut self.tx);
+
+        // Only release the bus once the last bit has left the shift register.
+        // TXRDY would be too early.
+        self.tx.wait_tx_complete();
+        self.de.set_low().void_unwrap();
+
+        result
+    }
+
+    /// Return the transmitter and the DE pin
+    pub fn free(self) -> (Tx<I, Enabled<W, Mode>, Throttle>, DePin) {
+        (self.tx, self.de)
+    }
+}
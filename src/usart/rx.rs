@@ -7,7 +7,8 @@ use crate::{
     dma::{self, transfer::state::Ready},
     embedded_hal::serial::Read,
     init_state,
-    pac::dma0::channel::xfercfg::SRCINC_A,
+    pac::{self, dma0::channel::xfercfg::SRCINC_A},
+    timer::Timer,
 };
 
 use super::{
@@ -109,6 +110,240 @@ where
         flag.is_set::<I>()
     }
 
+    /// Indicates whether the receiver is idle
+    ///
+    /// Returns `true`, if the receiver is not currently in the process of
+    /// receiving a character. This reflects the RXIDLE flag, which is set
+    /// whenever no character is being received, including when no character
+    /// has been received at all. It can't be cleared by software.
+    ///
+    /// To detect whether the line has been idle for a while, after characters
+    /// have been received, please use [`Rx::wait_idle`].
+    ///
+    /// [`Rx::wait_idle`]: #method.wait_idle
+    pub fn is_idle(&self) -> bool {
+        // Sound, as we're only reading from a register.
+        let usart = unsafe { &*I::REGISTERS };
+
+        usart.stat.read().rxidle().bit_is_set()
+    }
+
+    /// Wait until the line has been idle after receiving data
+    ///
+    /// Returns `Ok(())`, once at least one character has been received and no
+    /// further start bit has been detected for `ticks` ticks of `timer`. This
+    /// makes it possible to receive frames of unknown length, by reading until
+    /// the line goes quiet.
+    ///
+    /// This method must be called repeatedly, and the received characters
+    /// still need to be read, for example using [`Read::read`]. Every call
+    /// checks the START flag, which is set by the hardware whenever a start bit
+    /// is detected. If it is set, the flag is cleared and `timer` is
+    /// restarted. Since this happens at the beginning of a character, `ticks`
+    /// must include the time it takes to receive a character.
+    ///
+    /// If no character has been received since `timer` has last been started,
+    /// `timer` is not restarted and this method never returns `Ok(())`. This
+    /// is how "nothing received yet" is told apart from "received data, then
+    /// went idle". Please make sure `timer` isn't running when the first call
+    /// is made, for example by calling [`Timer::cancel`].
+    ///
+    /// The hardware can't trigger an interrupt when the line becomes idle. If
+    /// you need to be notified, enable the START interrupt to learn about
+    /// incoming data, and use a timer interrupt for the idle gap.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     prelude::*,
+    ///     timer::Timer,
+    ///     usart,
+    /// };
+    ///
+    /// # use lpc8xx_hal::Peripherals;
+    /// #
+    /// # let mut p = Peripherals::take().unwrap();
+    /// #
+    /// # let mut syscon = p.SYSCON.split();
+    /// # let mut swm    = p.SWM.split();
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let clock_config = {
+    /// #     syscon.uartfrg.set_clkdiv(6);
+    /// #     syscon.uartfrg.set_frgmult(22);
+    /// #     syscon.uartfrg.set_frgdiv(0xff);
+    /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
+    /// # };
+    /// # #[cfg(feature = "845")]
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// #
+    /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+    /// #     p.pins.pio0_0.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+    /// #     p.pins.pio0_4.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// #
+    /// # let mut usart = p.USART0.enable_async(
+    /// #     &clock_config,
+    /// #     &mut syscon.handle,
+    /// #     u0_rxd,
+    /// #     u0_txd,
+    /// #     usart::Settings::default(),
+    /// # );
+    /// #
+    /// let mut timer = p.MRT0.split(&mut syscon.handle).mrt0;
+    /// timer.cancel();
+    ///
+    /// let mut frame = [0; 32];
+    /// let mut len = 0;
+    ///
+    /// // At 115200 baud, a character takes less than 100 µs. At 12 MHz, this
+    /// // waits for 1 ms after the last start bit.
+    /// while let Err(nb::Error::WouldBlock) =
+    ///     usart.rx.wait_idle(&mut timer, 12_000)
+    /// {
+    ///     if let Ok(word) = usart.rx.read() {
+    ///         frame[len] = word;
+    ///         len += 1;
+    ///     }
+    /// }
+    ///
+    /// // Read the character that might have arrived before the line went
+    /// // idle.
+    /// if let Ok(word) = usart.rx.read() {
+    ///     frame[len] = word;
+    ///     len += 1;
+    /// }
+    /// ```
+    ///
+    /// [`Read::read`]: #method.read
+    /// [`Timer::cancel`]: ../timer/trait.Timer.html#tymethod.cancel
+    pub fn wait_idle<T>(
+        &mut self,
+        timer: &mut T,
+        ticks: u32,
+    ) -> nb::Result<(), Void>
+    where
+        T: Timer,
+    {
+        if Flag::START.is_set::<I>() {
+            timer.start(ticks);
+            return Err(nb::Error::WouldBlock);
+        }
+
+        timer.wait()?;
+
+        if !self.is_idle() {
+            // The gap was shorter than expected. Let's wait for the next
+            // start bit to restart the timer.
+            return Err(nb::Error::WouldBlock);
+        }
+
+        Ok(())
+    }
+
+    /// Reads a single word, together with its status
+    ///
+    /// Works like [`Read::read`], except that errors don't prevent the word
+    /// from being returned. Instead, a [`FrameStatus`] is returned alongside
+    /// each word, which tells whether the word was received with any errors.
+    /// This is useful for protocols that can tolerate occasional corruption,
+    /// where dropping the word would be worse than receiving a bad one.
+    ///
+    /// The framing, parity, and noise flags are read from the RXDATSTAT
+    /// register together with the word, so they always refer to that word. The
+    /// corresponding sticky flags in the STAT register are cleared, so they
+    /// don't affect later calls to [`Read::read`].
+    ///
+    /// # Example
+    ///
+    /// Uses USART1 to send words with odd parity to USART0, which expects even
+    /// parity, so every received word has a parity error.
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{prelude::*, usart, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let clock_config = {
+    ///     syscon.uartfrg.set_clkdiv(6);
+    ///     syscon.uartfrg.set_frgmult(22);
+    ///     syscon.uartfrg.set_frgdiv(0xff);
+    ///     usart::Clock::new(&syscon.uartfrg, 0, 16)
+    /// };
+    /// #[cfg(feature = "845")]
+    /// let clock_config = usart::Clock::new_with_baudrate(115200);
+    ///
+    /// // Connect USART1's TXD to USART0's RXD, using the same pin.
+    /// let pin = p.pins.pio0_0.into_swm_pin();
+    /// let (u1_txd, pin) =
+    ///     swm.movable_functions.u1_txd.assign(pin, &mut swm_handle);
+    /// let (u0_rxd, _) =
+    ///     swm.movable_functions.u0_rxd.assign(pin, &mut swm_handle);
+    ///
+    /// let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+    ///     p.pins.pio0_4.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// let (u1_rxd, _) = swm.movable_functions.u1_rxd.assign(
+    ///     p.pins.pio0_1.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let mut usart0 = p.USART0.enable_async(
+    ///     &clock_config,
+    ///     &mut syscon.handle,
+    ///     u0_rxd,
+    ///     u0_txd,
+    ///     usart::Settings::default().parity_even(),
+    /// );
+    /// let mut usart1 = p.USART1.enable_async(
+    ///     &clock_config,
+    ///     &mut syscon.handle,
+    ///     u1_rxd,
+    ///     u1_txd,
+    ///     usart::Settings::default().parity_odd(),
+    /// );
+    ///
+    /// usart1.tx.bwrite_all(b"x").unwrap();
+    ///
+    /// let (word, status) = nb::block!(usart0.rx.read_with_status()).unwrap();
+    /// assert_eq!(word, b'x');
+    /// assert!(status.parity);
+    /// assert!(!status.is_ok());
+    /// ```
+    ///
+    /// [`Read::read`]: #method.read
+    /// [`FrameStatus`]: struct.FrameStatus.html
+    pub fn read_with_status(&mut self) -> nb::Result<(W, FrameStatus), Void> {
+        // Sound, as `rxdatstat` is otherwise only accessed by `read`, which
+        // requires `&mut self` too. `stat` is written to, but writing to it
+        // only clears the flags that are written as 1, and we only write 1 to
+        // receiver error flags, which belong to us.
+        let usart = unsafe { &*I::REGISTERS };
+
+        let (word, status) = read_with_status(usart)?;
+        Ok((Word::from_u16(word), status))
+    }
+
     /// Enable interrupts
     ///
     /// Enables all interrupts set to `true` in `interrupts`. Interrupts set to
@@ -246,6 +481,29 @@ where
     ) -> dma::Transfer<Ready, I::RxChannel, Self, &'static mut [u8]> {
         dma::Transfer::new(channel, self, buffer)
     }
+
+    /// Continuously receive into the provided buffer, using DMA
+    ///
+    /// Starts a circular DMA transfer, which fills `buffer` with received
+    /// data, starting over at the beginning whenever the end is reached. Use
+    /// [`CircularTransfer::read`] to drain the buffer, before the DMA
+    /// controller comes around again.
+    ///
+    /// This is useful for receiving unframed data of unknown length, without
+    /// having to service an interrupt for every word.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    ///
+    /// [`CircularTransfer::read`]: ../dma/struct.CircularTransfer.html#method.read
+    pub fn read_circular(
+        self,
+        buffer: &'static mut [u8],
+        channel: dma::Channel<I::RxChannel, init_state::Enabled>,
+    ) -> dma::CircularTransfer<I::RxChannel, Self> {
+        dma::CircularTransfer::new(channel, self, buffer)
+    }
 }
 
 impl<I, W, Mode> Read<W> for Rx<I, Enabled<W, Mode>>
@@ -323,8 +581,80 @@ where
     }
 }
 
+/// The status of a received word
+///
+/// Returned by [`Rx::read_with_status`], alongside the word it refers to.
+///
+/// [`Rx::read_with_status`]: struct.Rx.html#method.read_with_status
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameStatus {
+    /// The word was received with a stop bit missing at the expected location
+    pub framing: bool,
+
+    /// The word was received with a parity error
+    pub parity: bool,
+
+    /// The word was corrupted by noise
+    pub noise: bool,
+
+    /// Words were lost before this one, as the receive buffer was still in use
+    pub overrun: bool,
+}
+
+impl FrameStatus {
+    /// Indicates whether the word was received without any errors
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::usart::FrameStatus;
+    ///
+    /// assert!(FrameStatus::default().is_ok());
+    ///
+    /// let status = FrameStatus { parity: true, ..FrameStatus::default() };
+    /// assert!(!status.is_ok());
+    /// ```
+    pub fn is_ok(&self) -> bool {
+        !(self.framing || self.parity || self.noise || self.overrun)
+    }
+}
+
+fn read_with_status(
+    usart: &pac::usart0::RegisterBlock,
+) -> nb::Result<(u16, FrameStatus), Void> {
+    let stat = usart.stat.read();
+
+    if stat.rxbrk().bit_is_set() || stat.rxrdy().bit_is_clear() {
+        return Err(nb::Error::WouldBlock);
+    }
+
+    // It's important to read this register all at once, as reading it
+    // changes the status flags.
+    let rx_dat_stat = usart.rxdatstat.read();
+
+    let status = FrameStatus {
+        framing: rx_dat_stat.framerr().bit_is_set(),
+        parity: rx_dat_stat.parityerr().bit_is_set(),
+        noise: rx_dat_stat.rxnoise().bit_is_set(),
+        overrun: stat.overrunint().bit_is_set(),
+    };
+
+    // Only clear the flags we've seen. Any flag that was set after we read
+    // `stat` belongs to a later word, and must not get lost.
+    usart.stat.write(|w| {
+        w.overrunint().bit(stat.overrunint().bit_is_set());
+        w.framerrint().bit(stat.framerrint().bit_is_set());
+        w.parityerrint().bit(stat.parityerrint().bit_is_set());
+        w.rxnoiseint().bit(stat.rxnoiseint().bit_is_set())
+    });
+
+    Ok((rx_dat_stat.rxdat().bits(), status))
+}
+
 /// A USART error
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
     /// Character received with a stop bit missing at the expected location
     Framing,
@@ -338,3 +668,74 @@ pub enum Error {
     /// Parity error detected in received character
     Parity,
 }
+
+#[cfg(test)]
+mod tests {
+    use core::{mem, ptr};
+
+    use crate::pac;
+
+    use super::FrameStatus;
+
+    const RXRDY: u32 = 1 << 0;
+    const OVERRUNINT: u32 = 1 << 8;
+    const FRAMERRINT: u32 = 1 << 13;
+    const PARITYERRINT: u32 = 1 << 14;
+    const RXNOISEINT: u32 = 1 << 15;
+
+    const ERROR_FLAGS: u32 =
+        OVERRUNINT | FRAMERRINT | PARITYERRINT | RXNOISEINT;
+
+    const PARITYERR: u32 = 1 << 14;
+
+    fn register_block(stat: u32, rxdatstat: u32) -> pac::usart0::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        let mut usart: pac::usart0::RegisterBlock = unsafe { mem::zeroed() };
+        usart.stat.write(|w| unsafe { w.bits(stat) });
+
+        // RXDATSTAT is read-only. Sound, as a register only consists of a
+        // `u32` value.
+        let register = ptr::addr_of_mut!(usart.rxdatstat) as *mut u32;
+        unsafe { ptr::write_volatile(register, rxdatstat) };
+
+        usart
+    }
+
+    #[test]
+    fn read_with_status_clears_only_observed_error_flags() {
+        let usart = register_block(RXRDY | PARITYERRINT, PARITYERR | 0x42);
+
+        let (word, status) = super::read_with_status(&usart).unwrap();
+
+        assert_eq!(word, 0x42);
+        assert_eq!(
+            status,
+            FrameStatus {
+                parity: true,
+                ..FrameStatus::default()
+            },
+        );
+        assert_eq!(usart.stat.read().bits() & ERROR_FLAGS, PARITYERRINT);
+    }
+
+    #[test]
+    fn read_with_status_reports_overrun_from_stat() {
+        let usart = register_block(RXRDY | OVERRUNINT | FRAMERRINT, 0x42);
+
+        let (_, status) = super::read_with_status(&usart).unwrap();
+
+        assert!(status.overrun);
+        assert_eq!(
+            usart.stat.read().bits() & ERROR_FLAGS,
+            OVERRUNINT | FRAMERRINT
+        );
+    }
+
+    #[test]
+    fn read_with_status_blocks_until_word_received() {
+        let usart = register_block(PARITYERRINT, 0);
+
+        assert!(super::read_with_status(&usart).is_err());
+        assert_eq!(usart.stat.read().bits(), PARITYERRINT);
+    }
+}
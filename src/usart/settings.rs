@@ -1,7 +1,7 @@
-use core::marker::PhantomData;
+use core::{marker::PhantomData, mem};
 
 use crate::pac::usart0::cfg::{
-    self, CLKPOL_A, DATALEN_A, PARITYSEL_A, RXPOL_A, STOPLEN_A, TXPOL_A,
+    self, CLKPOL_A, DATALEN_A, LOOP_A, PARITYSEL_A, RXPOL_A, STOPLEN_A, TXPOL_A,
 };
 
 /// USART settings
@@ -9,6 +9,7 @@ use crate::pac::usart0::cfg::{
 /// Expected as an argument by the various enable methods on [`USART`].
 ///
 /// [`USART`]: struct.USART.html
+#[derive(Debug, PartialEq)]
 pub struct Settings<Word = u8> {
     pub(super) data_len: DATALEN_A,
     pub(super) parity: PARITYSEL_A,
@@ -16,6 +17,7 @@ pub struct Settings<Word = u8> {
     pub(super) clock_pol: CLKPOL_A,
     pub(super) rx_pol: RXPOL_A,
     pub(super) tx_pol: TXPOL_A,
+    pub(super) loopback: LOOP_A,
 
     _word: PhantomData<Word>,
 }
@@ -137,6 +139,125 @@ impl<Word> Settings<Word> {
         self
     }
 
+    /// Select whether the RX signal is inverted
+    ///
+    /// Calls [`rx_pol_inverted`], if `inverted` is `true`, and
+    /// [`rx_pol_standard`] otherwise. This is useful, if the polarity is only
+    /// known at runtime. Inverting the signals is required for some IR
+    /// transceivers and inverting level shifters.
+    ///
+    /// The inversion is applied before the receiver, so break detection and
+    /// autobaud work as usual, as long as the polarity matches the line. If
+    /// it doesn't, the idle line looks like a break to the receiver. In that
+    /// case, [`USART::auto_baud`] returns [`AutoBaudError::Break`].
+    ///
+    /// # Example
+    ///
+    /// If both sides are inverted, data round-trips normally:
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{nb::block, prelude::*, usart, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let clock_config = {
+    ///     syscon.uartfrg.set_clkdiv(6);
+    ///     syscon.uartfrg.set_frgmult(22);
+    ///     syscon.uartfrg.set_frgdiv(0xff);
+    ///     usart::Clock::new(&syscon.uartfrg, 0, 16)
+    /// };
+    /// #[cfg(feature = "845")]
+    /// let clock_config = usart::Clock::new_with_baudrate(115_200);
+    ///
+    /// let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+    ///     p.pins.pio0_0.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+    ///     p.pins.pio0_4.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let mut serial = p.USART0.enable_async(
+    ///     &clock_config,
+    ///     &mut syscon.handle,
+    ///     u0_rxd,
+    ///     u0_txd,
+    ///     usart::Settings::default()
+    ///         .rx_inverted(true)
+    ///         .tx_inverted(true)
+    ///         .loopback_enabled(),
+    /// );
+    ///
+    /// block!(serial.write(b'a')).unwrap();
+    /// assert_eq!(block!(serial.read()).unwrap(), b'a');
+    /// ```
+    ///
+    /// [`rx_pol_inverted`]: #method.rx_pol_inverted
+    /// [`rx_pol_standard`]: #method.rx_pol_standard
+    /// [`USART::auto_baud`]: struct.USART.html#method.auto_baud
+    /// [`AutoBaudError::Break`]: enum.AutoBaudError.html#variant.Break
+    pub fn rx_inverted(self, inverted: bool) -> Self {
+        if inverted {
+            self.rx_pol_inverted()
+        } else {
+            self.rx_pol_standard()
+        }
+    }
+
+    /// Select whether the TX signal is inverted
+    ///
+    /// Calls [`tx_pol_inverted`], if `inverted` is `true`, and
+    /// [`tx_pol_standard`] otherwise. See [`rx_inverted`] for more
+    /// information.
+    ///
+    /// Please note that an inverted transmitter idles low, which the other
+    /// side will see as a break, unless it is inverted too.
+    ///
+    /// [`tx_pol_inverted`]: #method.tx_pol_inverted
+    /// [`tx_pol_standard`]: #method.tx_pol_standard
+    /// [`rx_inverted`]: #method.rx_inverted
+    pub fn tx_inverted(self, inverted: bool) -> Self {
+        if inverted {
+            self.tx_pol_inverted()
+        } else {
+            self.tx_pol_standard()
+        }
+    }
+
+    /// Don't loop back TX to RX
+    ///
+    /// Overwrites the previous loopback setting. This is the default.
+    pub fn loopback_disabled(mut self) -> Self {
+        self.loopback = LOOP_A::NORMAL;
+        self
+    }
+
+    /// Loop back TX to RX internally
+    ///
+    /// The transmitter output is connected to the receiver input internally,
+    /// so all transmitted data is received again. This makes it possible to
+    /// test the USART configuration without any external wiring.
+    ///
+    /// In loopback mode, the receiver ignores the RX pin. The TX pin (and the
+    /// RTS pin, if assigned) still shows the transmitted signal, so make sure
+    /// nothing is disturbed by that.
+    ///
+    /// Overwrites the previous loopback setting.
+    pub fn loopback_enabled(mut self) -> Self {
+        self.loopback = LOOP_A::LOOPBACK;
+        self
+    }
+
     fn transmute<NewW>(self) -> Settings<NewW> {
         Settings {
             data_len: self.data_len,
@@ -145,10 +266,83 @@ impl<Word> Settings<Word> {
             clock_pol: self.clock_pol,
             rx_pol: self.rx_pol,
             tx_pol: self.tx_pol,
+            loopback: self.loopback,
             _word: PhantomData,
         }
     }
 
+    /// Encode the settings into the bit layout of the CFG register
+    ///
+    /// Only the bits of the fields covered by `Settings` are set.
+    pub(super) fn to_cfg_bits(self) -> u32 {
+        (u8::from(self.data_len) as u32) << 2
+            | (u8::from(self.parity) as u32) << 4
+            | (bool::from(self.stop_len) as u32) << 6
+            | (bool::from(self.clock_pol) as u32) << 12
+            | (bool::from(self.loopback) as u32) << 15
+            | (bool::from(self.rx_pol) as u32) << 22
+            | (bool::from(self.tx_pol) as u32) << 23
+    }
+
+    /// Decode settings from the bit layout of the CFG register
+    ///
+    /// Bits that don't belong to the fields covered by `Settings` are
+    /// ignored. Returns `None`, if a field has a reserved value, or if the
+    /// data length doesn't match `Word`.
+    pub(super) fn from_cfg_bits(bits: u32) -> Option<Self> {
+        let data_len = match (bits >> 2) & 0x3 {
+            0 => DATALEN_A::BIT_7,
+            1 => DATALEN_A::BIT_8,
+            2 => DATALEN_A::BIT_9,
+            _ => return None,
+        };
+        let parity = match (bits >> 4) & 0x3 {
+            0 => PARITYSEL_A::NO_PARITY,
+            2 => PARITYSEL_A::EVEN_PARITY,
+            3 => PARITYSEL_A::ODD_PARITY,
+            _ => return None,
+        };
+
+        // 9-bit words are represented by `u16`, all others by `u8`.
+        let is_9_bit = data_len == DATALEN_A::BIT_9;
+        if is_9_bit != (mem::size_of::<Word>() == 2) {
+            return None;
+        }
+
+        let bit = |n: u32| bits & (0x1 << n) != 0;
+
+        Some(Settings {
+            data_len,
+            parity,
+            stop_len: if bit(6) {
+                STOPLEN_A::BITS_2
+            } else {
+                STOPLEN_A::BIT_1
+            },
+            clock_pol: if bit(12) {
+                CLKPOL_A::RISING_EDGE
+            } else {
+                CLKPOL_A::FALLING_EDGE
+            },
+            loopback: if bit(15) {
+                LOOP_A::LOOPBACK
+            } else {
+                LOOP_A::NORMAL
+            },
+            rx_pol: if bit(22) {
+                RXPOL_A::INVERTED
+            } else {
+                RXPOL_A::STANDARD
+            },
+            tx_pol: if bit(23) {
+                TXPOL_A::INVERTED
+            } else {
+                TXPOL_A::STANDARD
+            },
+            _word: PhantomData,
+        })
+    }
+
     pub(super) fn apply(&self, w: &mut cfg::W) {
         w.datalen().variant(self.data_len);
         w.paritysel().variant(self.parity);
@@ -156,9 +350,19 @@ impl<Word> Settings<Word> {
         w.clkpol().variant(self.clock_pol);
         w.rxpol().variant(self.rx_pol);
         w.txpol().variant(self.tx_pol);
+        w.loop_().variant(self.loopback);
     }
 }
 
+// Can't be derived, as that would require `Word: Clone`.
+impl<Word> Clone for Settings<Word> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Word> Copy for Settings<Word> {}
+
 impl Default for Settings {
     fn default() -> Self {
         Settings {
@@ -168,6 +372,7 @@ impl Default for Settings {
             clock_pol: CLKPOL_A::FALLING_EDGE,
             rx_pol: RXPOL_A::STANDARD,
             tx_pol: TXPOL_A::STANDARD,
+            loopback: LOOP_A::NORMAL,
             _word: PhantomData,
         }
     }
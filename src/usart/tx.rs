@@ -29,9 +29,20 @@ use super::{
 /// - [`embedded_hal::serial::Write`] for non-blocking writes
 /// - [`embedded_hal::blocking::serial::Write`] for blocking writes
 ///
+/// # Formatted output
+///
+/// If the word size is `u8`, `Tx` implements [`core::fmt::Write`], which means
+/// it can be used with `write!` and `writeln!`. Writing can't fail (the error
+/// type of [`embedded_hal::serial::Write`] is [`Void`]), so the result of
+/// those macros will always be `Ok`, and no formatted output is ever dropped
+/// halfway through. There's no need to `expect` or `unwrap` it, beyond
+/// silencing the unused result warning.
+///
 /// [`USART`]: struct.USART.html
 /// [`embedded_hal::serial::Write`]: #impl-Write<W>
 /// [`embedded_hal::blocking::serial::Write`]: #impl-Write<Word>
+/// [`core::fmt::Write`]: #impl-Write
+/// [`Void`]: https://docs.rs/void/1.0.2/void/enum.Void.html
 pub struct Tx<I, State, Throttle> {
     instance: PhantomData<I>,
     state: PhantomData<State>,
@@ -127,6 +138,29 @@ where
         flag.is_set::<I>()
     }
 
+    /// Wait until all previously written words have been sent
+    ///
+    /// Blocks until the transmitter is idle, meaning the last bit of the last
+    /// word has left the shift register. Use this before doing anything that
+    /// would cut off a transmission in progress, like powering down a device,
+    /// or switching the direction of an RS-485 transceiver.
+    ///
+    /// Please note that it is not enough for the transmitter to be ready (as
+    /// indicated by [`Flag::TXRDY`]). That only means that the next word can be
+    /// written, while the previous one might still be shifted out. This method
+    /// waits for [`Flag::TXIDLE`] instead, which is only set once all data has
+    /// been sent.
+    ///
+    /// This is equivalent to `bflush` from
+    /// [`embedded_hal::blocking::serial::Write`].
+    ///
+    /// [`Flag::TXRDY`]: enum.Flag.html#variant.TXRDY
+    /// [`Flag::TXIDLE`]: enum.Flag.html#variant.TXIDLE
+    /// [`embedded_hal::blocking::serial::Write`]: #impl-Write<Word>
+    pub fn wait_tx_complete(&mut self) {
+        while let Err(nb::Error::WouldBlock) = self.flush() {}
+    }
+
     /// Enable interrupts
     ///
     /// Enables all interrupts set to `true` in `interrupts`. Interrupts set to
@@ -331,6 +365,178 @@ where
     }
 }
 
+impl<I, W, Mode, Throttle> Tx<I, Enabled<W, Mode>, Throttle>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Start writing the provided buffer from the interrupt handler
+    ///
+    /// Returns a [`WriteHandle`] immediately, without writing anything, and
+    /// enables the TXRDY interrupt. [`WriteHandle::on_interrupt`], which needs
+    /// to be called from the interrupt handler of the USART instance, then
+    /// writes the buffer to the transmitter, as it becomes ready for more
+    /// words. This allows for doing other work while the transmission is in
+    /// progress.
+    ///
+    /// The transmitter is moved into the handle, and `buffer` has a `'static`
+    /// lifetime, so neither can be used for anything else, or go away, while
+    /// the interrupt handler is writing. [`WriteHandle::free`] returns both.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::usart;
+    ///
+    /// # use lpc8xx_hal::Peripherals;
+    /// #
+    /// # let mut p = Peripherals::take().unwrap();
+    /// #
+    /// # let mut syscon = p.SYSCON.split();
+    /// # let mut swm    = p.SWM.split();
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let clock_config = {
+    /// #     syscon.uartfrg.set_clkdiv(6);
+    /// #     syscon.uartfrg.set_frgmult(22);
+    /// #     syscon.uartfrg.set_frgdiv(0xff);
+    /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
+    /// # };
+    /// # #[cfg(feature = "845")]
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// #
+    /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+    /// #     p.pins.pio0_0.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+    /// #     p.pins.pio0_4.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// #
+    /// # let mut usart = p.USART0.enable_async(
+    /// #     &clock_config,
+    /// #     &mut syscon.handle,
+    /// #     u0_rxd,
+    /// #     u0_txd,
+    /// #     usart::Settings::default(),
+    /// # );
+    /// #
+    /// let write = usart.tx.start_write(b"Hello, world!\r\n");
+    ///
+    /// // Usually, the handle would be put into a static now, so the
+    /// // interrupt handler can call `on_interrupt`.
+    ///
+    /// while !write.is_done() {
+    ///     // do other work
+    /// }
+    ///
+    /// let (tx, _) = write.free();
+    /// ```
+    ///
+    /// [`WriteHandle`]: struct.WriteHandle.html
+    /// [`WriteHandle::on_interrupt`]: struct.WriteHandle.html#method.on_interrupt
+    /// [`WriteHandle::free`]: struct.WriteHandle.html#method.free
+    pub fn start_write(
+        mut self,
+        buffer: &'static [W],
+    ) -> WriteHandle<I, W, Mode, Throttle> {
+        self.enable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+
+        WriteHandle {
+            tx: self,
+            buffer,
+            written: 0,
+        }
+    }
+}
+
+type EnabledTx<I, W, Mode, Throttle> = Tx<I, Enabled<W, Mode>, Throttle>;
+
+/// A write that is in progress, driven by the interrupt handler
+///
+/// Returned by [`Tx::start_write`]. [`on_interrupt`], which needs to be called
+/// from the interrupt handler of the USART instance, writes the buffer to the
+/// transmitter. As with [`BufferedTx`], it's typical to put a `WriteHandle`
+/// into a static `Mutex<RefCell<...>>`, and the interrupt needs to be enabled
+/// in the NVIC.
+///
+/// [`Tx::start_write`]: struct.Tx.html#method.start_write
+/// [`on_interrupt`]: #method.on_interrupt
+/// [`BufferedTx`]: struct.BufferedTx.html
+pub struct WriteHandle<I, W: Word + 'static, Mode, Throttle> {
+    tx: EnabledTx<I, W, Mode, Throttle>,
+    buffer: &'static [W],
+    written: usize,
+}
+
+impl<I, W, Mode, Throttle> WriteHandle<I, W, Mode, Throttle>
+where
+    I: Instance,
+    W: Word + Copy,
+{
+    /// Write as many words to the transmitter as it can accept
+    ///
+    /// Must be called from the interrupt handler of the USART instance.
+    /// Disables the TXRDY interrupt, once all words have been written.
+    pub fn on_interrupt(&mut self) {
+        while let Some(&word) = self.buffer.get(self.written) {
+            match self.tx.write(word) {
+                Ok(()) => self.written += 1,
+                Err(nb::Error::WouldBlock) => return,
+                Err(nb::Error::Other(void)) => match void {},
+            }
+        }
+
+        self.tx.disable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+    }
+
+    /// Indicates whether the write is done
+    ///
+    /// Returns `true`, once all words have been written and the transmitter
+    /// is idle. This method only checks the progress, it doesn't make any.
+    pub fn is_done(&self) -> bool {
+        // Sound, as we're only reading from a register.
+        let usart = unsafe { &*I::REGISTERS };
+
+        self.written == self.buffer.len()
+            && usart.stat.read().txidle().bit_is_set()
+    }
+
+    /// Returns the number of words that have been written so far
+    ///
+    /// This includes words that have been written to the transmitter, but
+    /// have not necessarily been sent yet.
+    pub fn words_written(&self) -> usize {
+        self.written
+    }
+
+    /// Return the transmitter and the buffer
+    ///
+    /// Disables the TXRDY interrupt, which stops the write, if it isn't done
+    /// yet. Any words that have already been written to the transmitter are
+    /// still sent.
+    pub fn free(mut self) -> (EnabledTx<I, W, Mode, Throttle>, &'static [W]) {
+        self.tx.disable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+
+        (self.tx, self.buffer)
+    }
+}
+
 impl<I, W, Mode, Throttle> Write<W> for Tx<I, Enabled<W, Mode>, Throttle>
 where
     I: Instance,
@@ -354,10 +560,19 @@ where
         Ok(())
     }
 
+    /// Returns `Ok`, once all words have been sent completely
+    ///
+    /// Waits for TXIDLE, not TXRDY. Please refer to [`Tx::wait_tx_complete`]
+    /// for details.
+    ///
+    /// [`Tx::wait_tx_complete`]: #method.wait_tx_complete
     fn flush(&mut self) -> nb::Result<(), Self::Error> {
         // Sound, as we're only reading from a register.
         let usart = unsafe { &*I::REGISTERS };
 
+        // TXRDY would only indicate that TXDAT is empty, but the last word
+        // might still be in the shift register. TXIDLE is only set after it
+        // has been shifted out completely.
         if usart.stat.read().txidle().bit_is_clear() {
             return Err(nb::Error::WouldBlock);
         }
@@ -379,6 +594,9 @@ where
     Self: BlockingWriteDefault<u8>,
     I: Instance,
 {
+    /// Writes a string slice, blocking until it has been sent completely
+    ///
+    /// Never returns an error, as writing to the USART can't fail.
     fn write_str(&mut self, s: &str) -> fmt::Result {
         use crate::prelude::*;
 
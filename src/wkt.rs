@@ -30,17 +30,34 @@
 //!
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
+#[cfg(feature = "async")]
+use core::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    task::{Poll, Waker},
+};
+
+#[cfg(feature = "async")]
+use cortex_m::interrupt::{self, Mutex};
 use embedded_hal::timer;
 use nb;
 use void::Void;
 
 use crate::{
-    init_state,
+    clock, init_state,
     pac::{self, wkt::ctrl},
     pmu::LowPowerClock,
     syscon::{self, IoscDerivedClock},
 };
 
+/// The waker of the task waiting for the WKT interrupt
+#[cfg(feature = "async")]
+static WAKER: Mutex<RefCell<Option<Waker>>> = Mutex::new(RefCell::new(None));
+
+/// Set by the interrupt handler, after it cleared the alarm flag
+#[cfg(feature = "async")]
+static FIRED: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
+
 /// Interface to the self-wake-up timer (WKT)
 ///
 /// Controls the WKT. Use [`Peripherals`] to gain access to an instance of this
@@ -98,6 +115,9 @@ impl WKT<init_state::Enabled> {
     /// Code that attempts to call this method when the peripheral is already
     /// disabled will not compile.
     ///
+    /// Halts any count down that is still in progress and clears the alarm
+    /// flag, before disabling the clock of the WKT's register interface.
+    ///
     /// Consumes this instance of `WKT` and returns another instance that has
     /// its `State` type parameter set to [`Disabled`].
     ///
@@ -107,6 +127,7 @@ impl WKT<init_state::Enabled> {
         self,
         syscon: &mut syscon::Handle,
     ) -> WKT<init_state::Disabled> {
+        clear_counter(&self.wkt);
         syscon.disable_clock(&self.wkt);
 
         WKT {
@@ -129,9 +150,12 @@ impl WKT<init_state::Enabled> {
     ///
     /// Currently, nothing prevents the user from selecting a clock that is
     /// disabled, attempting to start the timer while the clock is disabled, or
-    /// disabling the clock while the timer is running.
+    /// disabling the clock while the timer is running. Consider using
+    /// [`select_enabled_clock`] instead, which at least makes sure the clock
+    /// is enabled at the time it is selected.
     ///
     /// [`wkt::Clock`]: trait.Clock.html
+    /// [`select_enabled_clock`]: #method.select_enabled_clock
     pub fn select_clock<C>(&mut self)
     where
         C: Clock,
@@ -141,6 +165,215 @@ impl WKT<init_state::Enabled> {
             w
         });
     }
+
+    /// Select an enabled clock to run the self-wake-up timer
+    ///
+    /// Works like [`select_clock`], except that it requires a reference to
+    /// the clock, which must be enabled. For the low-power clock, this means
+    /// that the low-power oscillator must have been powered up using
+    /// [`LowPowerClock::enable`].
+    ///
+    /// Switching clocks while a count down is running would result in
+    /// unpredictable timing. For that reason, this method clears the counter,
+    /// halting any count down in progress. Call [`start`] after this method,
+    /// to start a new one.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     prelude::*,
+    ///     Peripherals,
+    /// };
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut pmu    = p.PMU.split();
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut timer  = p.WKT.enable(&mut syscon.handle);
+    ///
+    /// let low_power_clock = pmu.low_power_clock.enable(&mut pmu.handle);
+    /// timer.select_enabled_clock(&low_power_clock);
+    ///
+    /// // The low-power clock runs at 10 kHz, so this is a one second wait.
+    /// timer.start(10_000u32);
+    /// ```
+    ///
+    /// [`select_clock`]: #method.select_clock
+    /// [`LowPowerClock::enable`]: ../pmu/struct.LowPowerClock.html#method.enable
+    /// [`start`]: #impl-CountDown
+    pub fn select_enabled_clock<C>(&mut self, _clock: &C)
+    where
+        C: Clock + clock::Enabled,
+    {
+        halt_and_select_clock::<C>(&self.wkt);
+    }
+
+    /// Select a clock source that was chosen at runtime
+    ///
+    /// Works like [`select_clock`], but takes a [`ClockSource`], as returned by
+    /// [`choose_clock`]. The same limitations apply. In addition, nothing
+    /// makes sure that the clock is enabled. If you select
+    /// [`ClockSource::LowPower`], please make sure that the low-power clock is
+    /// enabled, using [`LowPowerClock::enable`].
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{wkt, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut pmu = p.PMU.split();
+    /// let mut wkt = p.WKT.enable(&mut syscon.handle);
+    ///
+    /// // Count down up to 2 hours, with a resolution of at least 1 ms.
+    /// let source =
+    ///     wkt::choose_clock(2 * 60 * 60 * 1_000, 1_000_000).unwrap();
+    ///
+    /// let _low_power_clock = pmu.low_power_clock.enable(&mut pmu.handle);
+    /// wkt.select_clock_source(source);
+    /// ```
+    ///
+    /// [`select_clock`]: #method.select_clock
+    /// [`ClockSource`]: enum.ClockSource.html
+    /// [`choose_clock`]: fn.choose_clock.html
+    /// [`ClockSource::LowPower`]: enum.ClockSource.html#variant.LowPower
+    /// [`LowPowerClock::enable`]: ../pmu/struct.LowPowerClock.html#method.enable
+    pub fn select_clock_source(&mut self, source: ClockSource) {
+        match source {
+            ClockSource::IoscDerived => {
+                self.select_clock::<IoscDerivedClock<init_state::Enabled>>()
+            }
+            ClockSource::LowPower => {
+                self.select_clock::<LowPowerClock<init_state::Enabled>>()
+            }
+        }
+    }
+
+    /// Clears the counter, which halts counting and resets the alarm flag
+    pub(crate) fn clear_counter(&mut self) {
+        clear_counter(&self.wkt);
+    }
+
+    /// Returns the current value of the counter
+    pub(crate) fn count(&self) -> u32 {
+        self.wkt.count.read().value().bits()
+    }
+
+    /// Returns the number of ticks remaining until the count down finishes
+    ///
+    /// Returns zero, once the count down has finished.
+    pub(crate) fn remaining(&self) -> u32 {
+        remaining(&self.wkt)
+    }
+}
+
+/// Async API
+///
+/// These methods are only available, if the `async` feature is enabled. The
+/// interrupt handler of the WKT needs to call [`on_interrupt`] to resume them,
+/// and the WKT interrupt needs to be unmasked in the NVIC.
+#[cfg(feature = "async")]
+impl WKT<init_state::Enabled> {
+    /// Count down the given number of ticks, without blocking
+    ///
+    /// Starts the timer, then returns control to the executor until the count
+    /// down has finished. Returns immediately, if `ticks` is zero.
+    ///
+    /// If the returned future is dropped before it completes, the timer is
+    /// stopped.
+    pub async fn count_down_async(&mut self, ticks: u32) {
+        // The alarm will never fire, if we start the timer with zero ticks.
+        if ticks == 0 {
+            return;
+        }
+
+        interrupt::free(|cs| FIRED.borrow(cs).set(false));
+        timer::CountDown::start(self, ticks);
+
+        let guard = CancelOnDrop(&self.wkt);
+
+        poll_fn(|cx| {
+            interrupt::free(|cs| {
+                // We're in a critical section, so the interrupt handler can't
+                // fire between checking the flags and storing the waker.
+                if FIRED.borrow(cs).replace(false)
+                    || guard.0.ctrl.read().alarmflag().bit_is_set()
+                {
+                    return Poll::Ready(());
+                }
+
+                let mut waker = WAKER.borrow(cs).borrow_mut();
+                match waker.as_ref() {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    _ => *waker = Some(cx.waker().clone()),
+                }
+
+                Poll::Pending
+            })
+        })
+        .await
+    }
+}
+
+/// Handle the WKT interrupt
+///
+/// The async methods of [`WKT`] rely on this function being called from the
+/// WKT interrupt handler. It clears the alarm flag, which would otherwise keep
+/// the interrupt pending, and wakes the task that is waiting for the count down
+/// to finish.
+///
+/// Please note that, as the alarm flag is cleared, [`CountDown::wait`] never
+/// returns `Ok`, while the WKT interrupt is handled by this function.
+///
+/// This function is only available, if the `async` feature is enabled.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::wkt;
+///
+/// // Register this as the handler for the WKT interrupt, for example using
+/// // the `interrupt` attribute from the PAC.
+/// fn wkt_handler() {
+///     wkt::on_interrupt();
+/// }
+/// ```
+///
+/// [`WKT`]: struct.WKT.html
+/// [`CountDown::wait`]: struct.WKT.html#method.wait
+#[cfg(feature = "async")]
+pub fn on_interrupt() {
+    // Sound, as writing 1 to the alarm flag only clears it, and writing back
+    // the other bits as they were read doesn't change anything.
+    let wkt = unsafe { &*pac::WKT::ptr() };
+    wkt.ctrl.modify(|_, w| w.alarmflag().set_bit());
+
+    interrupt::free(|cs| {
+        FIRED.borrow(cs).set(true);
+        if let Some(waker) = WAKER.borrow(cs).borrow_mut().take() {
+            waker.wake();
+        }
+    });
+}
+
+/// Stops the timer when dropped
+///
+/// Used to clean up after the future returned by
+/// [`WKT::count_down_async`] is dropped, whether it completed or not.
+#[cfg(feature = "async")]
+struct CancelOnDrop<'a>(&'a pac::wkt::RegisterBlock);
+
+#[cfg(feature = "async")]
+impl Drop for CancelOnDrop<'_> {
+    fn drop(&mut self) {
+        clear_counter(self.0);
+        interrupt::free(|cs| {
+            WAKER.borrow(cs).borrow_mut().take();
+        });
+    }
 }
 
 impl timer::CountDown for WKT<init_state::Enabled> {
@@ -155,7 +388,7 @@ impl timer::CountDown for WKT<init_state::Enabled> {
         // flag, so no reason to worry about that here.
 
         // It's not allowed to write to the counter without clearing it first.
-        self.wkt.ctrl.modify(|_, w| w.clearctr().clear_bit());
+        self.clear_counter();
 
         // The counter has been cleared, which halts counting. Writing a new
         // count is perfectly safe.
@@ -186,7 +419,14 @@ impl<State> WKT<State> {
     /// request already exists, comment on the existing issue, so we can
     /// prioritize it accordingly.
     ///
+    /// The WKT uses no pins, so the raw peripheral is all there is to return.
+    /// A count down that was started before calling this method keeps running
+    /// and still sets the alarm flag (and wakes the microcontroller from deep
+    /// power-down mode, if configured) once it finishes. Call [`disable`]
+    /// first, if you need the timer to be halted.
+    ///
     /// [open an issue]: https://github.com/lpc-rs/lpc8xx-hal/issues
+    /// [`disable`]: #method.disable
     pub fn free(self) -> pac::WKT {
         self.wkt
     }
@@ -218,6 +458,165 @@ impl<State> Clock for LowPowerClock<State> {
     }
 }
 
+/// A clock source of the WKT, selectable at runtime
+///
+/// Can be chosen for given timing requirements using [`choose_clock`], and
+/// selected using [`WKT::select_clock_source`].
+///
+/// [`choose_clock`]: fn.choose_clock.html
+/// [`WKT::select_clock_source`]: struct.WKT.html#method.select_clock_source
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockSource {
+    /// The IRC/FRO-derived clock (750 kHz)
+    ///
+    /// See [`IoscDerivedClock`].
+    ///
+    /// [`IoscDerivedClock`]: ../syscon/struct.IoscDerivedClock.html
+    IoscDerived,
+
+    /// The low-power clock (10 kHz)
+    ///
+    /// See [`LowPowerClock`].
+    ///
+    /// [`LowPowerClock`]: ../pmu/struct.LowPowerClock.html
+    LowPower,
+}
+
+impl ClockSource {
+    /// All clock sources, from the finest to the coarsest resolution
+    const ALL: [ClockSource; 2] =
+        [ClockSource::IoscDerived, ClockSource::LowPower];
+
+    /// The nominal frequency of the clock source in Hz
+    pub fn hz(&self) -> u32 {
+        match self {
+            // Same as the `clock::Frequency` implementations of the clocks.
+            ClockSource::IoscDerived => 750_000,
+            ClockSource::LowPower => 10_000,
+        }
+    }
+
+    /// The duration of one tick in nanoseconds, rounded up
+    pub fn resolution_ns(&self) -> u32 {
+        1_000_000_000_u32.div_ceil(self.hz())
+    }
+
+    /// The longest count down possible with this clock in milliseconds
+    pub fn max_delay_ms(&self) -> u64 {
+        u32::MAX as u64 * 1_000 / self.hz() as u64
+    }
+}
+
+/// Choose a clock source for the WKT, based on timing requirements
+///
+/// Returns the clock source with the finest resolution that can still count
+/// down `max_delay_ms` milliseconds, without overflowing the 32-bit counter.
+/// Returns an error, if no clock source can count down that long, or if the
+/// resolution of that clock source is coarser than `resolution_ns`
+/// nanoseconds.
+///
+/// The achievable resolution is available via
+/// [`ClockSource::resolution_ns`]. The WKT has no pre-divider, so the
+/// resolution is determined by the clock source alone.
+///
+/// Please note that the low-power clock is not very accurate (&plusmn;40%,
+/// according to the user manual). If you choose it, consider calibrating it.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::wkt::{choose_clock, ChooseClockError, ClockSource};
+///
+/// // Short delays get the finest resolution (about 1.33 µs).
+/// let source = choose_clock(1_000, 10_000).unwrap();
+/// assert_eq!(source, ClockSource::IoscDerived);
+/// assert_eq!(source.resolution_ns(), 1_334);
+///
+/// // The IRC/FRO-derived clock can't count down for 2 hours.
+/// let source = choose_clock(2 * 60 * 60 * 1_000, 1_000_000).unwrap();
+/// assert_eq!(source, ClockSource::LowPower);
+/// assert_eq!(source.resolution_ns(), 100_000);
+///
+/// // Requirements that no clock source can satisfy
+/// assert_eq!(
+///     choose_clock(1_000, 1_000),
+///     Err(ChooseClockError::ResolutionTooFine),
+/// );
+/// assert_eq!(
+///     choose_clock(2 * 60 * 60 * 1_000, 10_000),
+///     Err(ChooseClockError::ResolutionTooFine),
+/// );
+/// assert_eq!(
+///     choose_clock(u32::MAX, 1_000_000),
+///     Err(ChooseClockError::DelayTooLong),
+/// );
+/// ```
+///
+/// [`ClockSource::resolution_ns`]: enum.ClockSource.html#method.resolution_ns
+pub fn choose_clock(
+    max_delay_ms: u32,
+    resolution_ns: u32,
+) -> Result<ClockSource, ChooseClockError> {
+    let source = ClockSource::ALL
+        .iter()
+        .find(|source| max_delay_ms as u64 <= source.max_delay_ms())
+        .ok_or(ChooseClockError::DelayTooLong)?;
+
+    // The other clock sources are even coarser.
+    if source.resolution_ns() > resolution_ns {
+        return Err(ChooseClockError::ResolutionTooFine);
+    }
+
+    Ok(*source)
+}
+
+/// Error returned by [`choose_clock`]
+///
+/// [`choose_clock`]: fn.choose_clock.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChooseClockError {
+    /// No clock source can count down the requested maximum delay
+    DelayTooLong,
+
+    /// No clock source that can count down the requested maximum delay has the
+    /// requested resolution
+    ResolutionTooFine,
+}
+
+/// Clears the counter, which halts counting and resets the alarm flag
+///
+/// CLEARCTR only has an effect when written as 1. ALARMFLAG is
+/// write-1-to-clear, so writing back a set flag clears it.
+fn clear_counter(wkt: &pac::wkt::RegisterBlock) {
+    wkt.ctrl.modify(|_, w| w.clearctr().set_bit());
+}
+
+fn remaining(wkt: &pac::wkt::RegisterBlock) -> u32 {
+    // Read the counter first. If it reaches zero after that, the alarm flag is
+    // set.
+    let count = wkt.count.read().value().bits();
+
+    if wkt.ctrl.read().alarmflag().bit_is_set() {
+        return 0;
+    }
+
+    count
+}
+
+/// Halts any count down in progress, then selects the clock
+fn halt_and_select_clock<C>(wkt: &pac::wkt::RegisterBlock)
+where
+    C: Clock,
+{
+    clear_counter(wkt);
+    wkt.ctrl.modify(|_, w| {
+        C::select(w);
+        w
+    });
+}
+
 #[cfg(feature = "82x")]
 mod target {
     pub fn select_internal_oscillator(w: &mut crate::pac::wkt::ctrl::W) {
@@ -231,3 +630,93 @@ mod target {
         w.clksel().divided_fro_clock();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::mem;
+
+    use crate::{pac, pmu::LowPowerClock};
+
+    const CLKSEL: u32 = 1 << 0;
+    const ALARMFLAG: u32 = 1 << 1;
+    const CLEARCTR: u32 = 1 << 2;
+
+    fn register_block() -> pac::wkt::RegisterBlock {
+        // Sound, as the register block only consists of `u32` values.
+        unsafe { mem::zeroed() }
+    }
+
+    #[test]
+    fn clear_counter_writes_clearctr() {
+        let wkt = register_block();
+
+        super::clear_counter(&wkt);
+
+        assert_eq!(wkt.ctrl.read().bits() & CLEARCTR, CLEARCTR);
+    }
+
+    #[test]
+    fn clear_counter_clears_alarm_flag() {
+        let wkt = register_block();
+        wkt.ctrl.write(|w| unsafe { w.bits(ALARMFLAG) });
+
+        super::clear_counter(&wkt);
+
+        // ALARMFLAG is write-1-to-clear, so it must be written back as 1.
+        assert_eq!(
+            wkt.ctrl.read().bits() & (ALARMFLAG | CLEARCTR),
+            ALARMFLAG | CLEARCTR
+        );
+    }
+
+    #[test]
+    fn free_returns_raw_peripheral() {
+        // Sound, as the peripheral is only compared, never accessed.
+        let wkt = unsafe { pac::Peripherals::steal() }.WKT;
+
+        let wkt = super::WKT::new(wkt).free();
+
+        assert_eq!(&*wkt as *const _, pac::WKT::ptr());
+    }
+
+    #[test]
+    fn remaining_is_count_until_alarm_flag_is_set() {
+        let wkt = register_block();
+
+        wkt.count.write(|w| unsafe { w.bits(42) });
+        assert_eq!(super::remaining(&wkt), 42);
+
+        wkt.ctrl.write(|w| unsafe { w.bits(ALARMFLAG) });
+        assert_eq!(super::remaining(&wkt), 0);
+    }
+
+    #[test]
+    fn select_enabled_clock_halts_counter() {
+        let wkt = register_block();
+
+        super::halt_and_select_clock::<LowPowerClock>(&wkt);
+
+        assert_eq!(wkt.ctrl.read().bits() & CLEARCTR, CLEARCTR);
+        assert_eq!(wkt.ctrl.read().bits() & CLKSEL, CLKSEL);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn cancel_on_drop_stops_timer_and_forgets_waker() {
+        use core::task::Waker;
+
+        use cortex_m::interrupt;
+
+        use super::{CancelOnDrop, WAKER};
+
+        let wkt = register_block();
+        interrupt::free(|cs| {
+            *WAKER.borrow(cs).borrow_mut() = Some(Waker::noop().clone());
+        });
+
+        drop(CancelOnDrop(&wkt));
+
+        assert_eq!(wkt.ctrl.read().bits() & CLEARCTR, CLEARCTR);
+        assert!(interrupt::free(|cs| WAKER.borrow(cs).borrow().is_none()));
+    }
+}
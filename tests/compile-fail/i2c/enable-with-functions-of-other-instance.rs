@@ -0,0 +1,31 @@
+use lpc8xx_hal::Peripherals;
+
+
+fn main() {
+    let p = Peripherals::take().unwrap();
+
+    let     swm    = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut swm_handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let (i2c1_scl, _) = swm.movable_functions.i2c1_scl.assign(
+        p.pins.pio0_16.into_swm_pin(),
+        &mut swm_handle,
+    );
+    let (i2c1_sda, _) = swm.movable_functions.i2c1_sda.assign(
+        p.pins.pio0_17.into_swm_pin(),
+        &mut swm_handle,
+    );
+
+    // Should fail: These are the functions of I2C1, not I2C0.
+    let _ = p.I2C0.enable(
+        &syscon.iosc,
+        i2c1_scl,
+        i2c1_sda,
+        &mut syscon.handle,
+    );
+}
@@ -0,0 +1,9 @@
+use lpc8xx_hal::{pins::iocon::I2cMode, Peripherals};
+
+
+fn main() {
+    let mut p = Peripherals::take().unwrap();
+
+    // Should fail: Only the true open-drain pins have an I2C mode.
+    p.pins.pio0_12.iocon().set_i2c_mode(I2cMode::Gpio);
+}
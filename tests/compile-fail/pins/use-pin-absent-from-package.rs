@@ -0,0 +1,9 @@
+use lpc8xx_hal::Peripherals;
+
+
+fn main() {
+    let p = Peripherals::take().unwrap();
+
+    // Should fail: PIO1_0 is not bonded out on the 33-pin package.
+    let _ = p.pins.pio1_0.into_swm_pin();
+}
@@ -0,0 +1,38 @@
+use lpc8xx_hal::{
+    Peripherals,
+    pins::{
+        self,
+        Pin,
+    },
+    swm,
+};
+
+
+fn main() {
+    let mut p = Peripherals::take().unwrap();
+
+    let swm = p.SWM.split();
+
+    #[cfg(feature = "82x")]
+    let mut swm_handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut swm_handle = {
+        let mut syscon = p.SYSCON.split();
+        swm.handle.enable(&mut syscon.handle)
+    };
+
+    let pio0_0: Pin<_, pins::state::Unused> = p.pins.pio0_0;
+
+    let u0_rxd: swm::Function<_, swm::state::Unassigned> =
+        swm.movable_functions.u0_rxd;
+    let u0_txd: swm::Function<_, swm::state::Unassigned> =
+        swm.movable_functions.u0_txd;
+
+    let pio0_0 = pio0_0.into_swm_pin();
+
+    // Should fail: Same pin passed twice, output function first.
+    let ((u0_txd, _), (u0_rxd, _)) = swm_handle.assign_all((
+        (u0_txd, pio0_0),
+        (u0_rxd, pio0_0),
+    ));
+}
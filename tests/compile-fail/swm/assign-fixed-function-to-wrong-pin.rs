@@ -0,0 +1,30 @@
+use lpc8xx_hal::{
+    Peripherals,
+    pins::{
+        self,
+        Pin,
+    },
+    swm,
+};
+
+
+fn main() {
+    let mut p = Peripherals::take().unwrap();
+
+    let     swm    = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    #[cfg(feature = "82x")]
+    let mut swm_handle = swm.handle;
+    #[cfg(feature = "845")]
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let pio0_0: Pin<_, pins::state::Unused> = p.pins.pio0_0;
+
+    let i2c0_sda: swm::Function<_, swm::state::Unassigned> =
+        swm.fixed_functions.i2c0_sda;
+
+    // Should fail: I2C0_SDA is fixed to PIO0_11.
+    let (i2c0_sda, _) =
+        i2c0_sda.assign(pio0_0.into_swm_pin(), &mut swm_handle);
+}
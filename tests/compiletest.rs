@@ -3,5 +3,11 @@
 #[test]
 fn compile_test() {
     let test_cases = trybuild::TestCases::new();
+    test_cases.compile_fail("tests/compile-fail/i2c/*.rs");
+    test_cases.compile_fail("tests/compile-fail/iocon/*.rs");
     test_cases.compile_fail("tests/compile-fail/swm/*.rs");
+
+    // Only relevant, if a package with missing pins has been selected.
+    #[cfg(all(feature = "845", feature = "33"))]
+    test_cases.compile_fail("tests/compile-fail/pins/*.rs");
 }